@@ -0,0 +1,247 @@
+use core::fmt::{self, Display, Formatter};
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use crate::{telemetry, DefaultState, Inner};
+
+/// Guaranteed lower and upper bounds on a probability computed from an
+/// [`Approximate`] distribution, or from the [`Covered`] outcomes of
+/// [`CoverageEnumerator`].
+///
+/// [`Covered`]: crate::Covered
+/// [`CoverageEnumerator`]: crate::CoverageEnumerator
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProbabilityBounds {
+    /// The smallest value the exact probability can take.
+    pub lower: f64,
+    /// The largest value the exact probability can take.
+    pub upper: f64,
+}
+
+impl ProbabilityBounds {
+    /// Returns bounds which are both equal to `probability`.
+    #[inline]
+    pub fn exact(probability: f64) -> Self {
+        Self {
+            lower: probability,
+            upper: probability,
+        }
+    }
+
+    /// Returns the distance between the bounds, which is the largest possible
+    /// error of any estimate between them.
+    #[inline]
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
+
+    /// Returns `true` if `probability` lies between the bounds, inclusive.
+    #[inline]
+    pub fn contains(&self, probability: f64) -> bool {
+        self.lower <= probability && probability <= self.upper
+    }
+}
+
+impl Display for ProbabilityBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lower, self.upper)
+    }
+}
+
+/// The outcomes of a random process evaluated with [`Counter`] after
+/// low-probability outcomes have been pruned, along with the total
+/// probability of every outcome that was pruned.
+///
+/// Pruning with [`Approximate::top_k`] or [`Approximate::prune_below`] keeps
+/// the number of outcomes small, at the cost of exactness. As the pruned
+/// outcomes could have led anywhere, probabilities computed from the
+/// remaining outcomes are returned as [`ProbabilityBounds`], which are
+/// guaranteed to contain the exact probability. Evaluation of the random
+/// process can continue with [`Approximate::map`], and pruning can be
+/// repeated between steps, as the discarded probability accumulates across
+/// every pruning.
+///
+/// The bounds only account for pruning through `Approximate`.
+/// [`CoverageEnumerator`] prunes outcomes during every operation instead, and
+/// its bounds are returned by [`Covered::probability_bounds`]. The outcomes of
+/// [`AdaptiveCounter`] and [`StratifiedCounter`] are chosen at random, and
+/// those of [`Thinned`] sample spaces depend on how the random process varies
+/// between neighbouring values, so none of them has a guaranteed error.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Approximate, Counter, Functor, RandomStrategy};
+///
+/// let mut rng = thread_rng();
+/// let f = <Counter>::fmap_rand_range(Functor::pure(0), 1..=6u8, &mut rng, |s, r| s + r);
+/// let f = <Counter>::fmap_rand_range(f, 1..=6u8, &mut rng, |s, r| s + r);
+/// let f = Approximate::exact(f).prune_below(0.06);
+/// assert_eq!(f.discarded(), 6.0 / 36.0);
+/// assert_eq!(f.outcomes().len(), 7);
+///
+/// let f = f.map(|f| <Counter>::fmap_rand_range(f, 1..=6u8, &mut rng, |s, r| s + r));
+/// let bounds = f.probability_where(|&s| s >= 10);
+/// assert!(bounds.contains(0.625));
+/// assert!(bounds.width() <= 6.0 / 36.0);
+/// ```
+///
+/// [`AdaptiveCounter`]: crate::AdaptiveCounter
+/// [`Counter`]: crate::Counter
+/// [`Covered::probability_bounds`]: crate::Covered::probability_bounds
+/// [`CoverageEnumerator`]: crate::CoverageEnumerator
+/// [`StratifiedCounter`]: crate::StratifiedCounter
+/// [`Thinned`]: crate::Thinned
+#[derive(Clone, Debug, PartialEq)]
+pub struct Approximate<I: Inner> {
+    outcomes: HashMap<I, usize, DefaultState>,
+    discarded: f64,
+}
+
+impl<I: Inner> Approximate<I> {
+    /// Wraps the outcomes of a random process evaluated with [`Counter`],
+    /// none of which have been pruned yet.
+    ///
+    /// [`Counter`]: crate::Counter
+    #[inline]
+    pub fn exact(outcomes: HashMap<I, usize, DefaultState>) -> Self {
+        Self {
+            outcomes,
+            discarded: 0.0,
+        }
+    }
+
+    /// Returns the outcomes which have not been pruned and their counts.
+    #[inline]
+    pub fn outcomes(&self) -> &HashMap<I, usize, DefaultState> {
+        &self.outcomes
+    }
+
+    /// Returns the outcomes which have not been pruned and their counts,
+    /// discarding the error bound.
+    #[inline]
+    pub fn into_inner(self) -> HashMap<I, usize, DefaultState> {
+        self.outcomes
+    }
+
+    /// Returns the total probability of every outcome pruned so far.
+    #[inline]
+    pub fn discarded(&self) -> f64 {
+        self.discarded
+    }
+
+    /// Returns `true` if no outcome with a nonzero count has been pruned.
+    #[inline]
+    pub fn is_exact(&self) -> bool {
+        self.discarded == 0.0
+    }
+
+    /// Keeps only the `k` outcomes with the largest counts.
+    ///
+    /// Ties between outcomes with equal counts at the cutoff are broken
+    /// arbitrarily.
+    pub fn top_k(self, k: usize) -> Self {
+        if self.outcomes.len() <= k {
+            return self;
+        }
+        let mut counts = self.outcomes.values().copied().collect::<Vec<_>>();
+        counts.select_nth_unstable_by(k.saturating_sub(1), |a, b| b.cmp(a));
+        let cutoff = if k == 0 { usize::MAX } else { counts[k - 1] };
+        let mut ties = counts[..k].iter().filter(|&&c| c == cutoff).count();
+        self.retain(|_, count| {
+            if count > cutoff {
+                true
+            } else if count == cutoff && ties > 0 {
+                ties -= 1;
+                true
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Removes every outcome whose probability is less than `threshold`.
+    pub fn prune_below(self, threshold: f64) -> Self {
+        let total = self.total();
+        let kept = 1.0 - self.discarded;
+        self.retain(|_, count| count as f64 / total * kept >= threshold)
+    }
+
+    /// Keeps only the outcomes for which `keep` returns `true`, adding the
+    /// probability of the others to the discarded probability.
+    pub fn retain(mut self, mut keep: impl FnMut(&I, usize) -> bool) -> Self {
+        let size = self.outcomes.len();
+        let total = self.total();
+        let mut removed = 0u128;
+        self.outcomes.retain(|outcome, count| {
+            let keep = keep(outcome, *count);
+            if !keep {
+                removed += *count as u128;
+            }
+            keep
+        });
+        telemetry::record_shrink("Approximate", size, size - self.outcomes.len());
+        if removed > 0 {
+            self.discarded += (1.0 - self.discarded) * removed as f64 / total;
+        }
+        self
+    }
+
+    /// Continues the evaluation of the random process from the outcomes which
+    /// have not been pruned, carrying the discarded probability forward.
+    ///
+    /// `func` must only use [`Counter`] operations, so that the relative
+    /// probabilities of the outcomes derived from each remaining outcome are
+    /// preserved.
+    ///
+    /// [`Counter`]: crate::Counter
+    #[inline]
+    pub fn map<J: Inner>(
+        self,
+        func: impl FnOnce(HashMap<I, usize, DefaultState>) -> HashMap<J, usize, DefaultState>,
+    ) -> Approximate<J> {
+        Approximate {
+            outcomes: func(self.outcomes),
+            discarded: self.discarded,
+        }
+    }
+
+    /// Returns bounds on the probability of `outcome`.
+    #[inline]
+    pub fn probability(&self, outcome: &I) -> ProbabilityBounds {
+        self.probability_where(|o| o == outcome)
+    }
+
+    /// Returns bounds on the probability of the event that `pred` holds.
+    ///
+    /// The lower bound is the probability of the matching outcomes which have
+    /// not been pruned, and the upper bound assumes that every pruned outcome
+    /// would have matched.
+    pub fn probability_where(&self, mut pred: impl FnMut(&I) -> bool) -> ProbabilityBounds {
+        let total = self.total();
+        let lower = if total == 0.0 {
+            0.0
+        } else {
+            let matching = self
+                .outcomes
+                .iter()
+                .filter(|(outcome, _)| pred(outcome))
+                .map(|(_, &count)| count as f64)
+                .sum::<f64>();
+            matching / total * (1.0 - self.discarded)
+        };
+        ProbabilityBounds {
+            lower,
+            upper: (lower + self.discarded).min(1.0),
+        }
+    }
+
+    fn total(&self) -> f64 {
+        self.outcomes.values().map(|&count| count as f64).sum()
+    }
+}
+
+impl<I: Inner> From<HashMap<I, usize, DefaultState>> for Approximate<I> {
+    #[inline]
+    fn from(outcomes: HashMap<I, usize, DefaultState>) -> Self {
+        Self::exact(outcomes)
+    }
+}
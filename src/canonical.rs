@@ -0,0 +1,146 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+/// A function mapping each value of type `T` to a canonical representative
+/// of its symmetry class, used by [`Canonical`] states.
+///
+/// This is implemented by a (typically zero-sized) marker type, so that the
+/// canonicalization is part of the type of the state. Values that should be
+/// treated as the same state, such as permutations of identical players, must
+/// be mapped to the same representative, and canonicalizing a representative
+/// must return it unchanged.
+pub trait Canonicalization<T> {
+    /// Returns the canonical representative of `value`.
+    fn canonicalize(value: T) -> T;
+}
+
+/// A state of type `T` that is canonicalized by the [`Canonicalization`] `C`
+/// whenever it is created.
+///
+/// As a `Canonical<T, C>` always holds a canonical representative, symmetric
+/// states compare and hash equal. Strategies which merge equal outcomes, such
+/// as [`Counter`] and [`UniqueEnumerator`], therefore merge symmetric states
+/// after every operation that produces them, which can shrink the enumerated
+/// space by orders of magnitude.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Canonical, Canonicalization, Counter, Functor, RandomStrategy};
+///
+/// // The two players are identical, so only the multiset of scores matters.
+/// struct Unordered;
+///
+/// impl Canonicalization<[u8; 2]> for Unordered {
+///     fn canonicalize(mut value: [u8; 2]) -> [u8; 2] {
+///         value.sort();
+///         value
+///     }
+/// }
+///
+/// let f = <Counter>::fmap_rand(Functor::pure([0; 2]), &mut thread_rng(), |[a, b], r: bool| {
+///     Canonical::<_, Unordered>::new(if r { [a + 1, b] } else { [a, b + 1] })
+/// });
+/// assert_eq!(f.len(), 1);
+/// assert_eq!(f[&Canonical::new([1, 0])], 2);
+/// ```
+///
+/// [`Counter`]: crate::Counter
+/// [`UniqueEnumerator`]: crate::UniqueEnumerator
+pub struct Canonical<T, C> {
+    value: T,
+    canonicalization: PhantomData<fn() -> C>,
+}
+
+impl<T, C: Canonicalization<T>> Canonical<T, C> {
+    /// Canonicalizes `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            value: C::canonicalize(value),
+            canonicalization: PhantomData,
+        }
+    }
+
+    /// Applies `func` to the canonical representative and canonicalizes the
+    /// result.
+    #[inline]
+    pub fn map(self, func: impl FnOnce(T) -> T) -> Self {
+        Self::new(func(self.value))
+    }
+}
+
+impl<T: Copy, C> Canonical<T, C> {
+    /// Returns the canonical representative.
+    #[inline]
+    pub fn get(self) -> T {
+        self.value
+    }
+}
+
+impl<T, C> Canonical<T, C> {
+    /// Returns the canonical representative.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, C: Canonicalization<T>> From<T> for Canonical<T, C> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, C> AsRef<T> for Canonical<T, C> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, C> Deref for Canonical<T, C> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+// These are implemented manually, rather than derived, so that they do not
+// require the canonicalization to implement them.
+impl<T: Clone, C> Clone for Canonical<T, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            canonicalization: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, C> Copy for Canonical<T, C> {}
+
+impl<T: fmt::Debug, C> fmt::Debug for Canonical<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Canonical").field(&self.value).finish()
+    }
+}
+
+impl<T: PartialEq, C> PartialEq for Canonical<T, C> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, C> Eq for Canonical<T, C> {}
+
+impl<T: Hash, C> Hash for Canonical<T, C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
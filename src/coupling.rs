@@ -0,0 +1,96 @@
+use std::vec::Vec;
+
+use rand::SeedableRng;
+use rand_core::RngCore;
+
+use crate::Inner;
+
+/// An exact sample from the stationary distribution of a random process,
+/// drawn by [`coupling_from_the_past`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ExactSample<T> {
+    /// The sampled state.
+    pub state: T,
+    /// The number of steps into the past from which the chains started by
+    /// the final attempt coalesced.
+    pub steps: usize,
+}
+
+/// Draws an exact sample from the stationary distribution of `step`, a
+/// monotone random process evaluated with [`Sampler`], by coupling from the
+/// past.
+///
+/// `bottom` and `top` must be the least and greatest states under the partial
+/// order `le`, and `step` must be monotone with respect to it: whenever
+/// `le(a, b)` holds, stepping `a` and `b` with identical random number
+/// generators must produce states which are also ordered. Two chains are then
+/// enough to bound every other chain. Starting from `1` step in the past, both
+/// chains are run up to the present, and the number of steps is doubled until
+/// they meet. The random number generator for each step in the past is seeded
+/// once from `rng` with [`SeedableRng::seed_from_u64`] and reused by every
+/// later attempt, which is what makes the sample exact rather than
+/// approximate. Each attempt is reported to the callback installed by
+/// [`with_progress`].
+///
+/// Returns [`None`] if the chains have not met after starting `max_steps`
+/// steps in the past.
+/// ```
+/// use rand::prelude::*;
+/// use rand_chacha::ChaCha8Rng;
+/// use rand_functors::{coupling_from_the_past, Functor, RandomStrategy, Sampler};
+///
+/// // A lazy random walk on 0..=3 which is uniformly distributed in the limit.
+/// let mut counts = [0; 4];
+/// for _ in 0..1000 {
+///     let sample = coupling_from_the_past(0u8, 3, |a, b| a <= b, 1024, &mut thread_rng(), |s, rng: &mut ChaCha8Rng| {
+///         Sampler::fmap_rand(s, rng, |s, r: bool| if r { (s + 1).min(3) } else { s.saturating_sub(1) })
+///     });
+///     counts[sample.unwrap().state as usize] += 1;
+/// }
+/// assert!(counts.iter().all(|&c| (150..350).contains(&c)));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `bottom` and `top` are not ordered by `le`, or if the two chains
+/// are ever observed out of order, which means that `step` is not monotone.
+///
+/// [`Sampler`]: crate::Sampler
+/// [`with_progress`]: crate::with_progress
+pub fn coupling_from_the_past<R: SeedableRng + RngCore, T: Inner>(
+    bottom: T,
+    top: T,
+    le: impl Fn(&T, &T) -> bool,
+    max_steps: usize,
+    rng: &mut impl RngCore,
+    mut step: impl FnMut(T, &mut R) -> T,
+) -> Option<ExactSample<T>> {
+    assert!(le(&bottom, &top), "bottom must not be greater than top");
+    // The seed of the step taken `i + 1` steps before the present.
+    let mut seeds = Vec::<u64>::new();
+    let mut steps = 1;
+    loop {
+        let steps_back = steps.min(max_steps);
+        while seeds.len() < steps_back {
+            seeds.push(rng.next_u64());
+        }
+        let mut lower = bottom.clone();
+        let mut upper = top.clone();
+        for &seed in seeds[..steps_back].iter().rev() {
+            lower = step(lower, &mut R::seed_from_u64(seed));
+            upper = step(upper, &mut R::seed_from_u64(seed));
+            assert!(le(&lower, &upper), "step must be monotone");
+        }
+        crate::progress::report_iteration("coupling_from_the_past", steps_back, Some(max_steps));
+        if lower == upper {
+            return Some(ExactSample {
+                state: lower,
+                steps: steps_back,
+            });
+        }
+        if steps_back == max_steps {
+            return None;
+        }
+        steps = steps.saturating_mul(2);
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+use std::vec;
+use std::vec::Vec;
+
+use num_traits::{FromPrimitive, NumAssign, PrimInt};
+
+use crate::DefaultState;
+
+/// The counts of every integer in a contiguous range, stored densely in a
+/// [`Vec`] rather than in the [`HashMap`] used by [`Counter`].
+///
+/// A [`Counter`] over an integer type whose outcomes fall within a small range
+/// can be compacted into `DenseCounts` with [`DenseCounts::compact`]. Looking
+/// up the count of a value is then an index into an array, rather than a hash
+/// table lookup. [`DenseCounts::convolve`] computes the distribution of the
+/// sum of two independent values, and [`DenseCounts::compare`] the weights of
+/// each ordering between them, without hashing a single outcome. Expressing
+/// the same arithmetic with [`Counter`] merges every pair of outcomes through
+/// a hash table, so long chains of sums, like the total of many dice, are
+/// much faster to compute densely.
+///
+/// Values are stored as offsets from the smallest value, as an [`i128`], so
+/// every integer type other than [`u128`] can be compacted. Values within the
+/// range with a count of zero are stored explicitly.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, DenseCounts, Functor, RandomStrategy};
+///
+/// let d6 = <Counter>::fmap_rand_range(Functor::pure(()), 1..=6u16, &mut thread_rng(), |_, r| r);
+/// let d6 = DenseCounts::compact(d6, 64).unwrap();
+/// let three_d6 = d6.convolve(&d6).convolve(&d6);
+/// assert_eq!(three_d6.min(), Some(3));
+/// assert_eq!(three_d6.get(10), 27);
+/// assert_eq!(three_d6.total(), 216);
+///
+/// // The number of ways for 3d6 to beat a d6, tie with it, or lose to it.
+/// let comparison = three_d6.compare(&d6);
+/// assert_eq!(comparison.greater + comparison.equal + comparison.less, 216 * 6);
+/// assert_eq!(comparison.less, 15);
+/// ```
+///
+/// [`Counter`]: crate::Counter
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DenseCounts<T, N = usize> {
+    start: i128,
+    counts: Vec<N>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+/// The total weights of each ordering of two independent values, as computed
+/// by [`DenseCounts::compare`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Comparison<N = usize> {
+    /// The weight of the first value being less than the second.
+    pub less: N,
+    /// The weight of the two values being equal.
+    pub equal: N,
+    /// The weight of the first value being greater than the second.
+    pub greater: N,
+}
+
+impl<T: PrimInt + FromPrimitive, N: Clone + NumAssign> DenseCounts<T, N> {
+    /// Creates a `DenseCounts` with the counts of consecutive values, the
+    /// first of which is `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` does not fit in an [`i128`].
+    pub fn new(start: T, counts: Vec<N>) -> Self {
+        Self {
+            start: start.to_i128().expect("values must fit in an i128"),
+            counts,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Compacts the outcomes of `f` into a `DenseCounts`, if they span at most
+    /// `max_len` consecutive values.
+    ///
+    /// Otherwise, `f` is returned unchanged, so that the random process can
+    /// continue to be evaluated with [`Counter`].
+    ///
+    /// [`Counter`]: crate::Counter
+    pub fn compact<S: BuildHasher>(
+        f: HashMap<T, N, S>,
+        max_len: usize,
+    ) -> Result<Self, HashMap<T, N, S>> {
+        let mut bounds = None;
+        for value in f.keys() {
+            let Some(value) = value.to_i128() else {
+                return Err(f);
+            };
+            let (min, max) = bounds.get_or_insert((value, value));
+            *min = value.min(*min);
+            *max = value.max(*max);
+        }
+        let Some((min, max)) = bounds else {
+            return Ok(Self {
+                start: 0,
+                counts: Vec::new(),
+                phantom: PhantomData,
+            });
+        };
+        let len = match max.checked_sub(min).map(usize::try_from) {
+            Some(Ok(span)) if span < max_len => span + 1,
+            _ => return Err(f),
+        };
+        let mut counts = vec![N::zero(); len];
+        for (value, count) in f {
+            let index = (value.to_i128().expect("checked above") - min) as usize;
+            counts[index] += count;
+        }
+        Ok(Self {
+            start: min,
+            counts,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns the number of consecutive values stored, including those with
+    /// a count of zero.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns `true` if no values are stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the count of each consecutive value, starting with
+    /// [`DenseCounts::min`].
+    #[inline]
+    pub fn counts(&self) -> &[N] {
+        &self.counts
+    }
+
+    /// Returns the smallest value stored, or [`None`] if there are none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value does not fit in `T`.
+    #[inline]
+    pub fn min(&self) -> Option<T> {
+        (!self.counts.is_empty()).then(|| self.value(0))
+    }
+
+    /// Returns the largest value stored, or [`None`] if there are none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value does not fit in `T`.
+    #[inline]
+    pub fn max(&self) -> Option<T> {
+        self.counts.len().checked_sub(1).map(|i| self.value(i))
+    }
+
+    /// Returns the count of `value`, which is zero if it is not stored.
+    pub fn get(&self, value: T) -> N {
+        value
+            .to_i128()
+            .and_then(|value| value.checked_sub(self.start))
+            .and_then(|index| usize::try_from(index).ok())
+            .and_then(|index| self.counts.get(index))
+            .cloned()
+            .unwrap_or_else(N::zero)
+    }
+
+    /// Returns the sum of the counts of every value.
+    pub fn total(&self) -> N {
+        self.counts
+            .iter()
+            .fold(N::zero(), |total, count| total + count.clone())
+    }
+
+    /// Returns an [`Iterator`] over the values with a nonzero count and their
+    /// counts, in ascending order of value.
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if a value does not fit in `T`.
+    pub fn iter(&self) -> impl Iterator<Item = (T, N)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| !count.is_zero())
+            .map(|(i, count)| (self.value(i), count.clone()))
+    }
+
+    /// Returns the counts of the sum of a value drawn from `self` and an
+    /// independent value drawn from `other`.
+    ///
+    /// The count of each sum is the number of pairs of outcomes producing it,
+    /// as if the sum had been computed with [`Counter`].
+    ///
+    /// [`Counter`]: crate::Counter
+    pub fn convolve(&self, other: &Self) -> Self {
+        if self.is_empty() || other.is_empty() {
+            return Self {
+                start: 0,
+                counts: Vec::new(),
+                phantom: PhantomData,
+            };
+        }
+        let mut counts = vec![N::zero(); self.len() + other.len() - 1];
+        for (i, a) in self.counts.iter().enumerate() {
+            if a.is_zero() {
+                continue;
+            }
+            for (j, b) in other.counts.iter().enumerate() {
+                counts[i + j] += a.clone() * b.clone();
+            }
+        }
+        Self {
+            start: self.start + other.start,
+            counts,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the total weights of a value drawn from `self` being less
+    /// than, equal to, or greater than an independent value drawn from
+    /// `other`.
+    ///
+    /// Each weight is the number of pairs of outcomes in that ordering, so the
+    /// three sum to the product of the totals of `self` and `other`.
+    pub fn compare(&self, other: &Self) -> Comparison<N> {
+        let mut below = Vec::with_capacity(other.len() + 1);
+        below.push(N::zero());
+        for count in &other.counts {
+            let total = below.last().cloned().unwrap_or_else(N::zero) + count.clone();
+            below.push(total);
+        }
+        let total = below.last().cloned().unwrap_or_else(N::zero);
+
+        let mut comparison = Comparison {
+            less: N::zero(),
+            equal: N::zero(),
+            greater: N::zero(),
+        };
+        for (i, a) in self.counts.iter().enumerate() {
+            if a.is_zero() {
+                continue;
+            }
+            let index = (self.start + i as i128 - other.start).clamp(0, other.len() as i128);
+            let index = index as usize;
+            let less_than = below[index].clone();
+            let equal = other.counts.get(index).cloned().unwrap_or_else(N::zero);
+            let greater_than = total.clone() - less_than.clone() - equal.clone();
+            comparison.greater += a.clone() * less_than;
+            comparison.equal += a.clone() * equal;
+            comparison.less += a.clone() * greater_than;
+        }
+        comparison
+    }
+
+    /// Converts back into the [`HashMap`] used by [`Counter`], omitting values
+    /// with a count of zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value does not fit in `T`.
+    ///
+    /// [`Counter`]: crate::Counter
+    pub fn into_counter<S: BuildHasher + Default>(self) -> HashMap<T, N, S>
+    where
+        T: core::hash::Hash,
+    {
+        let start = self.start;
+        self.counts
+            .into_iter()
+            .enumerate()
+            .filter(|(_, count)| !count.is_zero())
+            .map(|(i, count)| (Self::value_at(start, i), count))
+            .collect()
+    }
+
+    #[inline]
+    fn value(&self, index: usize) -> T {
+        Self::value_at(self.start, index)
+    }
+
+    #[inline]
+    fn value_at(start: i128, index: usize) -> T {
+        T::from_i128(start + index as i128).expect("value does not fit in the integer type")
+    }
+}
+
+impl<T: PrimInt + FromPrimitive + core::hash::Hash, N: Clone + NumAssign> From<DenseCounts<T, N>>
+    for HashMap<T, N, DefaultState>
+{
+    #[inline]
+    fn from(dense: DenseCounts<T, N>) -> Self {
+        dense.into_counter()
+    }
+}
@@ -0,0 +1,94 @@
+use core::fmt;
+
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{Cardinality, RandomVariable, SmallRandomVariable};
+
+/// The result of rolling a fair die with `SIDES` faces, numbered from one.
+///
+/// `Die` implements [`RandomVariable`], so a roll can be used directly with
+/// [`RandomStrategy::fmap_rand`]:
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, Functor, RandomStrategy, D6};
+///
+/// let f = <Counter>::fmap_rand(Functor::pure(0), &mut thread_rng(), |s, r: D6| s + r.value());
+/// let f = <Counter>::fmap_rand(f, &mut thread_rng(), |s, r: D6| s + r.value());
+/// assert_eq!(f[&7], 6);
+/// ```
+///
+/// A die with no sides causes a compile error when it is used.
+///
+/// [`RandomStrategy::fmap_rand`]: crate::RandomStrategy::fmap_rand
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Die<const SIDES: u8>(u8);
+
+/// A four-sided [`Die`].
+pub type D4 = Die<4>;
+/// A six-sided [`Die`].
+pub type D6 = Die<6>;
+/// An eight-sided [`Die`].
+pub type D8 = Die<8>;
+/// A ten-sided [`Die`].
+pub type D10 = Die<10>;
+/// A twelve-sided [`Die`].
+pub type D12 = Die<12>;
+/// A twenty-sided [`Die`].
+pub type D20 = Die<20>;
+
+impl<const SIDES: u8> Die<SIDES> {
+    const CHECKED_SIDES: u8 = {
+        assert!(SIDES != 0, "a die must have at least one side");
+        SIDES
+    };
+
+    /// Constructs a roll showing `value`, or returns [`None`] if the die has no
+    /// face numbered `value`.
+    #[inline]
+    pub fn new(value: u8) -> Option<Self> {
+        (1..=Self::CHECKED_SIDES)
+            .contains(&value)
+            .then_some(Self(value))
+    }
+
+    /// Returns the number shown by this roll, between one and `SIDES`.
+    #[inline]
+    pub fn value(self) -> u8 {
+        self.0
+    }
+}
+
+impl<const SIDES: u8> From<Die<SIDES>> for u8 {
+    #[inline]
+    fn from(die: Die<SIDES>) -> Self {
+        die.0
+    }
+}
+
+impl<const SIDES: u8> fmt::Display for Die<SIDES> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const SIDES: u8> Distribution<Die<SIDES>> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Die<SIDES> {
+        Die(rng.gen_range(1..=Die::<SIDES>::CHECKED_SIDES))
+    }
+}
+
+impl<const SIDES: u8> RandomVariable for Die<SIDES> {
+    #[inline]
+    fn sample_space() -> impl Iterator<Item = Self> {
+        (1..=Self::CHECKED_SIDES).map(Die)
+    }
+}
+
+impl<const SIDES: u8> SmallRandomVariable for Die<SIDES> {}
+
+impl<const SIDES: u8> Cardinality for Die<SIDES> {
+    const CARDINALITY: u128 = Self::CHECKED_SIDES as u128;
+}
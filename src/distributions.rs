@@ -0,0 +1,716 @@
+//! Enumerable versions of common discrete distributions.
+//!
+//! Each distribution in this module implements both [`Distribution`] and
+//! [`EnumerableDistribution`], allowing it to be used with
+//! [`WeightedRandomStrategy::fmap_rand_distribution`]. Probabilities are
+//! specified as exact fractions, so that the weights of the enumerated outcomes
+//! are exact integers.
+//!
+//! [`WeightedRandomStrategy::fmap_rand_distribution`]: crate::WeightedRandomStrategy::fmap_rand_distribution
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{EnumerableDistribution, RandomVariable, RandomVariableRange};
+
+/// An error produced when constructing one of the distributions in this
+/// module.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DistributionError {
+    /// The probability of success was not a fraction between 0 and 1, or its
+    /// denominator was zero.
+    InvalidProbability,
+    /// The range of values was empty.
+    EmptyRange,
+    /// The weight of at least one outcome does not fit in a [`u64`].
+    WeightOverflow,
+    /// The list of weights was empty, contained only zeros, or was too long.
+    InvalidWeights,
+    /// The number of successes or draws was larger than the population.
+    InvalidPopulation,
+}
+
+impl fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistributionError::InvalidProbability => {
+                write!(
+                    f,
+                    "probability must be a fraction in [0, 1] with a nonzero denominator"
+                )
+            }
+            DistributionError::EmptyRange => write!(f, "range must not be empty"),
+            DistributionError::WeightOverflow => write!(f, "weight of an outcome overflowed a u64"),
+            DistributionError::InvalidWeights => {
+                write!(
+                    f,
+                    "weights must be nonempty, not all zero, and not too numerous"
+                )
+            }
+            DistributionError::InvalidPopulation => {
+                write!(
+                    f,
+                    "successes and draws must not exceed the size of the population"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DistributionError {}
+
+#[inline]
+const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Reduces the fraction `numerator / denominator`, checking that it is a valid
+/// probability.
+#[inline]
+const fn reduce_probability(
+    numerator: u64,
+    denominator: u64,
+) -> Result<(u64, u64), DistributionError> {
+    if denominator == 0 || numerator > denominator {
+        return Err(DistributionError::InvalidProbability);
+    }
+    let divisor = gcd(numerator, denominator);
+    Ok((numerator / divisor, denominator / divisor))
+}
+
+/// Divides a list of weights by their greatest common divisor and converts
+/// them into cumulative weights.
+#[cfg(feature = "alloc")]
+#[inline]
+fn cumulative_weights(mut weights: Vec<u64>) -> Result<Vec<u64>, DistributionError> {
+    let divisor = weights.iter().copied().fold(0, gcd);
+    let mut total = 0u64;
+    for weight in weights.iter_mut() {
+        total = total
+            .checked_add(*weight / divisor)
+            .ok_or(DistributionError::WeightOverflow)?;
+        *weight = total;
+    }
+    Ok(weights)
+}
+
+/// Samples an index from a list of cumulative weights.
+#[cfg(feature = "alloc")]
+#[inline]
+fn sample_cumulative<R: Rng + ?Sized>(cumulative: &[u64], rng: &mut R) -> usize {
+    let total = *cumulative
+        .last()
+        .expect("cumulative weights must not be empty");
+    let x = rng.gen_range(0..total);
+    cumulative.partition_point(|&c| c <= x)
+}
+
+/// Iterates over the values of a list of cumulative weights and their
+/// individual weights, skipping values with zero weight.
+#[cfg(feature = "alloc")]
+#[inline]
+fn weighted_indices(cumulative: &[u64]) -> impl Iterator<Item = (usize, u64)> + '_ {
+    cumulative
+        .iter()
+        .scan(0, |previous, &c| {
+            let weight = c - *previous;
+            *previous = c;
+            Some(weight)
+        })
+        .enumerate()
+        .filter(|(_, weight)| *weight != 0)
+}
+
+/// The exact probability mass of a single value of a distribution, as passed
+/// to the closures of [`WeightedRandomStrategy::fmap_rand_with_prob`] and
+/// [`WeightedRandomStrategy::fmap_rand_distribution_with_prob`].
+///
+/// The fraction is always stored in lowest terms.
+///
+/// [`WeightedRandomStrategy::fmap_rand_with_prob`]: crate::WeightedRandomStrategy::fmap_rand_with_prob
+/// [`WeightedRandomStrategy::fmap_rand_distribution_with_prob`]: crate::WeightedRandomStrategy::fmap_rand_distribution_with_prob
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Probability {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl Probability {
+    /// The probability of a value which is certain to occur.
+    pub const ONE: Self = Self {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// Constructs the probability `numerator / denominator`.
+    #[inline]
+    pub fn new(numerator: u64, denominator: u64) -> Result<Self, DistributionError> {
+        let (numerator, denominator) = reduce_probability(numerator, denominator)?;
+        Ok(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// Returns the numerator of the probability in lowest terms.
+    #[inline]
+    pub fn numerator(self) -> u64 {
+        self.numerator
+    }
+
+    /// Returns the denominator of the probability in lowest terms.
+    #[inline]
+    pub fn denominator(self) -> u64 {
+        self.denominator
+    }
+
+    /// Returns the probability as an [`f64`].
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl fmt::Display for Probability {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl From<Probability> for f64 {
+    #[inline]
+    fn from(probability: Probability) -> Self {
+        probability.to_f64()
+    }
+}
+
+/// A distribution over the values of `D` paired with their probabilities.
+pub(crate) struct WithProbability<'a, D> {
+    distribution: &'a D,
+    total: u64,
+}
+
+impl<'a, D> WithProbability<'a, D> {
+    /// Pairs the values of `distribution` with their probabilities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total weight of `distribution` overflows a [`u64`].
+    #[inline]
+    pub(crate) fn new<T>(distribution: &'a D) -> Self
+    where
+        D: EnumerableDistribution<T>,
+    {
+        let total = distribution
+            .weighted_sample_space()
+            .try_fold(0u64, |total, (_, weight)| total.checked_add(weight))
+            .expect("the total weight of the distribution must fit in a u64");
+        Self {
+            distribution,
+            total,
+        }
+    }
+
+    #[inline]
+    fn probability(&self, weight: u64) -> Probability {
+        Probability::new(weight, self.total).expect("weights must not exceed their total")
+    }
+}
+
+impl<T: PartialEq, D: EnumerableDistribution<T>> Distribution<(T, Probability)>
+    for WithProbability<'_, D>
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (T, Probability) {
+        let value = self.distribution.sample(rng);
+        let weight = self
+            .distribution
+            .weighted_sample_space()
+            .find_map(|(v, weight)| (v == value).then_some(weight))
+            .expect("sampled values must be in the weighted sample space");
+        (value, self.probability(weight))
+    }
+}
+
+impl<T: PartialEq, D: EnumerableDistribution<T>> EnumerableDistribution<(T, Probability)>
+    for WithProbability<'_, D>
+{
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = ((T, Probability), u64)> {
+        self.distribution
+            .weighted_sample_space()
+            .map(|(value, weight)| ((value, self.probability(weight)), weight))
+    }
+}
+
+/// A distribution over [`bool`] which produces `true` with probability
+/// `numerator / denominator`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bernoulli {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl Bernoulli {
+    /// Constructs a new `Bernoulli` distribution with a probability of success
+    /// of `numerator / denominator`.
+    #[inline]
+    pub fn new(numerator: u64, denominator: u64) -> Result<Self, DistributionError> {
+        let (numerator, denominator) = reduce_probability(numerator, denominator)?;
+        Ok(Self {
+            numerator,
+            denominator,
+        })
+    }
+}
+
+impl Distribution<bool> for Bernoulli {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+        rng.gen_range(0..self.denominator) < self.numerator
+    }
+}
+
+impl EnumerableDistribution<bool> for Bernoulli {
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (bool, u64)> {
+        bernoulli_weights(self.numerator, self.denominator)
+    }
+}
+
+/// Iterates over the outcomes of a Bernoulli trial with a probability of
+/// success of `numerator / denominator` and their weights, skipping outcomes
+/// with zero weight.
+#[inline]
+fn bernoulli_weights(numerator: u64, denominator: u64) -> impl Iterator<Item = (bool, u64)> {
+    [(false, denominator - numerator), (true, numerator)]
+        .into_iter()
+        .filter(|(_, weight)| *weight != 0)
+}
+
+/// A [`Bernoulli`] distribution whose probability of success, `NUM / DEN`, is
+/// specified by const generic parameters.
+///
+/// As the probability is part of the type, a `ConstBernoulli` can be used
+/// without being constructed or unwrapped:
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::distributions::ConstBernoulli;
+/// use rand_functors::{Counter, Functor, WeightedRandomStrategy};
+///
+/// let f = <Counter>::fmap_rand_distribution(
+///     Functor::pure(()),
+///     &ConstBernoulli::<1, 3>,
+///     &mut thread_rng(),
+///     |_, b| b,
+/// );
+/// assert_eq!(f[&false], 2);
+/// assert_eq!(f[&true], 1);
+/// ```
+///
+/// A probability which is not a fraction in [0, 1] with a nonzero denominator
+/// causes a compile error when the distribution is used:
+/// ```compile_fail
+/// use rand::prelude::*;
+/// use rand_functors::distributions::ConstBernoulli;
+///
+/// let b: bool = thread_rng().sample(ConstBernoulli::<3, 2>);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ConstBernoulli<const NUM: u32, const DEN: u32>;
+
+impl<const NUM: u32, const DEN: u32> ConstBernoulli<NUM, DEN> {
+    const BERNOULLI: Bernoulli = match reduce_probability(NUM as u64, DEN as u64) {
+        Ok((numerator, denominator)) => Bernoulli {
+            numerator,
+            denominator,
+        },
+        Err(_) => panic!("probability must be a fraction in [0, 1] with a nonzero denominator"),
+    };
+}
+
+impl<const NUM: u32, const DEN: u32> Distribution<bool> for ConstBernoulli<NUM, DEN> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+        Self::BERNOULLI.sample(rng)
+    }
+}
+
+impl<const NUM: u32, const DEN: u32> EnumerableDistribution<bool> for ConstBernoulli<NUM, DEN> {
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (bool, u64)> {
+        let Bernoulli {
+            numerator,
+            denominator,
+        } = Self::BERNOULLI;
+        bernoulli_weights(numerator, denominator)
+    }
+}
+
+/// A distribution over the number of successes in `n` independent trials, each
+/// of which succeeds with probability `numerator / denominator`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Binomial {
+    cumulative_weights: Vec<u64>,
+}
+
+#[cfg(feature = "alloc")]
+impl Binomial {
+    /// Constructs a new `Binomial` distribution over `n` trials with a
+    /// probability of success of `numerator / denominator`.
+    ///
+    /// An error is returned if the weight of any number of successes does not
+    /// fit in a [`u64`].
+    pub fn new(n: u32, numerator: u64, denominator: u64) -> Result<Self, DistributionError> {
+        let (numerator, denominator) = reduce_probability(numerator, denominator)?;
+        let failure = denominator - numerator;
+        let weights = (0..=n)
+            .map(|k| {
+                let successes = numerator.checked_pow(k)?;
+                let failures = failure.checked_pow(n - k)?;
+                binomial_coefficient(n, k)?
+                    .checked_mul(successes)?
+                    .checked_mul(failures)
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(DistributionError::WeightOverflow)?;
+        Ok(Self {
+            cumulative_weights: cumulative_weights(weights)?,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+fn binomial_coefficient(n: u32, k: u32) -> Option<u64> {
+    let k = k.min(n - k) as u64;
+    let n = n as u64;
+    // Each intermediate result is itself a binomial coefficient, so the
+    // division is always exact.
+    (0..k).try_fold(1u64, |c, i| Some(c.checked_mul(n - i)? / (i + 1)))
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<u32> for Binomial {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+        sample_cumulative(&self.cumulative_weights, rng) as u32
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EnumerableDistribution<u32> for Binomial {
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (u32, u64)> {
+        weighted_indices(&self.cumulative_weights).map(|(k, weight)| (k as u32, weight))
+    }
+}
+
+/// A distribution over the number of successes in `draws` draws without
+/// replacement from a population of size `population` containing `successes`
+/// successes.
+///
+/// Each number of successes is enumerated once, weighted by the number of ways
+/// of drawing it, rather than enumerating every subset of the population.
+/// ```
+/// use rand_functors::distributions::Hypergeometric;
+/// use rand_functors::EnumerableDistribution;
+///
+/// // Draw a hand of five cards from a deck of 52 containing four aces. Each
+/// // weight is the number of hands with that many aces, divided by 48.
+/// let aces = Hypergeometric::new(52, 4, 5).unwrap();
+/// assert_eq!(
+///     aces.weighted_sample_space().collect::<Vec<_>>(),
+///     [(0, 35673), (1, 16215), (2, 2162), (3, 94), (4, 1)]
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Hypergeometric {
+    min_successes: u32,
+    cumulative_weights: Vec<u64>,
+}
+
+#[cfg(feature = "alloc")]
+impl Hypergeometric {
+    /// Constructs a new `Hypergeometric` distribution over the number of
+    /// successes in `draws` draws from a population of size `population`
+    /// containing `successes` successes.
+    ///
+    /// An error is returned if `successes` or `draws` is larger than
+    /// `population`, or if the weight of any number of successes does not fit
+    /// in a [`u64`].
+    pub fn new(population: u32, successes: u32, draws: u32) -> Result<Self, DistributionError> {
+        if successes > population || draws > population {
+            return Err(DistributionError::InvalidPopulation);
+        }
+        let failures = population - successes;
+        let min_successes = draws.saturating_sub(failures);
+        let weights = (min_successes..=draws.min(successes))
+            .map(|k| {
+                binomial_coefficient(successes, k)?
+                    .checked_mul(binomial_coefficient(failures, draws - k)?)
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(DistributionError::WeightOverflow)?;
+        Ok(Self {
+            min_successes,
+            cumulative_weights: cumulative_weights(weights)?,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<u32> for Hypergeometric {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+        self.min_successes + sample_cumulative(&self.cumulative_weights, rng) as u32
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EnumerableDistribution<u32> for Hypergeometric {
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (u32, u64)> {
+        weighted_indices(&self.cumulative_weights)
+            .map(|(k, weight)| (self.min_successes + k as u32, weight))
+    }
+}
+
+/// A uniform distribution over a [`RandomVariableRange`].
+///
+/// While [`RandomStrategy::fmap_rand_range`] can already sample from ranges,
+/// `DiscreteUniform` allows a range to be used wherever an
+/// [`EnumerableDistribution`] is expected.
+///
+/// [`RandomStrategy::fmap_rand_range`]: crate::RandomStrategy::fmap_rand_range
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DiscreteUniform<Ra> {
+    range: Ra,
+}
+
+impl<Ra> DiscreteUniform<Ra> {
+    /// Constructs a new `DiscreteUniform` distribution over the given range.
+    #[inline]
+    pub fn new<T: RandomVariable + SampleUniform>(range: Ra) -> Result<Self, DistributionError>
+    where
+        Ra: RandomVariableRange<T>,
+        Standard: Distribution<T>,
+    {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        Ok(Self { range })
+    }
+}
+
+impl<T: RandomVariable + SampleUniform, Ra: RandomVariableRange<T> + Clone> Distribution<T>
+    for DiscreteUniform<Ra>
+where
+    Standard: Distribution<T>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.range.clone().sample_single(rng)
+    }
+}
+
+impl<T: RandomVariable + SampleUniform, Ra: RandomVariableRange<T> + Clone>
+    EnumerableDistribution<T> for DiscreteUniform<Ra>
+where
+    Standard: Distribution<T>,
+{
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (T, u64)> {
+        self.range.sample_space().map(|t| (t, 1))
+    }
+}
+
+/// A Poisson distribution with a rate of `numerator / denominator`, truncated
+/// to the values `0..=max`.
+///
+/// The probabilities of values above `max` are not accumulated into `max`.
+/// Instead, the distribution is renormalized over `0..=max`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TruncatedPoisson {
+    cumulative_weights: Vec<u64>,
+}
+
+#[cfg(feature = "alloc")]
+impl TruncatedPoisson {
+    /// Constructs a new `TruncatedPoisson` distribution with a rate of
+    /// `numerator / denominator`, truncated to the values `0..=max`.
+    ///
+    /// An error is returned if the weight of any value does not fit in a
+    /// [`u64`].
+    pub fn new(numerator: u64, denominator: u64, max: u32) -> Result<Self, DistributionError> {
+        if denominator == 0 {
+            return Err(DistributionError::InvalidProbability);
+        }
+        let divisor = gcd(numerator, denominator);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        // Weights are proportional to rate^k / k!, scaled by
+        // denominator^max * max! to make every weight an integer.
+        let weights = (0..=max)
+            .map(|k| {
+                let falling_factorial =
+                    ((k + 1)..=max).try_fold(1u64, |product, i| product.checked_mul(i as u64))?;
+                numerator
+                    .checked_pow(k)?
+                    .checked_mul(denominator.checked_pow(max - k)?)?
+                    .checked_mul(falling_factorial)
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(DistributionError::WeightOverflow)?;
+        Ok(Self {
+            cumulative_weights: cumulative_weights(weights)?,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<u32> for TruncatedPoisson {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+        sample_cumulative(&self.cumulative_weights, rng) as u32
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EnumerableDistribution<u32> for TruncatedPoisson {
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (u32, u64)> {
+        weighted_indices(&self.cumulative_weights).map(|(k, weight)| (k as u32, weight))
+    }
+}
+
+/// A distribution over an arbitrary list of values with integer weights.
+///
+/// Values with a weight of zero are never sampled or enumerated, and the
+/// remaining weights are divided by their greatest common divisor. Sampling
+/// takes time logarithmic in the number of values.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Categorical<T> {
+    values: Vec<T>,
+    cumulative_weights: Vec<u64>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Categorical<T> {
+    /// Constructs a new `Categorical` distribution from an iterator of values
+    /// and their weights.
+    ///
+    /// An error is returned if there are no values with a nonzero weight, or
+    /// if the total weight does not fit in a [`u64`].
+    pub fn new(
+        weighted_values: impl IntoIterator<Item = (T, u64)>,
+    ) -> Result<Self, DistributionError> {
+        let (values, weights): (Vec<_>, Vec<_>) = weighted_values
+            .into_iter()
+            .filter(|(_, weight)| *weight != 0)
+            .unzip();
+        if weights.is_empty() {
+            return Err(DistributionError::InvalidWeights);
+        }
+        Ok(Self {
+            values,
+            cumulative_weights: cumulative_weights(weights)?,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> Distribution<T> for Categorical<T> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.values[sample_cumulative(&self.cumulative_weights, rng)].clone()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> EnumerableDistribution<T> for Categorical<T> {
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (T, u64)> {
+        weighted_indices(&self.cumulative_weights)
+            .map(|(i, weight)| (self.values[i].clone(), weight))
+    }
+}
+
+/// A distribution over an arbitrary list of values with integer weights,
+/// sampled in constant time using [`WeightedAliasIndex`].
+///
+/// `WeightedAlias` is intended for large weighted sample spaces, containing
+/// thousands of values. Enumeration strategies see the exact weights of every
+/// value, while [`Sampler`] can draw from the distribution without scanning
+/// the list of weights.
+///
+/// [`Sampler`]: crate::Sampler
+/// [`WeightedAliasIndex`]: rand_distr::WeightedAliasIndex
+#[cfg(feature = "rand_distr")]
+#[derive(Clone, Debug)]
+pub struct WeightedAlias<T> {
+    values: Vec<T>,
+    weights: Vec<u64>,
+    index: rand_distr::WeightedAliasIndex<u64>,
+}
+
+#[cfg(feature = "rand_distr")]
+impl<T> WeightedAlias<T> {
+    /// Constructs a new `WeightedAlias` distribution from an iterator of
+    /// values and their weights.
+    ///
+    /// Values with a weight of zero are never sampled or enumerated. An error
+    /// is returned if there are no values with a nonzero weight, or if the
+    /// total weight does not fit in a [`u64`].
+    pub fn new(
+        weighted_values: impl IntoIterator<Item = (T, u64)>,
+    ) -> Result<Self, DistributionError> {
+        let (values, weights): (Vec<_>, Vec<_>) = weighted_values
+            .into_iter()
+            .filter(|(_, weight)| *weight != 0)
+            .unzip();
+        weights
+            .iter()
+            .try_fold(0u64, |total, weight| total.checked_add(*weight))
+            .ok_or(DistributionError::WeightOverflow)?;
+        let index = rand_distr::WeightedAliasIndex::new(weights.clone())
+            .map_err(|_| DistributionError::InvalidWeights)?;
+        Ok(Self {
+            values,
+            weights,
+            index,
+        })
+    }
+}
+
+#[cfg(feature = "rand_distr")]
+impl<T: Clone> Distribution<T> for WeightedAlias<T> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.values[self.index.sample(rng)].clone()
+    }
+}
+
+#[cfg(feature = "rand_distr")]
+impl<T: Clone> EnumerableDistribution<T> for WeightedAlias<T> {
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (T, u64)> {
+        self.values
+            .iter()
+            .cloned()
+            .zip(self.weights.iter().copied())
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+
+use crate::{DefaultState, FlattenableRandomStrategy, Inner, UniqueEnumerator};
+
+/// The states reachable by a random process, as found by [`explore`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Exploration<T: Inner> {
+    states: HashSet<T, DefaultState>,
+    iterations: usize,
+    converged: bool,
+}
+
+impl<T: Inner> Exploration<T> {
+    /// Returns the states that were reached, including the initial states.
+    #[inline]
+    pub fn states(&self) -> &HashSet<T, DefaultState> {
+        &self.states
+    }
+
+    /// Returns the states that were reached, including the initial states.
+    #[inline]
+    pub fn into_states(self) -> HashSet<T, DefaultState> {
+        self.states
+    }
+
+    /// Returns the number of times the step was applied to the newly reached
+    /// states.
+    #[inline]
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns `true` if the set of reachable states stopped growing, in which
+    /// case it contains every state reachable from the initial states.
+    ///
+    /// Returns `false` if the bound on the number of iterations was reached,
+    /// or exploration was stopped, first.
+    #[inline]
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+/// Finds every state reachable from `initial_states` by repeatedly applying
+/// `step`, a random process evaluated with [`UniqueEnumerator`].
+///
+/// Each iteration applies `step` only to the states first reached by the
+/// previous iteration. Exploration stops once an iteration reaches no new
+/// states, or after `max_iterations` iterations, if it is [`Some`]. Each
+/// completed iteration is reported to the callback installed by
+/// [`with_progress`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{explore, Functor, RandomStrategy, UniqueEnumerator};
+///
+/// let reachable = explore([1u8], None, |s| {
+///     UniqueEnumerator::fmap_rand(Functor::pure(s), &mut thread_rng(), |s, r: bool| {
+///         if r { s * 2 % 7 } else { s * 3 % 7 }
+///     })
+/// });
+/// assert_eq!(reachable.states().len(), 6);
+/// assert!(reachable.converged());
+/// ```
+///
+/// [`with_progress`]: crate::with_progress
+pub fn explore<T: Inner>(
+    initial_states: impl IntoIterator<Item = T>,
+    max_iterations: Option<usize>,
+    step: impl FnMut(T) -> HashSet<T, DefaultState>,
+) -> Exploration<T> {
+    explore_until(initial_states, max_iterations, || false, step)
+}
+
+/// Finds the states reachable from `initial_states` like [`explore`], until
+/// `stop` returns `true`.
+///
+/// `stop` is called before each iteration, so it can check a cancellation
+/// flag or a deadline. If it returns `true`, exploration stops and the states
+/// reached by the completed iterations are returned, and
+/// [`Exploration::converged`] returns `false`.
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// use rand::prelude::*;
+/// use rand_functors::{explore_until, Functor, RandomStrategy, UniqueEnumerator};
+///
+/// let cancelled = AtomicBool::new(false);
+/// let reachable = explore_until([0u8], None, || cancelled.load(Ordering::Relaxed), |s| {
+///     if s == 3 {
+///         cancelled.store(true, Ordering::Relaxed);
+///     }
+///     UniqueEnumerator::fmap_rand(Functor::pure(s), &mut thread_rng(), |s, r: bool| {
+///         s.saturating_add(r as u8)
+///     })
+/// });
+/// assert_eq!(reachable.states().len(), 5);
+/// assert_eq!(reachable.iterations(), 4);
+/// assert!(!reachable.converged());
+/// ```
+pub fn explore_until<T: Inner>(
+    initial_states: impl IntoIterator<Item = T>,
+    max_iterations: Option<usize>,
+    mut stop: impl FnMut() -> bool,
+    mut step: impl FnMut(T) -> HashSet<T, DefaultState>,
+) -> Exploration<T> {
+    let mut states = initial_states.into_iter().collect::<HashSet<_, _>>();
+    let mut frontier = states.clone();
+    let mut iterations = 0;
+    while !frontier.is_empty() {
+        if max_iterations.is_some_and(|max| iterations >= max) || stop() {
+            return Exploration {
+                states,
+                iterations,
+                converged: false,
+            };
+        }
+        let reached = <UniqueEnumerator>::fmap_flat(frontier, &mut step);
+        frontier = reached
+            .into_iter()
+            .filter(|s| !states.contains(s))
+            .collect::<HashSet<_, _>>();
+        states.extend(frontier.iter().cloned());
+        iterations += 1;
+        crate::progress::report_iteration("explore", iterations, max_iterations);
+    }
+    Exploration {
+        states,
+        iterations,
+        converged: true,
+    }
+}
@@ -0,0 +1,6 @@
+#[cfg(feature = "arrow")]
+pub(crate) mod arrow;
+#[cfg(feature = "csv")]
+pub(crate) mod csv;
+#[cfg(feature = "rkyv")]
+pub(crate) mod rkyv;
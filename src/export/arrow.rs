@@ -0,0 +1,188 @@
+use std::sync::Arc;
+use std::vec::Vec;
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array,
+    Int8Array, RecordBatch, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use num_traits::ToPrimitive;
+
+use crate::{Inner, WeightedFunctor};
+
+/// A type that can be stored as a row of an Arrow [`RecordBatch`].
+///
+/// Implementations are provided for [`bool`] and the primitive numeric types
+/// supported by Arrow, each of which produces a single column named `value`.
+/// Structs of primitives will typically implement this trait by producing one
+/// column for each of their fields.
+/// ```
+/// use std::sync::Arc;
+///
+/// use arrow_array::{ArrayRef, UInt16Array, UInt8Array};
+/// use arrow_schema::{DataType, Field};
+/// use rand_functors::ArrowRecord;
+///
+/// struct State {
+///     health: u16,
+///     turn: u8,
+/// }
+///
+/// impl ArrowRecord for State {
+///     fn fields() -> Vec<Field> {
+///         vec![
+///             Field::new("health", DataType::UInt16, false),
+///             Field::new("turn", DataType::UInt8, false),
+///         ]
+///     }
+///
+///     fn columns(records: &[&Self]) -> Vec<ArrayRef> {
+///         vec![
+///             Arc::new(UInt16Array::from_iter_values(records.iter().map(|s| s.health))),
+///             Arc::new(UInt8Array::from_iter_values(records.iter().map(|s| s.turn))),
+///         ]
+///     }
+/// }
+/// ```
+pub trait ArrowRecord {
+    /// The fields describing the columns produced by
+    /// [`ArrowRecord::columns`].
+    fn fields() -> Vec<Field>;
+
+    /// Produce one column for each field returned by [`ArrowRecord::fields`],
+    /// with one row for each element of `records`.
+    fn columns(records: &[&Self]) -> Vec<ArrayRef>;
+}
+
+impl ArrowRecord for bool {
+    #[inline]
+    fn fields() -> Vec<Field> {
+        vec![Field::new("value", DataType::Boolean, false)]
+    }
+
+    #[inline]
+    fn columns(records: &[&Self]) -> Vec<ArrayRef> {
+        vec![Arc::new(BooleanArray::from_iter(
+            records.iter().map(|b| Some(**b)),
+        ))]
+    }
+}
+
+macro_rules! impl_arrow_record_for_primitive {
+    ($t:ty, $data_type:expr, $array:ty) => {
+        impl ArrowRecord for $t {
+            #[inline]
+            fn fields() -> Vec<Field> {
+                vec![Field::new("value", $data_type, false)]
+            }
+
+            #[inline]
+            fn columns(records: &[&Self]) -> Vec<ArrayRef> {
+                vec![Arc::new(<$array>::from_iter_values(
+                    records.iter().map(|r| **r),
+                ))]
+            }
+        }
+    };
+}
+
+impl_arrow_record_for_primitive!(u8, DataType::UInt8, UInt8Array);
+impl_arrow_record_for_primitive!(u16, DataType::UInt16, UInt16Array);
+impl_arrow_record_for_primitive!(u32, DataType::UInt32, UInt32Array);
+impl_arrow_record_for_primitive!(u64, DataType::UInt64, UInt64Array);
+
+impl_arrow_record_for_primitive!(i8, DataType::Int8, Int8Array);
+impl_arrow_record_for_primitive!(i16, DataType::Int16, Int16Array);
+impl_arrow_record_for_primitive!(i32, DataType::Int32, Int32Array);
+impl_arrow_record_for_primitive!(i64, DataType::Int64, Int64Array);
+
+impl_arrow_record_for_primitive!(f32, DataType::Float32, Float32Array);
+impl_arrow_record_for_primitive!(f64, DataType::Float64, Float64Array);
+
+/// Conversion of the outcomes stored in a [`WeightedFunctor`] and their weights
+/// into Arrow data.
+///
+/// `ArrowExt` is implemented for every [`WeightedFunctor`]. This trait is only
+/// available with the `arrow` feature.
+pub trait ArrowExt<I: Inner + ArrowRecord>: WeightedFunctor<I> {
+    /// Produces an Arrow [`RecordBatch`] containing the outcomes stored in this
+    /// functor and their weights.
+    ///
+    /// The columns of the batch are those produced by the outcomes'
+    /// [`ArrowRecord`] implementation, followed by a final `weight` column of
+    /// type `UInt64`. An error is returned if a weight does not fit in a
+    /// [`u64`].
+    fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        to_record_batch(schema::<I>(), self.weighted_outcomes())
+    }
+}
+
+impl<I: Inner + ArrowRecord, F: WeightedFunctor<I>> ArrowExt<I> for F {}
+
+/// Export of the outcomes stored in a [`WeightedFunctor`] and their weights as
+/// Parquet.
+///
+/// `ParquetExt` is implemented for every [`WeightedFunctor`]. This trait is
+/// only available with the `parquet` feature.
+#[cfg(feature = "parquet")]
+pub trait ParquetExt<I: Inner + ArrowRecord>: ArrowExt<I> {
+    /// Writes the outcomes stored in this functor and their weights to
+    /// `writer` as a Parquet file.
+    ///
+    /// The file has the same schema as the batch produced by
+    /// [`ArrowExt::to_record_batch`]. Outcomes are converted and written in
+    /// fixed-size batches, so the full outcome set is never duplicated in
+    /// memory.
+    fn write_parquet<W: std::io::Write + Send>(&self, writer: W) -> parquet::errors::Result<()> {
+        write_parquet(self.weighted_outcomes(), writer)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl<I: Inner + ArrowRecord, F: WeightedFunctor<I>> ParquetExt<I> for F {}
+
+pub(crate) fn schema<I: ArrowRecord>() -> Arc<Schema> {
+    let mut fields = I::fields();
+    fields.push(Field::new("weight", DataType::UInt64, false));
+    Arc::new(Schema::new(fields))
+}
+
+pub(crate) fn to_record_batch<'a, I: ArrowRecord + 'a, N: ToPrimitive>(
+    schema: Arc<Schema>,
+    outcomes: impl Iterator<Item = (&'a I, N)>,
+) -> Result<RecordBatch, ArrowError> {
+    let (records, weights): (Vec<_>, Vec<_>) = outcomes.unzip();
+    let weights = weights
+        .into_iter()
+        .map(|weight| {
+            weight
+                .to_u64()
+                .ok_or_else(|| ArrowError::CastError("weight does not fit in a u64".to_string()))
+        })
+        .collect::<Result<UInt64Array, _>>()?;
+    let mut columns = I::columns(&records);
+    columns.push(Arc::new(weights));
+    RecordBatch::try_new(schema, columns)
+}
+
+#[cfg(feature = "parquet")]
+pub(crate) fn write_parquet<'a, I: ArrowRecord + 'a, N: ToPrimitive, W: std::io::Write + Send>(
+    mut outcomes: impl Iterator<Item = (&'a I, N)>,
+    writer: W,
+) -> parquet::errors::Result<()> {
+    use parquet::arrow::ArrowWriter;
+
+    const BATCH_SIZE: usize = 1 << 16;
+
+    let schema = schema::<I>();
+    let mut writer = ArrowWriter::try_new(writer, schema.clone(), None)?;
+    loop {
+        let batch = to_record_batch(schema.clone(), outcomes.by_ref().take(BATCH_SIZE))?;
+        if batch.num_rows() == 0 {
+            break;
+        }
+        writer.write(&batch)?;
+    }
+    writer.close()?;
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use core::fmt::Display;
+use std::io::Write;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use csv::Writer;
+
+use crate::{Inner, WeightedFunctor};
+
+/// Export of the outcomes stored in a [`WeightedFunctor`] and their weights as
+/// CSV.
+///
+/// `CsvExt` is implemented for every [`WeightedFunctor`]. This trait is only
+/// available with the `csv` feature.
+pub trait CsvExt<I: Inner>: WeightedFunctor<I> {
+    /// Writes the outcomes stored in this functor and their weights to
+    /// `writer` as CSV.
+    ///
+    /// Each outcome is written as a single row, with the columns produced by
+    /// `projection` followed by a final `weight` column. `header` names the
+    /// columns produced by `projection`. An error is returned if `projection`
+    /// produces a different number of columns than `header` names, or if
+    /// `writer` fails.
+    fn write_csv<W, P, C>(&self, writer: W, header: &[&str], projection: P) -> csv::Result<()>
+    where
+        W: Write,
+        P: Fn(&I) -> C,
+        C: IntoIterator,
+        C::Item: Display,
+        Self::Weight: Display,
+    {
+        write_csv(self.weighted_outcomes(), writer, header, projection)
+    }
+
+    /// Produces a [`String`] containing the outcomes stored in this functor and
+    /// their weights as CSV.
+    ///
+    /// See [`CsvExt::write_csv`] for a description of the format. An error is
+    /// returned if `projection` produces a different number of columns than
+    /// `header` names.
+    fn to_csv<P, C>(&self, header: &[&str], projection: P) -> csv::Result<String>
+    where
+        P: Fn(&I) -> C,
+        C: IntoIterator,
+        C::Item: Display,
+        Self::Weight: Display,
+    {
+        let mut buffer = Vec::new();
+        self.write_csv(&mut buffer, header, projection)?;
+        Ok(String::from_utf8(buffer).expect("CSV fields are always valid UTF-8"))
+    }
+}
+
+impl<I: Inner, F: WeightedFunctor<I>> CsvExt<I> for F {}
+
+pub(crate) fn write_csv<'a, I: 'a, N: Display, W: Write, P, C>(
+    outcomes: impl Iterator<Item = (&'a I, N)>,
+    writer: W,
+    header: &[&str],
+    projection: P,
+) -> csv::Result<()>
+where
+    P: Fn(&I) -> C,
+    C: IntoIterator,
+    C::Item: Display,
+{
+    let mut writer = Writer::from_writer(writer);
+    writer.write_record(header.iter().copied().chain(["weight"]))?;
+    for (outcome, weight) in outcomes {
+        writer.write_record(
+            projection(outcome)
+                .into_iter()
+                .map(|field| field.to_string())
+                .chain([weight.to_string()]),
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::io::Write;
+use std::vec::Vec;
+
+use rkyv::api::high::{HighSerializer, HighValidator};
+use rkyv::bytecheck::CheckBytes;
+use rkyv::rancor::{Error, Source};
+use rkyv::ser::allocator::ArenaHandle;
+use rkyv::ser::writer::IoWriter;
+use rkyv::util::AlignedVec;
+use rkyv::vec::ArchivedVec;
+use rkyv::{Archive, Archived, Serialize};
+
+/// Archives the outcomes stored in a [`Vec`]-based functor, such as the one
+/// used by [`Enumerator`], into an aligned buffer.
+///
+/// The buffer can be accessed again in place with [`access_outcomes`], without
+/// a deserialization pass.
+///
+/// [`Enumerator`]: crate::Enumerator
+#[allow(clippy::ptr_arg)]
+pub fn archive_outcomes<I>(outcomes: &Vec<I>) -> Result<AlignedVec, Error>
+where
+    I: for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, Error>>,
+{
+    rkyv::to_bytes(outcomes)
+}
+
+/// Archives the outcomes stored in a [`Vec`]-based functor, such as the one
+/// used by [`Enumerator`], directly into `writer`.
+///
+/// Unlike [`archive_outcomes`], this does not buffer the archive in memory,
+/// which makes it suitable for writing very large enumerations to files that
+/// will later be memory-mapped and accessed with [`access_outcomes`].
+///
+/// [`Enumerator`]: crate::Enumerator
+#[allow(clippy::ptr_arg)]
+pub fn write_archived_outcomes<I, W: Write>(outcomes: &Vec<I>, writer: W) -> Result<W, Error>
+where
+    I: for<'a> Serialize<HighSerializer<IoWriter<W>, ArenaHandle<'a>, Error>>,
+{
+    rkyv::api::high::to_bytes_in(outcomes, IoWriter::new(writer)).map(IoWriter::into_inner)
+}
+
+/// Accesses the outcomes archived by [`archive_outcomes`] or
+/// [`write_archived_outcomes`] in place, after validating the archive.
+///
+/// `bytes` must be aligned to at least the alignment of the archived outcomes.
+/// Buffers produced by [`archive_outcomes`] and memory-mapped files both
+/// satisfy this requirement.
+pub fn access_outcomes<I: Archive, E: Source>(bytes: &[u8]) -> Result<&ArchivedVec<Archived<I>>, E>
+where
+    Archived<I>: for<'a> CheckBytes<HighValidator<'a, E>>,
+{
+    rkyv::access::<ArchivedVec<Archived<I>>, E>(bytes)
+}
+
+/// Accesses the outcomes archived by [`archive_outcomes`] or
+/// [`write_archived_outcomes`] in place, without validating the archive.
+///
+/// This avoids the linear-time validation pass performed by
+/// [`access_outcomes`].
+///
+/// # Safety
+///
+/// `bytes` must contain a valid archive of a `Vec<I>`, such as one produced by
+/// [`archive_outcomes`], and must be suitably aligned.
+pub unsafe fn access_outcomes_unchecked<I: Archive>(bytes: &[u8]) -> &ArchivedVec<Archived<I>> {
+    rkyv::access_unchecked::<ArchivedVec<Archived<I>>>(bytes)
+}
@@ -0,0 +1,59 @@
+//! Random variables representing sets of flags.
+//!
+//! A set of flags is stored as an integer mask in which only some bits are
+//! valid. The [`flags!`](crate::flags!) macro defines such a type and
+//! implements [`RandomVariable`] for it, with a sample space that contains
+//! exactly the masks made up of valid bits, rather than every value of the
+//! underlying integer.
+//!
+//! [`RandomVariable`]: crate::RandomVariable
+
+use num_traits::{One, PrimInt, Zero};
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+#[doc(hidden)]
+pub use rand as __rand;
+
+/// A set of flags, stored as an integer mask of type [`Flags::Bits`].
+///
+/// This trait is implemented by the types defined with the
+/// [`flags!`](crate::flags!) macro.
+pub trait Flags: Copy + Eq {
+    /// The integer type storing the mask.
+    type Bits: PrimInt;
+
+    /// The set containing every valid flag.
+    const ALL: Self;
+
+    /// Returns the mask of this set of flags.
+    fn bits(self) -> Self::Bits;
+
+    /// Constructs a set of flags from a mask, discarding any invalid bits.
+    fn from_bits_truncate(bits: Self::Bits) -> Self;
+}
+
+/// Produces an [`Iterator`] over every set of valid flags of type `F`.
+///
+/// Sets are produced in ascending order of their masks, starting with the
+/// empty set and ending with [`Flags::ALL`].
+pub fn sample_space<F: Flags>() -> impl Iterator<Item = F> {
+    let all = F::ALL.bits();
+    let mut next = Some(F::Bits::zero());
+    core::iter::from_fn(move || {
+        let bits = next?;
+        // Setting every invalid bit before incrementing carries past them, so
+        // this produces the next larger mask of valid bits.
+        next = (bits != all).then(|| ((bits | !all) + F::Bits::one()) & all);
+        Some(F::from_bits_truncate(bits))
+    })
+}
+
+/// Samples a set of valid flags of type `F` uniformly at random.
+#[inline]
+pub fn sample<F: Flags, R: Rng + ?Sized>(rng: &mut R) -> F
+where
+    Standard: Distribution<F::Bits>,
+{
+    F::from_bits_truncate(rng.gen())
+}
@@ -7,8 +7,16 @@ use std::collections::{HashMap, HashSet};
 #[cfg(feature = "std")]
 use std::hash::BuildHasher;
 
-use num_traits::NumAssign;
+#[cfg(feature = "bitvec")]
+use bitvec::vec::BitVec;
 
+#[cfg(feature = "std")]
+use num_traits::{NumAssign, ToPrimitive};
+
+#[cfg(feature = "alloc")]
+use crate::FromWeightedIterator;
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+use crate::WeightedFunctor;
 use crate::{Functor, Inner};
 
 impl<I: Inner> Functor<I> for I {
@@ -16,6 +24,11 @@ impl<I: Inner> Functor<I> for I {
     fn pure(i: I) -> I {
         i
     }
+
+    #[inline]
+    fn size(&self) -> usize {
+        1
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -24,6 +37,11 @@ impl<I: Inner> Functor<I> for Vec<I> {
     fn pure(i: I) -> Self {
         vec![i]
     }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.len()
+    }
 }
 
 #[cfg(feature = "std")]
@@ -36,6 +54,11 @@ impl<I: Inner, N: Clone + Default + NumAssign, S: BuildHasher + Default> Functor
         hm.insert(i, N::one());
         hm
     }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.len()
+    }
 }
 
 #[cfg(feature = "std")]
@@ -45,4 +68,157 @@ impl<I: Inner, S: BuildHasher + Default> Functor<I> for HashSet<I, S> {
         hs.insert(i);
         hs
     }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Stores each outcome of a random process over [`bool`] in a single bit.
+///
+/// The outcomes produced by the [`Vec`]-backed strategies can be packed into a
+/// [`BitVec`] with [`FromIterator`], which uses an eighth of the memory of the
+/// equivalent [`Vec<bool>`].
+#[cfg(feature = "bitvec")]
+impl Functor<bool> for BitVec {
+    #[inline]
+    fn pure(i: bool) -> Self {
+        BitVec::repeat(i, 1)
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<I: Inner, const N: usize> Functor<I> for heapless::Vec<I, N> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        let mut v = Self::new();
+        // A vector with no capacity is left empty.
+        let _ = v.push(i);
+        v
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Inner> WeightedFunctor<I> for Vec<I> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        self.iter().map(|i| (i, 1))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Inner, N: Clone + Default + NumAssign + ToPrimitive, S: BuildHasher + Default>
+    WeightedFunctor<I> for HashMap<I, N, S>
+{
+    type Weight = N;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, N)>
+    where
+        I: 'a,
+    {
+        self.iter().map(|(i, n)| (i, n.clone()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Inner, S: BuildHasher + Default> WeightedFunctor<I> for HashSet<I, S> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        self.iter().map(|i| (i, 1))
+    }
+}
+
+#[cfg(feature = "bitvec")]
+impl WeightedFunctor<bool> for BitVec {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a bool, usize)>
+    where
+        bool: 'a,
+    {
+        self.iter().map(|b| (if *b { &true } else { &false }, 1))
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<I: Inner, const N: usize> WeightedFunctor<I> for heapless::Vec<I, N> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        self.iter().map(|i| (i, 1))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Inner> FromWeightedIterator<I> for Vec<I> {
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, usize)>>(&mut self, outcomes: T) {
+        for (i, weight) in outcomes {
+            self.extend(core::iter::repeat(i).take(weight));
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Inner, N: Clone + Default + NumAssign + ToPrimitive, S: BuildHasher + Default>
+    FromWeightedIterator<I> for HashMap<I, N, S>
+{
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, N)>>(&mut self, outcomes: T) {
+        for (i, weight) in outcomes {
+            if !weight.is_zero() {
+                *self.entry(i).or_insert_with(N::zero) += weight;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Inner, S: BuildHasher + Default> FromWeightedIterator<I> for HashSet<I, S> {
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, usize)>>(&mut self, outcomes: T) {
+        self.extend(
+            outcomes
+                .into_iter()
+                .filter(|&(_, weight)| weight != 0)
+                .map(|(i, _)| i),
+        );
+    }
+}
+
+#[cfg(feature = "bitvec")]
+impl FromWeightedIterator<bool> for BitVec {
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (bool, usize)>>(&mut self, outcomes: T) {
+        for (b, weight) in outcomes {
+            self.extend(core::iter::repeat(b).take(weight));
+        }
+    }
 }
@@ -0,0 +1,117 @@
+use core::fmt;
+
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{Cardinality, RandomVariable, SmallRandomVariable};
+
+/// An unsigned integer whose sample space is enumerated in reflected binary
+/// Gray-code order.
+///
+/// `Gray` has the same sample space as the integer type it wraps, but its
+/// values are enumerated so that consecutive values differ in exactly one bit.
+/// When each outcome is derived from an expensive function of the bits of the
+/// random variable, this allows it to be updated incrementally from the
+/// previous outcome rather than recomputed. [`Gray::changed_bit`] returns the
+/// bit which differs between consecutive values.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Enumerator, Functor, Gray, RandomStrategy, RandomVariable};
+///
+/// let values = Gray::<u8>::sample_space().map(Gray::value).take(4).collect::<Vec<_>>();
+/// assert_eq!(values, [0b00, 0b01, 0b11, 0b10]);
+///
+/// let f = Enumerator::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: Gray<u8>| r.value());
+/// assert!(f.windows(2).all(|w| (w[0] ^ w[1]).count_ones() == 1));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Gray<T>(T);
+
+impl<T: Copy> Gray<T> {
+    /// Wraps `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the wrapped value.
+    #[inline]
+    pub fn value(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! impl_gray {
+    ($t:ty) => {
+        impl Gray<$t> {
+            /// Returns the value at position `index` in the Gray-code order.
+            #[inline]
+            pub fn from_index(index: $t) -> Self {
+                Self(index ^ (index >> 1))
+            }
+
+            /// Returns the position of this value in the Gray-code order.
+            #[inline]
+            pub fn index(self) -> $t {
+                let mut index = self.0;
+                let mut shift = 1;
+                while shift < <$t>::BITS {
+                    index ^= index >> shift;
+                    shift <<= 1;
+                }
+                index
+            }
+
+            /// Returns the position of the only bit which differs between this
+            /// value and `next`, or [`None`] if they do not differ in exactly
+            /// one bit.
+            #[inline]
+            pub fn changed_bit(self, next: Self) -> Option<u32> {
+                let difference = self.0 ^ next.0;
+                (difference.count_ones() == 1).then(|| difference.trailing_zeros())
+            }
+        }
+
+        impl From<Gray<$t>> for $t {
+            #[inline]
+            fn from(gray: Gray<$t>) -> Self {
+                gray.0
+            }
+        }
+
+        impl Distribution<Gray<$t>> for Standard {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Gray<$t> {
+                Gray(rng.gen())
+            }
+        }
+
+        impl RandomVariable for Gray<$t> {
+            #[inline]
+            fn sample_space() -> impl Iterator<Item = Self> {
+                (<$t>::MIN..=<$t>::MAX).map(Self::from_index)
+            }
+        }
+
+        impl Cardinality for Gray<$t> {
+            const CARDINALITY: u128 = <$t as Cardinality>::CARDINALITY;
+        }
+    };
+}
+
+impl_gray!(u8);
+impl_gray!(u16);
+impl_gray!(u32);
+impl_gray!(u64);
+impl_gray!(u128);
+impl_gray!(usize);
+
+impl SmallRandomVariable for Gray<u8> {}
+impl SmallRandomVariable for Gray<u16> {}
+
+impl<T: fmt::Display> fmt::Display for Gray<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
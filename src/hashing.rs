@@ -0,0 +1,137 @@
+use std::hash::{BuildHasher, Hasher};
+
+/// The [`BuildHasher`] used by default by the strategies that store their
+/// outcomes in hash tables, such as [`Counter`] and [`UniqueEnumerator`].
+///
+/// This is [`RandomState`]. A strategy can be made to use
+/// [`DeterministicState`] instead through its hasher parameter, as in
+/// `Counter<DeterministicState>`.
+///
+/// [`Counter`]: crate::Counter
+/// [`RandomState`]: std::collections::hash_map::RandomState
+/// [`UniqueEnumerator`]: crate::UniqueEnumerator
+pub type DefaultState = std::collections::hash_map::RandomState;
+
+/// A [`BuildHasher`] whose hashers are seeded identically every time, so that
+/// hash tables built from the same operations iterate in the same order.
+///
+/// Unlike [`RandomState`], which is seeded randomly for each process,
+/// `DeterministicState` makes identical runs of a random process produce
+/// identical functors, down to the order in which their outcomes are visited
+/// and serialized, across processes and machines. Integers are hashed as
+/// fixed-width little-endian bytes, so the result does not depend on the
+/// endianness or pointer width of the machine.
+///
+/// `DeterministicState` uses the 64-bit FNV-1a hash function. As its seed is
+/// public, it offers no protection against inputs crafted to collide, so it
+/// should only be used on outcomes that are not controlled by an adversary.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, DeterministicState, Functor, RandomStrategy};
+///
+/// fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u16> {
+///     S::fmap_rand(Functor::pure(0), rng, |s, r: u8| s + r as u16 % 10)
+/// }
+///
+/// type Deterministic = Counter<DeterministicState>;
+/// let first: Vec<_> = random_process::<Deterministic>(&mut thread_rng()).into_iter().collect();
+/// let second: Vec<_> = random_process::<Deterministic>(&mut thread_rng()).into_iter().collect();
+/// assert_eq!(first, second);
+/// ```
+///
+/// [`RandomState`]: std::collections::hash_map::RandomState
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DeterministicState;
+
+impl BuildHasher for DeterministicState {
+    type Hasher = DeterministicHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> DeterministicHasher {
+        DeterministicHasher::default()
+    }
+}
+
+/// The [`Hasher`] built by [`DeterministicState`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DeterministicHasher {
+    state: u64,
+}
+
+impl DeterministicHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+}
+
+impl Default for DeterministicHasher {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+}
+
+impl Hasher for DeterministicHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state = (self.state ^ byte as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
@@ -0,0 +1,116 @@
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{Cardinality, RandomVariable, SmallRandomVariable};
+
+/// A pair of independent [`RandomVariable`]s.
+///
+/// The sample space of `Joint<A, B>` is the Cartesian product of the sample
+/// spaces of `A` and `B`, enumerated with `A` as the outer variable. Each
+/// component is sampled independently from [`Standard`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Cardinality, Counter, Functor, Joint, RandomStrategy};
+///
+/// assert_eq!(<Joint<bool, u8>>::CARDINALITY, 512);
+/// let f = <Counter>::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, Joint(a, b): Joint<bool, bool>| {
+///     a && b
+/// });
+/// assert_eq!(f[&true], 1);
+/// ```
+///
+/// `Joint<A, B>` implements [`SmallRandomVariable`] when both `A` and `B` do.
+/// Nesting `Joint`s, as in `Joint<A, Joint<B, C>>`, produces the joint sample
+/// space of more than two variables.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Joint<A, B>(pub A, pub B);
+
+impl<A, B> From<(A, B)> for Joint<A, B> {
+    #[inline]
+    fn from((a, b): (A, B)) -> Self {
+        Joint(a, b)
+    }
+}
+
+impl<A, B> From<Joint<A, B>> for (A, B) {
+    #[inline]
+    fn from(Joint(a, b): Joint<A, B>) -> Self {
+        (a, b)
+    }
+}
+
+impl<A, B> Distribution<Joint<A, B>> for Standard
+where
+    Standard: Distribution<A> + Distribution<B>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Joint<A, B> {
+        Joint(self.sample(rng), self.sample(rng))
+    }
+}
+
+impl<A: RandomVariable + Clone, B: RandomVariable> RandomVariable for Joint<A, B>
+where
+    Standard: Distribution<A> + Distribution<B>,
+{
+    #[inline]
+    fn sample_space() -> impl Iterator<Item = Self> {
+        product_space(A::sample_space, B::sample_space).map(Joint::from)
+    }
+}
+
+impl<A: SmallRandomVariable + Clone, B: SmallRandomVariable> SmallRandomVariable for Joint<A, B> where
+    Standard: Distribution<A> + Distribution<B>
+{
+}
+
+impl<A: Cardinality + Clone, B: Cardinality> Cardinality for Joint<A, B>
+where
+    Standard: Distribution<A> + Distribution<B>,
+{
+    const CARDINALITY: u128 = A::CARDINALITY.saturating_mul(B::CARDINALITY);
+}
+
+/// Produces the Cartesian product of two sample spaces.
+///
+/// Each sample space is given as a function producing it, such as
+/// [`RandomVariable::sample_space`], since the second sample space must be
+/// produced again for each value of the first. Pairs are produced with
+/// `a_space` as the outer sample space.
+/// ```
+/// use rand::distributions::Standard;
+/// use rand::prelude::*;
+/// use rand_functors::{product_space, RandomVariable};
+///
+/// #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+/// struct Coordinate {
+///     x: u8,
+///     y: u8,
+/// }
+///
+/// # impl Distribution<Coordinate> for Standard {
+/// #     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Coordinate {
+/// #         Coordinate {
+/// #             x: self.sample(rng),
+/// #             y: self.sample(rng),
+/// #         }
+/// #     }
+/// # }
+/// #
+/// impl RandomVariable for Coordinate {
+///     fn sample_space() -> impl Iterator<Item = Self> {
+///         product_space(u8::sample_space, u8::sample_space).map(|(x, y)| Coordinate { x, y })
+///     }
+/// }
+///
+/// assert_eq!(Coordinate::sample_space().count(), 65536);
+/// ```
+#[inline]
+pub fn product_space<A: Clone, B, IA: IntoIterator<Item = A>, IB: IntoIterator<Item = B>>(
+    a_space: impl FnOnce() -> IA,
+    b_space: impl Fn() -> IB,
+) -> impl Iterator<Item = (A, B)> {
+    a_space()
+        .into_iter()
+        .flat_map(move |a| b_space().into_iter().map(move |b| (a.clone(), b)))
+}
@@ -0,0 +1,133 @@
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{Cardinality, RandomVariable, SmallRandomVariable};
+
+/// The largest number of values in the sample space of a random variable
+/// wrapped in [`LargeSampleSpace`].
+pub const LARGE_SAMPLE_SPACE_CARDINALITY: u128 = 1 << 32;
+
+/// A [`RandomVariable`] with up to 2<sup>32</sup> values, such as [`u32`] or
+/// [`i32`], whose sample space is explicitly allowed to be enumerated.
+///
+/// Types with more values than [`u16`] do not implement
+/// [`SmallRandomVariable`], so enumerating them with
+/// [`RandomStrategy::fmap_rand`] by accident is a compile error. Wrapping one
+/// in `LargeSampleSpace` opts out of this protection for a single use, when
+/// the machine evaluating the random process has the memory to store billions
+/// of outcomes for every state. Enumerating a `LargeSampleSpace` of a type
+/// with more than [`LARGE_SAMPLE_SPACE_CARDINALITY`] values causes a compile
+/// error:
+/// ```compile_fail
+/// use rand::prelude::*;
+/// use rand_functors::{Enumerator, Functor, LargeSampleSpace, RandomStrategy};
+///
+/// let f = Enumerator::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: LargeSampleSpace<u64>| r.0);
+/// ```
+///
+/// [`Sampler`] draws a single value rather than enumerating the sample space,
+/// so it accepts a `LargeSampleSpace` of a type of any size:
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{LargeSampleSpace, RandomStrategy, Sampler};
+///
+/// let r = Sampler::fmap_rand((), &mut thread_rng(), |_, r: LargeSampleSpace<u64>| r.0);
+/// ```
+///
+/// The sample space of a `LargeSampleSpace` reports its exact remaining size,
+/// so [`Enumerator`] reserves the memory for the outcomes of each state it
+/// expands in a single allocation, rather than growing its functor repeatedly
+/// while expanding it. Progress through the expansion is reported to the
+/// callback installed by [`with_progress`] every [`EXPANSION_INTERVAL`]
+/// outcomes.
+///
+/// ```no_run
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, Functor, LargeSampleSpace, RandomStrategy};
+///
+/// let f = <Counter>::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: LargeSampleSpace<u32>| {
+///     r.0.count_ones()
+/// });
+/// assert_eq!(f[&16], 601_080_390);
+/// ```
+///
+/// This type is only available with the `large-sample-spaces` feature.
+///
+/// [`Enumerator`]: crate::Enumerator
+/// [`EXPANSION_INTERVAL`]: crate::EXPANSION_INTERVAL
+/// [`RandomStrategy::fmap_rand`]: crate::RandomStrategy::fmap_rand
+/// [`Sampler`]: crate::Sampler
+/// [`with_progress`]: crate::with_progress
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LargeSampleSpace<R>(pub R);
+
+impl<R: Cardinality> LargeSampleSpace<R>
+where
+    Standard: Distribution<R>,
+{
+    const CHECKED_CARDINALITY: u128 = {
+        assert!(
+            R::CARDINALITY <= LARGE_SAMPLE_SPACE_CARDINALITY,
+            "the sample space of a LargeSampleSpace must have at most 2^32 values"
+        );
+        R::CARDINALITY
+    };
+}
+
+impl<R> Distribution<LargeSampleSpace<R>> for Standard
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> LargeSampleSpace<R> {
+        LargeSampleSpace(self.sample(rng))
+    }
+}
+
+impl<R: Cardinality> RandomVariable for LargeSampleSpace<R>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample_space() -> impl Iterator<Item = Self> {
+        SampleSpace {
+            values: R::sample_space(),
+            remaining: Self::CHECKED_CARDINALITY as u64,
+        }
+    }
+}
+
+impl<R: Cardinality> SmallRandomVariable for LargeSampleSpace<R> where Standard: Distribution<R> {}
+
+impl<R: Cardinality> Cardinality for LargeSampleSpace<R>
+where
+    Standard: Distribution<R>,
+{
+    const CARDINALITY: u128 = R::CARDINALITY;
+}
+
+/// The sample space of a [`LargeSampleSpace`], which reports the number of
+/// values remaining in it.
+struct SampleSpace<I> {
+    values: I,
+    remaining: u64,
+}
+
+impl<R, I: Iterator<Item = R>> Iterator for SampleSpace<I> {
+    type Item = LargeSampleSpace<R>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.values.next()?;
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(LargeSampleSpace(value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
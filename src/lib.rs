@@ -65,18 +65,146 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+pub use approximate::{Approximate, ProbabilityBounds};
+pub use canonical::{Canonical, Canonicalization};
+#[cfg(feature = "std")]
+pub use coupling::{coupling_from_the_past, ExactSample};
+#[cfg(feature = "std")]
+pub use dense::{Comparison, DenseCounts};
+pub use dice::{Die, D10, D12, D20, D4, D6, D8};
+#[cfg(feature = "std")]
+pub use explore::{explore, explore_until, Exploration};
+#[cfg(feature = "parquet")]
+pub use export::arrow::ParquetExt;
+#[cfg(feature = "arrow")]
+pub use export::arrow::{ArrowExt, ArrowRecord};
+#[cfg(feature = "csv")]
+pub use export::csv::CsvExt;
+#[cfg(feature = "rkyv")]
+pub use export::rkyv::{
+    access_outcomes, access_outcomes_unchecked, archive_outcomes, write_archived_outcomes,
+};
+pub use gray::Gray;
+#[cfg(feature = "std")]
+pub use hashing::{DefaultState, DeterministicHasher, DeterministicState};
+pub use joint::{product_space, Joint};
+#[cfg(feature = "large-sample-spaces")]
+pub use large::{LargeSampleSpace, LARGE_SAMPLE_SPACE_CARDINALITY};
+#[cfg(feature = "nalgebra")]
+pub use linalg::{probability_vector, transition_matrix};
+#[cfg(feature = "std")]
+pub use memoized::Memoized;
+#[cfg(feature = "std")]
+pub use monte_carlo::{monte_carlo, monte_carlo_until, MonteCarlo};
+pub use patch::Patch;
+pub use pipeline::Pipeline;
+#[cfg(feature = "plotters")]
+pub use plot::{render_histogram, HistogramKind, HistogramOptions};
+#[cfg(feature = "std")]
+pub use progress::{with_progress, Progress, EXPANSION_INTERVAL};
+pub use random_variable_ranges::RevSpace;
+#[cfg(feature = "std")]
+pub use report::{DisplayDistribution, DistributionReport, OutcomeProbability, ReportExt};
+pub use restricted::{Restricted, Restriction};
+#[cfg(feature = "alloc")]
+pub use reversed::Reversed;
+#[cfg(feature = "std")]
+pub use safety::{check_safety, Counterexample, SafetyCheck};
+#[cfg(feature = "alloc")]
+pub use shared::{share, unshare, Shared, SharedState};
+#[cfg(feature = "std")]
+pub use splitting::{multilevel_splitting, Splitting};
+#[cfg(feature = "std")]
+pub use step::{current_step, step};
 pub use strategies::*;
+#[cfg(feature = "std")]
+pub use sweep::sweep;
+#[cfg(feature = "std")]
+pub use table::LookupTable;
+pub use thinned::Thinned;
+#[cfg(feature = "std")]
+pub use time_series::{sample_series, TimeSeries};
+#[cfg(feature = "std")]
+pub use trajectory::{ensemble_divergence, evolve, run_ensemble, run_trajectory, Divergence};
 
+#[cfg(feature = "std")]
+mod approximate;
+mod canonical;
+#[cfg(feature = "std")]
+mod coupling;
+#[cfg(feature = "std")]
+mod dense;
+mod dice;
+pub mod distributions;
+#[cfg(feature = "std")]
+mod explore;
+#[cfg(any(feature = "arrow", feature = "csv", feature = "rkyv"))]
+mod export;
+pub mod flags;
 mod functors;
+mod gray;
+#[cfg(feature = "std")]
+mod hashing;
+mod joint;
+#[cfg(feature = "large-sample-spaces")]
+mod large;
+#[cfg(feature = "nalgebra")]
+mod linalg;
+mod macros;
+#[cfg(feature = "std")]
+mod memoized;
+pub mod monoid;
+#[cfg(feature = "std")]
+mod monte_carlo;
+mod patch;
+pub mod pipeline;
+#[cfg(feature = "plotters")]
+mod plot;
+#[cfg(feature = "std")]
+mod progress;
+#[cfg(feature = "pyo3")]
+pub mod python;
 mod random_variable_ranges;
 mod random_variables;
+#[cfg(feature = "std")]
+mod report;
+mod restricted;
+#[cfg(feature = "alloc")]
+mod reversed;
+#[cfg(feature = "std")]
+mod safety;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "std")]
+mod splitting;
+#[cfg(feature = "std")]
+mod step;
 mod strategies;
+#[cfg(feature = "std")]
+mod sweep;
+#[cfg(feature = "std")]
+mod table;
+#[cfg(feature = "metrics")]
+pub mod telemetry;
+#[cfg(all(feature = "alloc", not(feature = "metrics")))]
+mod telemetry;
+#[cfg(feature = "std")]
+pub mod testing;
+mod thinned;
+#[cfg(feature = "std")]
+mod time_series;
+#[cfg(feature = "std")]
+mod trajectory;
 
+use core::future::Future;
 use core::hash::Hash;
 
-use rand::distributions::uniform::{SampleRange, SampleUniform};
+use num_traits::{Num, One, ToPrimitive, Zero};
+use rand::distributions::uniform::SampleUniform;
 use rand::distributions::Standard;
 use rand::prelude::*;
+use rand_core::RngCore;
 
 /// A strategy for evaluating sequences of functions of random data.
 ///
@@ -84,6 +212,10 @@ use rand::prelude::*;
 /// same reason, they are typically unit structs. Behaviour should be specified
 /// at compile-time, to allow calls to `fmap_rand` and `Functor::fmap` to be
 /// properly inlined.
+///
+/// Random number generators are accepted as any implementor of [`RngCore`], so
+/// custom and hardware generators only need to implement that trait from
+/// `rand_core`.
 pub trait RandomStrategy {
     /// The functor that this strategy operates on.
     ///
@@ -96,7 +228,7 @@ pub trait RandomStrategy {
 
     /// Using the strategy specified by the implementor, applies the given
     /// binary function to the given functor and an element of the sample space
-    /// of a [`RandomVariable`].
+    /// of a [`SmallRandomVariable`].
     ///
     /// Note that **no guarantees** are made about whether or how the `rand`
     /// parameter will be used. It may be sampled zero, one, or arbitrarily many
@@ -104,9 +236,9 @@ pub trait RandomStrategy {
     /// or some other type. If some model of the random number generator is
     /// available, then that model should be responsible for enumerating
     /// possible outcomes.
-    fn fmap_rand<A: Inner, B: Inner, R: RandomVariable, F: Fn(A, R) -> B>(
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
-        rng: &mut impl Rng,
+        rng: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
@@ -122,14 +254,220 @@ pub trait RandomStrategy {
     /// or some other type. If some model of the random number generator is
     /// available, then that model should be responsible for enumerating
     /// possible outcomes.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is empty, strategies that enumerate produce an empty functor,
+    /// as no outcome can be reached. Strategies whose functors cannot be empty,
+    /// like [`Sampler`], or which account for the probability of every
+    /// outcome, like [`CoverageEnumerator`], panic instead. Use
+    /// [`RandomStrategy::try_fmap_rand_range`] to handle empty ranges
+    /// uniformly.
+    ///
+    /// [`CoverageEnumerator`]: crate::CoverageEnumerator
+    /// [`Sampler`]: crate::Sampler
     fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
         range: impl RandomVariableRange<R>,
-        rng: &mut impl Rng,
+        rng: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>;
+
+    /// Like [`RandomStrategy::fmap_rand`], but carries the outcomes satisfying
+    /// `absorbing` forward unchanged instead of applying `func` to them.
+    ///
+    /// Once a random process reaches an absorbing state, no later operation
+    /// can leave it, so strategies are free not to expand such outcomes at
+    /// all. Their weight is still scaled as if they had been expanded, so
+    /// they keep their weight relative to the other outcomes. By default,
+    /// `func` is skipped for absorbed outcomes, and [`Counter`] and
+    /// [`UniqueEnumerator`] additionally skip enumerating the sample space for
+    /// them.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Counter, Functor, RandomStrategy};
+    ///
+    /// // Flip coins until the first heads, for up to three flips.
+    /// let mut f = <Counter>::fmap_rand(Functor::pure(0u8), &mut thread_rng(), |_, r: bool| r as u8);
+    /// for _ in 0..2 {
+    ///     f = <Counter>::fmap_rand_absorbing(f, |&s| s == 1, &mut thread_rng(), |_, r: bool| r as u8);
+    /// }
+    /// assert_eq!(f[&1], 7);
+    /// assert_eq!(f[&0], 1);
+    /// ```
+    ///
+    /// [`Counter`]: crate::Counter
+    /// [`UniqueEnumerator`]: crate::UniqueEnumerator
+    #[inline]
+    fn fmap_rand_absorbing<A: Inner, R: SmallRandomVariable, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::fmap_rand(f, rng, |a, r| if absorbing(&a) { a } else { func(a, r) })
+    }
+
+    /// Like [`RandomStrategy::fmap_rand_range`], but carries the outcomes
+    /// satisfying `absorbing` forward unchanged instead of applying `func` to
+    /// them.
+    ///
+    /// See [`RandomStrategy::fmap_rand_absorbing`] for more.
+    #[inline]
+    fn fmap_rand_range_absorbing<A: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::fmap_rand_range(
+            f,
+            range,
+            rng,
+            |a, r| {
+                if absorbing(&a) {
+                    a
+                } else {
+                    func(a, r)
+                }
+            },
+        )
+    }
+
+    /// Like [`RandomStrategy::fmap_rand`], but `func` borrows each state and
+    /// returns a [`Patch`] describing how it changes, rather than consuming a
+    /// copy of the state and returning a new one.
+    ///
+    /// When states are large, this avoids copying a state just to compute an
+    /// outcome from it. By default, each patch is applied to the copy of the
+    /// state the strategy would have passed to `func`, while [`Counter`],
+    /// [`Enumerator`], and [`UniqueEnumerator`] compute every patch for a state
+    /// first and only copy it for all but the last of them.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Counter, Functor, Patch, RandomStrategy};
+    ///
+    /// struct Increment(usize);
+    ///
+    /// impl Patch<[u8; 4]> for Increment {
+    ///     fn apply(self, state: &mut [u8; 4]) {
+    ///         state[self.0] += 1;
+    ///     }
+    /// }
+    ///
+    /// let f = <Counter>::fmap_rand_delta(Functor::pure([0; 4]), &mut thread_rng(), |_, r: bool| {
+    ///     r.then_some(Increment(0))
+    /// });
+    /// assert_eq!(f[&[1, 0, 0, 0]], 1);
+    /// assert_eq!(f[&[0, 0, 0, 0]], 1);
+    /// ```
+    ///
+    /// [`Counter`]: crate::Counter
+    /// [`Enumerator`]: crate::Enumerator
+    /// [`UniqueEnumerator`]: crate::UniqueEnumerator
+    #[inline]
+    fn fmap_rand_delta<A: Inner, R: SmallRandomVariable, P: Patch<A>, F: Fn(&A, R) -> P>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::fmap_rand(f, rng, |mut a, r| {
+            func(&a, r).apply(&mut a);
+            a
+        })
+    }
+
+    /// Like [`RandomStrategy::fmap_rand_range`], but `func` borrows each state
+    /// and returns a [`Patch`] describing how it changes.
+    ///
+    /// See [`RandomStrategy::fmap_rand_delta`] for more.
+    #[inline]
+    fn fmap_rand_range_delta<
+        A: Inner,
+        R: RandomVariable + SampleUniform,
+        P: Patch<A>,
+        F: Fn(&A, R) -> P,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::fmap_rand_range(f, range, rng, |mut a, r| {
+            func(&a, r).apply(&mut a);
+            a
+        })
+    }
+
+    /// Like [`RandomStrategy::fmap_rand_range`], but returns an error if
+    /// `range` is empty rather than producing an empty functor or panicking.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::distributions::DistributionError;
+    /// use rand_functors::{RandomStrategy, Sampler};
+    ///
+    /// let output = Sampler::try_fmap_rand_range((), 5..5u8, &mut thread_rng(), |_, r| r);
+    /// assert_eq!(output, Err(DistributionError::EmptyRange));
+    /// ```
+    #[inline]
+    fn try_fmap_rand_range<
+        A: Inner,
+        B: Inner,
+        R: RandomVariable + SampleUniform,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Result<Self::Functor<B>, distributions::DistributionError>
+    where
+        Standard: Distribution<R>,
+    {
+        if range.is_empty() {
+            return Err(distributions::DistributionError::EmptyRange);
+        }
+        Ok(Self::fmap_rand_range(f, range, rng, func))
+    }
+
+    /// Produces a functor starting from each of the given states with equal
+    /// probability.
+    ///
+    /// This is a generalization of [`Functor::pure`] to a uniform distribution
+    /// over initial states. It is equivalent to selecting one of `states`
+    /// using [`RandomStrategy::fmap_rand_range`], so strategies that sample
+    /// will choose a single state at random, while strategies that enumerate
+    /// will store all of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `states` is empty.
+    #[cfg(feature = "alloc")]
+    fn from_states<I: Inner>(
+        states: impl IntoIterator<Item = I>,
+        rng: &mut impl RngCore,
+    ) -> Self::Functor<I> {
+        let states = states.into_iter().collect::<alloc::vec::Vec<_>>();
+        assert!(!states.is_empty(), "states must not be empty");
+        Self::fmap_rand_range(Functor::pure(()), 0..states.len(), rng, |_, i| {
+            states[i].clone()
+        })
+    }
 }
 
 /// A [`RandomStrategy`] that supports an `fmap_flat` operation.
@@ -145,6 +483,591 @@ pub trait FlattenableRandomStrategy: RandomStrategy {
         f: Self::Functor<A>,
         func: F,
     ) -> Self::Functor<B>;
+
+    /// Applies the given function to the functor's inner like
+    /// [`FlattenableRandomStrategy::fmap_flat`], producing the functor for
+    /// each outcome as a task on the [`rayon`] thread pool.
+    ///
+    /// The outcomes are first replaced by indices, then the functor for each
+    /// is produced in parallel, and finally they are flattened in place of
+    /// their indices, so the result is the same as that of `fmap_flat`. As
+    /// `rayon` schedules tasks by work stealing, calls to `fmap_flat_par`
+    /// nested inside `func` also run in parallel, keeping every thread busy
+    /// while expanding deep trees of sub-processes.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Enumerator, FlattenableRandomStrategy, Functor, RandomStrategy};
+    ///
+    /// fn expand(depth: u8, s: u16) -> Vec<u16> {
+    ///     let f = Enumerator::fmap_rand(Functor::pure(s), &mut thread_rng(), |s, r: bool| {
+    ///         s * 2 + r as u16
+    ///     });
+    ///     if depth == 0 {
+    ///         f
+    ///     } else {
+    ///         Enumerator::fmap_flat_par(f, |s| expand(depth - 1, s))
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(expand(9, 0), (0..1024).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn fmap_flat_par<A: Inner + Send, B: Inner, F: Fn(A) -> Self::Functor<B> + Sync>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Self::Functor<B>: Send,
+    {
+        use rayon::prelude::*;
+
+        // Flattening each outcome into a single index preserves its weight
+        // under every strategy, as the functor containing the index does not
+        // branch.
+        let mut inputs = alloc::vec::Vec::new();
+        let indices = Self::fmap_flat(f, |a| {
+            inputs.push(a);
+            Functor::pure(inputs.len() - 1)
+        });
+        let mut children = inputs
+            .into_par_iter()
+            .map(|a| Some(func(a)))
+            .collect::<alloc::vec::Vec<_>>();
+        Self::fmap_flat(indices, |i| {
+            children[i]
+                .take()
+                .expect("each index must be flattened once")
+        })
+    }
+
+    /// Repeatedly applies `step` to the outcomes of the given functor which do
+    /// not satisfy `pred`, up to `max_iters` times.
+    ///
+    /// This covers the common pattern of rerolling until a valid outcome is
+    /// produced. Outcomes which still do not satisfy `pred` after `max_iters`
+    /// iterations are kept as they are.
+    ///
+    /// On every iteration, `step` is applied to every outcome. Outcomes which
+    /// already satisfy `pred` are carried through its result unchanged, so that
+    /// they keep their weight relative to the outcomes being rerolled. The
+    /// weighting is therefore exact whenever the functors produced by `step`
+    /// have the same total weight for every input, as they do when `step`
+    /// performs the same random operations regardless of its input.
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use rand::prelude::*;
+    /// use rand_functors::{Counter, FlattenableRandomStrategy, Functor, RandomStrategy};
+    ///
+    /// fn roll() -> HashMap<u8, usize> {
+    ///     <Counter>::fmap_rand_range(Functor::pure(()), 1..=6, &mut thread_rng(), |_, r| r)
+    /// }
+    ///
+    /// // Roll a die, rerolling a 1 up to twice.
+    /// let output = <Counter>::loop_until(roll(), |&r| r != 1, 2, |_| roll());
+    /// assert_eq!(output[&1], 1);
+    /// assert_eq!(output[&6], 36 + 6 + 1);
+    /// ```
+    fn loop_until<A: Inner>(
+        f: Self::Functor<A>,
+        pred: impl Fn(&A) -> bool,
+        max_iters: usize,
+        mut step: impl FnMut(A) -> Self::Functor<A>,
+    ) -> Self::Functor<A> {
+        (0..max_iters).fold(f, |f, _| {
+            Self::fmap_flat(f, |a| {
+                if pred(&a) {
+                    Self::fmap(step(a.clone()), move |_| a.clone())
+                } else {
+                    step(a)
+                }
+            })
+        })
+    }
+
+    /// Routes each outcome of the given functor through `then_process` if it
+    /// satisfies `pred`, or through `else_process` otherwise.
+    ///
+    /// Both sub-processes are applied to every outcome. The outcomes of the
+    /// sub-process chosen for an outcome are weighted by the total weight of
+    /// the outcomes of the other, so both routes scale the weight of the
+    /// original outcome by the same amount. The weighting is therefore exact
+    /// whenever the total weight of the outcomes of each sub-process does not
+    /// depend on its input, even if the two sub-processes perform different
+    /// random operations.
+    ///
+    /// As both sub-processes typically need a random number generator, `rng`
+    /// is passed to whichever of them is being applied.
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use rand::prelude::*;
+    /// use rand_functors::{Counter, FlattenableRandomStrategy, Functor, RandomStrategy};
+    ///
+    /// let mut rng = thread_rng();
+    /// let f: HashMap<bool, usize> = Counter::fmap_rand(Functor::pure(()), &mut rng, |_, r| r);
+    /// // Roll a d4 for a critical hit, or a d2 otherwise.
+    /// let output = <Counter>::if_then_else(
+    ///     f,
+    ///     |&critical| critical,
+    ///     &mut rng,
+    ///     |_, rng| Counter::fmap_rand_range(Functor::pure(()), 1..=4u8, rng, |_, r| r),
+    ///     |_, rng| Counter::fmap_rand_range(Functor::pure(()), 1..=2u8, rng, |_, r| r),
+    /// );
+    /// assert_eq!(output[&1], 2 + 4);
+    /// assert_eq!(output[&4], 2);
+    /// ```
+    fn if_then_else<A: Inner, B: Inner, R: RngCore>(
+        f: Self::Functor<A>,
+        pred: impl Fn(&A) -> bool,
+        rng: &mut R,
+        mut then_process: impl FnMut(A, &mut R) -> Self::Functor<B>,
+        mut else_process: impl FnMut(A, &mut R) -> Self::Functor<B>,
+    ) -> Self::Functor<B> {
+        Self::fmap_flat(f, |a| {
+            if pred(&a) {
+                let weight = Self::fmap(else_process(a.clone(), rng), |_| ());
+                Self::fmap_flat(weight, |()| then_process(a.clone(), rng))
+            } else {
+                let weight = Self::fmap(then_process(a.clone(), rng), |_| ());
+                Self::fmap_flat(weight, |()| else_process(a.clone(), rng))
+            }
+        })
+    }
+}
+
+/// A [`RandomStrategy`] that supports sampling from an
+/// [`EnumerableDistribution`].
+///
+/// This requires a separate trait as strategies must account for the weight of
+/// each outcome of the distribution. [`Counter`] multiplies counts by weights,
+/// [`Enumerator`] repeats each outcome in proportion to its weight, and
+/// [`Sampler`] samples from the distribution directly.
+///
+/// [`Counter`]: crate::Counter
+/// [`Enumerator`]: crate::Enumerator
+/// [`Sampler`]: crate::Sampler
+pub trait WeightedRandomStrategy: RandomStrategy {
+    /// Using the strategy specified by the implementor, applies the given
+    /// binary function to the given functor and an element of the sample space
+    /// of an [`EnumerableDistribution`].
+    ///
+    /// As with [`RandomStrategy::fmap_rand`], **no guarantees** are made about
+    /// whether or how the `rng` parameter will be used.
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>;
+
+    /// Like [`RandomStrategy::fmap_rand`], but also passes `func` the exact
+    /// probability of the value of the random variable being applied.
+    ///
+    /// Every value of a [`SmallRandomVariable`] is equally likely, so each has
+    /// a probability of one over the size of its sample space. This allows
+    /// states to accumulate quantities such as log-likelihoods or expected
+    /// costs as part of the process itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample space of `R` has more than [`u64::MAX`] values.
+    #[inline]
+    fn fmap_rand_with_prob<
+        A: Inner,
+        B: Inner,
+        R: SmallRandomVariable,
+        F: Fn(A, R, distributions::Probability) -> B,
+    >(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let size = u64::try_from(R::sample_space().count())
+            .expect("the sample space must have at most u64::MAX values");
+        let probability =
+            distributions::Probability::new(1, size).expect("sample spaces must not be empty");
+        Self::fmap_rand(f, rng, |a, r| func(a, r, probability))
+    }
+
+    /// Like [`WeightedRandomStrategy::fmap_rand_distribution`], but also
+    /// passes `func` the exact probability of the value of the distribution
+    /// being applied.
+    ///
+    /// Strategies that sample find the probability of each sampled value by
+    /// searching the weighted sample space of the distribution for it.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::distributions::{Bernoulli, Probability};
+    /// use rand_functors::{Counter, Functor, WeightedRandomStrategy};
+    ///
+    /// let coin = Bernoulli::new(1, 4).unwrap();
+    /// let f = <Counter>::fmap_rand_distribution_with_prob(
+    ///     Functor::pure(()),
+    ///     &coin,
+    ///     &mut thread_rng(),
+    ///     |_, heads, p| (heads, p),
+    /// );
+    /// assert_eq!(f[&(true, Probability::new(1, 4).unwrap())], 1);
+    /// assert_eq!(f[&(false, Probability::new(3, 4).unwrap())], 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total weight of `distribution` overflows a [`u64`].
+    #[inline]
+    fn fmap_rand_distribution_with_prob<
+        A: Inner,
+        B: Inner,
+        R: PartialEq,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R, distributions::Probability) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        Self::fmap_rand_distribution(
+            f,
+            &distributions::WithProbability::new(distribution),
+            rng,
+            |a, (r, probability)| func(a, r, probability),
+        )
+    }
+
+    /// Produces a functor starting from each of the given states with
+    /// probability proportional to its weight.
+    ///
+    /// This allows a random process to be continued from an arbitrary prior
+    /// distribution over its states, such as the output of an earlier
+    /// process. Strategies that sample will choose a single state according
+    /// to the weights, while strategies that enumerate will store every state
+    /// with a nonzero weight.
+    ///
+    /// An error is returned under the same conditions as
+    /// [`Categorical::new`].
+    ///
+    /// [`Categorical::new`]: distributions::Categorical::new
+    #[cfg(feature = "alloc")]
+    fn from_weighted<I: Inner>(
+        states: impl IntoIterator<Item = (I, u64)>,
+        rng: &mut impl RngCore,
+    ) -> Result<Self::Functor<I>, distributions::DistributionError> {
+        let distribution = distributions::Categorical::new(states)?;
+        Ok(Self::fmap_rand_distribution(
+            Functor::pure(()),
+            &distribution,
+            rng,
+            |_, state| state,
+        ))
+    }
+}
+
+/// A [`RandomStrategy`] that guarantees the order of the outcomes stored in its
+/// functors.
+///
+/// Implementing this trait is a promise that the order described here is part
+/// of the strategy's stable behaviour, so it is legitimate to compare the
+/// functors it produces against a fixed, ordered expectation, as in a
+/// golden-file test. Outcomes are stored outer-outcome-major:
+///
+/// - [`fmap`] maps each outcome in place.
+/// - [`fmap_rand`] and [`fmap_rand_range`] replace each outcome of the input,
+///   in order, with one outcome for each value of the sample space, in the
+///   order produced by [`RandomVariable::sample_space`] or
+///   [`RandomVariableRange::sample_space`].
+/// - [`fmap_rand_distribution`], where supported, does the same using the
+///   order of [`EnumerableDistribution::weighted_sample_space`], with each
+///   outcome repeated consecutively according to its weight.
+/// - [`fmap_flat`], where supported, concatenates the functors produced from
+///   each outcome of the input, in order.
+///
+/// Strategies that shrink their functors, like [`PopulationSampler`], choose
+/// which outcomes to discard at random, but the outcomes that remain keep this
+/// relative order.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Enumerator, Functor, RandomStrategy};
+///
+/// let f = <Enumerator>::fmap_rand_range(Functor::pure(0u8), 1..=2u8, &mut thread_rng(), |a, r| a + r);
+/// let f = <Enumerator>::fmap_rand(f, &mut thread_rng(), |a, b: bool| (a, b));
+/// assert_eq!(f, [(1, false), (1, true), (2, false), (2, true)]);
+/// ```
+///
+/// [`fmap`]: RandomStrategy::fmap
+/// [`fmap_flat`]: FlattenableRandomStrategy::fmap_flat
+/// [`fmap_rand`]: RandomStrategy::fmap_rand
+/// [`fmap_rand_distribution`]: WeightedRandomStrategy::fmap_rand_distribution
+/// [`fmap_rand_range`]: RandomStrategy::fmap_rand_range
+/// [`PopulationSampler`]: crate::PopulationSampler
+pub trait OrderedRandomStrategy: RandomStrategy {}
+
+/// A [`RandomStrategy`] that never uses the random number generator it is
+/// given.
+///
+/// Strategies that enumerate every outcome, like [`Enumerator`], [`Counter`],
+/// and [`UniqueEnumerator`], produce the same functor regardless of the
+/// random number generator passed to them. This trait provides variants of
+/// their methods that take no random number generator at all, so analyses
+/// that only use these strategies need not construct one.
+/// ```
+/// use rand_functors::{Counter, ExactRandomStrategy, Functor};
+///
+/// let f = <Counter>::fmap_rand_exact(Functor::pure(0u8), |s, r: bool| s + r as u8);
+/// let f = <Counter>::fmap_rand_range_exact(f, 1..=2u8, |s, r| s + r);
+/// assert_eq!(f[&2], 2);
+/// ```
+///
+/// # Panics
+///
+/// The random number generator passed on to the methods of
+/// [`RandomStrategy`] panics if it is used, so implementing this trait for a
+/// strategy that samples is a logic error that is reported at runtime.
+///
+/// [`Counter`]: crate::Counter
+/// [`Enumerator`]: crate::Enumerator
+/// [`UniqueEnumerator`]: crate::UniqueEnumerator
+pub trait ExactRandomStrategy: RandomStrategy {
+    /// Equivalent to [`RandomStrategy::fmap_rand`], without a random number
+    /// generator.
+    #[inline]
+    fn fmap_rand_exact<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::fmap_rand(f, &mut NoRng, func)
+    }
+
+    /// Equivalent to [`RandomStrategy::fmap_rand_range`], without a random
+    /// number generator.
+    #[inline]
+    fn fmap_rand_range_exact<
+        A: Inner,
+        B: Inner,
+        R: RandomVariable + SampleUniform,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::fmap_rand_range(f, range, &mut NoRng, func)
+    }
+
+    /// Equivalent to [`WeightedRandomStrategy::fmap_rand_distribution`],
+    /// without a random number generator.
+    #[inline]
+    fn fmap_rand_distribution_exact<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Self: WeightedRandomStrategy,
+    {
+        Self::fmap_rand_distribution(f, distribution, &mut NoRng, func)
+    }
+}
+
+/// The random number generator passed on by the methods of
+/// [`ExactRandomStrategy`], which panics if it is used, except through the
+/// fallible [`RngCore::try_fill_bytes`], which returns an error instead.
+struct NoRng;
+
+impl RngCore for NoRng {
+    fn next_u32(&mut self) -> u32 {
+        panic!("exact strategies must not use the random number generator")
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        panic!("exact strategies must not use the random number generator")
+    }
+
+    fn fill_bytes(&mut self, _: &mut [u8]) {
+        panic!("exact strategies must not use the random number generator")
+    }
+
+    fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), rand_core::Error> {
+        #[cfg(feature = "std")]
+        let error =
+            rand_core::Error::new("exact strategies must not use the random number generator");
+        #[cfg(not(feature = "std"))]
+        let error = rand_core::Error::from(
+            core::num::NonZeroU32::new(rand_core::Error::CUSTOM_START)
+                .expect("custom error codes are nonzero"),
+        );
+        Err(error)
+    }
+}
+
+/// A [`RandomStrategy`] whose mapping functions may be asynchronous.
+///
+/// Each method mirrors one of [`RandomStrategy`], except that `func` returns a
+/// [`Future`] which is awaited for every outcome. This allows random processes
+/// that consult asynchronous services, such as a remote policy evaluated on
+/// each state, to be written once and evaluated under multiple strategies.
+///
+/// Futures are awaited one at a time, in the order in which the outcomes
+/// would have been produced by the corresponding synchronous method. No
+/// particular executor is required.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{AsyncRandomStrategy, Functor};
+///
+/// async fn policy(state: u8) -> u8 {
+///     state * 2
+/// }
+///
+/// async fn random_process<S: AsyncRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+///     let f = S::fmap_rand_range_async(Functor::pure(0), 1..=6u8, rng, |_, r| async move { r }).await;
+///     S::fmap_async(f, policy).await
+/// }
+/// ```
+pub trait AsyncRandomStrategy: RandomStrategy {
+    /// Applies the given asynchronous function to the functor's inner.
+    fn fmap_async<A: Inner, B: Inner, Fut: Future<Output = B>, F: Fn(A) -> Fut>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> impl Future<Output = Self::Functor<B>>;
+
+    /// Like [`RandomStrategy::fmap_rand`], but with an asynchronous function.
+    fn fmap_rand_async<
+        A: Inner,
+        B: Inner,
+        R: SmallRandomVariable,
+        Fut: Future<Output = B>,
+        F: Fn(A, R) -> Fut,
+    >(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> impl Future<Output = Self::Functor<B>>
+    where
+        Standard: Distribution<R>;
+
+    /// Like [`RandomStrategy::fmap_rand_range`], but with an asynchronous
+    /// function.
+    fn fmap_rand_range_async<
+        A: Inner,
+        B: Inner,
+        R: RandomVariable + SampleUniform,
+        Fut: Future<Output = B>,
+        F: Fn(A, R) -> Fut,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> impl Future<Output = Self::Functor<B>>
+    where
+        Standard: Distribution<R>;
+}
+
+/// A [`RandomStrategy`] that evaluates each mapping function at most once per
+/// operation.
+///
+/// The methods of [`RandomStrategy`] require [`Fn`] closures, as strategies
+/// that enumerate call them once for every value of a sample space. A strategy
+/// that only ever follows a single outcome, like [`Sampler`], can instead
+/// accept [`FnOnce`] closures, which may move captured values into the
+/// outcome they produce. Each method mirrors one of [`RandomStrategy`],
+/// [`FlattenableRandomStrategy`], or [`WeightedRandomStrategy`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Functor, Sampler, SingleShotRandomStrategy};
+///
+/// fn random_process<S: SingleShotRandomStrategy>(
+///     log: Vec<String>,
+///     rng: &mut impl Rng,
+/// ) -> S::Functor<Vec<String>> {
+///     S::fmap_rand_once(Functor::pure(()), rng, move |_, heads: bool| {
+///         let mut log = log;
+///         log.push(if heads { "heads" } else { "tails" }.to_string());
+///         log
+///     })
+/// }
+///
+/// assert_eq!(random_process::<Sampler>(Vec::new(), &mut thread_rng()).len(), 1);
+/// ```
+///
+/// [`Sampler`]: crate::Sampler
+pub trait SingleShotRandomStrategy: RandomStrategy {
+    /// Like [`RandomStrategy::fmap`], but with a function that is called at
+    /// most once.
+    fn fmap_once<A: Inner, B: Inner, F: FnOnce(A) -> B>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B>;
+
+    /// Like [`RandomStrategy::fmap_rand`], but with a function that is called
+    /// at most once.
+    fn fmap_rand_once<A: Inner, B: Inner, R: SmallRandomVariable, F: FnOnce(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>;
+
+    /// Like [`RandomStrategy::fmap_rand_range`], but with a function that is
+    /// called at most once.
+    fn fmap_rand_range_once<
+        A: Inner,
+        B: Inner,
+        R: RandomVariable + SampleUniform,
+        F: FnOnce(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>;
+
+    /// Like [`WeightedRandomStrategy::fmap_rand_distribution`], but with a
+    /// function that is called at most once.
+    fn fmap_rand_distribution_once<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: FnOnce(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>;
+
+    /// Like [`FlattenableRandomStrategy::fmap_flat`], but with a function that
+    /// is called at most once.
+    fn fmap_flat_once<A: Inner, B: Inner, F: FnOnce(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B>;
 }
 
 /// A type that is enumerable and can be sampled from uniformly.
@@ -161,15 +1084,18 @@ pub trait FlattenableRandomStrategy: RandomStrategy {
 ///
 /// # Provided Implementations
 ///
-/// This crate provides implementations of `RandomVariable` for [`bool`] and all
-/// twelve built-in integer types.
+/// This crate provides implementations of `RandomVariable` for [`bool`], all
+/// twelve built-in integer types, and [`Wrapping<T>`] for any `RandomVariable`
+/// `T`.
 ///
 /// Implementations are provided for [`u32`], [`u64`], [`u128`], [`usize`],
 /// [`i32`], [`i64`], [`i128`], and [`isize`] strictly for sampling from ranges
 /// (through [`RandomStrategy::fmap_rand_range`]). The use of
 /// [`RandomStrategy::fmap_rand`] with a 32-bit integer `RandomVariable` would
 /// involve, at minimum, a 4 GiB allocation just to enumerate the outcomes of a
-/// random process. This is obviously intractable on current computers.
+/// random process. This is obviously intractable on current computers, so
+/// `fmap_rand` only accepts implementors of [`SmallRandomVariable`], which
+/// these types are not.
 ///
 /// # Implementing `RandomVariable`
 ///
@@ -177,12 +1103,14 @@ pub trait FlattenableRandomStrategy: RandomStrategy {
 /// derivable. However, implementations for simple structs tends to follow a
 /// pattern. [`Distribution<Self>`] implementations will typically call
 /// `self.sample(rng)` for each field of the struct. `RandomVariable`
-/// implementations will typically use [`Iterator::flat_map`] to create a
-/// Cartesian product of all the sample spaces of the struct's fields.
+/// implementations will typically use [`product_space`] to create a Cartesian
+/// product of all the sample spaces of the struct's fields. Where a dedicated
+/// type is not needed, [`Joint`] provides these implementations for a pair of
+/// random variables.
 /// ```
 /// use rand::distributions::Standard;
 /// use rand::prelude::*;
-/// use rand_functors::RandomVariable;
+/// use rand_functors::{product_space, RandomVariable};
 ///
 /// #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 /// struct Coordinate {
@@ -201,10 +1129,12 @@ pub trait FlattenableRandomStrategy: RandomStrategy {
 ///
 /// impl RandomVariable for Coordinate {
 ///     fn sample_space() -> impl Iterator<Item = Self> {
-///         u8::sample_space().flat_map(|x| u8::sample_space().map(move |y| Coordinate { x, y }))
+///         product_space(u8::sample_space, u8::sample_space).map(|(x, y)| Coordinate { x, y })
 ///     }
 /// }
 /// ```
+///
+/// [`Wrapping<T>`]: core::num::Wrapping
 pub trait RandomVariable: Sized
 where
     Standard: Distribution<Self>,
@@ -217,15 +1147,221 @@ where
     fn sample_space() -> impl Iterator<Item = Self>;
 }
 
+/// A [`RandomVariable`] whose sample space is small enough to be enumerated in
+/// its entirety.
+///
+/// Only implementors of this trait can be used with
+/// [`RandomStrategy::fmap_rand`]. This turns the enumeration of an intractably
+/// large sample space, such as that of [`u32`], into a compile error. A value
+/// of such a type can still be drawn from an explicit range using
+/// [`RandomStrategy::fmap_rand_range`], including the full range of the type,
+/// or, with the `large-sample-spaces` feature, wrapped in `LargeSampleSpace`
+/// to opt out of this restriction.
+/// ```compile_fail
+/// use rand::prelude::*;
+/// use rand_functors::{Enumerator, Functor, RandomStrategy};
+///
+/// let f = Enumerator::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: u32| r);
+/// ```
+///
+/// This crate provides implementations of `SmallRandomVariable` for [`bool`],
+/// [`u8`], [`u16`], [`i8`], [`i16`], and [`Wrapping`](core::num::Wrapping)
+/// versions of these. As a marker trait, it can be implemented for a custom
+/// `RandomVariable` with an empty `impl` block:
+/// ```
+/// # use rand::distributions::Standard;
+/// # use rand::prelude::*;
+/// use rand_functors::{RandomVariable, SmallRandomVariable};
+///
+/// # #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+/// # struct Coordinate {
+/// #     x: u8,
+/// #     y: u8,
+/// # }
+/// #
+/// # impl Distribution<Coordinate> for Standard {
+/// #     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Coordinate {
+/// #         Coordinate {
+/// #             x: self.sample(rng),
+/// #             y: self.sample(rng),
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # impl RandomVariable for Coordinate {
+/// #     fn sample_space() -> impl Iterator<Item = Self> {
+/// #         u8::sample_space().flat_map(|x| u8::sample_space().map(move |y| Coordinate { x, y }))
+/// #     }
+/// # }
+/// #
+/// impl SmallRandomVariable for Coordinate {}
+/// ```
+pub trait SmallRandomVariable: RandomVariable
+where
+    Standard: Distribution<Self>,
+{
+}
+
+/// A [`RandomVariable`] whose sample space has a known number of values.
+///
+/// This crate provides implementations of `Cardinality` for every type for
+/// which it provides an implementation of [`RandomVariable`]. The cardinality
+/// of [`u128`] and [`i128`], which do not fit in a [`u128`], saturates at
+/// [`u128::MAX`].
+pub trait Cardinality: RandomVariable
+where
+    Standard: Distribution<Self>,
+{
+    /// The number of values in the sample space of this type.
+    const CARDINALITY: u128;
+}
+
+/// A [`RandomVariable`] with no more than `MAX` values in its sample space.
+///
+/// `MaxCardinality` is implemented for every implementor of [`Cardinality`].
+/// Stable Rust cannot compare const generic parameters in trait bounds, so the
+/// limit is enforced when [`MaxCardinality::WITHIN_MAX`] is evaluated, which
+/// happens when a function referring to it is monomorphized. A function
+/// accepting a random variable with a bound of `MaxCardinality<MAX>` should
+/// refer to it to turn the use of a larger random variable into a compile
+/// error:
+/// ```
+/// use rand::distributions::Standard;
+/// use rand::prelude::*;
+/// use rand_functors::MaxCardinality;
+///
+/// fn all_outcomes<R: MaxCardinality<256>>() -> Vec<R>
+/// where
+///     Standard: Distribution<R>,
+/// {
+///     let () = R::WITHIN_MAX;
+///     R::sample_space().collect()
+/// }
+///
+/// assert_eq!(all_outcomes::<bool>(), [false, true]);
+/// assert_eq!(all_outcomes::<u8>().len(), 256);
+/// ```
+///
+/// Given the function above, the following does not compile:
+/// ```compile_fail
+/// # use rand::distributions::Standard;
+/// # use rand::prelude::*;
+/// # use rand_functors::MaxCardinality;
+/// #
+/// # fn all_outcomes<R: MaxCardinality<256>>() -> Vec<R>
+/// # where
+/// #     Standard: Distribution<R>,
+/// # {
+/// #     let () = R::WITHIN_MAX;
+/// #     R::sample_space().collect()
+/// # }
+/// #
+/// all_outcomes::<u16>();
+/// ```
+pub trait MaxCardinality<const MAX: u128>: Cardinality
+where
+    Standard: Distribution<Self>,
+{
+    /// Evaluates to `()` if the sample space of this type has no more than
+    /// `MAX` values, and fails to compile otherwise.
+    const WITHIN_MAX: ();
+}
+
+impl<R: Cardinality, const MAX: u128> MaxCardinality<MAX> for R
+where
+    Standard: Distribution<R>,
+{
+    const WITHIN_MAX: () = assert!(
+        R::CARDINALITY <= MAX,
+        "sample space of random variable exceeds maximum cardinality"
+    );
+}
+
 /// A (possibly inclusive) range of a [`RandomVariable`] that can be enumerated
 /// or sampled from.
+///
+/// This is implemented for each kind of range over the built-in integer types.
+/// Ranges that are unbounded on either side, such as `x..`, `..=x`, and `..`,
+/// are clamped to the smallest or largest value of the type.
 pub trait RandomVariableRange<R: RandomVariable + SampleUniform>
 where
     Standard: Distribution<R>,
-    Self: SampleRange<R>,
 {
     /// Produce an [`Iterator`] containing all possible values in this range.
-    fn sample_space(&self) -> impl Iterator<Item = R>;
+    ///
+    /// Enumerating strategies store outcomes in the order of this iterator,
+    /// so implementing this trait for a custom range type is the way to
+    /// enumerate a range in a custom order.
+    fn sample_space(&self) -> impl DoubleEndedIterator<Item = R>;
+
+    /// Samples a single value uniformly from this range.
+    ///
+    /// The default implementation counts the values in the sample space and
+    /// returns one chosen uniformly at random, which takes time linear in the
+    /// size of the range. The implementations for the built-in ranges sample
+    /// in constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    #[inline]
+    fn sample_single<G: RngCore + ?Sized>(self, rng: &mut G) -> R
+    where
+        Self: Sized,
+    {
+        let size = self.sample_space().count();
+        assert!(size != 0, "cannot sample from an empty range");
+        self.sample_space()
+            .nth(rng.gen_range(0..size))
+            .expect("the sample space must not change between iterations")
+    }
+
+    /// Returns `true` if this range contains no values, like `5..5`.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.sample_space().next().is_none()
+    }
+
+    /// Produces a range containing the same values as this one, whose sample
+    /// space is enumerated in reverse order.
+    ///
+    /// Sampling from the reversed range is unaffected. The sample space of a
+    /// [`RandomVariable`] passed to [`RandomStrategy::fmap_rand`] can be
+    /// reversed with [`Reversed`].
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Enumerator, Functor, RandomStrategy, RandomVariableRange};
+    ///
+    /// let descending =
+    ///     <Enumerator>::fmap_rand_range(Functor::pure(()), (1..=3u8).rev_space(), &mut thread_rng(), |_, r| r);
+    /// assert_eq!(descending, [3, 2, 1]);
+    /// ```
+    #[inline]
+    fn rev_space(self) -> RevSpace<Self>
+    where
+        Self: Sized,
+    {
+        RevSpace(self)
+    }
+}
+
+/// A distribution over a finite set of values which can be enumerated along
+/// with their relative weights.
+///
+/// Unlike [`RandomVariable`], the values of an `EnumerableDistribution` need
+/// not be equally likely. The probability of sampling a value must be
+/// proportional to its weight in [`EnumerableDistribution::weighted_sample_space`].
+/// As with [`RandomVariable`], **a mismatch between the two will result in a
+/// logic error**.
+///
+/// Enumerable versions of several common distributions are provided in the
+/// [`distributions`] module.
+pub trait EnumerableDistribution<T>: Distribution<T> {
+    /// Produce an [`Iterator`] containing every value with a nonzero
+    /// probability of being sampled and its weight.
+    ///
+    /// Values must not be repeated and weights must not be zero.
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (T, u64)>;
 }
 
 /// A container used by a [`RandomStrategy`] during computations.
@@ -251,6 +1387,417 @@ pub trait Functor<I: Inner> {
     /// computations. The associated functions of [`RandomStrategy`] only
     /// operate on the `Functor` associated with that [`RandomStrategy`].
     fn pure(i: I) -> Self;
+
+    /// The number of outcomes stored in this functor.
+    ///
+    /// Functors which merge repeated outcomes, like the [`HashMap`] used by
+    /// [`Counter`], count each distinct outcome once.
+    ///
+    /// [`Counter`]: crate::Counter
+    /// [`HashMap`]: std::collections::HashMap
+    fn size(&self) -> usize;
+}
+
+/// Method-call syntax for the associated functions of [`RandomStrategy`] and
+/// its subtraits.
+///
+/// Each method forwards to the associated function of the same name on the
+/// strategy `S`, which must be given explicitly. This allows a series of
+/// operations to be written in the order that they are applied:
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Functor, FunctorExt, RandomStrategy};
+///
+/// fn next_state<S: RandomStrategy>(state: u8, rng: &mut impl Rng) -> S::Functor<u8> {
+///     let out: S::Functor<u8> = Functor::pure(state);
+///     out.fmap_rand::<S, _, _>(rng, |s, r: u8| s.wrapping_add(r))
+///         .fmap_rand::<S, _, _>(rng, |s, r: bool| if r { s % 3 } else { s })
+/// }
+/// ```
+///
+/// `FunctorExt` is implemented for every [`Functor`].
+pub trait FunctorExt<A: Inner>: Functor<A> + Sized {
+    /// Equivalent to [`RandomStrategy::fmap`].
+    #[inline]
+    fn fmap<S, B: Inner>(self, func: impl Fn(A) -> B) -> S::Functor<B>
+    where
+        S: RandomStrategy<Functor<A> = Self>,
+    {
+        S::fmap(self, func)
+    }
+
+    /// Equivalent to [`RandomStrategy::fmap_rand`].
+    #[inline]
+    fn fmap_rand<S, B: Inner, R: SmallRandomVariable>(
+        self,
+        rng: &mut impl RngCore,
+        func: impl Fn(A, R) -> B,
+    ) -> S::Functor<B>
+    where
+        S: RandomStrategy<Functor<A> = Self>,
+        Standard: Distribution<R>,
+    {
+        S::fmap_rand(self, rng, func)
+    }
+
+    /// Equivalent to [`RandomStrategy::fmap_rand_range`].
+    #[inline]
+    fn fmap_rand_range<S, B: Inner, R: RandomVariable + SampleUniform>(
+        self,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: impl Fn(A, R) -> B,
+    ) -> S::Functor<B>
+    where
+        S: RandomStrategy<Functor<A> = Self>,
+        Standard: Distribution<R>,
+    {
+        S::fmap_rand_range(self, range, rng, func)
+    }
+
+    /// Equivalent to [`FlattenableRandomStrategy::fmap_flat`].
+    #[inline]
+    fn fmap_flat<S, B: Inner>(self, func: impl FnMut(A) -> S::Functor<B>) -> S::Functor<B>
+    where
+        S: FlattenableRandomStrategy<Functor<A> = Self>,
+    {
+        S::fmap_flat::<A, B, _>(self, func)
+    }
+
+    /// Equivalent to [`WeightedRandomStrategy::fmap_rand_distribution`].
+    #[inline]
+    fn fmap_rand_distribution<S, B: Inner, R>(
+        self,
+        distribution: &impl EnumerableDistribution<R>,
+        rng: &mut impl RngCore,
+        func: impl Fn(A, R) -> B,
+    ) -> S::Functor<B>
+    where
+        S: WeightedRandomStrategy<Functor<A> = Self>,
+    {
+        S::fmap_rand_distribution(self, distribution, rng, func)
+    }
+}
+
+impl<A: Inner, T: Functor<A>> FunctorExt<A> for T {}
+
+/// A [`Functor`] whose outcomes can be inspected along with their relative
+/// weights.
+///
+/// The weight of an outcome is proportional to the probability of the random
+/// process producing it. For [`Counter`], this is the number of times the
+/// outcome was produced. Functors that store each outcome individually, like
+/// the [`Vec`] used by [`Enumerator`], assign a weight of one to every element.
+///
+/// [`Counter`]: crate::Counter
+/// [`Enumerator`]: crate::Enumerator
+/// [`Vec`]: alloc::vec::Vec
+pub trait WeightedFunctor<I: Inner>: Functor<I> {
+    /// The type used to represent the weight of an outcome.
+    type Weight: Clone + Num + ToPrimitive;
+
+    /// Produce an [`Iterator`] over the outcomes stored in this functor and
+    /// their weights.
+    ///
+    /// Outcomes are not guaranteed to be unique. Functors with repetition will
+    /// yield the same outcome once for every time it is stored.
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, Self::Weight)>
+    where
+        I: 'a;
+
+    /// Collects the distinct outcomes stored in this functor and their total
+    /// weights into a [`Vec`], in ascending order of outcome.
+    ///
+    /// The result depends only on the distribution of outcomes, not on the
+    /// order in which they are stored, so it is suitable for snapshot tests.
+    /// For the same random process, [`Enumerator`] and [`Counter`] produce
+    /// equal results.
+    ///
+    /// [`Counter`]: crate::Counter
+    /// [`Enumerator`]: crate::Enumerator
+    /// [`Vec`]: alloc::vec::Vec
+    #[cfg(feature = "alloc")]
+    fn to_sorted_vec(&self) -> alloc::vec::Vec<(I, Self::Weight)>
+    where
+        I: Ord,
+    {
+        let mut weights = alloc::collections::BTreeMap::new();
+        for (outcome, weight) in self.weighted_outcomes() {
+            weights
+                .entry(outcome)
+                .and_modify(|w: &mut Self::Weight| *w = w.clone() + weight.clone())
+                .or_insert(weight);
+        }
+        weights
+            .into_iter()
+            .map(|(outcome, weight)| (outcome.clone(), weight))
+            .collect()
+    }
+
+    /// Consumes this functor, collecting its distinct outcomes and their total
+    /// weights into a [`Vec`] sorted by the key extracted by `f`.
+    ///
+    /// The sort is stable. Outcomes with equal keys are left in the order in
+    /// which they were first stored.
+    ///
+    /// [`Vec`]: std::vec::Vec
+    #[cfg(feature = "std")]
+    fn into_sorted_by_key<K: Ord>(self, mut f: impl FnMut(&I) -> K) -> Vec<(I, Self::Weight)>
+    where
+        Self: Sized,
+    {
+        let mut indices = std::collections::HashMap::<&I, usize>::new();
+        let mut outcomes: Vec<(I, Self::Weight)> = Vec::new();
+        for (outcome, weight) in self.weighted_outcomes() {
+            if let Some(&i) = indices.get(outcome) {
+                let total: &mut Self::Weight = &mut outcomes[i].1;
+                *total = total.clone() + weight;
+            } else {
+                indices.insert(outcome, outcomes.len());
+                outcomes.push((outcome.clone(), weight));
+            }
+        }
+        outcomes.sort_by_key(|(outcome, _)| f(outcome));
+        outcomes
+    }
+
+    /// Returns the probability that an outcome stored in this functor satisfies
+    /// `pred`.
+    ///
+    /// This is the total weight of the outcomes satisfying `pred` divided by
+    /// the total weight of all outcomes, computed in a single pass without
+    /// allocating. It returns `NaN` if the functor stores no outcomes.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Counter, Functor, RandomStrategy, WeightedFunctor};
+    ///
+    /// let f = <Counter>::fmap_rand_range(Functor::pure(()), 1..=6u8, &mut thread_rng(), |_, r| r);
+    /// let f = <Counter>::fmap_rand_range(f, 1..=6u8, &mut thread_rng(), |s, r| s + r);
+    /// assert_eq!(f.probability_where(|&s| s == 7), 1.0 / 6.0);
+    /// ```
+    fn probability_where(&self, mut pred: impl FnMut(&I) -> bool) -> f64 {
+        let (matching, total) =
+            self.weighted_outcomes()
+                .fold((0.0, 0.0), |(matching, total), (outcome, weight)| {
+                    let weight = weight.to_f64().unwrap_or(f64::NAN);
+                    if pred(outcome) {
+                        (matching + weight, total + weight)
+                    } else {
+                        (matching, total + weight)
+                    }
+                });
+        matching / total
+    }
+
+    /// Returns the mean and variance of the values that `project` assigns to
+    /// the outcomes stored in this functor, or [`None`] if their total weight
+    /// is zero.
+    ///
+    /// Both are computed exactly over the stored outcomes, in a single pass
+    /// without allocating, using the weighted form of Welford's algorithm. The
+    /// variance is that of the distribution itself, not a sample estimate.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Counter, Functor, RandomStrategy, WeightedFunctor};
+    ///
+    /// let f = <Counter>::fmap_rand_range(Functor::pure(()), 1..=6u8, &mut thread_rng(), |_, r| r);
+    /// let (mean, variance) = f.mean_variance(|&s| s as f64).unwrap();
+    /// assert!((mean - 3.5).abs() < 1e-12);
+    /// assert!((variance - 35.0 / 12.0).abs() < 1e-12);
+    /// ```
+    fn mean_variance(&self, mut project: impl FnMut(&I) -> f64) -> Option<(f64, f64)> {
+        let (total, mean, sum_squares) = self.weighted_outcomes().fold(
+            (0.0, 0.0, 0.0),
+            |(total, mean, sum_squares), (outcome, weight)| {
+                let weight = weight.to_f64().unwrap_or(f64::NAN);
+                if weight == 0.0 {
+                    return (total, mean, sum_squares);
+                }
+                let x = project(outcome);
+                let total = total + weight;
+                let delta = x - mean;
+                let mean = mean + delta * weight / total;
+                (total, mean, sum_squares + weight * delta * (x - mean))
+            },
+        );
+        (total > 0.0).then(|| (mean, sum_squares / total))
+    }
+
+    /// Combines the values that `project` assigns to the outcomes stored in
+    /// this functor under the [`Monoid`] `M`.
+    ///
+    /// Each outcome contributes its projection combined with itself as many
+    /// times as its weight, so that functors with and without repetition
+    /// produce the same result for the same random process. The aggregation
+    /// runs in a single pass without allocating.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::monoid::{Max, Sum};
+    /// use rand_functors::{Counter, Functor, RandomStrategy, WeightedFunctor};
+    ///
+    /// let f = <Counter>::fmap_rand_range(Functor::pure(()), 1..=6u8, &mut thread_rng(), |_, r| r);
+    /// let f = <Counter>::fmap_rand_range(f, 1..=6u8, &mut thread_rng(), |s, r| s + r);
+    /// let (Sum(total), Max(max)) = f.aggregate(|&s| (Sum(s as u32), Max(s)));
+    /// assert_eq!(total, 7 * 36);
+    /// assert_eq!(max, 12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is not an integer that fits in a [`u128`].
+    ///
+    /// [`Monoid`]: crate::monoid::Monoid
+    fn aggregate<M: monoid::Monoid>(&self, mut project: impl FnMut(&I) -> M) -> M {
+        self.weighted_outcomes()
+            .fold(M::identity(), |acc, (outcome, weight)| {
+                assert!(
+                    (weight.clone() % Self::Weight::one()).is_zero(),
+                    "aggregate requires integer weights"
+                );
+                let n = weight
+                    .to_u128()
+                    .expect("aggregate requires weights that fit in a u128");
+                acc.combine(project(outcome).pow(n))
+            })
+    }
+
+    /// Returns the outcomes stored in this functor with the smallest and
+    /// largest keys extracted by `f`, or [`None`] if it stores no outcomes.
+    ///
+    /// Weights are ignored, so for a functor produced by [`UniqueEnumerator`],
+    /// which stores every reachable outcome once, this gives the best and worst
+    /// reachable outcomes of the random process under the objective `f`.
+    ///
+    /// If several outcomes share the smallest key, the first of them in the
+    /// iteration order of the functor is returned, and if several share the
+    /// largest key, the last of them is. The iteration order of functors
+    /// backed by hash tables is unspecified, so the result is only
+    /// deterministic if the key also breaks ties, for example by including the
+    /// outcome itself.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Functor, RandomStrategy, UniqueEnumerator, WeightedFunctor};
+    ///
+    /// let f = <UniqueEnumerator>::fmap_rand_range(Functor::pure(10i8), -3..=3i8, &mut thread_rng(), |s, r| s * r);
+    /// assert_eq!(f.bounds_by_key(|&s| s), Some((&-30, &30)));
+    /// // Both -30 and 30 have the largest magnitude, so break the tie by value.
+    /// assert_eq!(f.bounds_by_key(|&s| (s.abs(), s)), Some((&0, &30)));
+    /// ```
+    ///
+    /// [`UniqueEnumerator`]: crate::UniqueEnumerator
+    fn bounds_by_key<K: Ord>(&self, mut f: impl FnMut(&I) -> K) -> Option<(&I, &I)> {
+        let (min, _) = self
+            .weighted_outcomes()
+            .min_by_key(|(outcome, _)| f(outcome))?;
+        let (max, _) = self
+            .weighted_outcomes()
+            .max_by_key(|(outcome, _)| f(outcome))?;
+        Some((min, max))
+    }
+
+    /// Converts the weights of the outcomes stored in this functor into
+    /// probabilities.
+    ///
+    /// Repeated outcomes are merged. The distinct outcomes are returned in
+    /// descending order of probability, with ties left in the order in which
+    /// outcomes were first stored.
+    #[cfg(feature = "std")]
+    fn normalize(&self) -> Vec<(I, f64)> {
+        report::report(self.weighted_outcomes(), None)
+            .outcomes
+            .into_iter()
+            .map(|o| (o.outcome.clone(), o.probability))
+            .collect()
+    }
+
+    /// Converts the weights of the outcomes stored in this functor into
+    /// probabilities, collected into a [`HashMap`] keyed by outcome.
+    ///
+    /// [`HashMap`]: std::collections::HashMap
+    #[cfg(feature = "std")]
+    fn normalize_map(&self) -> std::collections::HashMap<I, f64> {
+        report::report(self.weighted_outcomes(), None)
+            .outcomes
+            .into_iter()
+            .map(|o| (o.outcome.clone(), o.probability))
+            .collect()
+    }
+
+    /// Partitions the outcomes stored in this functor by the key extracted by
+    /// `key` into a [`HashMap`] of distributions, merging repeated outcomes and
+    /// totalling their weights.
+    ///
+    /// This produces the distribution within every category of outcomes from
+    /// a single run of a random process, rather than one run per category.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Counter, Functor, RandomStrategy, WeightedFunctor};
+    ///
+    /// let f = <Counter>::fmap_rand_range(Functor::pure(()), 1..=6u8, &mut thread_rng(), |_, r| r);
+    /// let f = <Counter>::fmap_rand_range(f, 1..=6u8, &mut thread_rng(), |s, r| s + r);
+    /// let groups = f.group_by(|&s| s % 2 == 0);
+    /// assert_eq!(groups[&true].values().sum::<usize>(), 18);
+    /// assert_eq!(groups[&false][&7], 6);
+    /// ```
+    ///
+    /// [`HashMap`]: std::collections::HashMap
+    #[cfg(feature = "std")]
+    fn group_by<K: Eq + Hash>(
+        &self,
+        mut key: impl FnMut(&I) -> K,
+    ) -> std::collections::HashMap<K, std::collections::HashMap<I, Self::Weight>> {
+        let mut groups = std::collections::HashMap::new();
+        for (outcome, weight) in self.weighted_outcomes() {
+            groups
+                .entry(key(outcome))
+                .or_insert_with(std::collections::HashMap::new)
+                .entry(outcome.clone())
+                .and_modify(|w: &mut Self::Weight| *w = w.clone() + weight.clone())
+                .or_insert(weight);
+        }
+        groups
+    }
+}
+
+/// A [`WeightedFunctor`] which can be built directly from outcomes and their
+/// weights, and extended with more of them.
+///
+/// This is the weighted counterpart of [`FromIterator`] and [`Extend`]. It
+/// allows an existing dataset, such as observed states and their frequencies,
+/// to seed a random process directly, rather than starting from a single
+/// state with [`Functor::pure`]. Functors which merge repeated outcomes add
+/// their weights together, while functors which store each outcome
+/// individually, like the [`Vec`] used by [`Enumerator`], repeat each outcome
+/// according to its weight. Outcomes with a weight of zero are not stored.
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, DefaultState, Enumerator, FromWeightedIterator, RandomStrategy};
+///
+/// let observed = [(1u8, 2), (2, 1), (1, 1)];
+/// let f = HashMap::<_, usize, DefaultState>::from_weighted_iter(observed);
+/// let f = <Counter>::fmap_rand(f, &mut thread_rng(), |s, r: bool| s + r as u8);
+/// assert_eq!(f[&2], 4);
+///
+/// let f = Vec::from_weighted_iter(observed);
+/// let f = Enumerator::fmap_rand(f, &mut thread_rng(), |s, r: bool| s + r as u8);
+/// assert_eq!(f, [1, 2, 1, 2, 2, 3, 1, 2]);
+/// ```
+///
+/// [`Enumerator`]: crate::Enumerator
+/// [`Vec`]: alloc::vec::Vec
+pub trait FromWeightedIterator<I: Inner>: WeightedFunctor<I> + Default {
+    /// Adds every outcome in `outcomes` to this functor with its weight.
+    fn extend_weighted<T: IntoIterator<Item = (I, Self::Weight)>>(&mut self, outcomes: T);
+
+    /// Creates a functor storing every outcome in `outcomes` with its weight.
+    #[inline]
+    fn from_weighted_iter<T: IntoIterator<Item = (I, Self::Weight)>>(outcomes: T) -> Self {
+        let mut f = Self::default();
+        f.extend_weighted(outcomes);
+        f
+    }
 }
 
 /// A valid inner type for a [`Functor`].
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use nalgebra::{DMatrix, DVector};
+use num_traits::ToPrimitive;
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{Inner, RandomStrategy, RandomVariable, WeightedFunctor};
+
+/// Maps each value in the sample space of `T` to its position in the sample
+/// space.
+fn state_indices<T: Inner + RandomVariable>() -> HashMap<T, usize>
+where
+    Standard: Distribution<T>,
+{
+    T::sample_space().enumerate().map(|(i, t)| (t, i)).collect()
+}
+
+/// Accumulates the probability of each outcome into the entries of `row`,
+/// indexed by the position of the outcome in its sample space.
+fn accumulate<'a, T: Inner + 'a, N: ToPrimitive>(
+    indices: &HashMap<T, usize>,
+    outcomes: impl Iterator<Item = (&'a T, N)>,
+    mut row: impl FnMut(usize, f64),
+) {
+    let weights = outcomes
+        .map(|(t, w)| (indices[t], w.to_f64().unwrap_or(f64::NAN)))
+        .collect::<Vec<_>>();
+    let total_weight = weights.iter().map(|(_, w)| w).sum::<f64>();
+    for (i, w) in weights {
+        row(i, w / total_weight);
+    }
+}
+
+/// Produces the transition matrix of a random process over the sample space of
+/// a [`RandomVariable`].
+///
+/// `step` is evaluated once for every value in the sample space of `T`, using
+/// the strategy `S`. The entry in row `i` and column `j` of the resulting
+/// matrix is the probability of a step from the `i`th value in the sample space
+/// producing the `j`th value, so each row sums to one.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{transition_matrix, Counter, Functor, RandomStrategy};
+///
+/// let matrix = transition_matrix::<Counter, bool>(|b| {
+///     Counter::fmap_rand(Functor::pure(b), &mut thread_rng(), |b, r: bool| b && r)
+/// });
+/// assert_eq!(matrix[(0, 0)], 1.0);
+/// assert_eq!(matrix[(1, 0)], 0.5);
+/// assert_eq!(matrix[(1, 1)], 0.5);
+/// ```
+pub fn transition_matrix<S: RandomStrategy, T: Inner + RandomVariable>(
+    mut step: impl FnMut(T) -> S::Functor<T>,
+) -> DMatrix<f64>
+where
+    Standard: Distribution<T>,
+    S::Functor<T>: WeightedFunctor<T>,
+{
+    let indices = state_indices::<T>();
+    let mut matrix = DMatrix::zeros(indices.len(), indices.len());
+    for (i, t) in T::sample_space().enumerate() {
+        let next = step(t);
+        accumulate(&indices, next.weighted_outcomes(), |j, p| {
+            matrix[(i, j)] += p
+        });
+    }
+    matrix
+}
+
+/// Produces a dense vector of the probabilities of each value in the sample
+/// space of `T`.
+///
+/// The `i`th entry of the vector is the probability of the `i`th value in the
+/// sample space of `T`, so the vector is compatible with the matrices produced
+/// by [`transition_matrix`].
+pub fn probability_vector<T: Inner + RandomVariable>(
+    functor: &impl WeightedFunctor<T>,
+) -> DVector<f64>
+where
+    Standard: Distribution<T>,
+{
+    let indices = state_indices::<T>();
+    let mut vector = DVector::zeros(indices.len());
+    accumulate(&indices, functor.weighted_outcomes(), |i, p| vector[i] += p);
+    vector
+}
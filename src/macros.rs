@@ -0,0 +1,296 @@
+/// Evaluates an ordinary function of a state and some random variables under a
+/// [`RandomStrategy`].
+///
+/// `lift!(S, rng, func, functor; R1, R2, ...)` applies `func(state, r1, r2,
+/// ...)` to each state in `functor`, which must be an `S::Functor`, where
+/// `r1: R1`, `r2: R2`, and so on are [`RandomVariable`]s. It expands into one
+/// call to [`RandomStrategy::fmap_rand`] for each random variable, followed by
+/// a call to [`RandomStrategy::fmap`]. The random variables and the state are
+/// collected into tuples in between, so all of them must implement [`Inner`].
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{lift, Enumerator, Functor, RandomStrategy};
+///
+/// fn step(state: u8, reset: bool, increment: u8) -> u8 {
+///     if reset {
+///         0
+///     } else {
+///         state.saturating_add(increment)
+///     }
+/// }
+///
+/// fn next_state<S: RandomStrategy>(state: u8, rng: &mut impl Rng) -> S::Functor<u8> {
+///     lift!(S, rng, step, Functor::pure(state); bool, u8)
+/// }
+///
+/// assert_eq!(next_state::<Enumerator>(3, &mut thread_rng()).len(), 512);
+/// ```
+///
+/// [`Inner`]: crate::Inner
+/// [`RandomStrategy`]: crate::RandomStrategy
+/// [`RandomStrategy::fmap`]: crate::RandomStrategy::fmap
+/// [`RandomStrategy::fmap_rand`]: crate::RandomStrategy::fmap_rand
+/// [`RandomVariable`]: crate::RandomVariable
+#[macro_export]
+macro_rules! lift {
+    ($strategy:ty, $rng:expr, $func:expr, $functor:expr; $($r:ty),+ $(,)?) => {{
+        let rng = $rng;
+        let f = <$strategy as $crate::RandomStrategy>::fmap($functor, |state| (state, ()));
+        $crate::lift!(@rand $strategy, rng, $func, f, []; $($r),+)
+    }};
+    (@rand $strategy:ty, $rng:ident, $func:expr, $f:ident, [$($args:ident)*]; $r:ty $(, $rest:ty)*) => {{
+        let f = <$strategy as $crate::RandomStrategy>::fmap_rand(
+            $f,
+            &mut *$rng,
+            |(state, ($($args,)*)), r: $r| (state, ($($args,)* r,)),
+        );
+        $crate::lift!(@rand $strategy, $rng, $func, f, [$($args)* r]; $($rest),*)
+    }};
+    (@rand $strategy:ty, $rng:ident, $func:expr, $f:ident, [$($args:ident)*];) => {
+        <$strategy as $crate::RandomStrategy>::fmap($f, |(state, ($($args,)*))| {
+            ($func)(state, $($args),*)
+        })
+    };
+}
+
+/// Defines a set of flags that can be used as a [`RandomVariable`].
+///
+/// `flags!` defines a newtype around an unsigned integer mask, with an
+/// associated constant for each flag. Only the bits belonging to some flag are
+/// valid, so the sample space of the type contains exactly the masks made up of
+/// valid bits, rather than every value of the integer. Sampling from
+/// [`Standard`] is uniform over that sample space.
+///
+/// The type implements [`Flags`], [`RandomVariable`], and [`Cardinality`], as
+/// well as [`SmallRandomVariable`] when the mask is a `u8` or `u16`. It also
+/// provides bitflags-style methods for constructing and inspecting sets of
+/// flags.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{flags, Cardinality, Counter, Functor, RandomStrategy};
+///
+/// flags! {
+///     /// The status effects a character can have.
+///     pub struct Status: u8 {
+///         const POISONED = 0b0001;
+///         const STUNNED = 0b0100;
+///         const BURNING = 0b1000;
+///     }
+/// }
+///
+/// assert_eq!(Status::CARDINALITY, 8);
+/// let counts = <Counter>::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, s: Status| {
+///     s.contains(Status::STUNNED)
+/// });
+/// assert_eq!(counts[&true], 4);
+/// ```
+///
+/// [`Cardinality`]: crate::Cardinality
+/// [`Flags`]: crate::flags::Flags
+/// [`RandomVariable`]: crate::RandomVariable
+/// [`SmallRandomVariable`]: crate::SmallRandomVariable
+/// [`Standard`]: rand::distributions::Standard
+#[macro_export]
+macro_rules! flags {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $bits:ident {
+            $(
+                $(#[$flag_attr:meta])*
+                const $flag:ident = $value:expr;
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        $vis struct $name($bits);
+
+        #[allow(dead_code)]
+        impl $name {
+            $(
+                $(#[$flag_attr])*
+                pub const $flag: Self = Self($value);
+            )*
+
+            /// Returns the set containing no flags.
+            #[inline]
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Returns the set containing every flag.
+            #[inline]
+            pub const fn all() -> Self {
+                Self(0 $(| $value)*)
+            }
+
+            /// Returns the mask of this set of flags.
+            #[inline]
+            pub const fn bits(self) -> $bits {
+                self.0
+            }
+
+            /// Constructs a set of flags from a mask, returning `None` if any
+            /// bit does not belong to a flag.
+            #[inline]
+            pub const fn from_bits(bits: $bits) -> Option<Self> {
+                if bits & !Self::all().0 == 0 {
+                    Some(Self(bits))
+                } else {
+                    None
+                }
+            }
+
+            /// Constructs a set of flags from a mask, discarding any bit that
+            /// does not belong to a flag.
+            #[inline]
+            pub const fn from_bits_truncate(bits: $bits) -> Self {
+                Self(bits & Self::all().0)
+            }
+
+            /// Returns `true` if this set contains no flags.
+            #[inline]
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns `true` if this set contains every flag in `other`.
+            #[inline]
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Adds every flag in `other` to this set.
+            #[inline]
+            pub fn insert(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+
+            /// Removes every flag in `other` from this set.
+            #[inline]
+            pub fn remove(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+        }
+
+        impl ::core::ops::BitOr for $name {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl ::core::ops::BitAnd for $name {
+            type Output = Self;
+
+            #[inline]
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl $crate::flags::Flags for $name {
+            type Bits = $bits;
+
+            const ALL: Self = Self::all();
+
+            #[inline]
+            fn bits(self) -> $bits {
+                self.0
+            }
+
+            #[inline]
+            fn from_bits_truncate(bits: $bits) -> Self {
+                Self::from_bits_truncate(bits)
+            }
+        }
+
+        impl $crate::flags::__rand::distributions::Distribution<$name>
+            for $crate::flags::__rand::distributions::Standard
+        {
+            #[inline]
+            fn sample<R: $crate::flags::__rand::Rng + ?Sized>(&self, rng: &mut R) -> $name {
+                $crate::flags::sample(rng)
+            }
+        }
+
+        impl $crate::RandomVariable for $name {
+            fn sample_space() -> impl Iterator<Item = Self> {
+                $crate::flags::sample_space()
+            }
+        }
+
+        impl $crate::Cardinality for $name {
+            const CARDINALITY: u128 = match 1u128.checked_shl(Self::all().0.count_ones()) {
+                Some(cardinality) => cardinality,
+                None => u128::MAX,
+            };
+        }
+
+        $crate::flags!(@small $name: $bits);
+    };
+    (@small $name:ident: u8) => {
+        impl $crate::SmallRandomVariable for $name {}
+    };
+    (@small $name:ident: u16) => {
+        impl $crate::SmallRandomVariable for $name {}
+    };
+    (@small $name:ident: $bits:ident) => {};
+}
+
+/// Asserts that the distribution of the outcomes stored in a
+/// [`WeightedFunctor`] matches a [`DistributionSnapshot`].
+///
+/// `assert_distribution_eq!(functor, snapshot)` records a snapshot of
+/// `functor` with [`DistributionSnapshot::new`] and compares it against
+/// `snapshot`, allowing the probability of each outcome to differ by up to
+/// [`DEFAULT_SNAPSHOT_TOLERANCE`]. A different tolerance can be given as a
+/// third argument. If any probabilities differ by more than the tolerance, the
+/// assertion panics and lists every difference.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::testing::DistributionSnapshot;
+/// use rand_functors::{assert_distribution_eq, Counter, Enumerator, Functor, RandomStrategy};
+///
+/// let before = <Counter>::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: u8| r % 2 == 0);
+/// let after = Enumerator::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: bool| r);
+/// assert_distribution_eq!(after, DistributionSnapshot::new(&before));
+/// ```
+///
+/// This macro is only available with the `std` feature.
+///
+/// [`DEFAULT_SNAPSHOT_TOLERANCE`]: crate::testing::DEFAULT_SNAPSHOT_TOLERANCE
+/// [`DistributionSnapshot`]: crate::testing::DistributionSnapshot
+/// [`DistributionSnapshot::new`]: crate::testing::DistributionSnapshot::new
+/// [`WeightedFunctor`]: crate::WeightedFunctor
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! assert_distribution_eq {
+    ($functor:expr, $snapshot:expr $(,)?) => {
+        $crate::assert_distribution_eq!(
+            $functor,
+            $snapshot,
+            $crate::testing::DEFAULT_SNAPSHOT_TOLERANCE
+        )
+    };
+    ($functor:expr, $snapshot:expr, $tolerance:expr $(,)?) => {{
+        let actual = $crate::testing::DistributionSnapshot::new(&$functor);
+        let differences = actual.differences(&$snapshot, $tolerance);
+        if !differences.is_empty() {
+            let differences = differences
+                .iter()
+                .map(|d| ::std::format!("\n  {}", d))
+                .collect::<::std::string::String>();
+            ::std::panic!(
+                "distribution does not match snapshot within a tolerance of {}:{}",
+                $tolerance,
+                differences
+            );
+        }
+    }};
+}
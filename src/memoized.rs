@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::{DefaultState, FlattenableRandomStrategy, Inner};
+
+/// A step of a random process which remembers the functor it produced for
+/// every state it has been applied to.
+///
+/// When a process is run for many steps, the same states are often reached
+/// again and again. `Memoized` evaluates the step once for each distinct
+/// state, and afterwards returns a copy of the cached functor instead of
+/// evaluating it again. This is only appropriate for strategies whose results
+/// do not depend on the random number generator, such as [`Counter`] and
+/// [`Enumerator`], as a strategy which samples would otherwise reuse the same
+/// sample for every occurrence of a state.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, Functor, Memoized, RandomStrategy};
+///
+/// let mut step = Memoized::<Counter, _, _>::new(|s: u8| {
+///     Counter::fmap_rand(Functor::pure(s), &mut thread_rng(), |s, r: bool| {
+///         if r { s.saturating_add(1).min(3) } else { s.saturating_sub(1) }
+///     })
+/// });
+/// let output = step.run(Functor::pure(0), 10);
+/// assert_eq!(output.values().sum::<usize>(), 1 << 10);
+/// assert_eq!(step.len(), 4);
+/// ```
+///
+/// [`Counter`]: crate::Counter
+/// [`Enumerator`]: crate::Enumerator
+pub struct Memoized<S: FlattenableRandomStrategy, T: Inner, F> {
+    step: F,
+    cache: HashMap<T, S::Functor<T>, DefaultState>,
+    hits: usize,
+}
+
+impl<S: FlattenableRandomStrategy, T: Inner, F: FnMut(T) -> S::Functor<T>> Memoized<S, T, F>
+where
+    S::Functor<T>: Clone,
+{
+    /// Memoizes `step`, a random process evaluated with `S`.
+    #[inline]
+    pub fn new(step: F) -> Self {
+        Self {
+            step,
+            cache: HashMap::default(),
+            hits: 0,
+        }
+    }
+
+    /// Returns the functor produced by applying the step to `state`,
+    /// evaluating it only if it has not been applied to `state` before.
+    pub fn step(&mut self, state: T) -> S::Functor<T> {
+        if let Some(f) = self.cache.get(&state) {
+            self.hits += 1;
+            return f.clone();
+        }
+        let f = (self.step)(state.clone());
+        self.cache.insert(state, f.clone());
+        f
+    }
+
+    /// Applies the step to every outcome of `f`, `steps` times, flattening
+    /// the results with [`FlattenableRandomStrategy::fmap_flat`].
+    ///
+    /// Each completed step is reported to the callback installed by
+    /// [`with_progress`].
+    ///
+    /// [`with_progress`]: crate::with_progress
+    #[inline]
+    pub fn run(&mut self, f: S::Functor<T>, steps: usize) -> S::Functor<T> {
+        self.run_until(f, steps, || false).0
+    }
+
+    /// Applies the step to every outcome of `f` like [`Memoized::run`], until
+    /// `stop` returns `true`.
+    ///
+    /// `stop` is called before each step, so it can check a cancellation flag
+    /// or a deadline. Returns the functor produced by the completed steps,
+    /// along with the number of steps that were completed.
+    pub fn run_until(
+        &mut self,
+        mut f: S::Functor<T>,
+        steps: usize,
+        mut stop: impl FnMut() -> bool,
+    ) -> (S::Functor<T>, usize) {
+        for i in 0..steps {
+            if stop() {
+                return (f, i);
+            }
+            f = S::fmap_flat(f, |t| self.step(t));
+            crate::progress::report_iteration("Memoized", i + 1, Some(steps));
+        }
+        (f, steps)
+    }
+
+    /// Returns the number of distinct states whose functors are cached.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if no functors are cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the number of times a cached functor was returned instead of
+    /// evaluating the step.
+    #[inline]
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Discards every cached functor.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+    }
+}
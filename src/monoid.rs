@@ -0,0 +1,169 @@
+//! Monoids for aggregating the outcomes of a random process.
+//!
+//! A [`Monoid`] describes how to combine per-outcome values into a single
+//! summary, which [`WeightedFunctor::aggregate`] computes without allocating.
+//! The wrapper types in this module select a particular monoid for types that
+//! admit several, such as integers, which can be summed, minimized or
+//! maximized.
+//!
+//! [`WeightedFunctor::aggregate`]: crate::WeightedFunctor::aggregate
+
+use core::ops::BitOr;
+
+use num_traits::{Bounded, Zero};
+
+/// A type with an associative binary operation and an identity element.
+///
+/// Implementations must satisfy `a.combine(b).combine(c) ==
+/// a.combine(b.combine(c))` and `M::identity().combine(a) == a ==
+/// a.combine(M::identity())`. Aggregations rely on these laws to combine
+/// outcomes in whatever order a functor stores them.
+pub trait Monoid: Clone {
+    /// Returns the identity element.
+    fn identity() -> Self;
+
+    /// Combines `self` with `other`.
+    fn combine(self, other: Self) -> Self;
+
+    /// Combines `n` copies of `self`, returning [`Monoid::identity`] if `n` is
+    /// zero.
+    ///
+    /// The default implementation uses exponentiation by squaring, performing
+    /// `O(log n)` combinations. Monoids with a cheaper closed form, such as
+    /// idempotent ones, may override it.
+    fn pow(self, mut n: u128) -> Self {
+        let mut base = self;
+        let mut acc = Self::identity();
+        while n > 0 {
+            if n & 1 == 1 {
+                acc = acc.combine(base.clone());
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.clone().combine(base);
+            }
+        }
+        acc
+    }
+}
+
+impl Monoid for () {
+    #[inline]
+    fn identity() -> Self {}
+
+    #[inline]
+    fn combine(self, _: Self) -> Self {}
+}
+
+impl<A: Monoid, B: Monoid> Monoid for (A, B) {
+    #[inline]
+    fn identity() -> Self {
+        (A::identity(), B::identity())
+    }
+
+    #[inline]
+    fn combine(self, other: Self) -> Self {
+        (self.0.combine(other.0), self.1.combine(other.1))
+    }
+
+    #[inline]
+    fn pow(self, n: u128) -> Self {
+        (self.0.pow(n), self.1.pow(n))
+    }
+}
+
+/// The monoid of values under addition, with identity zero.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Sum<T>(pub T);
+
+impl<T: Clone + Zero> Monoid for Sum<T> {
+    #[inline]
+    fn identity() -> Self {
+        Self(T::zero())
+    }
+
+    #[inline]
+    fn combine(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+/// The monoid of values under taking the minimum, with identity the largest
+/// value.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Min<T>(pub T);
+
+impl<T: Bounded + Clone + Ord> Monoid for Min<T> {
+    #[inline]
+    fn identity() -> Self {
+        Self(T::max_value())
+    }
+
+    #[inline]
+    fn combine(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    #[inline]
+    fn pow(self, n: u128) -> Self {
+        if n == 0 {
+            Self::identity()
+        } else {
+            self
+        }
+    }
+}
+
+/// The monoid of values under taking the maximum, with identity the smallest
+/// value.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Max<T>(pub T);
+
+impl<T: Bounded + Clone + Ord> Monoid for Max<T> {
+    #[inline]
+    fn identity() -> Self {
+        Self(T::min_value())
+    }
+
+    #[inline]
+    fn combine(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    #[inline]
+    fn pow(self, n: u128) -> Self {
+        if n == 0 {
+            Self::identity()
+        } else {
+            self
+        }
+    }
+}
+
+/// The monoid of values under bitwise OR, with identity the [`Default`] value.
+///
+/// This covers [`bool`], where it computes whether any outcome satisfies a
+/// condition, integer masks, and flags defined with [`flags!`](crate::flags!).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Or<T>(pub T);
+
+impl<T: BitOr<Output = T> + Clone + Default> Monoid for Or<T> {
+    #[inline]
+    fn identity() -> Self {
+        Self(T::default())
+    }
+
+    #[inline]
+    fn combine(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    #[inline]
+    fn pow(self, n: u128) -> Self {
+        if n == 0 {
+            Self::identity()
+        } else {
+            self
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::ops::{Deref, Range};
+use std::vec::Vec;
+
+use num_traits::ToPrimitive;
+use rand::SeedableRng;
+use rand_core::RngCore;
+
+use crate::{DefaultState, Inner};
+
+/// The outcomes of a process run once for each of many seeds by
+/// [`monte_carlo`], along with how often each occurred.
+///
+/// `MonteCarlo` dereferences to the [`HashMap`] of counts, which implements
+/// [`WeightedFunctor`], so the results can also be reported and exported like
+/// those of [`Counter`].
+///
+/// [`Counter`]: crate::Counter
+/// [`WeightedFunctor`]: crate::WeightedFunctor
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonteCarlo<T: Inner> {
+    counts: HashMap<T, usize, DefaultState>,
+    samples: usize,
+}
+
+impl<T: Inner> MonteCarlo<T> {
+    /// Returns the number of times the process was run.
+    #[inline]
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Returns each outcome and the number of times it occurred.
+    #[inline]
+    pub fn into_counts(self) -> HashMap<T, usize, DefaultState> {
+        self.counts
+    }
+}
+
+impl<T: Inner + ToPrimitive> MonteCarlo<T> {
+    /// Returns the mean of the outcomes, or [`None`] if there are none.
+    pub fn mean(&self) -> Option<f64> {
+        if self.samples == 0 {
+            return None;
+        }
+        let sum = self
+            .counts
+            .iter()
+            .map(|(t, &count)| to_f64(t) * count as f64)
+            .sum::<f64>();
+        Some(sum / self.samples as f64)
+    }
+
+    /// Returns the sample variance of the outcomes, or [`None`] if there are
+    /// fewer than two.
+    pub fn variance(&self) -> Option<f64> {
+        if self.samples < 2 {
+            return None;
+        }
+        let mean = self.mean()?;
+        let sum = self
+            .counts
+            .iter()
+            .map(|(t, &count)| (to_f64(t) - mean).powi(2) * count as f64)
+            .sum::<f64>();
+        Some(sum / (self.samples - 1) as f64)
+    }
+
+    /// Counts the outcomes falling in each of `bins` bins of equal width
+    /// spanning `range`.
+    ///
+    /// Each bin includes its lower bound, and the last bin also includes the
+    /// end of `range`. Outcomes outside of `range` are not counted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero or `range` is empty.
+    pub fn histogram(&self, range: Range<f64>, bins: usize) -> Vec<usize> {
+        assert!(bins != 0, "bins must not be zero");
+        assert!(range.start < range.end, "range must not be empty");
+        let width = (range.end - range.start) / bins as f64;
+        let mut histogram = vec![0; bins];
+        for (t, &count) in &self.counts {
+            let t = to_f64(t);
+            if !(range.start..=range.end).contains(&t) {
+                continue;
+            }
+            let bin = (((t - range.start) / width) as usize).min(bins - 1);
+            histogram[bin] += count;
+        }
+        histogram
+    }
+}
+
+impl<T: Inner> Deref for MonteCarlo<T> {
+    type Target = HashMap<T, usize, DefaultState>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.counts
+    }
+}
+
+#[inline(always)]
+fn to_f64(t: &impl ToPrimitive) -> f64 {
+    t.to_f64().expect("outcomes must be representable as f64")
+}
+
+/// Runs `process`, a random process evaluated with [`Sampler`], once for each
+/// seed in `seeds`, spread across `threads` threads.
+///
+/// Each run is given a new random number generator of type `R`, seeded with
+/// [`SeedableRng::seed_from_u64`]. Seeds are divided between the threads in
+/// contiguous blocks, and the results do not depend on the number of threads,
+/// so a run can be reproduced exactly from its range of seeds. As each thread
+/// finishes, the number of runs completed so far is reported to the callback
+/// installed by [`with_progress`] on the calling thread.
+/// ```
+/// use rand_chacha::ChaCha8Rng;
+/// use rand_functors::{monte_carlo, RandomStrategy, Sampler};
+///
+/// let results = monte_carlo(0..10_000, 4, |rng: &mut ChaCha8Rng| {
+///     let f = Sampler::fmap_rand(0u8, rng, |s, r: bool| s + r as u8);
+///     Sampler::fmap_rand(f, rng, |s, r: bool| s + r as u8)
+/// });
+/// assert_eq!(results.samples(), 10_000);
+/// assert!((results.mean().unwrap() - 1.0).abs() < 0.05);
+/// assert!((results.variance().unwrap() - 0.5).abs() < 0.05);
+/// assert_eq!(results.histogram(0.0..2.0, 2).iter().sum::<usize>(), 10_000);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `threads` is zero, or if `process` panics.
+///
+/// [`Sampler`]: crate::Sampler
+/// [`with_progress`]: crate::with_progress
+pub fn monte_carlo<R: SeedableRng + RngCore, T: Inner + Send>(
+    seeds: Range<u64>,
+    threads: usize,
+    process: impl Fn(&mut R) -> T + Sync,
+) -> MonteCarlo<T> {
+    monte_carlo_until(seeds, threads, || false, process)
+}
+
+/// Runs `process` once for each seed in `seeds` like [`monte_carlo`], until
+/// `stop` returns `true`.
+///
+/// Every thread calls `stop` before each run, so it can check a cancellation
+/// flag shared with another thread, or a deadline. Once it returns `true`, the
+/// threads finish their current runs and the results of every completed run
+/// are returned. [`MonteCarlo::samples`] is the number of runs that were
+/// completed.
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// use rand_chacha::ChaCha8Rng;
+/// use rand_functors::{monte_carlo_until, RandomStrategy, Sampler};
+///
+/// let cancelled = AtomicBool::new(true);
+/// let results = monte_carlo_until(0..10_000, 4, || cancelled.load(Ordering::Relaxed), |rng: &mut ChaCha8Rng| {
+///     Sampler::fmap_rand((), rng, |_, r: bool| r)
+/// });
+/// assert_eq!(results.samples(), 0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `threads` is zero, or if `process` or `stop` panics.
+pub fn monte_carlo_until<R: SeedableRng + RngCore, T: Inner + Send>(
+    seeds: Range<u64>,
+    threads: usize,
+    stop: impl Fn() -> bool + Sync,
+    process: impl Fn(&mut R) -> T + Sync,
+) -> MonteCarlo<T> {
+    assert!(threads != 0, "threads must not be zero");
+    let samples = seeds.end.saturating_sub(seeds.start);
+    let block = samples.div_ceil(threads as u64).max(1);
+    let (stop, process) = (&stop, &process);
+    let blocks = std::thread::scope(|scope| {
+        let handles = (0..threads as u64)
+            .map(|i| {
+                let start = seeds.start.saturating_add(i * block).min(seeds.end);
+                let end = start.saturating_add(block).min(seeds.end);
+                scope.spawn(move || {
+                    let mut counts = HashMap::<T, usize, DefaultState>::default();
+                    for seed in start..end {
+                        if stop() {
+                            break;
+                        }
+                        let outcome = process(&mut R::seed_from_u64(seed));
+                        *counts.entry(outcome).or_insert(0) += 1;
+                    }
+                    counts
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut completed = 0;
+        handles
+            .into_iter()
+            .map(|handle| {
+                let counts = handle
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e));
+                completed += counts.values().sum::<usize>();
+                crate::progress::report_iteration("monte_carlo", completed, Some(samples as usize));
+                counts
+            })
+            .collect::<Vec<_>>()
+    });
+    let mut counts = HashMap::default();
+    let mut completed = 0;
+    for block in blocks {
+        for (outcome, count) in block {
+            *counts.entry(outcome).or_insert(0) += count;
+            completed += count;
+        }
+    }
+    MonteCarlo {
+        counts,
+        samples: completed,
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A change to a state of type `T`, produced by
+/// [`RandomStrategy::fmap_rand_delta`].
+///
+/// A patch describes only the part of a state that an operation changes, so
+/// an operation producing many outcomes from a large state does not need to
+/// construct a full copy of it for every outcome in order to compute them.
+/// Strategies apply each patch to a copy of the state it was computed from,
+/// making those copies only when they are needed.
+///
+/// `()` is the patch which changes nothing, and [`None`] is a patch which
+/// changes nothing when the patch it wraps would otherwise be applied.
+///
+/// [`RandomStrategy::fmap_rand_delta`]: crate::RandomStrategy::fmap_rand_delta
+pub trait Patch<T> {
+    /// Applies this change to `state`.
+    fn apply(self, state: &mut T);
+}
+
+impl<T> Patch<T> for () {
+    #[inline]
+    fn apply(self, _: &mut T) {}
+}
+
+impl<T, P: Patch<T>> Patch<T> for Option<P> {
+    #[inline]
+    fn apply(self, state: &mut T) {
+        if let Some(patch) = self {
+            patch.apply(state);
+        }
+    }
+}
+
+/// Applies each of `patches` to its own copy of `base`, cloning `base` for
+/// every patch but the last, which is applied to `base` itself.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub(crate) fn apply_patches<T: Clone, P: Patch<T>>(
+    base: T,
+    patches: Vec<P>,
+) -> impl Iterator<Item = T> {
+    let last = patches.len().saturating_sub(1);
+    let mut base = Some(base);
+    patches.into_iter().enumerate().map(move |(i, patch)| {
+        let mut state = if i == last { base.take() } else { base.clone() }
+            .expect("the base state is only taken for the last patch");
+        patch.apply(&mut state);
+        state
+    })
+}
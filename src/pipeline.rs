@@ -0,0 +1,1262 @@
+//! A builder for random processes that are evaluated under a strategy chosen
+//! when they are run.
+//!
+//! A [`Pipeline`] records a series of operations on a starting state without
+//! performing any of them. Calling [`Pipeline::run`] evaluates the whole
+//! series using a single [`RandomStrategy`] and random number generator:
+//! ```
+//! use rand::prelude::*;
+//! use rand_functors::{Enumerator, Pipeline, Sampler};
+//!
+//! let pipeline = Pipeline::start(0u8)
+//!     .rand(|s, r: u8| s.wrapping_add(r))
+//!     .rand(|s, r: bool| if r { s % 3 } else { s })
+//!     .map(|s| s.wrapping_mul(2));
+//!
+//! let sample = pipeline.run::<Sampler>(&mut thread_rng());
+//! let outcomes = pipeline.run::<Enumerator>(&mut thread_rng());
+//! assert!(outcomes.contains(&sample));
+//! ```
+//!
+//! The types in this module are the stages that make up a pipeline. They are
+//! not typically named directly.
+
+#[cfg(feature = "std")]
+use core::fmt;
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::ops::ControlFlow;
+#[cfg(feature = "std")]
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+#[cfg(feature = "std")]
+use std::thread::Scope;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+#[cfg(feature = "std")]
+use crate::{Counter, DefaultState, Enumerator, Sampler};
+use crate::{
+    FlattenableRandomStrategy, Functor, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable,
+};
+
+/// A random process assembled from a series of stages.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Clone, Copy, Debug)]
+pub struct Pipeline<P> {
+    stage: P,
+}
+
+/// A stage of a [`Pipeline`], producing values of type [`Stage::Output`].
+pub trait Stage {
+    /// The type of the values produced by this stage.
+    type Output: Inner;
+
+    /// Returns the total count of the outcomes produced by this stage and all
+    /// stages preceding it when evaluated with [`Counter`], without evaluating
+    /// any of them.
+    ///
+    /// This is the product of the number of values sampled by each stage, so
+    /// it bounds the count of every individual outcome. [`None`] is returned
+    /// if it does not fit in a [`u128`], or if it cannot be known before
+    /// evaluation, as for a [`Flat`] stage.
+    ///
+    /// [`Counter`]: crate::Counter
+    fn max_total_count(&self) -> Option<u128>;
+}
+
+/// The outcomes of a [`Pipeline`] and their counts, as produced by
+/// [`Pipeline::run_counted`] using the narrowest count type that cannot
+/// overflow.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Counts<I: Inner> {
+    /// Counts produced by `Counter<DefaultState, usize>`.
+    Usize(HashMap<I, usize, DefaultState>),
+    /// Counts produced by `Counter<DefaultState, u128>`.
+    U128(HashMap<I, u128, DefaultState>),
+}
+
+#[cfg(feature = "std")]
+impl<I: Inner> Counts<I> {
+    /// Returns the number of times `outcome` was produced.
+    #[inline]
+    pub fn count(&self, outcome: &I) -> u128 {
+        match self {
+            Counts::Usize(counts) => counts.get(outcome).map_or(0, |&c| c as u128),
+            Counts::U128(counts) => counts.get(outcome).copied().unwrap_or(0),
+        }
+    }
+
+    /// Returns the outcomes and their counts, widened to [`u128`].
+    #[inline]
+    pub fn into_u128(self) -> HashMap<I, u128, DefaultState> {
+        match self {
+            Counts::Usize(counts) => counts.into_iter().map(|(o, c)| (o, c as u128)).collect(),
+            Counts::U128(counts) => counts,
+        }
+    }
+}
+
+/// An error returned by [`Pipeline::run_counted`] when the counts of a
+/// pipeline's outcomes cannot be shown to fit in a [`u128`].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CountOverflowError;
+
+#[cfg(feature = "std")]
+impl fmt::Display for CountOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "counts of the outcomes of the pipeline may overflow a u128"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CountOverflowError {}
+
+/// The outcomes of a [`Pipeline`] evaluated within a time budget by
+/// [`Pipeline::run_budgeted`].
+///
+/// Like the output of [`Enumerator`], every outcome is equally likely. The
+/// outcomes of the stages evaluated before the budget ran out are enumerated
+/// exactly, and each of them is continued by a single sample of the remaining
+/// stages. `Budgeted` dereferences to the [`Vec`] of outcomes.
+///
+/// [`Enumerator`]: crate::Enumerator
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Budgeted<I> {
+    outcomes: Vec<I>,
+    exact_stages: usize,
+    exact: bool,
+}
+
+#[cfg(feature = "std")]
+impl<I> Budgeted<I> {
+    /// Returns the number of stages, following [`Pipeline::start`], which
+    /// were evaluated exactly before the first stage that was sampled.
+    ///
+    /// Stages of a pipeline continuing a value in a [`Pipeline::flat`] stage
+    /// are not counted separately. The [`Pipeline::flat`] stage is counted if
+    /// every such pipeline was evaluated exactly.
+    #[inline]
+    pub fn exact_stages(&self) -> usize {
+        self.exact_stages
+    }
+
+    /// Returns `true` if every stage was evaluated exactly, in which case the
+    /// outcomes are those produced by [`Enumerator`].
+    ///
+    /// [`Enumerator`]: crate::Enumerator
+    #[inline]
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+
+    /// Returns the outcomes.
+    #[inline]
+    pub fn into_inner(self) -> Vec<I> {
+        self.outcomes
+    }
+
+    /// Continues these outcomes with a stage, enumerating its outcomes with
+    /// `exact` if the stages so far were exact and `deadline` has not passed,
+    /// or sampling one for each outcome with `sample` otherwise.
+    #[inline(always)]
+    fn continue_with<B, R: RngCore>(
+        self,
+        deadline: Option<Instant>,
+        rng: &mut R,
+        exact: impl FnOnce(Vec<I>, &mut R) -> Vec<B>,
+        mut sample: impl FnMut(I, &mut R) -> B,
+    ) -> Budgeted<B> {
+        if self.exact && !expired(deadline) {
+            Budgeted {
+                outcomes: exact(self.outcomes, rng),
+                exact_stages: self.exact_stages + 1,
+                exact: true,
+            }
+        } else {
+            Budgeted {
+                outcomes: self.outcomes.into_iter().map(|a| sample(a, rng)).collect(),
+                exact_stages: self.exact_stages,
+                exact: false,
+            }
+        }
+    }
+}
+
+/// Returns `true` if `deadline` has passed. [`None`] is a deadline that never
+/// passes.
+#[cfg(feature = "std")]
+#[inline(always)]
+fn expired(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+#[cfg(feature = "std")]
+impl<I> Deref for Budgeted<I> {
+    type Target = Vec<I>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.outcomes
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I> IntoIterator for Budgeted<I> {
+    type Item = I;
+    type IntoIter = std::vec::IntoIter<I>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.outcomes.into_iter()
+    }
+}
+
+/// A [`Stage`] that can be evaluated using the strategy `S`.
+pub trait Evaluate<S: RandomStrategy>: Stage {
+    /// Evaluates this stage and all stages preceding it.
+    fn evaluate(&self, rng: &mut impl RngCore) -> S::Functor<Self::Output>;
+}
+
+/// The random choices leading to an outcome visited by [`Search::search`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    /// The index into the sample space of each random variable sampled, in
+    /// the order in which they were sampled.
+    pub trace: Vec<usize>,
+    /// The probability of making these choices.
+    pub probability: f64,
+}
+
+#[cfg(feature = "alloc")]
+impl Path {
+    /// Continues this path with the choice of the value at `index` in a
+    /// sample space of `size` values, runs `op`, and then undoes the choice.
+    #[inline]
+    fn choose<T>(&mut self, index: usize, size: usize, op: impl FnOnce(&mut Self) -> T) -> T {
+        let probability = self.probability;
+        self.trace.push(index);
+        self.probability /= size as f64;
+        let output = op(self);
+        self.probability = probability;
+        self.trace.pop();
+        output
+    }
+}
+
+/// A [`Stage`] whose outcomes can be searched lazily by
+/// [`Pipeline::find_first`] and [`Pipeline::run_until`].
+#[cfg(feature = "alloc")]
+pub trait Search: Stage {
+    /// Passes each outcome of this stage to `visit` in depth-first order, along
+    /// with the [`Path`] leading to it, until `visit` breaks.
+    ///
+    /// `path` describes the random choices made before this stage began.
+    /// Implementations extend it before calling `visit`, and restore it
+    /// before returning.
+    fn search(
+        &self,
+        path: &mut Path,
+        visit: &mut impl FnMut(Self::Output, &mut Path) -> ControlFlow<()>,
+    ) -> ControlFlow<()>;
+}
+
+/// A [`Stage`] that can be evaluated on its own thread by
+/// [`Pipeline::run_pipelined`].
+#[cfg(feature = "std")]
+pub trait Stream: Stage {
+    /// Spawns threads in `scope` evaluating this stage and all stages
+    /// preceding it, returning a channel on which this stage sends its
+    /// outcomes in chunks of `chunk_size`.
+    ///
+    /// Each channel holds at most `capacity` chunks, after which the stage
+    /// sending on it waits for the next stage to catch up. A stage stops
+    /// early if the next stage hangs up.
+    fn stream<'scope, 'env>(
+        &'env self,
+        scope: &'scope Scope<'scope, 'env>,
+        chunk_size: usize,
+        capacity: usize,
+    ) -> Receiver<Vec<Self::Output>>;
+}
+
+/// A [`Stage`] that can be evaluated within a time budget by
+/// [`Pipeline::run_budgeted`].
+#[cfg(feature = "std")]
+pub trait Budget: Stage {
+    /// Evaluates this stage and all stages preceding it, enumerating the
+    /// outcomes of each stage exactly until `deadline` passes, and sampling
+    /// them afterwards. A `deadline` of [`None`] never passes.
+    fn evaluate_within(
+        &self,
+        rng: &mut impl RngCore,
+        deadline: Option<Instant>,
+    ) -> Budgeted<Self::Output>;
+}
+
+/// Buffers outcomes, sending them to the next stage of a [`Pipeline`] once
+/// enough have been produced to fill a chunk.
+#[cfg(feature = "std")]
+struct Chunks<T> {
+    sender: SyncSender<Vec<T>>,
+    buffer: Vec<T>,
+    chunk_size: usize,
+}
+
+#[cfg(feature = "std")]
+impl<T> Chunks<T> {
+    #[inline]
+    fn new(sender: SyncSender<Vec<T>>, chunk_size: usize) -> Self {
+        Self {
+            sender,
+            buffer: Vec::with_capacity(chunk_size),
+            chunk_size,
+        }
+    }
+
+    /// Adds an outcome to the current chunk, breaking if the next stage has
+    /// hung up.
+    #[inline]
+    fn push(&mut self, outcome: T) -> ControlFlow<()> {
+        self.buffer.push(outcome);
+        if self.buffer.len() < self.chunk_size {
+            return ControlFlow::Continue(());
+        }
+        let chunk = core::mem::replace(&mut self.buffer, Vec::with_capacity(self.chunk_size));
+        match self.sender.send(chunk) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(_) => ControlFlow::Break(()),
+        }
+    }
+
+    /// Sends the last, partially filled chunk.
+    #[inline]
+    fn finish(self) {
+        if !self.buffer.is_empty() {
+            let _ = self.sender.send(self.buffer);
+        }
+    }
+}
+
+/// The first stage of a [`Pipeline`], producing its starting state.
+#[derive(Clone, Copy, Debug)]
+pub struct Start<I> {
+    state: I,
+}
+
+/// A stage applying a function to the values produced by the previous stage.
+#[derive(Clone, Copy, Debug)]
+pub struct Map<P, F, B> {
+    prev: P,
+    func: F,
+    output_phantom: PhantomData<fn() -> B>,
+}
+
+/// A stage applying a function to the values produced by the previous stage
+/// and a [`RandomVariable`].
+#[derive(Clone, Copy, Debug)]
+pub struct Rand<P, F, R, B> {
+    prev: P,
+    func: F,
+    phantom: PhantomData<fn() -> (R, B)>,
+}
+
+/// A stage applying a function to the values produced by the previous stage
+/// and a [`RandomVariableRange`].
+#[derive(Clone, Copy, Debug)]
+pub struct RandRange<P, G, F, R, B> {
+    prev: P,
+    range: G,
+    func: F,
+    phantom: PhantomData<fn() -> (R, B)>,
+}
+
+/// A stage continuing each value produced by the previous stage with another
+/// [`Pipeline`].
+#[derive(Clone, Copy, Debug)]
+pub struct Flat<P, F, Q> {
+    prev: P,
+    func: F,
+    pipeline_phantom: PhantomData<fn() -> Q>,
+}
+
+impl<I: Inner> Pipeline<Start<I>> {
+    /// Begins a pipeline with the given starting state.
+    #[inline]
+    pub fn start(state: I) -> Self {
+        Self {
+            stage: Start { state },
+        }
+    }
+}
+
+impl<P: Stage> Pipeline<P> {
+    /// Appends an operation equivalent to [`RandomStrategy::fmap`].
+    #[inline]
+    pub fn map<B: Inner, F: Fn(P::Output) -> B>(self, func: F) -> Pipeline<Map<P, F, B>> {
+        Pipeline {
+            stage: Map {
+                prev: self.stage,
+                func,
+                output_phantom: PhantomData,
+            },
+        }
+    }
+
+    /// Appends an operation equivalent to [`RandomStrategy::fmap_rand`].
+    #[inline]
+    pub fn rand<B: Inner, R: SmallRandomVariable, F: Fn(P::Output, R) -> B>(
+        self,
+        func: F,
+    ) -> Pipeline<Rand<P, F, R, B>>
+    where
+        Standard: Distribution<R>,
+    {
+        Pipeline {
+            stage: Rand {
+                prev: self.stage,
+                func,
+                phantom: PhantomData,
+            },
+        }
+    }
+
+    /// Appends an operation equivalent to [`RandomStrategy::fmap_rand_range`].
+    #[inline]
+    pub fn rand_range<
+        B: Inner,
+        R: RandomVariable + SampleUniform,
+        G: RandomVariableRange<R> + Clone,
+        F: Fn(P::Output, R) -> B,
+    >(
+        self,
+        range: G,
+        func: F,
+    ) -> Pipeline<RandRange<P, G, F, R, B>>
+    where
+        Standard: Distribution<R>,
+    {
+        Pipeline {
+            stage: RandRange {
+                prev: self.stage,
+                range,
+                func,
+                phantom: PhantomData,
+            },
+        }
+    }
+
+    /// Appends an operation equivalent to
+    /// [`FlattenableRandomStrategy::fmap_flat`], which continues each value
+    /// with the pipeline returned by `func`.
+    #[inline]
+    pub fn flat<Q: Stage, F: Fn(P::Output) -> Pipeline<Q>>(
+        self,
+        func: F,
+    ) -> Pipeline<Flat<P, F, Q>> {
+        Pipeline {
+            stage: Flat {
+                prev: self.stage,
+                func,
+                pipeline_phantom: PhantomData,
+            },
+        }
+    }
+
+    /// Evaluates this pipeline using the strategy `S`.
+    ///
+    /// A pipeline can be run any number of times, with any strategies that
+    /// support all of its stages.
+    #[inline]
+    pub fn run<S: RandomStrategy>(&self, rng: &mut impl RngCore) -> S::Functor<P::Output>
+    where
+        P: Evaluate<S>,
+    {
+        self.stage.evaluate(rng)
+    }
+
+    /// Returns the total count of the outcomes of this pipeline when evaluated
+    /// with [`Counter`], without evaluating it.
+    ///
+    /// See [`Stage::max_total_count`] for more.
+    ///
+    /// [`Counter`]: crate::Counter
+    #[inline]
+    pub fn max_total_count(&self) -> Option<u128> {
+        self.stage.max_total_count()
+    }
+
+    /// Evaluates this pipeline using [`Counter`], with a count type chosen
+    /// from [`Stage::max_total_count`] before any stage is evaluated.
+    ///
+    /// Counts are stored as [`usize`]s when their total is known to fit in
+    /// one, and as [`u128`]s otherwise, so they never overflow, even on
+    /// targets with a narrow [`usize`]. An error is returned up front if the
+    /// total may not fit in a [`u128`], including whenever the pipeline has a
+    /// [`Pipeline::flat`] stage.
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::pipeline::Counts;
+    /// use rand_functors::Pipeline;
+    ///
+    /// // Only two outcomes are stored, but each is reached 2^79 times.
+    /// let flip = |s: bool, r: u16| s ^ (r % 2 == 1);
+    /// let pipeline = Pipeline::start(false)
+    ///     .rand(flip)
+    ///     .rand(flip)
+    ///     .rand(flip)
+    ///     .rand(flip)
+    ///     .rand(flip);
+    /// assert_eq!(pipeline.max_total_count(), Some(1 << 80));
+    ///
+    /// let counts = pipeline.run_counted(&mut thread_rng()).unwrap();
+    /// assert!(matches!(counts, Counts::U128(_)));
+    /// assert_eq!(counts.count(&false), 1 << 79);
+    /// ```
+    ///
+    /// [`Counter`]: crate::Counter
+    #[cfg(feature = "std")]
+    pub fn run_counted(
+        &self,
+        rng: &mut impl RngCore,
+    ) -> Result<Counts<P::Output>, CountOverflowError>
+    where
+        P: Evaluate<Counter<DefaultState, usize>> + Evaluate<Counter<DefaultState, u128>>,
+    {
+        match self.stage.max_total_count() {
+            Some(total) if total <= usize::MAX as u128 => {
+                Ok(Counts::Usize(self.run::<Counter<DefaultState, usize>>(rng)))
+            }
+            Some(_) => Ok(Counts::U128(self.run::<Counter<DefaultState, u128>>(rng))),
+            None => Err(CountOverflowError),
+        }
+    }
+
+    /// Evaluates this pipeline exactly like [`Enumerator`] until `budget` has
+    /// elapsed, and finishes it by sampling like [`Sampler`].
+    ///
+    /// The deadline is checked before each stage. Once it has passed, each
+    /// outcome enumerated so far is continued by a single sample of the
+    /// remaining stages, so every outcome remains equally likely and the
+    /// result stays unbiased, while the time spent on the remaining stages is
+    /// proportional to the number of outcomes already enumerated. The result
+    /// records how many stages were evaluated exactly.
+    ///
+    /// A [`Pipeline::flat`] stage is enumerated or sampled as a whole: if the
+    /// deadline has not passed before it, the pipelines continuing each value
+    /// are all enumerated in full, even if the deadline passes while they are
+    /// evaluated, so no value contributes more outcomes than another because
+    /// it was evaluated earlier.
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use rand::prelude::*;
+    /// use rand_functors::{Enumerator, Pipeline};
+    ///
+    /// let pipeline = Pipeline::start(0u16)
+    ///     .rand(|s, r: u8| s + r as u16)
+    ///     .rand(|s, r: bool| s + r as u16);
+    ///
+    /// let exact = pipeline.run_budgeted(&mut thread_rng(), Duration::MAX);
+    /// assert!(exact.is_exact());
+    /// assert_eq!(exact.exact_stages(), 2);
+    /// assert_eq!(*exact, pipeline.run::<Enumerator>(&mut thread_rng()));
+    ///
+    /// let sampled = pipeline.run_budgeted(&mut thread_rng(), Duration::ZERO);
+    /// assert_eq!(sampled.exact_stages(), 0);
+    /// assert_eq!(sampled.len(), 1);
+    /// ```
+    ///
+    /// [`Enumerator`]: crate::Enumerator
+    /// [`Sampler`]: crate::Sampler
+    #[cfg(feature = "std")]
+    pub fn run_budgeted(&self, rng: &mut impl RngCore, budget: Duration) -> Budgeted<P::Output>
+    where
+        P: Budget,
+    {
+        // A budget too large to represent as a deadline never runs out.
+        let deadline = Instant::now().checked_add(budget);
+        self.stage.evaluate_within(rng, deadline)
+    }
+
+    /// Searches the outcomes of this pipeline for the first one satisfying
+    /// `pred`, returning it along with its trace.
+    ///
+    /// Outcomes are expanded lazily, in depth-first order, and the search
+    /// stops as soon as one satisfies `pred`, so only the outcomes preceding
+    /// it are ever produced. The outcome found is the first satisfying `pred`
+    /// in the output of [`Enumerator`], and its trace is the one recorded by
+    /// [`TraceEnumerator`]: the index into the sample space of each random
+    /// variable sampled on the way to it. [`None`] is returned if no outcome
+    /// satisfies `pred`, after exploring every outcome.
+    ///
+    /// This answers whether a state is reachable without enumerating the
+    /// outcomes of a random process in full:
+    /// ```
+    /// use rand_functors::Pipeline;
+    ///
+    /// let pipeline = Pipeline::start(0u16)
+    ///     .rand(|s, r: u8| s + r as u16)
+    ///     .rand(|s, r: u8| s + r as u16);
+    ///
+    /// assert_eq!(pipeline.find_first(|&s| s == 300), Some((300, vec![45, 255])));
+    /// assert_eq!(pipeline.find_first(|&s| s > 510), None);
+    /// ```
+    ///
+    /// [`Enumerator`]: crate::Enumerator
+    /// [`TraceEnumerator`]: crate::TraceEnumerator
+    #[cfg(feature = "alloc")]
+    pub fn find_first(
+        &self,
+        mut pred: impl FnMut(&P::Output) -> bool,
+    ) -> Option<(P::Output, Vec<usize>)>
+    where
+        P: Search,
+    {
+        let mut found = None;
+        let mut path = Path {
+            trace: Vec::new(),
+            probability: 1.0,
+        };
+        let _ = self.stage.search(&mut path, &mut |outcome, path| {
+            if pred(&outcome) {
+                found = Some((outcome, path.trace.clone()));
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        found
+    }
+
+    /// Evaluates this pipeline exactly, outcome by outcome, until `stop`
+    /// returns `true`.
+    ///
+    /// Outcomes are produced in the same depth-first order as by
+    /// [`Pipeline::find_first`], and `stop` is called before each one is
+    /// recorded, so it can check a cancellation flag, a deadline, or a budget
+    /// of outcomes. The result holds the exact probability of every outcome
+    /// recorded before stopping. Its [`Covered::residual`] is the probability
+    /// of the outcomes that were not reached, which bounds the error of every
+    /// probability in it, and is zero if the pipeline was evaluated in full.
+    /// ```
+    /// use rand_functors::Pipeline;
+    ///
+    /// let pipeline = Pipeline::start(0u16)
+    ///     .rand(|s, r: u8| s + r as u16)
+    ///     .rand(|s, r: bool| s + r as u16);
+    ///
+    /// let mut visited = 0;
+    /// let partial = pipeline.run_until(|| {
+    ///     visited += 1;
+    ///     visited > 128
+    /// });
+    /// assert_eq!(partial.residual(), 0.75);
+    ///
+    /// let full = pipeline.run_until(|| false);
+    /// assert_eq!(full.residual(), 0.0);
+    /// assert_eq!(full.len(), 257);
+    /// ```
+    ///
+    /// [`Covered::residual`]: crate::Covered::residual
+    #[cfg(feature = "std")]
+    pub fn run_until(&self, mut stop: impl FnMut() -> bool) -> crate::Covered<P::Output>
+    where
+        P: Search,
+    {
+        let mut outcomes = std::collections::HashMap::default();
+        let mut covered = 0.0;
+        let mut path = Path {
+            trace: Vec::new(),
+            probability: 1.0,
+        };
+        let flow = self.stage.search(&mut path, &mut |outcome, path| {
+            if stop() {
+                return ControlFlow::Break(());
+            }
+            *outcomes.entry(outcome).or_insert(0.0) += path.probability;
+            covered += path.probability;
+            ControlFlow::Continue(())
+        });
+        let residual = match flow {
+            ControlFlow::Continue(()) => 0.0,
+            ControlFlow::Break(()) => (1.0 - covered).max(0.0),
+        };
+        crate::Covered::new(outcomes, residual)
+    }
+
+    /// Evaluates this pipeline like [`Enumerator`], with each stage running on
+    /// its own thread.
+    ///
+    /// Stages are connected by channels holding at most `capacity` chunks of
+    /// `chunk_size` outcomes each. A stage begins expanding the outcomes of
+    /// the previous one as soon as their first chunk is ready, so the
+    /// expansion of outcomes by early stages overlaps with their consumption
+    /// by later ones. This suits long pipelines whose stages have unbalanced
+    /// costs, while the bounded channels keep memory usage proportional to
+    /// the number of stages rather than the number of outcomes in flight.
+    /// Pipelines produced by a [`Pipeline::flat`] stage are evaluated on that
+    /// stage's thread.
+    ///
+    /// Outcomes are returned in the same order as by [`Enumerator`].
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand_functors::{Enumerator, Pipeline};
+    ///
+    /// let pipeline = Pipeline::start(0u16)
+    ///     .rand(|s, r: u8| s + r as u16)
+    ///     .rand(|s, r: bool| s + r as u16)
+    ///     .map(|s| s % 7);
+    ///
+    /// let outcomes = pipeline.run_pipelined(64, 4);
+    /// assert_eq!(outcomes, pipeline.run::<Enumerator>(&mut thread_rng()));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero, or if any stage panics.
+    ///
+    /// [`Enumerator`]: crate::Enumerator
+    #[cfg(feature = "std")]
+    pub fn run_pipelined(&self, chunk_size: usize, capacity: usize) -> Vec<P::Output>
+    where
+        P: Stream + Sync,
+        P::Output: Send,
+    {
+        self.run_pipelined_until(chunk_size, capacity, || false).0
+    }
+
+    /// Evaluates this pipeline like [`Pipeline::run_pipelined`], until `stop`
+    /// returns `true`.
+    ///
+    /// `stop` is called on the calling thread before each chunk of outcomes
+    /// produced by the final stage is collected, so it can check a
+    /// cancellation flag or a deadline. Once it returns `true`, every stage
+    /// stops after its current chunk. Returns the outcomes collected so far,
+    /// which are a prefix of those returned by [`Pipeline::run_pipelined`],
+    /// along with whether the pipeline was evaluated in full.
+    /// ```
+    /// use rand_functors::Pipeline;
+    ///
+    /// let pipeline = Pipeline::start(0u16)
+    ///     .rand(|s, r: u8| s + r as u16)
+    ///     .rand(|s, r: bool| s + r as u16);
+    ///
+    /// let mut chunks = 0;
+    /// let (outcomes, complete) = pipeline.run_pipelined_until(64, 4, || {
+    ///     chunks += 1;
+    ///     chunks > 2
+    /// });
+    /// assert!(!complete);
+    /// assert_eq!(outcomes, pipeline.run_pipelined(64, 4)[..128]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero, or if any stage panics.
+    #[cfg(feature = "std")]
+    pub fn run_pipelined_until(
+        &self,
+        chunk_size: usize,
+        capacity: usize,
+        mut stop: impl FnMut() -> bool,
+    ) -> (Vec<P::Output>, bool)
+    where
+        P: Stream + Sync,
+        P::Output: Send,
+    {
+        assert!(chunk_size != 0, "chunk_size must not be zero");
+        std::thread::scope(|scope| {
+            let mut outcomes = Vec::new();
+            // Returning early drops the receiver, so the final stage fails to
+            // send its next chunk and stops, as does each stage before it.
+            for chunk in self.stage.stream(scope, chunk_size, capacity) {
+                if stop() {
+                    return (outcomes, false);
+                }
+                outcomes.extend(chunk);
+            }
+            (outcomes, true)
+        })
+    }
+}
+
+impl<I: Inner> Stage for Start<I> {
+    type Output = I;
+
+    #[inline]
+    fn max_total_count(&self) -> Option<u128> {
+        Some(1)
+    }
+}
+
+impl<S: RandomStrategy, I: Inner> Evaluate<S> for Start<I> {
+    #[inline]
+    fn evaluate(&self, _: &mut impl RngCore) -> S::Functor<I> {
+        Functor::pure(self.state.clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Inner> Search for Start<I> {
+    #[inline]
+    fn search(
+        &self,
+        path: &mut Path,
+        visit: &mut impl FnMut(I, &mut Path) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        visit(self.state.clone(), path)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Inner> Budget for Start<I> {
+    #[inline]
+    fn evaluate_within(&self, _: &mut impl RngCore, _: Option<Instant>) -> Budgeted<I> {
+        Budgeted {
+            outcomes: vec![self.state.clone()],
+            exact_stages: 0,
+            exact: true,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Inner> Stream for Start<I> {
+    #[inline]
+    fn stream<'scope, 'env>(
+        &'env self,
+        _: &'scope Scope<'scope, 'env>,
+        _: usize,
+        _: usize,
+    ) -> Receiver<Vec<I>> {
+        let (sender, receiver) = sync_channel(1);
+        let _ = sender.send(vec![self.state.clone()]);
+        receiver
+    }
+}
+
+impl<P: Stage, F: Fn(P::Output) -> B, B: Inner> Stage for Map<P, F, B> {
+    type Output = B;
+
+    #[inline]
+    fn max_total_count(&self) -> Option<u128> {
+        self.prev.max_total_count()
+    }
+}
+
+impl<S: RandomStrategy, P: Evaluate<S>, F: Fn(P::Output) -> B, B: Inner> Evaluate<S>
+    for Map<P, F, B>
+{
+    #[inline]
+    fn evaluate(&self, rng: &mut impl RngCore) -> S::Functor<B> {
+        S::fmap(self.prev.evaluate(rng), &self.func)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Search, F: Fn(P::Output) -> B, B: Inner> Search for Map<P, F, B> {
+    #[inline]
+    fn search(
+        &self,
+        path: &mut Path,
+        visit: &mut impl FnMut(B, &mut Path) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.prev
+            .search(path, &mut |a, path| visit((self.func)(a), path))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P: Budget, F: Fn(P::Output) -> B, B: Inner> Budget for Map<P, F, B> {
+    #[inline]
+    fn evaluate_within(&self, rng: &mut impl RngCore, deadline: Option<Instant>) -> Budgeted<B> {
+        // Mapping is equally cheap either way, so it is always exact, but it
+        // only extends the exact prefix if the stages before it are exact.
+        let f = self.prev.evaluate_within(rng, deadline);
+        Budgeted {
+            outcomes: f.outcomes.into_iter().map(&self.func).collect(),
+            exact_stages: f.exact_stages + f.exact as usize,
+            exact: f.exact,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P: Stream, F: Fn(P::Output) -> B + Sync, B: Inner + Send> Stream for Map<P, F, B>
+where
+    P::Output: Send,
+{
+    #[inline]
+    fn stream<'scope, 'env>(
+        &'env self,
+        scope: &'scope Scope<'scope, 'env>,
+        chunk_size: usize,
+        capacity: usize,
+    ) -> Receiver<Vec<B>> {
+        let input = self.prev.stream(scope, chunk_size, capacity);
+        let (sender, output) = sync_channel(capacity);
+        let func = &self.func;
+        scope.spawn(move || {
+            for chunk in input {
+                if sender.send(chunk.into_iter().map(func).collect()).is_err() {
+                    return;
+                }
+            }
+        });
+        output
+    }
+}
+
+impl<P: Stage, F: Fn(P::Output, R) -> B, R: SmallRandomVariable, B: Inner> Stage
+    for Rand<P, F, R, B>
+where
+    Standard: Distribution<R>,
+{
+    type Output = B;
+
+    #[inline]
+    fn max_total_count(&self) -> Option<u128> {
+        let size = R::sample_space().count() as u128;
+        self.prev.max_total_count()?.checked_mul(size)
+    }
+}
+
+impl<
+        S: RandomStrategy,
+        P: Evaluate<S>,
+        F: Fn(P::Output, R) -> B,
+        R: SmallRandomVariable,
+        B: Inner,
+    > Evaluate<S> for Rand<P, F, R, B>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn evaluate(&self, rng: &mut impl RngCore) -> S::Functor<B> {
+        let f = self.prev.evaluate(rng);
+        S::fmap_rand(f, rng, &self.func)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Search, F: Fn(P::Output, R) -> B, R: SmallRandomVariable, B: Inner> Search
+    for Rand<P, F, R, B>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn search(
+        &self,
+        path: &mut Path,
+        visit: &mut impl FnMut(B, &mut Path) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let size = R::sample_space().count();
+        self.prev.search(path, &mut |a, path| {
+            for (index, r) in R::sample_space().enumerate() {
+                path.choose(index, size, |path| visit((self.func)(a.clone(), r), path))?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P: Budget, F: Fn(P::Output, R) -> B, R: SmallRandomVariable, B: Inner> Budget
+    for Rand<P, F, R, B>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn evaluate_within(&self, rng: &mut impl RngCore, deadline: Option<Instant>) -> Budgeted<B> {
+        let f = self.prev.evaluate_within(rng, deadline);
+        f.continue_with(
+            deadline,
+            rng,
+            |f, rng| Enumerator::fmap_rand(f, rng, &self.func),
+            |a, rng| Sampler::fmap_rand(a, rng, &self.func),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P: Stream, F: Fn(P::Output, R) -> B + Sync, R: SmallRandomVariable, B: Inner + Send> Stream
+    for Rand<P, F, R, B>
+where
+    Standard: Distribution<R>,
+    P::Output: Send,
+{
+    #[inline]
+    fn stream<'scope, 'env>(
+        &'env self,
+        scope: &'scope Scope<'scope, 'env>,
+        chunk_size: usize,
+        capacity: usize,
+    ) -> Receiver<Vec<B>> {
+        let input = self.prev.stream(scope, chunk_size, capacity);
+        let (sender, output) = sync_channel(capacity);
+        let func = &self.func;
+        scope.spawn(move || {
+            let mut chunks = Chunks::new(sender, chunk_size);
+            for a in input.into_iter().flatten() {
+                for r in R::sample_space() {
+                    chunks.push(func(a.clone(), r))?;
+                }
+            }
+            chunks.finish();
+            ControlFlow::Continue(())
+        });
+        output
+    }
+}
+
+impl<
+        P: Stage,
+        G: RandomVariableRange<R> + Clone,
+        F: Fn(P::Output, R) -> B,
+        R: RandomVariable + SampleUniform,
+        B: Inner,
+    > Stage for RandRange<P, G, F, R, B>
+where
+    Standard: Distribution<R>,
+{
+    type Output = B;
+
+    #[inline]
+    fn max_total_count(&self) -> Option<u128> {
+        // The sample space of a range may be too large to count by iterating
+        // over it, so only an exact size hint is trusted.
+        let size = match self.range.sample_space().size_hint() {
+            (lower, Some(upper)) if lower == upper => lower as u128,
+            _ => return None,
+        };
+        self.prev.max_total_count()?.checked_mul(size)
+    }
+}
+
+impl<
+        S: RandomStrategy,
+        P: Evaluate<S>,
+        G: RandomVariableRange<R> + Clone,
+        F: Fn(P::Output, R) -> B,
+        R: RandomVariable + SampleUniform,
+        B: Inner,
+    > Evaluate<S> for RandRange<P, G, F, R, B>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn evaluate(&self, rng: &mut impl RngCore) -> S::Functor<B> {
+        let f = self.prev.evaluate(rng);
+        S::fmap_rand_range(f, self.range.clone(), rng, &self.func)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<
+        P: Search,
+        G: RandomVariableRange<R> + Clone,
+        F: Fn(P::Output, R) -> B,
+        R: RandomVariable + SampleUniform,
+        B: Inner,
+    > Search for RandRange<P, G, F, R, B>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn search(
+        &self,
+        path: &mut Path,
+        visit: &mut impl FnMut(B, &mut Path) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let size = self.range.sample_space().count();
+        self.prev.search(path, &mut |a, path| {
+            for (index, r) in self.range.sample_space().enumerate() {
+                path.choose(index, size, |path| visit((self.func)(a.clone(), r), path))?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<
+        P: Budget,
+        G: RandomVariableRange<R> + Clone,
+        F: Fn(P::Output, R) -> B,
+        R: RandomVariable + SampleUniform,
+        B: Inner,
+    > Budget for RandRange<P, G, F, R, B>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn evaluate_within(&self, rng: &mut impl RngCore, deadline: Option<Instant>) -> Budgeted<B> {
+        let f = self.prev.evaluate_within(rng, deadline);
+        f.continue_with(
+            deadline,
+            rng,
+            |f, rng| Enumerator::fmap_rand_range(f, self.range.clone(), rng, &self.func),
+            |a, rng| Sampler::fmap_rand_range(a, self.range.clone(), rng, &self.func),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<
+        P: Stream,
+        G: RandomVariableRange<R> + Clone + Sync,
+        F: Fn(P::Output, R) -> B + Sync,
+        R: RandomVariable + SampleUniform,
+        B: Inner + Send,
+    > Stream for RandRange<P, G, F, R, B>
+where
+    Standard: Distribution<R>,
+    P::Output: Send,
+{
+    #[inline]
+    fn stream<'scope, 'env>(
+        &'env self,
+        scope: &'scope Scope<'scope, 'env>,
+        chunk_size: usize,
+        capacity: usize,
+    ) -> Receiver<Vec<B>> {
+        let input = self.prev.stream(scope, chunk_size, capacity);
+        let (sender, output) = sync_channel(capacity);
+        let (range, func) = (&self.range, &self.func);
+        scope.spawn(move || {
+            let mut chunks = Chunks::new(sender, chunk_size);
+            for a in input.into_iter().flatten() {
+                for r in range.sample_space() {
+                    chunks.push(func(a.clone(), r))?;
+                }
+            }
+            chunks.finish();
+            ControlFlow::Continue(())
+        });
+        output
+    }
+}
+
+impl<P: Stage, F: Fn(P::Output) -> Pipeline<Q>, Q: Stage> Stage for Flat<P, F, Q> {
+    type Output = Q::Output;
+
+    #[inline]
+    fn max_total_count(&self) -> Option<u128> {
+        // The pipelines continuing each value are only known once the values
+        // preceding them have been produced.
+        None
+    }
+}
+
+impl<
+        S: FlattenableRandomStrategy,
+        P: Evaluate<S>,
+        F: Fn(P::Output) -> Pipeline<Q>,
+        Q: Evaluate<S>,
+    > Evaluate<S> for Flat<P, F, Q>
+{
+    #[inline]
+    fn evaluate(&self, rng: &mut impl RngCore) -> S::Functor<Q::Output> {
+        let f = self.prev.evaluate(rng);
+        S::fmap_flat(f, |a| (self.func)(a).stage.evaluate(rng))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Search, F: Fn(P::Output) -> Pipeline<Q>, Q: Search> Search for Flat<P, F, Q> {
+    #[inline]
+    fn search(
+        &self,
+        path: &mut Path,
+        visit: &mut impl FnMut(Q::Output, &mut Path) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.prev.search(path, &mut |a, path| {
+            (self.func)(a).stage.search(path, visit)
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P: Budget, F: Fn(P::Output) -> Pipeline<Q>, Q: Budget> Budget for Flat<P, F, Q> {
+    #[inline]
+    fn evaluate_within(
+        &self,
+        rng: &mut impl RngCore,
+        deadline: Option<Instant>,
+    ) -> Budgeted<Q::Output> {
+        // Whether the pipelines continuing the values are enumerated or
+        // sampled is decided once for all of them. Were the deadline checked
+        // for each pipeline, those evaluated before it passed would contribute
+        // all of their outcomes and those evaluated after it a single sample,
+        // over-weighting the earlier values.
+        let f = self.prev.evaluate_within(rng, deadline);
+        let exact = f.exact && !expired(deadline);
+        // Each pipeline is enumerated in full, or sampled, producing a single
+        // outcome, by evaluating it against a deadline that has passed.
+        let deadline = if exact { None } else { Some(Instant::now()) };
+        let mut outcomes = Vec::with_capacity(f.outcomes.len());
+        for a in f.outcomes {
+            outcomes.extend((self.func)(a).stage.evaluate_within(rng, deadline).outcomes);
+        }
+        Budgeted {
+            outcomes,
+            exact_stages: f.exact_stages + exact as usize,
+            exact,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P: Stream, F: Fn(P::Output) -> Pipeline<Q> + Sync, Q: Search> Stream for Flat<P, F, Q>
+where
+    P::Output: Send,
+    Q::Output: Send,
+{
+    #[inline]
+    fn stream<'scope, 'env>(
+        &'env self,
+        scope: &'scope Scope<'scope, 'env>,
+        chunk_size: usize,
+        capacity: usize,
+    ) -> Receiver<Vec<Q::Output>> {
+        let input = self.prev.stream(scope, chunk_size, capacity);
+        let (sender, output) = sync_channel(capacity);
+        let func = &self.func;
+        scope.spawn(move || {
+            let mut chunks = Chunks::new(sender, chunk_size);
+            let mut path = Path {
+                trace: Vec::new(),
+                probability: 1.0,
+            };
+            for a in input.into_iter().flatten() {
+                func(a)
+                    .stage
+                    .search(&mut path, &mut |b, _| chunks.push(b))?;
+            }
+            chunks.finish();
+            ControlFlow::Continue(())
+        });
+        output
+    }
+}
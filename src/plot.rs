@@ -0,0 +1,158 @@
+use std::boxed::Box;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use std::string::String;
+use std::vec::Vec;
+
+use num_traits::ToPrimitive;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::{Inner, WeightedFunctor};
+
+/// The kind of chart drawn by [`render_histogram`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum HistogramKind {
+    /// A bar chart of the probability of each distinct outcome.
+    #[default]
+    Bar,
+    /// A step plot of the cumulative distribution function of the outcomes.
+    Cdf,
+}
+
+/// Options for [`render_histogram`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct HistogramOptions {
+    /// The kind of chart to draw.
+    pub kind: HistogramKind,
+    /// The width of the image, in pixels.
+    pub width: u32,
+    /// The height of the image, in pixels.
+    pub height: u32,
+    /// The caption drawn above the chart, if any.
+    pub title: Option<String>,
+}
+
+impl Default for HistogramOptions {
+    fn default() -> Self {
+        Self {
+            kind: HistogramKind::Bar,
+            width: 800,
+            height: 600,
+            title: None,
+        }
+    }
+}
+
+/// Renders the distribution of the outcomes stored in `functor` as a chart and
+/// saves it to `path`.
+///
+/// The kind of chart and its dimensions are specified by `options`. If `path`
+/// has an `svg` extension, the chart is saved as an SVG image. Otherwise, it is
+/// saved as a bitmap in the format implied by its extension.
+pub fn render_histogram<I: Inner + ToPrimitive>(
+    functor: &impl WeightedFunctor<I>,
+    path: impl AsRef<Path>,
+    options: &HistogramOptions,
+) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    let mut weights = HashMap::new();
+    for (outcome, weight) in functor.weighted_outcomes() {
+        let x = outcome
+            .to_f64()
+            .ok_or("outcome is not representable as an f64")?;
+        *weights.entry(x.to_bits()).or_insert(0.0) += weight.to_f64().unwrap_or(f64::NAN);
+    }
+    let total_weight = weights.values().sum::<f64>();
+    let mut points = weights
+        .into_iter()
+        .map(|(x, w)| (f64::from_bits(x), w / total_weight))
+        .collect::<Vec<_>>();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let size = (options.width, options.height);
+    if path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("svg"))
+    {
+        draw(
+            SVGBackend::new(path, size).into_drawing_area(),
+            &points,
+            options,
+        )?;
+    } else {
+        draw(
+            BitMapBackend::new(path, size).into_drawing_area(),
+            &points,
+            options,
+        )?;
+    }
+    Ok(())
+}
+
+fn draw<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    points: &[(f64, f64)],
+    options: &HistogramOptions,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    root.fill(&WHITE)?;
+
+    // Bars are as wide as the smallest gap between adjacent outcomes.
+    let bar_width = points
+        .windows(2)
+        .map(|w| w[1].0 - w[0].0)
+        .fold(f64::INFINITY, f64::min);
+    let bar_width = if bar_width.is_finite() {
+        bar_width
+    } else {
+        1.0
+    };
+    let x_min = points.first().map_or(0.0, |p| p.0) - bar_width;
+    let x_max = points.last().map_or(1.0, |p| p.0) + bar_width;
+    let y_max = match options.kind {
+        HistogramKind::Bar => points.iter().map(|p| p.1).fold(0.0, f64::max) * 1.05,
+        HistogramKind::Cdf => 1.05,
+    };
+
+    let mut builder = ChartBuilder::on(&root);
+    builder
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(50);
+    if let Some(title) = &options.title {
+        builder.caption(title, ("sans-serif", 24));
+    }
+    let mut chart = builder.build_cartesian_2d(x_min..x_max, 0.0..y_max.max(f64::EPSILON))?;
+    chart
+        .configure_mesh()
+        .y_desc(match options.kind {
+            HistogramKind::Bar => "probability",
+            HistogramKind::Cdf => "cumulative probability",
+        })
+        .draw()?;
+
+    match options.kind {
+        HistogramKind::Bar => {
+            chart.draw_series(points.iter().map(|(x, p)| {
+                Rectangle::new(
+                    [(x - 0.4 * bar_width, 0.0), (x + 0.4 * bar_width, *p)],
+                    BLUE.filled(),
+                )
+            }))?;
+        }
+        HistogramKind::Cdf => {
+            let mut cumulative = 0.0;
+            let mut steps = vec![(x_min, 0.0)];
+            for (x, p) in points {
+                steps.push((*x, cumulative));
+                cumulative += p;
+                steps.push((*x, cumulative));
+            }
+            steps.push((x_max, cumulative));
+            chart.draw_series(LineSeries::new(steps, &BLUE))?;
+        }
+    }
+
+    root.present()
+}
@@ -0,0 +1,181 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::boxed::Box;
+use std::cell::RefCell;
+
+use crate::current_step;
+
+/// The number of outcomes an operation produces between reports of
+/// [`Progress::Expanding`].
+pub const EXPANSION_INTERVAL: usize = 1 << 16;
+
+type Callback = Box<dyn FnMut(Progress)>;
+
+thread_local! {
+    static CALLBACK: RefCell<Option<Callback>> = const { RefCell::new(None) };
+}
+
+/// The number of calls to [`with_progress`] running on any thread, which lets
+/// strategies skip progress reporting entirely while it is zero.
+static INSTALLED: AtomicUsize = AtomicUsize::new(0);
+
+/// An update on the progress of a random process, passed to the callback
+/// installed by [`with_progress`].
+///
+/// Every update carries the label of the innermost [`step`] that was running
+/// when it was reported, if there was one.
+///
+/// [`step`]: crate::step
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Progress {
+    /// An operation of `strategy` has produced `produced` outcomes so far.
+    ///
+    /// This is reported every [`EXPANSION_INTERVAL`] outcomes by strategies
+    /// which store every outcome they produce, such as [`Enumerator`] and
+    /// [`UniqueEnumerator`].
+    ///
+    /// [`Enumerator`]: crate::Enumerator
+    /// [`UniqueEnumerator`]: crate::UniqueEnumerator
+    Expanding {
+        /// The name of the strategy.
+        strategy: &'static str,
+        /// The label of the active step.
+        step: Option<&'static str>,
+        /// The number of outcomes produced so far.
+        produced: usize,
+    },
+    /// An operation of `strategy` has finished, having produced `produced`
+    /// outcomes, of which `stored` remained distinct after merging.
+    ///
+    /// This is reported by the same strategies, under the same names, as the
+    /// metrics of the `telemetry` module.
+    Operation {
+        /// The name of the strategy.
+        strategy: &'static str,
+        /// The label of the active step.
+        step: Option<&'static str>,
+        /// The number of outcomes produced by the operation.
+        produced: usize,
+        /// The number of outcomes stored in the resulting functor.
+        stored: usize,
+    },
+    /// A multi-step driver such as [`explore`], [`Memoized::run`], or
+    /// [`monte_carlo`] has completed `completed` of its `total` iterations.
+    ///
+    /// `total` is [`None`] if the number of iterations is not known in
+    /// advance.
+    ///
+    /// [`explore`]: crate::explore
+    /// [`Memoized::run`]: crate::Memoized::run
+    /// [`monte_carlo`]: crate::monte_carlo
+    Iteration {
+        /// The name of the driver.
+        driver: &'static str,
+        /// The label of the active step.
+        step: Option<&'static str>,
+        /// The number of iterations completed so far.
+        completed: usize,
+        /// The total number of iterations, if it is known.
+        total: Option<usize>,
+    },
+}
+
+/// Restores the previously installed callback when dropped, including on
+/// unwinding.
+struct ProgressGuard {
+    previous: Option<Callback>,
+}
+
+impl Drop for ProgressGuard {
+    #[inline]
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CALLBACK.with(|callback| *callback.borrow_mut() = previous);
+        INSTALLED.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Runs `op`, passing updates on the progress of the random processes it
+/// evaluates to `callback`.
+///
+/// The callback only receives updates reported on the current thread while
+/// `op` runs. Calls may be nested, in which case only the innermost callback
+/// receives updates. Operations performed by the callback itself are not
+/// reported. While no callback is installed on any thread, strategies skip
+/// progress reporting entirely, so it costs nothing when it is not used.
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// use rand::prelude::*;
+/// use rand_functors::{with_progress, Enumerator, Functor, Progress, RandomStrategy};
+///
+/// let expanded = Rc::new(Cell::new(0));
+/// let counter = expanded.clone();
+/// let f: Vec<u16> = with_progress(
+///     move |progress| {
+///         if let Progress::Operation { produced, .. } = progress {
+///             counter.set(counter.get() + produced);
+///         }
+///     },
+///     || {
+///         let f = Enumerator::fmap_rand(Functor::pure(0), &mut thread_rng(), |_, r: u8| r as u16);
+///         Enumerator::fmap_rand(f, &mut thread_rng(), |s, r: bool| s + r as u16)
+///     },
+/// );
+/// assert_eq!(f.len(), 512);
+/// assert_eq!(expanded.get(), 256 + 512);
+/// ```
+pub fn with_progress<T>(callback: impl FnMut(Progress) + 'static, op: impl FnOnce() -> T) -> T {
+    INSTALLED.fetch_add(1, Ordering::Relaxed);
+    let _guard = ProgressGuard {
+        previous: CALLBACK.with(|current| current.replace(Some(Box::new(callback)))),
+    };
+    op()
+}
+
+/// Returns `true` if a callback may be installed on the current thread.
+///
+/// This is a single atomic load, so strategies check it once per operation
+/// before doing any work to report their progress. A callback installed on
+/// another thread makes it return `true` as well, which only costs time.
+#[inline]
+pub(crate) fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed) != 0
+}
+
+/// Passes the update produced by `progress` to the installed callback, if
+/// there is one.
+#[inline]
+pub(crate) fn report(progress: impl FnOnce(Option<&'static str>) -> Progress) {
+    if !is_installed() {
+        return;
+    }
+    // The callback is removed while it runs, so that any operations it
+    // performs are not reported to it recursively.
+    let Some(mut callback) = CALLBACK
+        .try_with(|callback| callback.borrow_mut().take())
+        .ok()
+        .flatten()
+    else {
+        return;
+    };
+    callback(progress(current_step()));
+    CALLBACK.with(|current| {
+        let mut current = current.borrow_mut();
+        if current.is_none() {
+            *current = Some(callback);
+        }
+    });
+}
+
+/// Reports that `driver` has completed `completed` of `total` iterations.
+#[inline]
+pub(crate) fn report_iteration(driver: &'static str, completed: usize, total: Option<usize>) {
+    report(|step| Progress::Iteration {
+        driver,
+        step,
+        completed,
+        total,
+    });
+}
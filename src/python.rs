@@ -0,0 +1,230 @@
+//! Python bindings for the results of random processes.
+//!
+//! This module does not define a Python extension module by itself. Instead,
+//! crates which do so with PyO3 can call [`register`] from their module
+//! initializer and return the results of their random processes to Python
+//! using [`to_python`], or the distributions produced by [`evolve`] after
+//! every step using [`evolution_to_python`]. The trajectories produced by
+//! [`run_trajectory`] are outcomes like any other, so they are returned with
+//! [`to_python`], while those produced by [`run_ensemble`] convert to nested
+//! Python lists directly.
+//!
+//! [`evolve`]: crate::evolve
+//! [`run_ensemble`]: crate::run_ensemble
+//! [`run_trajectory`]: crate::run_trajectory
+
+use std::collections::HashMap;
+use std::format;
+use std::string::String;
+use std::vec::Vec;
+
+use num_traits::ToPrimitive;
+use pyo3::prelude::*;
+use pyo3::IntoPyObjectExt;
+
+use crate::report::{report, DistributionReport};
+use crate::{Inner, WeightedFunctor};
+
+/// The distribution of the outcomes of a random process, exposed to Python as
+/// `rand_functors.Distribution`.
+///
+/// Repeated outcomes are merged and outcomes are sorted in descending order of
+/// probability. The `outcomes`, `weights`, and `probabilities` attributes are
+/// parallel lists, which can be passed directly to `numpy.asarray`.
+#[pyclass(name = "Distribution", module = "rand_functors", frozen)]
+pub struct PyDistribution {
+    outcomes: Vec<Py<PyAny>>,
+    weights: Vec<f64>,
+    total_weight: f64,
+}
+
+impl PyDistribution {
+    fn new<'a, 'py, I: Inner + IntoPyObject<'py> + 'a, N: ToPrimitive>(
+        py: Python<'py>,
+        outcomes: impl Iterator<Item = (&'a I, N)>,
+    ) -> PyResult<Self> {
+        Self::from_report(py, report(outcomes, None))
+    }
+
+    fn from_report<'py, I: Inner + IntoPyObject<'py>>(
+        py: Python<'py>,
+        report: DistributionReport<'_, I>,
+    ) -> PyResult<Self> {
+        let total_weight = report.total_weight;
+        let (outcomes, weights) = report
+            .outcomes
+            .into_iter()
+            .map(|o| {
+                Ok((
+                    o.outcome.clone().into_py_any(py)?,
+                    o.probability * total_weight,
+                ))
+            })
+            .collect::<PyResult<(Vec<_>, Vec<_>)>>()?;
+        Ok(Self {
+            outcomes,
+            weights,
+            total_weight,
+        })
+    }
+}
+
+#[pymethods]
+impl PyDistribution {
+    /// The distinct outcomes of the distribution.
+    #[getter]
+    fn outcomes(&self, py: Python<'_>) -> Vec<Py<PyAny>> {
+        self.outcomes.iter().map(|o| o.clone_ref(py)).collect()
+    }
+
+    /// The weight of each outcome.
+    #[getter]
+    fn weights(&self) -> Vec<f64> {
+        self.weights.clone()
+    }
+
+    /// The probability of each outcome.
+    #[getter]
+    fn probabilities(&self) -> Vec<f64> {
+        self.weights.iter().map(|w| w / self.total_weight).collect()
+    }
+
+    /// The sum of the weights of all outcomes.
+    #[getter]
+    fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+
+    fn __len__(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Distribution(outcomes={}, total_weight={})",
+            self.outcomes.len(),
+            self.total_weight
+        )
+    }
+}
+
+/// The distributions of the states of a random process after each of a
+/// number of steps, exposed to Python as `rand_functors.Evolution`.
+///
+/// The `outcomes` attribute lists every state that occurs at any step, in
+/// order of first occurrence. The `probabilities` attribute is a list with a
+/// row for each step and a column for each of these states, which can be
+/// passed directly to `numpy.asarray` to obtain a two-dimensional array.
+#[pyclass(name = "Evolution", module = "rand_functors", frozen)]
+pub struct PyEvolution {
+    outcomes: Vec<Py<PyAny>>,
+    probabilities: Vec<Vec<f64>>,
+    distributions: Vec<Py<PyDistribution>>,
+}
+
+impl PyEvolution {
+    fn new<'a, 'py, I: Inner + IntoPyObject<'py> + 'a, F: WeightedFunctor<I> + 'a>(
+        py: Python<'py>,
+        functors: impl Iterator<Item = &'a F>,
+    ) -> PyResult<Self> {
+        let mut columns = HashMap::new();
+        let mut outcomes = Vec::new();
+        let mut rows = Vec::new();
+        let mut distributions = Vec::new();
+        for functor in functors {
+            let report = report(functor.weighted_outcomes(), None);
+            let row = report
+                .outcomes
+                .iter()
+                .map(|o| {
+                    let column = *columns.entry(o.outcome).or_insert_with(|| {
+                        outcomes.push(o.outcome);
+                        outcomes.len() - 1
+                    });
+                    (column, o.probability)
+                })
+                .collect::<Vec<_>>();
+            rows.push(row);
+            distributions.push(Py::new(py, PyDistribution::from_report(py, report)?)?);
+        }
+        let probabilities = rows
+            .into_iter()
+            .map(|row| {
+                let mut probabilities = Vec::new();
+                probabilities.resize(outcomes.len(), 0.0);
+                for (column, probability) in row {
+                    probabilities[column] = probability;
+                }
+                probabilities
+            })
+            .collect();
+        let outcomes = outcomes
+            .into_iter()
+            .map(|o| o.clone().into_py_any(py))
+            .collect::<PyResult<_>>()?;
+        Ok(Self {
+            outcomes,
+            probabilities,
+            distributions,
+        })
+    }
+}
+
+#[pymethods]
+impl PyEvolution {
+    /// The distinct outcomes of every step, in order of first occurrence.
+    #[getter]
+    fn outcomes(&self, py: Python<'_>) -> Vec<Py<PyAny>> {
+        self.outcomes.iter().map(|o| o.clone_ref(py)).collect()
+    }
+
+    /// The probability of each outcome after each step.
+    #[getter]
+    fn probabilities(&self) -> Vec<Vec<f64>> {
+        self.probabilities.clone()
+    }
+
+    /// The distribution of outcomes after each step.
+    #[getter]
+    fn distributions(&self, py: Python<'_>) -> Vec<Py<PyDistribution>> {
+        self.distributions.iter().map(|d| d.clone_ref(py)).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.distributions.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Evolution(steps={}, outcomes={})",
+            self.distributions.len().saturating_sub(1),
+            self.outcomes.len()
+        )
+    }
+}
+
+/// Adds the classes defined by this crate to a Python module.
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyDistribution>()?;
+    module.add_class::<PyEvolution>()
+}
+
+/// Converts the outcomes stored in `functor` and their weights into a Python
+/// `rand_functors.Distribution` object.
+pub fn to_python<'py, I: Inner + IntoPyObject<'py>>(
+    py: Python<'py>,
+    functor: &impl WeightedFunctor<I>,
+) -> PyResult<Bound<'py, PyDistribution>> {
+    Bound::new(py, PyDistribution::new(py, functor.weighted_outcomes())?)
+}
+
+/// Converts the distributions produced by [`evolve`], one for each step, into
+/// a Python `rand_functors.Evolution` object.
+///
+/// [`evolve`]: crate::evolve
+pub fn evolution_to_python<'py, I: Inner + IntoPyObject<'py>, F: WeightedFunctor<I>>(
+    py: Python<'py>,
+    functors: &[F],
+) -> PyResult<Bound<'py, PyEvolution>> {
+    Bound::new(py, PyEvolution::new(py, functors.iter())?)
+}
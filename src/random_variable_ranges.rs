@@ -1,6 +1,34 @@
-use core::ops::{Range, RangeInclusive};
+use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
-use crate::RandomVariableRange;
+use rand::distributions::uniform::SampleRange;
+use rand_core::RngCore;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::{Distribution, Standard};
+
+use crate::{RandomVariable, RandomVariableRange};
+
+/// A [`RandomVariableRange`] whose sample space is enumerated in reverse order.
+///
+/// This is produced by [`RandomVariableRange::rev_space`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RevSpace<Ra>(pub(crate) Ra);
+
+impl<R: RandomVariable + SampleUniform, Ra: RandomVariableRange<R>> RandomVariableRange<R>
+    for RevSpace<Ra>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample_space(&self) -> impl DoubleEndedIterator<Item = R> {
+        self.0.sample_space().rev()
+    }
+
+    #[inline]
+    fn sample_single<G: RngCore + ?Sized>(self, rng: &mut G) -> R {
+        self.0.sample_single(rng)
+    }
+}
 
 // A generic implementation of RandomVariableRange<T> for Range<T> is impossible
 // until std::iter::Step is stabilized.
@@ -8,9 +36,14 @@ macro_rules! impl_random_variable_range_for_range {
     ($t:ty) => {
         impl RandomVariableRange<$t> for Range<$t> {
             #[inline]
-            fn sample_space(&self) -> impl Iterator<Item = $t> {
+            fn sample_space(&self) -> impl DoubleEndedIterator<Item = $t> {
                 self.clone()
             }
+
+            #[inline]
+            fn sample_single<G: RngCore + ?Sized>(self, rng: &mut G) -> $t {
+                SampleRange::sample_single(self, rng)
+            }
         }
     };
 }
@@ -21,9 +54,68 @@ macro_rules! impl_random_variable_range_for_range_inclusive {
     ($t:ty) => {
         impl RandomVariableRange<$t> for RangeInclusive<$t> {
             #[inline]
-            fn sample_space(&self) -> impl Iterator<Item = $t> {
+            fn sample_space(&self) -> impl DoubleEndedIterator<Item = $t> {
                 self.clone()
             }
+
+            #[inline]
+            fn sample_single<G: RngCore + ?Sized>(self, rng: &mut G) -> $t {
+                SampleRange::sample_single(self, rng)
+            }
+        }
+    };
+}
+
+// Ranges that are unbounded on either side are clamped to the bounds of the
+// type and delegate to the equivalent bounded range.
+macro_rules! impl_random_variable_range_for_unbounded_ranges {
+    ($t:ty) => {
+        impl RandomVariableRange<$t> for RangeFrom<$t> {
+            #[inline]
+            fn sample_space(&self) -> impl DoubleEndedIterator<Item = $t> {
+                self.start..=<$t>::MAX
+            }
+
+            #[inline]
+            fn sample_single<G: RngCore + ?Sized>(self, rng: &mut G) -> $t {
+                SampleRange::sample_single(self.start..=<$t>::MAX, rng)
+            }
+        }
+
+        impl RandomVariableRange<$t> for RangeTo<$t> {
+            #[inline]
+            fn sample_space(&self) -> impl DoubleEndedIterator<Item = $t> {
+                <$t>::MIN..self.end
+            }
+
+            #[inline]
+            fn sample_single<G: RngCore + ?Sized>(self, rng: &mut G) -> $t {
+                SampleRange::sample_single(<$t>::MIN..self.end, rng)
+            }
+        }
+
+        impl RandomVariableRange<$t> for RangeToInclusive<$t> {
+            #[inline]
+            fn sample_space(&self) -> impl DoubleEndedIterator<Item = $t> {
+                <$t>::MIN..=self.end
+            }
+
+            #[inline]
+            fn sample_single<G: RngCore + ?Sized>(self, rng: &mut G) -> $t {
+                SampleRange::sample_single(<$t>::MIN..=self.end, rng)
+            }
+        }
+
+        impl RandomVariableRange<$t> for RangeFull {
+            #[inline]
+            fn sample_space(&self) -> impl DoubleEndedIterator<Item = $t> {
+                <$t>::MIN..=<$t>::MAX
+            }
+
+            #[inline]
+            fn sample_single<G: RngCore + ?Sized>(self, rng: &mut G) -> $t {
+                SampleRange::sample_single(<$t>::MIN..=<$t>::MAX, rng)
+            }
         }
     };
 }
@@ -55,3 +147,17 @@ impl_random_variable_range_for_range_inclusive!(i32);
 impl_random_variable_range_for_range_inclusive!(i64);
 impl_random_variable_range_for_range_inclusive!(i128);
 impl_random_variable_range_for_range_inclusive!(isize);
+
+impl_random_variable_range_for_unbounded_ranges!(u8);
+impl_random_variable_range_for_unbounded_ranges!(u16);
+impl_random_variable_range_for_unbounded_ranges!(u32);
+impl_random_variable_range_for_unbounded_ranges!(u64);
+impl_random_variable_range_for_unbounded_ranges!(u128);
+impl_random_variable_range_for_unbounded_ranges!(usize);
+
+impl_random_variable_range_for_unbounded_ranges!(i8);
+impl_random_variable_range_for_unbounded_ranges!(i16);
+impl_random_variable_range_for_unbounded_ranges!(i32);
+impl_random_variable_range_for_unbounded_ranges!(i64);
+impl_random_variable_range_for_unbounded_ranges!(i128);
+impl_random_variable_range_for_unbounded_ranges!(isize);
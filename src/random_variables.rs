@@ -1,4 +1,9 @@
-use crate::RandomVariable;
+use core::num::Wrapping;
+
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{Cardinality, RandomVariable, SmallRandomVariable};
 
 impl RandomVariable for bool {
     #[inline]
@@ -7,7 +12,18 @@ impl RandomVariable for bool {
     }
 }
 
+impl SmallRandomVariable for bool {}
+
+impl Cardinality for bool {
+    const CARDINALITY: u128 = 2;
+}
+
 macro_rules! impl_random_variable_for_int {
+    ($t:ty, small) => {
+        impl_random_variable_for_int!($t);
+
+        impl SmallRandomVariable for $t {}
+    };
     ($t:ty) => {
         impl RandomVariable for $t {
             #[inline]
@@ -15,19 +31,45 @@ macro_rules! impl_random_variable_for_int {
                 Self::MIN..=Self::MAX
             }
         }
+
+        impl Cardinality for $t {
+            const CARDINALITY: u128 = match 1u128.checked_shl(<$t>::BITS) {
+                Some(cardinality) => cardinality,
+                None => u128::MAX,
+            };
+        }
     };
 }
 
-impl_random_variable_for_int!(u8);
-impl_random_variable_for_int!(u16);
+impl_random_variable_for_int!(u8, small);
+impl_random_variable_for_int!(u16, small);
 impl_random_variable_for_int!(u32);
 impl_random_variable_for_int!(u64);
 impl_random_variable_for_int!(u128);
 impl_random_variable_for_int!(usize);
 
-impl_random_variable_for_int!(i8);
-impl_random_variable_for_int!(i16);
+impl_random_variable_for_int!(i8, small);
+impl_random_variable_for_int!(i16, small);
 impl_random_variable_for_int!(i32);
 impl_random_variable_for_int!(i64);
 impl_random_variable_for_int!(i128);
 impl_random_variable_for_int!(isize);
+
+impl<T: RandomVariable> RandomVariable for Wrapping<T>
+where
+    Standard: Distribution<T>,
+{
+    #[inline]
+    fn sample_space() -> impl Iterator<Item = Self> {
+        T::sample_space().map(Wrapping)
+    }
+}
+
+impl<T: SmallRandomVariable> SmallRandomVariable for Wrapping<T> where Standard: Distribution<T> {}
+
+impl<T: Cardinality> Cardinality for Wrapping<T>
+where
+    Standard: Distribution<T>,
+{
+    const CARDINALITY: u128 = T::CARDINALITY;
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display, Formatter, Write};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use num_traits::ToPrimitive;
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+use crate::{Inner, WeightedFunctor};
+
+/// A summary of the distribution of outcomes stored in a
+/// [`WeightedFunctor`].
+///
+/// Reports are produced by [`ReportExt::report`]. With the `json` feature
+/// enabled, they can be serialized with a stable schema, which is used by
+/// [`DistributionReport::to_json`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct DistributionReport<'a, I> {
+    /// The number of distinct outcomes in the distribution.
+    pub distinct_outcomes: usize,
+    /// The sum of the weights of all outcomes.
+    pub total_weight: f64,
+    /// The Shannon entropy of the distribution, in bits.
+    pub entropy: f64,
+    /// Whether `outcomes` omits some of the distinct outcomes.
+    pub truncated: bool,
+    /// The distinct outcomes of the distribution, in descending order of
+    /// probability.
+    pub outcomes: Vec<OutcomeProbability<'a, I>>,
+}
+
+#[cfg(feature = "json")]
+impl<I: Serialize> DistributionReport<'_, I> {
+    /// Produces a compact JSON representation of this report.
+    ///
+    /// The schema of the output is stable: an object with the fields
+    /// `distinct_outcomes`, `total_weight`, `entropy`, `truncated`, and
+    /// `outcomes`, the last of which is an array of objects with the fields
+    /// `outcome` and `probability`. This method is only available with the
+    /// `json` feature.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("outcomes must be serializable as JSON")
+    }
+}
+
+/// An outcome of a distribution and its probability.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct OutcomeProbability<'a, I> {
+    /// The outcome.
+    pub outcome: &'a I,
+    /// The probability of the outcome.
+    pub probability: f64,
+}
+
+/// Formats a distribution as a list of outcomes in descending order of
+/// probability, one per line, each preceded by its probability as a
+/// percentage.
+///
+/// Produced by [`ReportExt::display`]. Percentages are printed with two
+/// decimal places unless a precision is given in the format string, as in
+/// `{:.4}`. When only the most probable outcomes are listed, a final line
+/// accounts for the remainder.
+///
+/// ```
+/// use rand_functors::ReportExt;
+///
+/// let outcomes = vec![3u8, 1, 3, 3, 2, 3, 1, 3];
+/// assert_eq!(
+///     outcomes.display(Some(2)).to_string(),
+///     " 62.50%  3\n 25.00%  1\n ... 1 more outcome (12.50%)"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplayDistribution<'a, I> {
+    report: DistributionReport<'a, I>,
+}
+
+impl<'a, I> From<DistributionReport<'a, I>> for DisplayDistribution<'a, I> {
+    #[inline]
+    fn from(report: DistributionReport<'a, I>) -> Self {
+        Self { report }
+    }
+}
+
+impl<I: Debug> Display for DisplayDistribution<'_, I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        let width = if precision == 0 { 3 } else { 4 + precision };
+        let mut listed = 0.0;
+        for (i, o) in self.report.outcomes.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "{:>width$.precision$}%  {:?}",
+                o.probability * 100.0,
+                o.outcome
+            )?;
+            listed += o.probability;
+        }
+        if self.report.truncated {
+            let remaining = self.report.distinct_outcomes - self.report.outcomes.len();
+            if !self.report.outcomes.is_empty() {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                " ... {} more outcome{} ({:.precision$}%)",
+                remaining,
+                if remaining == 1 { "" } else { "s" },
+                (1.0 - listed).max(0.0) * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Summaries and human-readable renderings of the outcomes stored in a
+/// [`WeightedFunctor`].
+///
+/// `ReportExt` is implemented for every [`WeightedFunctor`].
+pub trait ReportExt<I: Inner>: WeightedFunctor<I> {
+    /// Produces a [`DistributionReport`] summarizing the outcomes stored in
+    /// this functor.
+    ///
+    /// Repeated outcomes are merged. If `top_k` is `Some(k)`, only the `k` most
+    /// probable outcomes are listed in the report, though the summary
+    /// statistics still account for every outcome.
+    fn report(&self, top_k: Option<usize>) -> DistributionReport<'_, I> {
+        report(self.weighted_outcomes(), top_k)
+    }
+
+    /// Produces a [`DisplayDistribution`] which formats the outcomes stored
+    /// in this functor in descending order of probability.
+    ///
+    /// Repeated outcomes are merged. If `top_k` is `Some(k)`, only the `k` most
+    /// probable outcomes are printed.
+    fn display(&self, top_k: Option<usize>) -> DisplayDistribution<'_, I> {
+        self.report(top_k).into()
+    }
+
+    /// Renders the distribution of numeric outcomes stored in this functor as
+    /// a histogram made of ASCII characters.
+    ///
+    /// Each distinct outcome is printed on its own line, in ascending order,
+    /// followed by a bar of `#` characters and its probability as a
+    /// percentage. The bar of the most probable outcome is `width` characters
+    /// long and all other bars are scaled accordingly.
+    ///
+    /// ```
+    /// use rand_functors::ReportExt;
+    ///
+    /// let outcomes = vec![3u8, 1, 3, 3, 10];
+    /// assert_eq!(
+    ///     outcomes.ascii_histogram(6),
+    ///     concat!(
+    ///         " 1 | ##      20.00%\n",
+    ///         " 3 | ######  60.00%\n",
+    ///         "10 | ##      20.00%\n",
+    ///     )
+    /// );
+    /// ```
+    fn ascii_histogram(&self, width: usize) -> String
+    where
+        I: Display + ToPrimitive,
+    {
+        ascii_histogram(self.weighted_outcomes(), width)
+    }
+}
+
+impl<I: Inner, F: WeightedFunctor<I>> ReportExt<I> for F {}
+
+pub(crate) fn report<'a, I: Eq + core::hash::Hash + 'a, N: ToPrimitive>(
+    outcomes: impl Iterator<Item = (&'a I, N)>,
+    top_k: Option<usize>,
+) -> DistributionReport<'a, I> {
+    let mut order = Vec::new();
+    let mut weights = HashMap::new();
+    for (outcome, weight) in outcomes {
+        let weight = weight.to_f64().unwrap_or(f64::NAN);
+        weights
+            .entry(outcome)
+            .and_modify(|w| *w += weight)
+            .or_insert_with(|| {
+                order.push(outcome);
+                weight
+            });
+    }
+
+    let total_weight = weights.values().sum::<f64>();
+    let mut outcomes = order
+        .into_iter()
+        .map(|outcome| OutcomeProbability {
+            outcome,
+            probability: weights[outcome] / total_weight,
+        })
+        .collect::<Vec<_>>();
+    // A stable sort keeps ties in the order in which they were first produced.
+    outcomes.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+
+    let entropy = -outcomes
+        .iter()
+        .map(|o| o.probability)
+        .filter(|p| *p > 0.0)
+        .map(|p| p * p.log2())
+        .sum::<f64>();
+    let distinct_outcomes = outcomes.len();
+    let truncated = top_k.is_some_and(|k| k < distinct_outcomes);
+    if let Some(k) = top_k {
+        outcomes.truncate(k);
+    }
+
+    DistributionReport {
+        distinct_outcomes,
+        total_weight,
+        entropy,
+        truncated,
+        outcomes,
+    }
+}
+
+pub(crate) fn ascii_histogram<'a, I: Display + Eq + core::hash::Hash + ToPrimitive + 'a>(
+    outcomes: impl Iterator<Item = (&'a I, impl ToPrimitive)>,
+    width: usize,
+) -> String {
+    let mut outcomes = report(outcomes, None).outcomes;
+    outcomes.sort_by(|a, b| {
+        let a = a.outcome.to_f64().unwrap_or(f64::NAN);
+        let b = b.outcome.to_f64().unwrap_or(f64::NAN);
+        a.total_cmp(&b)
+    });
+
+    let labels = outcomes
+        .iter()
+        .map(|o| o.outcome.to_string())
+        .collect::<Vec<_>>();
+    let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+    let max_probability = outcomes.iter().map(|o| o.probability).fold(0.0, f64::max);
+
+    let mut histogram = String::new();
+    for (label, o) in labels.iter().zip(&outcomes) {
+        let bar = if max_probability > 0.0 {
+            (o.probability / max_probability * width as f64).round() as usize
+        } else {
+            0
+        };
+        let _ = writeln!(
+            histogram,
+            "{label:>label_width$} | {:<width$} {:>6.2}%",
+            "#".repeat(bar),
+            o.probability * 100.0
+        );
+    }
+    histogram
+}
@@ -0,0 +1,146 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{RandomVariable, SmallRandomVariable};
+
+/// A predicate on values of type `R`, used to restrict the sample space of a
+/// [`Restricted`] random variable.
+///
+/// This is implemented by a (typically zero-sized) marker type, so that the
+/// predicate is part of the type of the random variable.
+pub trait Restriction<R> {
+    /// Returns `true` if `value` is in the restricted sample space.
+    fn allows(value: &R) -> bool;
+}
+
+/// A [`RandomVariable`] whose sample space contains only the values of `R`
+/// allowed by the [`Restriction`] `P`.
+///
+/// Sampling a `Restricted<R, P>` repeatedly samples from `R` until a value is
+/// allowed by `P`, so each allowed value is equally likely. **If `P` allows no
+/// values, sampling never terminates.**
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Enumerator, Functor, RandomStrategy, Restricted, Restriction};
+///
+/// struct NonZero;
+///
+/// impl Restriction<u8> for NonZero {
+///     fn allows(value: &u8) -> bool {
+///         *value != 0
+///     }
+/// }
+///
+/// let f = Enumerator::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, x: Restricted<u8, NonZero>| {
+///     x.get()
+/// });
+/// assert_eq!(f, (1..=255).collect::<Vec<_>>());
+/// assert_ne!(thread_rng().gen::<Restricted<u8, NonZero>>().get(), 0);
+/// ```
+pub struct Restricted<R, P> {
+    value: R,
+    restriction: PhantomData<fn() -> P>,
+}
+
+impl<R, P: Restriction<R>> Restricted<R, P> {
+    /// Restricts `value`, or returns [`None`] if it is not allowed by `P`.
+    #[inline]
+    pub fn new(value: R) -> Option<Self> {
+        P::allows(&value).then_some(Self {
+            value,
+            restriction: PhantomData,
+        })
+    }
+}
+
+impl<R: Copy, P> Restricted<R, P> {
+    /// Returns the restricted value.
+    #[inline]
+    pub fn get(self) -> R {
+        self.value
+    }
+}
+
+impl<R, P> Restricted<R, P> {
+    /// Returns the restricted value.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.value
+    }
+}
+
+impl<R, P> AsRef<R> for Restricted<R, P> {
+    #[inline]
+    fn as_ref(&self) -> &R {
+        &self.value
+    }
+}
+
+// These are implemented manually, rather than derived, so that they do not
+// require the restriction to implement them.
+impl<R: Clone, P> Clone for Restricted<R, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            restriction: PhantomData,
+        }
+    }
+}
+
+impl<R: Copy, P> Copy for Restricted<R, P> {}
+
+impl<R: fmt::Debug, P> fmt::Debug for Restricted<R, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Restricted").field(&self.value).finish()
+    }
+}
+
+impl<R: PartialEq, P> PartialEq for Restricted<R, P> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<R: Eq, P> Eq for Restricted<R, P> {}
+
+impl<R: Hash, P> Hash for Restricted<R, P> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<R, P: Restriction<R>> Distribution<Restricted<R, P>> for Standard
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> Restricted<R, P> {
+        loop {
+            if let Some(restricted) = Restricted::new(self.sample(rng)) {
+                return restricted;
+            }
+        }
+    }
+}
+
+impl<R: RandomVariable, P: Restriction<R>> RandomVariable for Restricted<R, P>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample_space() -> impl Iterator<Item = Self> {
+        R::sample_space().filter_map(Restricted::new)
+    }
+}
+
+impl<R: SmallRandomVariable, P: Restriction<R>> SmallRandomVariable for Restricted<R, P> where
+    Standard: Distribution<R>
+{
+}
@@ -0,0 +1,68 @@
+use alloc::vec::Vec;
+
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{Cardinality, RandomVariable, SmallRandomVariable};
+
+/// A [`RandomVariable`] whose sample space is enumerated in reverse order.
+///
+/// `Reversed` has the same sample space as the random variable it wraps, but
+/// enumerating strategies store the outcomes derived from it in the opposite
+/// order. It is the counterpart of [`RandomVariableRange::rev_space`] for
+/// [`RandomStrategy::fmap_rand`]. As [`RandomVariable::sample_space`] is not
+/// double-ended, the sample space of the wrapped type is collected before it
+/// is reversed.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Enumerator, Functor, RandomStrategy, Reversed};
+///
+/// let f = Enumerator::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, Reversed(r): Reversed<bool>| r);
+/// assert_eq!(f, [true, false]);
+/// ```
+///
+/// [`RandomStrategy::fmap_rand`]: crate::RandomStrategy::fmap_rand
+/// [`RandomVariableRange::rev_space`]: crate::RandomVariableRange::rev_space
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Reversed<R>(pub R);
+
+impl<R> Reversed<R> {
+    /// Returns the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+impl<R> Distribution<Reversed<R>> for Standard
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> Reversed<R> {
+        Reversed(self.sample(rng))
+    }
+}
+
+impl<R: RandomVariable> RandomVariable for Reversed<R>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample_space() -> impl Iterator<Item = Self> {
+        R::sample_space()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .map(Reversed)
+    }
+}
+
+impl<R: Cardinality> Cardinality for Reversed<R>
+where
+    Standard: Distribution<R>,
+{
+    const CARDINALITY: u128 = R::CARDINALITY;
+}
+
+impl<R: SmallRandomVariable> SmallRandomVariable for Reversed<R> where Standard: Distribution<R> {}
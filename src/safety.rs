@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use crate::{DefaultState, Inner, WitnessedCounts};
+
+/// A bad state found by [`check_safety`], along with the random values which
+/// reach it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Counterexample<T> {
+    /// The bad state.
+    pub state: T,
+    /// The number of steps taken to reach the bad state.
+    pub steps: usize,
+    /// The index of each random value drawn on the way to the bad state,
+    /// across every step, as recorded by [`WitnessCounter`].
+    ///
+    /// [`WitnessCounter`]: crate::WitnessCounter
+    pub trace: Vec<usize>,
+}
+
+/// The result of checking that no bad state is reachable with
+/// [`check_safety`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SafetyCheck<T> {
+    probability: f64,
+    counterexample: Option<Counterexample<T>>,
+}
+
+impl<T> SafetyCheck<T> {
+    /// Returns `true` if no bad state was reachable within the bound.
+    #[inline]
+    pub fn is_safe(&self) -> bool {
+        self.counterexample.is_none()
+    }
+
+    /// Returns the probability that the process reaches a bad state within
+    /// the bound.
+    #[inline]
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// Returns the counterexample with the fewest steps, if a bad state was
+    /// reachable.
+    ///
+    /// Of the counterexamples with the fewest steps, the one with the
+    /// lexicographically smallest trace is returned, so the result does not
+    /// depend on the iteration order of hash tables.
+    #[inline]
+    pub fn counterexample(&self) -> Option<&Counterexample<T>> {
+        self.counterexample.as_ref()
+    }
+
+    /// Returns the counterexample with the fewest steps, if a bad state was
+    /// reachable.
+    #[inline]
+    pub fn into_counterexample(self) -> Option<Counterexample<T>> {
+        self.counterexample
+    }
+}
+
+/// Checks whether a random process can reach a state satisfying `bad` within
+/// `steps` steps of `step`, evaluated with [`WitnessCounter`], starting from
+/// `initial`.
+///
+/// Every reachable state is explored exhaustively, one step at a time, and
+/// states reached in more than one way are merged. Bad states are not
+/// expanded further, so the result includes the probability of reaching a bad
+/// state within the bound, computed in floating point, and a counterexample
+/// trace of random values leading to one. The probabilities of the outcomes of each step are the
+/// counts produced by `step` divided by their total, so `step` may use a
+/// different number of random values in different states. Each completed step
+/// is reported to the callback installed by [`with_progress`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{check_safety, Functor, RandomStrategy, WitnessCounter};
+///
+/// // Two processes each try to take a lock by flipping a coin, and collide if
+/// // both succeed at once.
+/// let check = check_safety((false, false), 3, |&(a, b)| a && b, |_| {
+///     let f = WitnessCounter::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, a: bool| a);
+///     WitnessCounter::fmap_rand(f, &mut thread_rng(), |a, b: bool| (a, b))
+/// });
+/// assert!(!check.is_safe());
+/// assert!((check.probability() - (1.0 - 0.75f64.powi(3))).abs() < 1e-12);
+/// let counterexample = check.counterexample().unwrap();
+/// assert_eq!(counterexample.steps, 1);
+/// assert_eq!(counterexample.trace, [1, 1]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `step` produces no outcomes for a state, as the probabilities of
+/// its outcomes would then be undefined.
+///
+/// [`WitnessCounter`]: crate::WitnessCounter
+/// [`with_progress`]: crate::with_progress
+pub fn check_safety<T: Inner>(
+    initial: T,
+    steps: usize,
+    bad: impl Fn(&T) -> bool,
+    mut step: impl FnMut(T) -> WitnessedCounts<T>,
+) -> SafetyCheck<T> {
+    if bad(&initial) {
+        return SafetyCheck {
+            probability: 1.0,
+            counterexample: Some(Counterexample {
+                state: initial,
+                steps: 0,
+                trace: Vec::new(),
+            }),
+        };
+    }
+    let mut frontier = HashMap::<T, (f64, Vec<usize>), DefaultState>::default();
+    frontier.insert(initial, (1.0, Vec::new()));
+    let mut probability = 0.0;
+    let mut counterexample = None;
+    for i in 0..steps {
+        let mut next = HashMap::<T, (f64, Vec<usize>), DefaultState>::default();
+        let mut found = None::<(T, Vec<usize>)>;
+        for (state, (p, trace)) in frontier {
+            let outcomes = step(state);
+            let total = outcomes.values().map(|(count, _)| *count).sum::<usize>();
+            assert!(total != 0, "step must produce at least one outcome");
+            let total = total as f64;
+            for (outcome, (count, child_trace)) in outcomes.into_inner() {
+                let p = p * count as f64 / total;
+                let mut trace = trace.clone();
+                trace.extend(child_trace);
+                if bad(&outcome) {
+                    probability += p;
+                    if found.as_ref().map_or(true, |(_, t)| trace < *t) {
+                        found = Some((outcome, trace));
+                    }
+                    continue;
+                }
+                let entry = next.entry(outcome).or_insert((0.0, trace.clone()));
+                entry.0 += p;
+                if trace < entry.1 {
+                    entry.1 = trace;
+                }
+            }
+        }
+        if counterexample.is_none() {
+            counterexample = found.map(|(state, trace)| Counterexample {
+                state,
+                steps: i + 1,
+                trace,
+            });
+        }
+        frontier = next;
+        crate::progress::report_iteration("check_safety", i + 1, Some(steps));
+        if frontier.is_empty() {
+            break;
+        }
+    }
+    SafetyCheck {
+        probability,
+        counterexample,
+    }
+}
@@ -0,0 +1,97 @@
+use alloc::sync::Arc;
+
+use crate::{Inner, Patch, RandomStrategy};
+
+/// Copy-on-write updates for large states shared between outcomes through an
+/// [`Arc`].
+///
+/// Every outcome of a random process stored by an enumerating strategy is a
+/// separate value, so a large state is usually copied once for each outcome
+/// derived from it, even by operations which leave most or all of it
+/// unchanged. Storing states as `Arc<T>` instead makes these copies cheap,
+/// as only the reference count is incremented. The methods of `SharedState`
+/// use [`Arc::make_mut`] to copy the state only when an outcome actually
+/// changes it, and only if it is still shared with another outcome.
+///
+/// `Arc<T>` is [`Inner`] whenever `T` is, and compares states which share an
+/// allocation without comparing their contents, so [`Counter`] cheaply merges
+/// outcomes which were left unchanged. Use [`share`] and [`unshare`] to move a
+/// random process into and out of shared states.
+/// ```
+/// use std::sync::Arc;
+///
+/// use rand::prelude::*;
+/// use rand_functors::{share, unshare, Counter, Functor, RandomStrategy, SharedState};
+///
+/// let f = share::<Counter, _>(Functor::pure([0u8; 1024]));
+/// let f = <Counter>::fmap_rand(f, &mut thread_rng(), |s, r: u8| {
+///     if r < 128 { s } else { s.update(|s| s[r as usize] = 1) }
+/// });
+/// let f = unshare::<Counter, _>(f);
+/// assert_eq!(f.len(), 129);
+/// assert_eq!(f[&[0; 1024]], 128);
+/// ```
+///
+/// [`Counter`]: crate::Counter
+pub trait SharedState<T>: Sized {
+    /// Applies `func` to the state, first copying it if it is shared with
+    /// another outcome.
+    fn update(self, func: impl FnOnce(&mut T)) -> Self;
+
+    /// Applies `patch` to the state, first copying it if it is shared with
+    /// another outcome.
+    #[inline]
+    fn apply_patch(self, patch: impl Patch<T>) -> Self {
+        self.update(|state| patch.apply(state))
+    }
+
+    /// Returns the state, copying it only if it is shared with another
+    /// outcome.
+    fn into_owned(self) -> T;
+}
+
+impl<T: Clone> SharedState<T> for Arc<T> {
+    #[inline]
+    fn update(mut self, func: impl FnOnce(&mut T)) -> Self {
+        func(Arc::make_mut(&mut self));
+        self
+    }
+
+    #[inline]
+    fn into_owned(self) -> T {
+        Arc::try_unwrap(self).unwrap_or_else(|shared| T::clone(&shared))
+    }
+}
+
+/// A [`Patch`] to a state of type `T` which can be applied to an `Arc<T>`,
+/// copying the state first only if it is shared with another outcome.
+///
+/// This allows closures passed to [`RandomStrategy::fmap_rand_delta`] to
+/// return the patches of `T` when states are stored as `Arc<T>`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Shared<P>(pub P);
+
+impl<T: Clone, P: Patch<T>> Patch<Arc<T>> for Shared<P> {
+    #[inline]
+    fn apply(self, state: &mut Arc<T>) {
+        self.0.apply(Arc::make_mut(state));
+    }
+}
+
+/// Wraps each state of `f` in an [`Arc`], so that it can be shared between
+/// the outcomes derived from it.
+///
+/// See [`SharedState`] for more.
+#[inline]
+pub fn share<S: RandomStrategy, T: Inner>(f: S::Functor<T>) -> S::Functor<Arc<T>> {
+    S::fmap(f, Arc::new)
+}
+
+/// Unwraps each state of `f` from its [`Arc`], copying it only if it is
+/// shared with another outcome.
+///
+/// See [`SharedState`] for more.
+#[inline]
+pub fn unshare<S: RandomStrategy, T: Inner>(f: S::Functor<Arc<T>>) -> S::Functor<T> {
+    S::fmap(f, SharedState::into_owned)
+}
@@ -0,0 +1,152 @@
+use std::vec::Vec;
+
+use rand::{Rng, SeedableRng};
+use rand_core::RngCore;
+
+use crate::Inner;
+
+/// An estimate of the probability of a rare event made by
+/// [`multilevel_splitting`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Splitting<T> {
+    stage_probabilities: Vec<f64>,
+    population: usize,
+    states: Vec<T>,
+}
+
+impl<T> Splitting<T> {
+    /// Returns the estimated probability of reaching the final level, which
+    /// is the product of the probabilities of every stage.
+    pub fn probability(&self) -> f64 {
+        self.stage_probabilities.iter().product()
+    }
+
+    /// Returns the fraction of trajectories in each stage which reached the
+    /// next level.
+    ///
+    /// If no trajectory reached a level, the estimate is zero and later
+    /// stages are not run.
+    #[inline]
+    pub fn stage_probabilities(&self) -> &[f64] {
+        &self.stage_probabilities
+    }
+
+    /// Returns an approximation of the relative standard error of
+    /// [`Splitting::probability`], or [`None`] if no trajectory reached the
+    /// final level.
+    ///
+    /// The approximation treats the stages as independent, so it is
+    /// optimistic when the states at which trajectories reach a level differ
+    /// greatly in how likely they are to reach the next.
+    pub fn relative_error(&self) -> Option<f64> {
+        if self.states.is_empty() {
+            return None;
+        }
+        let n = self.population as f64;
+        let variance = self
+            .stage_probabilities
+            .iter()
+            .map(|&p| (1.0 - p) / (n * p))
+            .sum::<f64>();
+        Some(variance.sqrt())
+    }
+
+    /// Returns the states at which trajectories in the last stage reached the
+    /// final level.
+    #[inline]
+    pub fn states(&self) -> &[T] {
+        &self.states
+    }
+}
+
+/// Estimates the probability that `step`, a random process evaluated with
+/// [`Sampler`], reaches a rare set of states starting from `initial`, by
+/// multilevel splitting.
+///
+/// The rare event is `importance` reaching the last of `levels`, which must be
+/// increasing, before `stop` holds. Each stage runs `population` trajectories
+/// until they either reach the next level, or stop. The trajectories of the
+/// first stage start from `initial`, and those of each later stage start from
+/// states chosen uniformly at random, with replacement, from the states at
+/// which trajectories of the previous stage reached its level. Trajectories
+/// which approach the rare event are thereby cloned, while the others are
+/// abandoned, so each stage only has to estimate a probability which is not
+/// rare. The estimate is the product of the probabilities of every stage,
+/// which makes it possible to estimate probabilities far too small for plain
+/// [`Sampler`] runs to ever observe. `stop` must eventually hold for every
+/// trajectory which does not reach the next level, for example by including
+/// the number of steps taken in the state.
+///
+/// Every random value is drawn from a single random number generator of type
+/// `R`, seeded from `seed` with [`SeedableRng::seed_from_u64`], so an estimate
+/// can be reproduced exactly from its seed. Each completed stage is reported
+/// to the callback installed by [`with_progress`].
+/// ```
+/// use rand_chacha::ChaCha8Rng;
+/// use rand_functors::{multilevel_splitting, RandomStrategy, Sampler};
+///
+/// // A walk which steps up with probability 1/4, reaching 20 before 0.
+/// let levels = (2..=20).map(f64::from).collect::<Vec<_>>();
+/// let splitting = multilevel_splitting(1u8, &levels, 1000, 0, |&s| s.into(), |&s| s == 0, |s, rng: &mut ChaCha8Rng| {
+///     Sampler::fmap_rand(s, rng, |s, r: u8| if r % 4 == 0 { s + 1 } else { s - 1 })
+/// });
+/// let exact = 2.0 / (3.0f64.powi(20) - 1.0);
+/// assert!((0.5..2.0).contains(&(splitting.probability() / exact)));
+/// assert!(splitting.relative_error().unwrap() < 0.5);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `population` is zero or `levels` is not increasing.
+///
+/// [`Sampler`]: crate::Sampler
+/// [`with_progress`]: crate::with_progress
+pub fn multilevel_splitting<R: SeedableRng + RngCore, T: Inner>(
+    initial: T,
+    levels: &[f64],
+    population: usize,
+    seed: u64,
+    importance: impl Fn(&T) -> f64,
+    stop: impl Fn(&T) -> bool,
+    mut step: impl FnMut(T, &mut R) -> T,
+) -> Splitting<T> {
+    assert!(population != 0, "population must not be zero");
+    assert!(
+        levels.windows(2).all(|w| w[0] < w[1]),
+        "levels must be increasing"
+    );
+    let mut rng = R::seed_from_u64(seed);
+    let mut stage_probabilities = Vec::with_capacity(levels.len());
+    let mut starts = vec![initial];
+    for (i, &level) in levels.iter().enumerate() {
+        let mut reached = Vec::new();
+        for _ in 0..population {
+            let mut state = starts[rng.gen_range(0..starts.len())].clone();
+            loop {
+                if importance(&state) >= level {
+                    reached.push(state);
+                    break;
+                }
+                if stop(&state) {
+                    break;
+                }
+                state = step(state, &mut rng);
+            }
+        }
+        stage_probabilities.push(reached.len() as f64 / population as f64);
+        crate::progress::report_iteration("multilevel_splitting", i + 1, Some(levels.len()));
+        if reached.is_empty() {
+            return Splitting {
+                stage_probabilities,
+                population,
+                states: reached,
+            };
+        }
+        starts = reached;
+    }
+    Splitting {
+        stage_probabilities,
+        population,
+        states: starts,
+    }
+}
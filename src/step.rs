@@ -0,0 +1,55 @@
+use std::cell::Cell;
+
+thread_local! {
+    static CURRENT_STEP: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Restores the previously active step when dropped, including on unwinding.
+struct StepGuard {
+    previous: Option<&'static str>,
+}
+
+impl Drop for StepGuard {
+    #[inline]
+    fn drop(&mut self) {
+        CURRENT_STEP.with(|current| current.set(self.previous));
+    }
+}
+
+/// Runs `op` as a step of a random process named `label`.
+///
+/// While `op` runs, [`current_step`] returns `label` on the current thread.
+/// [`Traced`] records it in a `step` field on the span of every operation, and
+/// with the `tracing` feature enabled, `op` also runs inside a `DEBUG`-level
+/// span named `step`. Steps may be nested, in which case the innermost label
+/// is active.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{current_step, step, Enumerator, Functor, RandomStrategy};
+///
+/// let f: Vec<u8> = step("roll", || {
+///     assert_eq!(current_step(), Some("roll"));
+///     Enumerator::fmap_rand_range(Functor::pure(0), 1..=6, &mut thread_rng(), |_, r| r)
+/// });
+/// assert_eq!(f.len(), 6);
+/// assert_eq!(current_step(), None);
+/// ```
+///
+/// [`Traced`]: crate::Traced
+#[inline]
+pub fn step<T>(label: &'static str, op: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "tracing")]
+    let _entered = tracing::debug_span!("step", name = label).entered();
+    let _guard = StepGuard {
+        previous: CURRENT_STEP.with(|current| current.replace(Some(label))),
+    };
+    op()
+}
+
+/// Returns the label of the innermost [`step`] running on the current thread,
+/// if there is one.
+#[inline]
+pub fn current_step() -> Option<&'static str> {
+    CURRENT_STEP.with(Cell::get)
+}
@@ -1,19 +1,81 @@
 #[cfg(feature = "std")]
+pub use adaptive_counter::{AdaptiveCounter, AdaptiveCounts, SwitchPoint};
+#[cfg(feature = "std")]
+pub use annotated_counter::{Annotated, AnnotatedCounter};
+#[cfg(feature = "dashu-float")]
+pub use big_float_counter::BigFloatCounter;
+#[cfg(feature = "std")]
 pub use counter::Counter;
+#[cfg(feature = "std")]
+pub use coverage_enumerator::{CoverageEnumerator, Covered};
+pub use dual::{Dual, DualFunctor};
+#[cfg(feature = "std")]
+pub use dyn_population_sampler::{DynPopulation, DynPopulationSampler};
+#[cfg(feature = "std")]
+pub use dyn_strategy::{DynFunctor, DynStrategy};
 #[cfg(feature = "alloc")]
 pub use enumerator::Enumerator;
+#[cfg(feature = "heapless")]
+pub use heapless_population_sampler::HeaplessPopulationSampler;
 #[cfg(feature = "alloc")]
 pub use population_sampler::PopulationSampler;
+#[cfg(all(feature = "std", feature = "num-rational"))]
+pub use rational_counter::RationalCounter;
 pub use sampler::Sampler;
 #[cfg(feature = "std")]
+pub use snapshots::{Snapshot, Snapshots, Snapshotted};
+#[cfg(feature = "std")]
+pub use stratified_counter::StratifiedCounter;
+#[cfg(feature = "num-rational")]
+pub use tagged_enumerator::{Tagged, TaggedEnumerator};
+#[cfg(feature = "alloc")]
+pub use trace_enumerator::{TraceEnumerator, Witnessed};
+#[cfg(feature = "tracing")]
+pub use traced::Traced;
+#[cfg(feature = "std")]
 pub use unique_enumerator::UniqueEnumerator;
+#[cfg(feature = "alloc")]
+pub use weighted_population_sampler::{Particles, WeightedPopulationSampler};
+#[cfg(feature = "std")]
+pub use witness_counter::{WitnessCounter, WitnessedCounts};
 
+#[cfg(feature = "std")]
+mod adaptive_counter;
+#[cfg(feature = "std")]
+mod annotated_counter;
+#[cfg(feature = "dashu-float")]
+mod big_float_counter;
 #[cfg(feature = "std")]
 mod counter;
+#[cfg(feature = "std")]
+mod coverage_enumerator;
+mod dual;
+#[cfg(feature = "std")]
+mod dyn_population_sampler;
+#[cfg(feature = "std")]
+mod dyn_strategy;
 #[cfg(feature = "alloc")]
 mod enumerator;
+#[cfg(feature = "heapless")]
+mod heapless_population_sampler;
 #[cfg(feature = "alloc")]
 mod population_sampler;
+#[cfg(all(feature = "std", feature = "num-rational"))]
+mod rational_counter;
 mod sampler;
 #[cfg(feature = "std")]
+mod snapshots;
+#[cfg(feature = "std")]
+mod stratified_counter;
+#[cfg(feature = "num-rational")]
+mod tagged_enumerator;
+#[cfg(feature = "alloc")]
+mod trace_enumerator;
+#[cfg(feature = "tracing")]
+mod traced;
+#[cfg(feature = "std")]
 mod unique_enumerator;
+#[cfg(feature = "alloc")]
+mod weighted_population_sampler;
+#[cfg(feature = "std")]
+mod witness_counter;
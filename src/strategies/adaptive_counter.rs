@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    current_step, telemetry, Counter, DefaultState, EnumerableDistribution,
+    FlattenableRandomStrategy, Functor, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedFunctor, WeightedRandomStrategy,
+};
+
+/// The point in a random process at which an [`AdaptiveCounter`] first
+/// switched from exact counting to subsampling.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SwitchPoint {
+    /// The number of operations which produced the functor before the switch,
+    /// so the switch happened during the operation with this zero-based index.
+    pub operation: usize,
+    /// The label of the [`step`](crate::step) that was running when the switch
+    /// happened, if there was one.
+    pub step: Option<&'static str>,
+}
+
+/// The operations which produced an [`AdaptiveCounts`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct History {
+    operations: usize,
+    switch: Option<SwitchPoint>,
+}
+
+impl History {
+    /// Records an operation, and whether it switched to subsampling.
+    #[inline(always)]
+    fn record(self, switched: bool) -> Self {
+        let switch = self.switch.or_else(|| {
+            switched.then(|| SwitchPoint {
+                operation: self.operations,
+                step: current_step(),
+            })
+        });
+        History {
+            operations: self.operations + 1,
+            switch,
+        }
+    }
+}
+
+/// The functor of [`AdaptiveCounter`], a [`HashMap`] from each outcome to its
+/// count, along with the point at which subsampling first happened, if it did.
+///
+/// It dereferences to its [`HashMap`], which can be recovered with
+/// [`AdaptiveCounts::into_inner`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdaptiveCounts<I: Inner> {
+    counts: HashMap<I, usize, DefaultState>,
+    history: History,
+}
+
+impl<I: Inner> AdaptiveCounts<I> {
+    /// Returns the point at which the operations that produced this functor
+    /// first switched to subsampling, if they did.
+    ///
+    /// Operations are counted from the [`Functor::pure`] call that created the
+    /// initial functor.
+    #[inline]
+    pub fn switch(&self) -> Option<SwitchPoint> {
+        self.history.switch
+    }
+
+    /// Returns the outcomes and their counts.
+    #[inline]
+    pub fn into_inner(self) -> HashMap<I, usize, DefaultState> {
+        self.counts
+    }
+}
+
+impl<I: Inner> Deref for AdaptiveCounts<I> {
+    type Target = HashMap<I, usize, DefaultState>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.counts
+    }
+}
+
+impl<I: Inner> Functor<I> for AdaptiveCounts<I> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        AdaptiveCounts {
+            counts: Functor::pure(i),
+            history: History::default(),
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+impl<I: Inner> WeightedFunctor<I> for AdaptiveCounts<I> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        self.counts.weighted_outcomes()
+    }
+}
+
+/// Counts all possible outputs of the random process exactly, like
+/// [`Counter`], until they exceed `LIMIT` distinct outcomes, after which it
+/// subsamples them.
+///
+/// Whenever an operation produces more than `LIMIT` distinct outcomes,
+/// `AdaptiveCounter` draws `LIMIT` outcomes at random, with probability
+/// proportional to their counts, and counts how many times each was drawn.
+/// Random processes with few outcomes are therefore counted exactly, while
+/// those with many outcomes are approximated with bounded memory, so the same
+/// code can handle both small and huge parameterizations.
+///
+/// The [`SwitchPoint`] at which subsampling first happened, if it did, is
+/// recorded in the resulting [`AdaptiveCounts`].
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{step, AdaptiveCounter, Functor, RandomStrategy};
+///
+/// fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u16> {
+///     let f = step("first", || S::fmap_rand(Functor::pure(0), rng, |_, r: u8| r as u16));
+///     step("second", || S::fmap_rand(f, rng, |s, r: u8| s * 256 + r as u16))
+/// }
+///
+/// let f = random_process::<AdaptiveCounter<1000>>(&mut thread_rng());
+/// assert!(f.len() <= 1000);
+/// assert_eq!(f.values().sum::<usize>(), 1000);
+/// let switch = f.switch().unwrap();
+/// assert_eq!((switch.operation, switch.step), (1, Some("second")));
+/// ```
+///
+/// Using a `LIMIT` of zero causes a compile error.
+///
+/// [`Counter`]: crate::Counter
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AdaptiveCounter<const LIMIT: usize>;
+
+impl<const LIMIT: usize> AdaptiveCounter<LIMIT> {
+    const CHECKED_LIMIT: usize = {
+        assert!(LIMIT != 0, "the outcome limit must be nonzero");
+        LIMIT
+    };
+
+    /// Subsamples `counts` if it contains more than `LIMIT` distinct outcomes,
+    /// and records the operation which produced it in `history`.
+    #[inline(always)]
+    fn limit<T: Inner>(
+        history: History,
+        counts: HashMap<T, usize, DefaultState>,
+        rng: &mut impl RngCore,
+    ) -> AdaptiveCounts<T> {
+        let limit = Self::CHECKED_LIMIT;
+        if counts.len() <= limit {
+            return AdaptiveCounts {
+                counts,
+                history: history.record(false),
+            };
+        }
+
+        let size = counts.len();
+        let (outcomes, cumulative_counts): (Vec<_>, Vec<_>) = counts
+            .into_iter()
+            .scan(0u128, |total, (outcome, count)| {
+                *total += count as u128;
+                Some((outcome, *total))
+            })
+            .unzip();
+        let total = cumulative_counts[cumulative_counts.len() - 1];
+        let mut sampled = HashMap::with_capacity_and_hasher(limit, DefaultState::default());
+        for _ in 0..limit {
+            let x = rng.gen_range(0..total);
+            let index = cumulative_counts.partition_point(|&c| c <= x);
+            *sampled.entry(outcomes[index].clone()).or_insert(0) += 1;
+        }
+        telemetry::record_shrink("AdaptiveCounter", size, size - sampled.len());
+        AdaptiveCounts {
+            counts: sampled,
+            history: history.record(true),
+        }
+    }
+}
+
+impl<const LIMIT: usize> RandomStrategy for AdaptiveCounter<LIMIT> {
+    type Functor<I: Inner> = AdaptiveCounts<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        AdaptiveCounts {
+            counts: <Counter>::fmap(f.counts, func),
+            history: f.history.record(false),
+        }
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::limit(f.history, <Counter>::fmap_rand(f.counts, rng, func), rng)
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::limit(
+            f.history,
+            <Counter>::fmap_rand_range(f.counts, range, rng, func),
+            rng,
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_absorbing<A: Inner, R: SmallRandomVariable, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::limit(
+            f.history,
+            <Counter>::fmap_rand_absorbing(f.counts, absorbing, rng, func),
+            rng,
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range_absorbing<A: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::limit(
+            f.history,
+            <Counter>::fmap_rand_range_absorbing(f.counts, absorbing, range, rng, func),
+            rng,
+        )
+    }
+}
+
+impl<const LIMIT: usize> FlattenableRandomStrategy for AdaptiveCounter<LIMIT> {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        // Without a random number generator, outcomes cannot be subsampled
+        // here. Each functor produced by func has already been limited, so
+        // the result can exceed LIMIT until the next random operation.
+        let mut inner_switch = None;
+        let counts = <Counter>::fmap_flat(f.counts, |a| {
+            let child = func(a);
+            inner_switch = inner_switch.or(child.history.switch);
+            child.counts
+        });
+        // A switch inside func is attributed to this operation.
+        let switch = f.history.switch.or(inner_switch.map(|s| SwitchPoint {
+            operation: f.history.operations,
+            ..s
+        }));
+        AdaptiveCounts {
+            counts,
+            history: History {
+                operations: f.history.operations + 1,
+                switch,
+            },
+        }
+    }
+}
+
+impl<const LIMIT: usize> WeightedRandomStrategy for AdaptiveCounter<LIMIT> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        Self::limit(
+            f.history,
+            <Counter>::fmap_rand_distribution(f.counts, distribution, rng, func),
+            rng,
+        )
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::monoid::Monoid;
+use crate::{
+    telemetry, DefaultState, EnumerableDistribution, ExactRandomStrategy,
+    FlattenableRandomStrategy, FromWeightedIterator, Functor, Inner, RandomStrategy,
+    RandomVariable, RandomVariableRange, SmallRandomVariable, WeightedFunctor,
+    WeightedRandomStrategy,
+};
+
+/// The functor of [`AnnotatedCounter`], a [`HashMap`] from each outcome to
+/// its count and a user-defined [`Monoid`] of metadata.
+///
+/// Outcomes derived from another carry its metadata forward unchanged, and
+/// the metadata of outcomes which collide is merged with
+/// [`Monoid::combine`]. Metadata is set with [`Annotated::annotate`], usually
+/// between the steps of a random process.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Annotated<I: Inner, M>(pub HashMap<I, (usize, M), DefaultState>);
+
+impl<I: Inner, M> Default for Annotated<I, M> {
+    #[inline]
+    fn default() -> Self {
+        Annotated(HashMap::default())
+    }
+}
+
+impl<I: Inner, M: Monoid> Annotated<I, M> {
+    /// Creates a functor containing only `i`, with `metadata`.
+    #[inline]
+    pub fn new(i: I, metadata: M) -> Self {
+        Annotated([(i, (1, metadata))].into_iter().collect())
+    }
+
+    /// Returns the metadata of `outcome`, if it was produced.
+    #[inline]
+    pub fn metadata(&self, outcome: &I) -> Option<&M> {
+        self.0.get(outcome).map(|(_, metadata)| metadata)
+    }
+
+    /// Returns the number of times `outcome` was produced.
+    #[inline]
+    pub fn count(&self, outcome: &I) -> usize {
+        self.0.get(outcome).map_or(0, |(count, _)| *count)
+    }
+
+    /// Replaces the metadata of every outcome with the result of `func`,
+    /// given the outcome and its current metadata.
+    pub fn annotate(mut self, mut func: impl FnMut(&I, M) -> M) -> Self {
+        for (outcome, (_, metadata)) in self.0.iter_mut() {
+            let current = core::mem::replace(metadata, M::identity());
+            *metadata = func(outcome, current);
+        }
+        self
+    }
+
+    /// Returns the outcomes along with their counts and metadata.
+    #[inline]
+    pub fn into_inner(self) -> HashMap<I, (usize, M), DefaultState> {
+        self.0
+    }
+}
+
+impl<I: Inner, M> Deref for Annotated<I, M> {
+    type Target = HashMap<I, (usize, M), DefaultState>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<I: Inner, M> IntoIterator for Annotated<I, M> {
+    type Item = (I, (usize, M));
+    type IntoIter = std::collections::hash_map::IntoIter<I, (usize, M)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<I: Inner, M: Monoid> Functor<I> for Annotated<I, M> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        Annotated::new(i, M::identity())
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<I: Inner, M: Monoid> WeightedFunctor<I> for Annotated<I, M> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        self.0.iter().map(|(i, (count, _))| (i, *count))
+    }
+}
+
+impl<I: Inner, M: Monoid> FromIterator<(I, (usize, M))> for Annotated<I, M> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (I, (usize, M))>>(iter: T) -> Self {
+        let mut f = Self::default();
+        f.extend(iter);
+        f
+    }
+}
+
+impl<I: Inner, M: Monoid> Extend<(I, (usize, M))> for Annotated<I, M> {
+    /// Adds the counts of outcomes which are already stored, combining their
+    /// metadata.
+    #[inline]
+    fn extend<T: IntoIterator<Item = (I, (usize, M))>>(&mut self, iter: T) {
+        for (outcome, (count, metadata)) in iter {
+            insert(&mut self.0, outcome, count, metadata);
+        }
+    }
+}
+
+impl<I: Inner, M: Monoid> FromWeightedIterator<I> for Annotated<I, M> {
+    /// Adds every outcome with a nonzero count, with the identity as its
+    /// metadata.
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, usize)>>(&mut self, outcomes: T) {
+        self.extend(
+            outcomes
+                .into_iter()
+                .filter(|&(_, count)| count != 0)
+                .map(|(outcome, count)| (outcome, (count, M::identity()))),
+        );
+    }
+}
+
+/// Adds `count` to the count of `outcome` in `map`, combining `metadata` with
+/// its existing metadata.
+#[inline(always)]
+fn insert<I: Inner, M: Monoid>(
+    map: &mut HashMap<I, (usize, M), DefaultState>,
+    outcome: I,
+    count: usize,
+    metadata: M,
+) {
+    match map.get_mut(&outcome) {
+        Some((c, m)) => {
+            *c += count;
+            let current = core::mem::replace(m, M::identity());
+            *m = current.combine(metadata);
+        }
+        None => {
+            map.insert(outcome, (count, metadata));
+        }
+    }
+}
+
+/// Produces all possible outputs of the random process, with repetition,
+/// stored in a [`HashMap`] along with a user-defined [`Monoid`] of metadata for
+/// each of them.
+///
+/// `AnnotatedCounter` counts outcomes exactly like [`Counter`]. Each outcome
+/// also carries metadata of type `M`, which is inherited by the outcomes
+/// derived from it and merged with [`Monoid::combine`] when outcomes collide.
+/// [`FlattenableRandomStrategy::fmap_flat`] combines the metadata of each
+/// outcome with that of the outcomes produced from it. This makes it possible
+/// to track properties of the paths leading to each state, such as the
+/// earliest step at which it was reached, without making them part of the
+/// state and multiplying the number of distinct outcomes.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::monoid::{Min, Monoid};
+/// use rand_functors::{Annotated, AnnotatedCounter, Functor, RandomStrategy};
+///
+/// let mut f: Annotated<u8, Min<usize>> = Functor::pure(0);
+/// for step in 1..=4 {
+///     f = AnnotatedCounter::fmap_rand(f, &mut thread_rng(), |s, r: bool| (s + r as u8).min(2));
+///     // Record the step at which each path first reached 2.
+///     f = f.annotate(|&s, first| if s == 2 { first.combine(Min(step)) } else { first });
+/// }
+/// assert_eq!(f.count(&2), 11);
+/// assert_eq!(f.metadata(&2), Some(&Min(2)));
+/// ```
+///
+/// [`Counter`]: crate::Counter
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AnnotatedCounter<M> {
+    phantom: PhantomData<M>,
+}
+
+impl<M: Monoid> AnnotatedCounter<M> {
+    /// Collects outcomes into a new functor, summing the counts and combining
+    /// the metadata of outcomes that occur more than once.
+    #[inline(always)]
+    fn merge<I: Inner>(
+        capacity: usize,
+        outcomes: impl Iterator<Item = (I, usize, M)>,
+    ) -> Annotated<I, M> {
+        let mut new_functor = HashMap::with_capacity_and_hasher(capacity, DefaultState::default());
+        let mut produced = 0;
+        outcomes.for_each(|(o, count, metadata)| {
+            produced += 1;
+            insert(&mut new_functor, o, count, metadata);
+        });
+        telemetry::record_outcomes("AnnotatedCounter", produced, new_functor.len());
+        Annotated(new_functor)
+    }
+}
+
+impl<M: Monoid> RandomStrategy for AnnotatedCounter<M> {
+    type Functor<I: Inner> = Annotated<I, M>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        let capacity = f.len();
+        Self::merge(
+            capacity,
+            f.into_iter()
+                .map(|(a, (count, metadata))| (func(a), count, metadata)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let capacity = f.len();
+        Self::merge(
+            capacity,
+            f.into_iter()
+                .flat_map(|(a, (count, metadata))| {
+                    R::sample_space().map(move |r| (a.clone(), r, count, metadata.clone()))
+                })
+                .map(|(a, r, count, metadata)| (func(a, r), count, metadata)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let capacity = f.len();
+        Self::merge(
+            capacity,
+            f.into_iter()
+                .flat_map(|(a, (count, metadata))| {
+                    range
+                        .sample_space()
+                        .map(move |r| (a.clone(), r, count, metadata.clone()))
+                })
+                .map(|(a, r, count, metadata)| (func(a, r), count, metadata)),
+        )
+    }
+}
+
+impl<M: Monoid> FlattenableRandomStrategy for AnnotatedCounter<M> {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        let capacity = f.len();
+        let children = f
+            .into_iter()
+            .map(|(a, (count, metadata))| (func(a), count, metadata))
+            .collect::<Vec<_>>();
+        Self::merge(
+            capacity,
+            children
+                .into_iter()
+                .flat_map(|(child, outer_count, outer_metadata)| {
+                    child
+                        .into_iter()
+                        .map(move |(b, (inner_count, inner_metadata))| {
+                            (
+                                b,
+                                inner_count * outer_count,
+                                outer_metadata.clone().combine(inner_metadata),
+                            )
+                        })
+                }),
+        )
+    }
+}
+
+impl<M: Monoid> WeightedRandomStrategy for AnnotatedCounter<M> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let capacity = f.len();
+        Self::merge(
+            capacity,
+            f.into_iter()
+                .flat_map(|(a, (count, metadata))| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| {
+                            (a.clone(), r, count * weight as usize, metadata.clone())
+                        })
+                })
+                .map(|(a, r, count, metadata)| (func(a, r), count, metadata)),
+        )
+    }
+}
+
+impl<M: Monoid> ExactRandomStrategy for AnnotatedCounter<M> {}
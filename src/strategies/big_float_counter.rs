@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+use std::vec::Vec;
+
+use dashu_float::FBig;
+use num_traits::Zero;
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    telemetry, DefaultState, EnumerableDistribution, FlattenableRandomStrategy, Inner,
+    RandomStrategy, RandomVariable, RandomVariableRange, SmallRandomVariable,
+    WeightedRandomStrategy,
+};
+
+/// Produces all possible outputs of the random process, without repetition,
+/// stored in a [`HashMap`] alongside their probabilities as arbitrary-precision
+/// binary floating-point numbers.
+///
+/// `BigFloatCounter` normalizes probabilities like [`RationalCounter`], but
+/// stores each as an [`FBig`] with a significand of `PRECISION` bits and an
+/// unbounded exponent. The probability of an outcome reached only after many
+/// unlikely steps is therefore never rounded to zero, as it would be by an
+/// [`f64`], and long random processes never overflow a denominator, as they
+/// can with [`RationalCounter`]. This makes `BigFloatCounter` suitable for
+/// analyzing rare events over many steps. Probabilities are rounded towards
+/// zero, so they may sum to slightly less than one.
+///
+/// ```
+/// use dashu_float::FBig;
+/// use rand::prelude::*;
+/// use rand_functors::{BigFloatCounter, Functor, RandomStrategy};
+///
+/// let mut rng = thread_rng();
+/// let mut f = <BigFloatCounter>::fmap_rand(Functor::pure(true), &mut rng, |_, r: u8| r == 0);
+/// for _ in 0..199 {
+///     f = <BigFloatCounter>::fmap_rand(f, &mut rng, |s, r: u8| s && r == 0);
+/// }
+/// // Every one of 200 bytes is zero with a probability of 2^-1600.
+/// let p: FBig = FBig::ONE >> 1600;
+/// assert_eq!(f[&true], p);
+/// assert_eq!(f[&true].to_f64().value(), 0.0);
+/// ```
+///
+/// Using a `PRECISION` of zero causes a compile error.
+///
+/// This type is only available with the `dashu-float` feature.
+///
+/// [`RationalCounter`]: crate::RationalCounter
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BigFloatCounter<const PRECISION: usize = 128, S: BuildHasher + Default = DefaultState> {
+    phantom: PhantomData<S>,
+}
+
+impl<const PRECISION: usize, S: BuildHasher + Default> BigFloatCounter<PRECISION, S> {
+    const CHECKED_PRECISION: usize = {
+        assert!(
+            PRECISION != 0,
+            "probabilities must have a nonzero precision"
+        );
+        PRECISION
+    };
+
+    /// Divides `p` by `n`, rounding the quotient to `PRECISION` bits.
+    #[inline(always)]
+    fn divide(p: FBig, n: impl Into<FBig>) -> FBig {
+        let n: FBig = n.into();
+        p / n.with_precision(Self::CHECKED_PRECISION).value()
+    }
+
+    /// Collects outcomes into a new functor, summing the probabilities of
+    /// outcomes that occur more than once.
+    #[inline(always)]
+    fn merge_probabilities<I: Inner>(
+        capacity: usize,
+        outcomes: impl Iterator<Item = (I, FBig)>,
+    ) -> HashMap<I, FBig, S> {
+        let mut new_functor = HashMap::with_capacity_and_hasher(capacity, Default::default());
+        let mut produced = 0;
+        outcomes.for_each(|(o, p)| {
+            produced += 1;
+            *new_functor.entry(o).or_insert(FBig::ZERO) += p;
+        });
+        telemetry::record_outcomes("BigFloatCounter", produced, new_functor.len());
+        new_functor
+    }
+
+    /// Expands the outcomes of `f` not satisfying `absorbing` over the sample
+    /// space produced by `sample_space`, and carries the others forward with
+    /// their probabilities unchanged.
+    #[inline(always)]
+    fn absorb<A: Inner, R, I: Iterator<Item = R>>(
+        f: HashMap<A, FBig, S>,
+        absorbing: impl Fn(&A) -> bool,
+        sample_space: impl Fn() -> I,
+        func: impl Fn(A, R) -> A,
+    ) -> HashMap<A, FBig, S> {
+        let n = sample_space().count();
+        let capacity = f.len();
+        let (absorbed, expanded): (Vec<_>, Vec<_>) = f.into_iter().partition(|(a, _)| absorbing(a));
+        Self::merge_probabilities(
+            capacity,
+            absorbed.into_iter().chain(
+                expanded
+                    .into_iter()
+                    .map(|(a, p)| (a, Self::divide(p, n)))
+                    .flat_map(|a| sample_space().map(move |r| (a.clone(), r)))
+                    .map(|((a, p), r)| (func(a, r), p)),
+            ),
+        )
+    }
+}
+
+impl<const PRECISION: usize, S: BuildHasher + Default> RandomStrategy
+    for BigFloatCounter<PRECISION, S>
+{
+    type Functor<I: Inner> = HashMap<I, FBig, S>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        let capacity = f.len();
+        Self::merge_probabilities(capacity, f.into_iter().map(|(i, p)| (func(i), p)))
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let n = R::sample_space().count();
+        let capacity = f.len();
+        Self::merge_probabilities(
+            capacity,
+            f.into_iter()
+                .map(|(a, p)| (a, Self::divide(p, n)))
+                .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
+                .map(|((a, p), r)| (func(a, r), p)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let n = range.sample_space().count();
+        let capacity = f.len();
+        Self::merge_probabilities(
+            capacity,
+            f.into_iter()
+                .map(|(a, p)| (a, Self::divide(p, n)))
+                .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
+                .map(|((a, p), r)| (func(a, r), p)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_absorbing<A: Inner, R: SmallRandomVariable, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::absorb(f, absorbing, R::sample_space, func)
+    }
+
+    #[inline]
+    fn fmap_rand_range_absorbing<A: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::absorb(f, absorbing, || range.sample_space(), func)
+    }
+}
+
+impl<const PRECISION: usize, S: BuildHasher + Default> FlattenableRandomStrategy
+    for BigFloatCounter<PRECISION, S>
+{
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        let capacity = f.len();
+        let children = f
+            .into_iter()
+            .map(|(i, p)| {
+                let child = func(i);
+                let total = child.values().cloned().sum::<FBig>();
+                let scale = if total.is_zero() {
+                    total
+                } else {
+                    Self::divide(p, total)
+                };
+                (child, scale)
+            })
+            .collect::<Vec<_>>();
+        Self::merge_probabilities(
+            capacity,
+            children.into_iter().flat_map(|(child, scale)| {
+                child
+                    .into_iter()
+                    .map(move |(output, q)| (output, q * scale.clone()))
+            }),
+        )
+    }
+}
+
+impl<const PRECISION: usize, S: BuildHasher + Default> WeightedRandomStrategy
+    for BigFloatCounter<PRECISION, S>
+{
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let total = distribution
+            .weighted_sample_space()
+            .map(|(_, weight)| weight as u128)
+            .sum::<u128>();
+        let capacity = f.len();
+        Self::merge_probabilities(
+            capacity,
+            f.into_iter()
+                .flat_map(|a| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| (a.clone(), r, weight))
+                })
+                .map(|((a, p), r, weight)| {
+                    (func(a, r), Self::divide(p * FBig::from(weight), total))
+                }),
+        )
+    }
+}
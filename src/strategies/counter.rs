@@ -1,15 +1,19 @@
-use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
 use std::hash::BuildHasher;
 use std::marker::PhantomData;
+use std::vec::Vec;
 
-use num_traits::{NumAssign, Unsigned};
+use num_traits::{CheckedMul, FromPrimitive, NumAssign, Unsigned};
 use rand::distributions::uniform::SampleUniform;
 use rand::distributions::Standard;
 use rand::prelude::*;
+use rand_core::RngCore;
 
+use crate::patch::apply_patches;
 use crate::{
-    FlattenableRandomStrategy, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    telemetry, DefaultState, EnumerableDistribution, ExactRandomStrategy,
+    FlattenableRandomStrategy, Inner, Patch, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedRandomStrategy,
 };
 
 /// Produces all possible outputs of the random process, with repetition, stored
@@ -19,15 +23,69 @@ use crate::{
 /// inputs to the same output. Examples include conditionally zeroing out a
 /// field of a struct or the use of functions like `saturating_add` or
 /// `saturating_mul`.
+///
+/// The counts are stored as `N`, which is [`usize`] by default.
+///
+/// # Panics
+///
+/// [`WeightedRandomStrategy::fmap_rand_distribution`] panics if a weight of
+/// the distribution, or the product of a weight and a count, cannot be
+/// represented as an `N`, rather than storing a corrupted count.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Counter<
-    S: BuildHasher + Default = RandomState,
+    S: BuildHasher + Default = DefaultState,
     N: Clone + Default + NumAssign + Unsigned = usize,
 > {
     count_phantom: PhantomData<N>,
     hasher_phantom: PhantomData<S>,
 }
 
+impl<S: BuildHasher + Default, N: Clone + Default + NumAssign + Unsigned> Counter<S, N> {
+    /// Collects outcomes into a new functor, summing the counts of outcomes
+    /// that occur more than once.
+    #[inline(always)]
+    fn merge_counts<I: Inner>(
+        capacity: usize,
+        outcomes: impl Iterator<Item = (I, N)>,
+    ) -> HashMap<I, N, S> {
+        let mut new_functor = HashMap::with_capacity_and_hasher(capacity, Default::default());
+        let mut produced = 0;
+        outcomes.for_each(|(o, count)| {
+            produced += 1;
+            *new_functor.entry(o).or_insert(N::zero()) += count;
+        });
+        telemetry::record_outcomes("Counter", produced, new_functor.len());
+        new_functor
+    }
+
+    /// Expands the outcomes of `f` not satisfying `absorbing` over the sample
+    /// space produced by `sample_space`, and scales the counts of the others by
+    /// its size without expanding them.
+    #[inline(always)]
+    fn absorb<A: Inner, R, I: Iterator<Item = R>>(
+        f: HashMap<A, N, S>,
+        absorbing: impl Fn(&A) -> bool,
+        sample_space: impl Fn() -> I,
+        func: impl Fn(A, R) -> A,
+    ) -> HashMap<A, N, S> {
+        let size = sample_space().fold(N::zero(), |size, _| size + N::one());
+        let capacity = f.len();
+        let (absorbed, expanded): (Vec<_>, Vec<_>) = f.into_iter().partition(|(a, _)| absorbing(a));
+        Self::merge_counts(
+            capacity,
+            absorbed
+                .into_iter()
+                .map(|(a, c)| (a, c * size.clone()))
+                .chain(
+                    expanded
+                        .into_iter()
+                        .flat_map(|a| sample_space().map(move |r| (a.clone(), r)))
+                        .map(|((a, c), r)| (func(a, r), c)),
+                ),
+        )
+    }
+}
+
 impl<S: BuildHasher + Default, N: Clone + Default + NumAssign + Unsigned> RandomStrategy
     for Counter<S, N>
 {
@@ -37,52 +95,119 @@ impl<S: BuildHasher + Default, N: Clone + Default + NumAssign + Unsigned> Random
     fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
         // Constructing a new HashMap is necessary, as there may be fewer new
         // keys than old keys, which requires merging some or all counts.
-        let mut new_functor = Self::Functor::with_capacity_and_hasher(f.len(), Default::default());
-        f.into_iter()
-            .map(|(i, count)| (func(i), count))
-            .for_each(|(o, count)| {
-                *new_functor.entry(o).or_insert(N::zero()) += count;
-            });
-        new_functor
+        let capacity = f.len();
+        Self::merge_counts(capacity, f.into_iter().map(|(i, count)| (func(i), count)))
     }
 
     #[inline]
-    fn fmap_rand<A: Inner, B: Inner, R: RandomVariable, F: Fn(A, R) -> B>(
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
-        _: &mut impl Rng,
+        _: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>,
     {
-        let mut new_functor = Self::Functor::with_capacity_and_hasher(f.len(), Default::default());
-        f.into_iter()
-            .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
-            .map(|((a, c), r)| (func(a, r), c))
-            .for_each(|(b, count)| {
-                *new_functor.entry(b).or_insert(N::zero()) += count;
-            });
-        new_functor
+        let capacity = f.len();
+        Self::merge_counts(
+            capacity,
+            f.into_iter()
+                .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
+                .map(|((a, c), r)| (func(a, r), c)),
+        )
     }
 
     #[inline]
     fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
         range: impl RandomVariableRange<R>,
-        _: &mut impl Rng,
+        _: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>,
     {
-        let mut new_functor = Self::Functor::with_capacity_and_hasher(f.len(), Default::default());
-        f.into_iter()
-            .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
-            .map(|((a, c), r)| (func(a, r), c))
-            .for_each(|(b, count)| {
-                *new_functor.entry(b).or_insert(N::zero()) += count;
-            });
-        new_functor
+        let capacity = f.len();
+        Self::merge_counts(
+            capacity,
+            f.into_iter()
+                .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
+                .map(|((a, c), r)| (func(a, r), c)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_absorbing<A: Inner, R: SmallRandomVariable, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::absorb(f, absorbing, R::sample_space, func)
+    }
+
+    #[inline]
+    fn fmap_rand_range_absorbing<A: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::absorb(f, absorbing, || range.sample_space(), func)
+    }
+
+    #[inline]
+    fn fmap_rand_delta<A: Inner, R: SmallRandomVariable, P: Patch<A>, F: Fn(&A, R) -> P>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        let capacity = f.len();
+        Self::merge_counts(
+            capacity,
+            f.into_iter().flat_map(|(a, c)| {
+                let patches = R::sample_space().map(|r| func(&a, r)).collect::<Vec<_>>();
+                apply_patches(a, patches).map(move |a| (a, c.clone()))
+            }),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range_delta<
+        A: Inner,
+        R: RandomVariable + SampleUniform,
+        P: Patch<A>,
+        F: Fn(&A, R) -> P,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        let capacity = f.len();
+        Self::merge_counts(
+            capacity,
+            f.into_iter().flat_map(|(a, c)| {
+                let patches = range
+                    .sample_space()
+                    .map(|r| func(&a, r))
+                    .collect::<Vec<_>>();
+                apply_patches(a, patches).map(move |a| (a, c.clone()))
+            }),
+        )
     }
 }
 
@@ -94,17 +219,61 @@ impl<S: BuildHasher + Default, N: Clone + Default + NumAssign + Unsigned> Flatte
         f: Self::Functor<A>,
         mut func: F,
     ) -> Self::Functor<B> {
-        let mut new_functor = Self::Functor::with_capacity_and_hasher(f.len(), Default::default());
+        let capacity = f.len();
         let children = f
             .into_iter()
             .map(|(i, count)| (func(i), count))
             .collect::<Vec<_>>();
-        for (child, outer_count) in children {
-            for (output, inner_count) in child {
-                *new_functor.entry(output).or_insert(N::zero()) +=
-                    inner_count * outer_count.clone();
-            }
-        }
-        new_functor
+        Self::merge_counts(
+            capacity,
+            children.into_iter().flat_map(|(child, outer_count)| {
+                child
+                    .into_iter()
+                    .map(move |(output, inner_count)| (output, inner_count * outer_count.clone()))
+            }),
+        )
     }
 }
+
+impl<
+        S: BuildHasher + Default,
+        N: CheckedMul + Clone + Default + FromPrimitive + NumAssign + Unsigned,
+    > WeightedRandomStrategy for Counter<S, N>
+{
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let capacity = f.len();
+        Self::merge_counts(
+            capacity,
+            f.into_iter()
+                .flat_map(|a| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| (a.clone(), r, weight))
+                })
+                .map(|((a, c), r, weight)| {
+                    let weight = N::from_u64(weight).expect("weight must fit in the count type");
+                    let count = c
+                        .checked_mul(&weight)
+                        .expect("weighted count must fit in the count type");
+                    (func(a, r), count)
+                }),
+        )
+    }
+}
+
+impl<S: BuildHasher + Default, N: Clone + Default + NumAssign + Unsigned> ExactRandomStrategy
+    for Counter<S, N>
+{
+}
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    telemetry, DefaultState, EnumerableDistribution, FlattenableRandomStrategy,
+    FromWeightedIterator, Functor, Inner, ProbabilityBounds, RandomStrategy, RandomVariable,
+    RandomVariableRange, SmallRandomVariable, WeightedFunctor, WeightedRandomStrategy,
+};
+
+/// The functor of [`CoverageEnumerator`], a [`HashMap`] from each outcome to
+/// its probability, along with the coverage to maintain and the probability of
+/// the outcomes that were discarded.
+///
+/// It is also produced by [`Pipeline::run_until`], in which case the residual
+/// is the probability of the outcomes that were not reached before it stopped.
+///
+/// [`Pipeline::run_until`]: crate::Pipeline::run_until
+#[derive(Clone, Debug, PartialEq)]
+pub struct Covered<I: Inner> {
+    outcomes: HashMap<I, f64, DefaultState>,
+    coverage: f64,
+    residual: f64,
+}
+
+impl<I: Inner> Default for Covered<I> {
+    #[inline]
+    fn default() -> Self {
+        Covered {
+            outcomes: HashMap::default(),
+            coverage: 1.0,
+            residual: 0.0,
+        }
+    }
+}
+
+impl<I: Inner> Covered<I> {
+    #[inline]
+    pub(crate) fn new(outcomes: HashMap<I, f64, DefaultState>, residual: f64) -> Self {
+        Covered {
+            outcomes,
+            coverage: 1.0 - residual,
+            residual,
+        }
+    }
+
+    /// Creates a functor containing only `i`, whose least probable outcomes
+    /// [`CoverageEnumerator`] will discard as long as their total probability
+    /// stays within one minus `coverage`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coverage` is not between zero and one.
+    #[inline]
+    pub fn with_coverage(i: I, coverage: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&coverage),
+            "coverage must be between zero and one"
+        );
+        Covered {
+            outcomes: [(i, 1.0)].into_iter().collect(),
+            coverage,
+            residual: 0.0,
+        }
+    }
+
+    /// Returns the coverage maintained by [`CoverageEnumerator`], which is one
+    /// for functors created with [`Functor::pure`].
+    #[inline]
+    pub fn coverage(&self) -> f64 {
+        self.coverage
+    }
+
+    /// Returns the probability of the outcomes that were discarded, which is
+    /// at most one minus its [`coverage`](Covered::coverage).
+    #[inline]
+    pub fn residual(&self) -> f64 {
+        self.residual
+    }
+
+    /// Returns the total probability of the outcomes that were kept.
+    #[inline]
+    pub fn covered(&self) -> f64 {
+        1.0 - self.residual
+    }
+
+    /// Returns bounds on the probability of the event that `pred` holds.
+    ///
+    /// The lower bound is the probability of the matching outcomes that were
+    /// kept, and the upper bound assumes that every discarded outcome would
+    /// have matched.
+    pub fn probability_bounds(&self, mut pred: impl FnMut(&I) -> bool) -> ProbabilityBounds {
+        let lower = self
+            .outcomes
+            .iter()
+            .filter(|(outcome, _)| pred(outcome))
+            .map(|(_, p)| p)
+            .sum::<f64>();
+        ProbabilityBounds {
+            lower,
+            upper: (lower + self.residual).min(1.0),
+        }
+    }
+
+    /// Returns the outcomes that were kept along with their probabilities.
+    #[inline]
+    pub fn into_inner(self) -> HashMap<I, f64, DefaultState> {
+        self.outcomes
+    }
+}
+
+impl<I: Inner> Deref for Covered<I> {
+    type Target = HashMap<I, f64, DefaultState>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.outcomes
+    }
+}
+
+impl<I: Inner> IntoIterator for Covered<I> {
+    type Item = (I, f64);
+    type IntoIter = std::collections::hash_map::IntoIter<I, f64>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.outcomes.into_iter()
+    }
+}
+
+impl<I: Inner> Functor<I> for Covered<I> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        Covered {
+            outcomes: [(i, 1.0)].into_iter().collect(),
+            coverage: 1.0,
+            residual: 0.0,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.outcomes.len()
+    }
+}
+
+impl<I: Inner> WeightedFunctor<I> for Covered<I> {
+    type Weight = f64;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, f64)>
+    where
+        I: 'a,
+    {
+        self.outcomes.iter().map(|(i, &p)| (i, p))
+    }
+}
+
+impl<I: Inner> FromIterator<(I, f64)> for Covered<I> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (I, f64)>>(iter: T) -> Self {
+        let mut f = Self::default();
+        f.extend(iter);
+        f
+    }
+}
+
+impl<I: Inner> Extend<(I, f64)> for Covered<I> {
+    /// Adds the probabilities of outcomes which are already stored, leaving
+    /// the residual unchanged.
+    #[inline]
+    fn extend<T: IntoIterator<Item = (I, f64)>>(&mut self, iter: T) {
+        for (outcome, p) in iter {
+            *self.outcomes.entry(outcome).or_insert(0.0) += p;
+        }
+    }
+}
+
+impl<I: Inner> FromWeightedIterator<I> for Covered<I> {
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, f64)>>(&mut self, outcomes: T) {
+        self.extend(outcomes.into_iter().filter(|&(_, p)| p != 0.0));
+    }
+}
+
+/// Produces the most probable outputs of the random process, discarding the
+/// least probable ones until they cover a target probability.
+///
+/// Outcomes are stored with their exact probabilities in a [`Covered`]. After
+/// every operation, `CoverageEnumerator` sorts the outcomes by probability and
+/// discards the least probable ones, as long as the total probability
+/// discarded over the whole random process stays within one minus the
+/// coverage of the initial functor, created with [`Covered::with_coverage`].
+/// Only the outcomes that carry most of the probability are expanded further,
+/// and the probability of those that were discarded is reported by
+/// [`Covered::residual`]. Outcomes with equal probabilities are discarded in
+/// an unspecified order.
+///
+/// Unlike other enumerating strategies, `CoverageEnumerator` panics when
+/// given an empty range or sample space, rather than producing an empty
+/// functor, as the probability of every outcome would otherwise be lost
+/// without being accounted for in the residual.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{CoverageEnumerator, Covered, RandomStrategy};
+///
+/// fn random_process<S: RandomStrategy>(f: S::Functor<u8>, rng: &mut impl Rng) -> S::Functor<u8> {
+///     let f = S::fmap_rand_range(f, 0..10u8, rng, |_, r| r);
+///     S::fmap_rand_range(f, 0..10u8, rng, |s, r| if s == 0 { r } else { 0 })
+/// }
+///
+/// let output =
+///     random_process::<CoverageEnumerator>(Covered::with_coverage(0, 0.95), &mut thread_rng());
+/// assert!(output[&0] > 0.9);
+/// assert!(output.residual() <= 0.05);
+/// ```
+///
+/// A functor created with [`Functor::pure`] has a coverage of one, so no
+/// outcomes are discarded. Within
+/// [`fmap_flat`](FlattenableRandomStrategy::fmap_flat), the outcomes produced
+/// by the function passed to it are discarded according to the coverage of the
+/// outer functor.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CoverageEnumerator;
+
+impl CoverageEnumerator {
+    /// Collects outcomes into a new functor, summing the probabilities of
+    /// outcomes that occur more than once, and discards the least probable
+    /// outcomes within the residual allowed by the coverage.
+    #[inline(always)]
+    fn collect<I: Inner>(
+        coverage: f64,
+        mut residual: f64,
+        outcomes: impl Iterator<Item = (I, f64)>,
+    ) -> Covered<I> {
+        let mut merged = HashMap::<_, _, DefaultState>::default();
+        let mut produced = 0;
+        for (outcome, p) in outcomes {
+            produced += 1;
+            *merged.entry(outcome).or_insert(0.0) += p;
+        }
+        telemetry::record_outcomes("CoverageEnumerator", produced, merged.len());
+
+        let size = merged.len();
+        let mut sorted: Vec<_> = merged.into_iter().collect();
+        sorted.sort_unstable_by(|a, b| b.1.total_cmp(&a.1));
+        while let Some(&(_, p)) = sorted.last() {
+            if residual + p > 1.0 - coverage {
+                break;
+            }
+            residual += p;
+            sorted.pop();
+        }
+        telemetry::record_shrink("CoverageEnumerator", size, size - sorted.len());
+        Covered {
+            outcomes: sorted.into_iter().collect(),
+            coverage,
+            residual,
+        }
+    }
+}
+
+impl RandomStrategy for CoverageEnumerator {
+    type Functor<I: Inner> = Covered<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        Self::collect(
+            f.coverage,
+            f.residual,
+            f.into_iter().map(|(a, p)| (func(a), p)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let size = R::sample_space().count();
+        assert!(size != 0, "cannot enumerate an empty sample space");
+        let size = size as f64;
+        Self::collect(
+            f.coverage,
+            f.residual,
+            f.into_iter()
+                .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
+                .map(|((a, p), r)| (func(a, r), p / size)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        assert!(!range.is_empty(), "cannot enumerate an empty range");
+        let size = range.sample_space().count() as f64;
+        Self::collect(
+            f.coverage,
+            f.residual,
+            f.into_iter()
+                .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
+                .map(|((a, p), r)| (func(a, r), p / size)),
+        )
+    }
+}
+
+impl FlattenableRandomStrategy for CoverageEnumerator {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        let coverage = f.coverage;
+        let mut residual = f.residual;
+        let outcomes: Vec<_> = f
+            .into_iter()
+            .flat_map(|(a, p)| {
+                let child = func(a);
+                residual += p * child.residual;
+                child.into_iter().map(move |(b, q)| (b, p * q))
+            })
+            .collect();
+        Self::collect(coverage, residual, outcomes.into_iter())
+    }
+}
+
+impl WeightedRandomStrategy for CoverageEnumerator {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let total = distribution
+            .weighted_sample_space()
+            .map(|(_, weight)| weight as f64)
+            .sum::<f64>();
+        Self::collect(
+            f.coverage,
+            f.residual,
+            f.into_iter()
+                .flat_map(|a| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| (a.clone(), r, weight))
+                })
+                .map(|((a, p), r, weight)| (func(a, r), p * weight as f64 / total)),
+        )
+    }
+}
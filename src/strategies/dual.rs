@@ -0,0 +1,248 @@
+#[cfg(feature = "std")]
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+#[cfg(feature = "std")]
+use crate::WeightedFunctor;
+use crate::{
+    EnumerableDistribution, ExactRandomStrategy, FlattenableRandomStrategy, Functor, Inner,
+    RandomStrategy, RandomVariable, RandomVariableRange, SmallRandomVariable,
+    WeightedRandomStrategy,
+};
+
+/// A borrowed range, so that both strategies of a [`Dual`] can use the same
+/// range.
+struct SharedRange<'a, G>(&'a G);
+
+impl<'a, R: RandomVariable + SampleUniform, G: RandomVariableRange<R>> RandomVariableRange<R>
+    for SharedRange<'a, G>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample_space(&self) -> impl DoubleEndedIterator<Item = R> {
+        self.0.sample_space()
+    }
+
+    #[inline]
+    fn sample_single<T: RngCore + ?Sized>(self, rng: &mut T) -> R {
+        // The range cannot be moved out of the borrow, so a value is selected
+        // uniformly by its position in the sample space instead.
+        let size = self.0.sample_space().count();
+        assert!(size != 0, "cannot sample from an empty range");
+        let index = rng.gen_range(0..size);
+        self.0.sample_space().nth(index).unwrap()
+    }
+}
+
+/// The functor of [`Dual`], storing the outcomes produced by each of its two
+/// strategies.
+///
+/// The outcome type `I` is part of this type so that its [`Functor`]
+/// implementation does not overlap with the one for `I` itself.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DualFunctor<I, F1, F2> {
+    left: F1,
+    right: F2,
+    phantom: PhantomData<fn() -> I>,
+}
+
+impl<I, F1, F2> DualFunctor<I, F1, F2> {
+    /// Pairs the outcomes produced by two strategies.
+    #[inline]
+    pub fn new(left: F1, right: F2) -> Self {
+        Self {
+            left,
+            right,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the outcomes produced by the first strategy.
+    #[inline]
+    pub fn left(&self) -> &F1 {
+        &self.left
+    }
+
+    /// Returns the outcomes produced by the second strategy.
+    #[inline]
+    pub fn right(&self) -> &F2 {
+        &self.right
+    }
+
+    /// Returns the outcomes produced by each strategy.
+    #[inline]
+    pub fn into_inner(self) -> (F1, F2) {
+        (self.left, self.right)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Inner, F1: WeightedFunctor<I>, F2: WeightedFunctor<I>> DualFunctor<I, F1, F2> {
+    /// Returns the first outcome whose probability differs by more than
+    /// `tolerance` between the two strategies, along with its probability
+    /// under each, or [`None`] if the strategies agree.
+    ///
+    /// Outcomes are compared by their probabilities, so strategies which store
+    /// outcomes differently, like [`Enumerator`] and [`Counter`], agree
+    /// whenever they produce the same distribution.
+    ///
+    /// [`Counter`]: crate::Counter
+    /// [`Enumerator`]: crate::Enumerator
+    pub fn disagreement(&self, tolerance: f64) -> Option<(I, f64, f64)> {
+        let left = self.left.normalize_map();
+        let right = self.right.normalize_map();
+        left.iter()
+            .map(|(outcome, &p)| (outcome, p, right.get(outcome).copied().unwrap_or(0.0)))
+            .chain(
+                right
+                    .iter()
+                    .filter(|(outcome, _)| !left.contains_key(outcome))
+                    .map(|(outcome, &q)| (outcome, 0.0, q)),
+            )
+            .find(|&(_, p, q)| (p - q).abs() > tolerance)
+            .map(|(outcome, p, q)| (outcome.clone(), p, q))
+    }
+
+    /// Asserts that the two strategies produced the same distribution, with
+    /// probabilities agreeing to within `1e-9`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probability of some outcome differs between the two
+    /// strategies, naming the outcome.
+    #[track_caller]
+    pub fn assert_agree(&self)
+    where
+        I: Debug,
+    {
+        if let Some((outcome, p, q)) = self.disagreement(1e-9) {
+            panic!("strategies disagree on {outcome:?}: probability {p} versus {q}");
+        }
+    }
+}
+
+impl<I: Inner, F1: Functor<I>, F2: Functor<I>> Functor<I> for DualFunctor<I, F1, F2> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        Self::new(F1::pure(i.clone()), F2::pure(i))
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.left.size()
+    }
+}
+
+/// Evaluates a random process with two strategies in lockstep.
+///
+/// Every operation is performed by both `S1` and `S2` on their own functors,
+/// which are stored side by side in a [`DualFunctor`]. Running a random
+/// process once with `Dual` therefore evaluates it under both strategies,
+/// after which [`DualFunctor::assert_agree`] checks that they produced the
+/// same distribution. This makes it easy to test a custom strategy against a
+/// reference such as [`Counter`].
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, Dual, Enumerator, Functor, RandomStrategy};
+///
+/// fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+///     let f = S::fmap_rand(Functor::pure(0), rng, |_, r: bool| r as u8);
+///     S::fmap_rand_range(f, 1..=3u8, rng, |s, r| s * r)
+/// }
+///
+/// let output = random_process::<Dual<Enumerator, Counter>>(&mut thread_rng());
+/// output.assert_agree();
+/// ```
+///
+/// Both strategies are given the same random number generator, one after the
+/// other. For [`FlattenableRandomStrategy::fmap_flat`], the function passed to
+/// it is called once for each strategy on every outcome, and only the part of
+/// its result belonging to that strategy is used.
+///
+/// [`Counter`]: crate::Counter
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Dual<S1, S2> {
+    phantom: PhantomData<(S1, S2)>,
+}
+
+impl<S1: RandomStrategy, S2: RandomStrategy> RandomStrategy for Dual<S1, S2> {
+    type Functor<I: Inner> = DualFunctor<I, S1::Functor<I>, S2::Functor<I>>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        DualFunctor::new(S1::fmap(f.left, &func), S2::fmap(f.right, &func))
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let left = S1::fmap_rand(f.left, rng, &func);
+        DualFunctor::new(left, S2::fmap_rand(f.right, rng, &func))
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let left = S1::fmap_rand_range(f.left, SharedRange(&range), rng, &func);
+        DualFunctor::new(left, S2::fmap_rand_range(f.right, range, rng, &func))
+    }
+}
+
+impl<S1: FlattenableRandomStrategy, S2: FlattenableRandomStrategy> FlattenableRandomStrategy
+    for Dual<S1, S2>
+{
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        let left = S1::fmap_flat(f.left, |a| func(a).left);
+        DualFunctor::new(left, S2::fmap_flat(f.right, |a| func(a).right))
+    }
+}
+
+impl<S1: WeightedRandomStrategy, S2: WeightedRandomStrategy> WeightedRandomStrategy
+    for Dual<S1, S2>
+{
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let left = S1::fmap_rand_distribution(f.left, distribution, rng, &func);
+        DualFunctor::new(
+            left,
+            S2::fmap_rand_distribution(f.right, distribution, rng, &func),
+        )
+    }
+}
+
+impl<S1: ExactRandomStrategy, S2: ExactRandomStrategy> ExactRandomStrategy for Dual<S1, S2> {}
@@ -0,0 +1,218 @@
+use std::ops::Deref;
+use std::vec::{IntoIter, Vec};
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::strategies::population_sampler::shrink_to_capacity;
+use crate::{
+    EnumerableDistribution, Enumerator, Functor, Inner, OrderedRandomStrategy, RandomStrategy,
+    RandomVariable, RandomVariableRange, SmallRandomVariable, WeightedFunctor,
+    WeightedRandomStrategy,
+};
+
+/// The functor of [`DynPopulationSampler`], a [`Vec`] of outcomes along with
+/// the capacity of the population.
+///
+/// It dereferences to a slice of its outcomes, and the [`Vec`] can be
+/// recovered with [`DynPopulation::into_inner`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DynPopulation<I> {
+    outcomes: Vec<I>,
+    capacity: Option<usize>,
+}
+
+impl<I> DynPopulation<I> {
+    #[inline]
+    pub(crate) fn new(outcomes: Vec<I>, capacity: Option<usize>) -> Self {
+        DynPopulation { outcomes, capacity }
+    }
+
+    /// Creates a population containing only `i`, which
+    /// [`DynPopulationSampler`] will shrink to at most `capacity` outcomes
+    /// after every operation.
+    #[inline]
+    pub fn with_capacity(i: I, capacity: usize) -> Self {
+        DynPopulation::new(Vec::from([i]), Some(capacity))
+    }
+
+    /// Returns the capacity of this population, or `None` if it was created
+    /// with [`Functor::pure`] and is therefore unbounded.
+    #[inline]
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Returns the outcomes in this population.
+    #[inline]
+    pub fn into_inner(self) -> Vec<I> {
+        self.outcomes
+    }
+}
+
+impl<I> Deref for DynPopulation<I> {
+    type Target = [I];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.outcomes
+    }
+}
+
+impl<I> IntoIterator for DynPopulation<I> {
+    type Item = I;
+    type IntoIter = IntoIter<I>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.outcomes.into_iter()
+    }
+}
+
+impl<I> From<DynPopulation<I>> for Vec<I> {
+    #[inline]
+    fn from(population: DynPopulation<I>) -> Self {
+        population.outcomes
+    }
+}
+
+impl<I: Inner> Functor<I> for DynPopulation<I> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        DynPopulation::new(Vec::from([i]), None)
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.outcomes.len()
+    }
+}
+
+impl<I: Inner> WeightedFunctor<I> for DynPopulation<I> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        self.outcomes.weighted_outcomes()
+    }
+}
+
+/// Produces a random subset (technically, submultiset) of possible outputs of
+/// the random process, with a capacity chosen at runtime.
+///
+/// `DynPopulationSampler` behaves exactly like [`PopulationSampler`], except
+/// that its capacity is stored in its functor, a [`DynPopulation`], rather than
+/// in a const generic parameter. The capacity is set when the initial
+/// population is created with [`DynPopulation::with_capacity`] and is carried
+/// along by every operation, so random processes which accept their initial
+/// functor need no changes.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{DynPopulation, DynPopulationSampler, RandomStrategy};
+///
+/// fn random_process<S: RandomStrategy>(f: S::Functor<u8>, rng: &mut impl Rng) -> S::Functor<u8> {
+///     S::fmap_rand(f, rng, |_, r: u8| r)
+/// }
+///
+/// let capacity = 16; // e.g. parsed from a command line flag
+/// let output = random_process::<DynPopulationSampler>(
+///     DynPopulation::with_capacity(0, capacity),
+///     &mut thread_rng(),
+/// );
+/// assert_eq!(output.len(), 16);
+/// assert_eq!(output.capacity(), Some(16));
+/// ```
+///
+/// A population created with [`Functor::pure`] has no capacity, so
+/// `DynPopulationSampler` keeps all of its outcomes, like [`Enumerator`].
+///
+/// [`PopulationSampler`]: crate::PopulationSampler
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DynPopulationSampler;
+
+impl DynPopulationSampler {
+    #[inline(always)]
+    fn shrink_to_capacity<T: Inner>(
+        outcomes: Vec<T>,
+        capacity: Option<usize>,
+        rng: &mut impl RngCore,
+    ) -> DynPopulation<T> {
+        let outcomes = match capacity {
+            Some(capacity) => shrink_to_capacity("DynPopulationSampler", outcomes, capacity, rng),
+            None => outcomes,
+        };
+        DynPopulation::new(outcomes, capacity)
+    }
+}
+
+impl RandomStrategy for DynPopulationSampler {
+    type Functor<I: Inner> = DynPopulation<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        DynPopulation::new(Enumerator::fmap(f.outcomes, func), f.capacity)
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::shrink_to_capacity(
+            Enumerator::fmap_rand(f.outcomes, rng, func),
+            f.capacity,
+            rng,
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::shrink_to_capacity(
+            Enumerator::fmap_rand_range(f.outcomes, range, rng, func),
+            f.capacity,
+            rng,
+        )
+    }
+}
+
+impl OrderedRandomStrategy for DynPopulationSampler {}
+
+impl WeightedRandomStrategy for DynPopulationSampler {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        Self::shrink_to_capacity(
+            Enumerator::fmap_rand_distribution(f.outcomes, distribution, rng, func),
+            f.capacity,
+            rng,
+        )
+    }
+}
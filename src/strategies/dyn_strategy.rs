@@ -0,0 +1,295 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    Counter, DefaultState, DynPopulation, DynPopulationSampler, EnumerableDistribution, Enumerator,
+    FlattenableRandomStrategy, Functor, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    Sampler, SmallRandomVariable, UniqueEnumerator, WeightedFunctor, WeightedRandomStrategy,
+};
+
+/// A strategy that delegates to one of the built-in strategies, selected at
+/// runtime.
+///
+/// A random process written against [`RandomStrategy`] is instantiated once,
+/// with `DynStrategy`, and evaluated by whichever strategy produced the
+/// [`DynFunctor`] it is given. The initial functor of a random process is
+/// created with [`DynStrategy::pure`], and every operation dispatches on the
+/// variant of its input, so the selected strategy is carried along with the
+/// outcomes.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{DynFunctor, DynStrategy, RandomStrategy};
+///
+/// fn random_process<S: RandomStrategy>(f: S::Functor<u8>, rng: &mut impl Rng) -> S::Functor<u8> {
+///     let f = S::fmap_rand(f, rng, |_, r: bool| r as u8);
+///     S::fmap_rand(f, rng, |s, r: bool| s + r as u8)
+/// }
+///
+/// let strategy = DynStrategy::Counter; // e.g. parsed from a configuration file
+/// let output = random_process::<DynStrategy>(strategy.pure(0), &mut thread_rng());
+/// assert_eq!(output, DynFunctor::Map([(0, 1), (1, 2), (2, 1)].into_iter().collect()));
+/// ```
+///
+/// [`Functor::pure`] produces a [`DynFunctor::Vec`], so functors created with
+/// it are evaluated by [`Enumerator`]. Within [`fmap_flat`], the functors
+/// produced by the function passed to it are converted to the functor of the
+/// outer strategy, so random processes which create them with
+/// [`Functor::pure`] are still evaluated exactly by [`DynStrategy::Counter`]
+/// and [`DynStrategy::UniqueEnumerator`].
+///
+/// Under [`DynStrategy::PopulationSampler`], [`fmap_flat`] does not discard
+/// any outcomes, as it is not given a random number generator. Any excess
+/// outcomes are discarded by the next operation which is.
+///
+/// # Panics
+///
+/// [`fmap_flat`] panics if the function passed to it produces functors under a
+/// different strategy than that of its input, other than
+/// [`DynStrategy::Enumerator`]. Under [`DynStrategy::Sampler`], it also
+/// panics if such a functor stores more than one outcome.
+///
+/// [`fmap_flat`]: FlattenableRandomStrategy::fmap_flat
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DynStrategy {
+    /// Delegates to [`Sampler`].
+    Sampler,
+    /// Delegates to [`Enumerator`].
+    Enumerator,
+    /// Delegates to [`UniqueEnumerator`].
+    UniqueEnumerator,
+    /// Delegates to [`Counter`].
+    Counter,
+    /// Delegates to [`DynPopulationSampler`], with the given capacity.
+    PopulationSampler(usize),
+}
+
+/// The functor of [`DynStrategy`], storing outcomes as the functor of the
+/// selected strategy would.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DynFunctor<I: Inner> {
+    /// A single outcome, as produced by [`Sampler`].
+    Single(I),
+    /// Outcomes with repetition, as produced by [`Enumerator`] and
+    /// [`Functor::pure`].
+    Vec(Vec<I>),
+    /// Outcomes with repetition and a capacity, as produced by
+    /// [`DynPopulationSampler`].
+    Population(DynPopulation<I>),
+    /// Distinct outcomes, as produced by [`UniqueEnumerator`].
+    Set(HashSet<I, DefaultState>),
+    /// Distinct outcomes and their counts, as produced by [`Counter`].
+    Map(HashMap<I, usize, DefaultState>),
+}
+
+impl DynStrategy {
+    /// Creates a functor containing only `i`, which is evaluated by this
+    /// strategy.
+    #[inline]
+    pub fn pure<I: Inner>(self, i: I) -> DynFunctor<I> {
+        match self {
+            DynStrategy::Sampler => DynFunctor::Single(i),
+            DynStrategy::Enumerator => DynFunctor::Vec(Functor::pure(i)),
+            DynStrategy::UniqueEnumerator => DynFunctor::Set(Functor::pure(i)),
+            DynStrategy::Counter => DynFunctor::Map(Functor::pure(i)),
+            DynStrategy::PopulationSampler(capacity) => {
+                DynFunctor::Population(DynPopulation::with_capacity(i, capacity))
+            }
+        }
+    }
+}
+
+impl<I: Inner> DynFunctor<I> {
+    #[inline(always)]
+    fn into_single(self) -> I {
+        match self {
+            DynFunctor::Single(i) => i,
+            DynFunctor::Vec(v) if v.len() == 1 => v.into_iter().next().unwrap(),
+            DynFunctor::Vec(_) => {
+                panic!("DynStrategy::Sampler cannot flatten a DynFunctor storing several outcomes")
+            }
+            _ => panic!("DynFunctor was produced under a different DynStrategy"),
+        }
+    }
+
+    #[inline(always)]
+    fn into_vec(self) -> Vec<I> {
+        match self {
+            DynFunctor::Vec(v) => v,
+            DynFunctor::Population(p) => p.into_inner(),
+            _ => panic!("DynFunctor was produced under a different DynStrategy"),
+        }
+    }
+
+    #[inline(always)]
+    fn into_set(self) -> HashSet<I, DefaultState> {
+        match self {
+            DynFunctor::Vec(v) => v.into_iter().collect(),
+            DynFunctor::Set(s) => s,
+            _ => panic!("DynFunctor was produced under a different DynStrategy"),
+        }
+    }
+
+    #[inline(always)]
+    fn into_map(self) -> HashMap<I, usize, DefaultState> {
+        match self {
+            DynFunctor::Vec(v) => v.into_iter().fold(HashMap::default(), |mut m, i| {
+                *m.entry(i).or_insert(0) += 1;
+                m
+            }),
+            DynFunctor::Map(m) => m,
+            _ => panic!("DynFunctor was produced under a different DynStrategy"),
+        }
+    }
+}
+
+/// Evaluates `$body` with the strategy that produced `$f` bound to the type
+/// `$s`, and the functor stored in `$f` bound to `$g`.
+macro_rules! dispatch {
+    ($f:expr, |$s:ident, $g:ident| $body:expr) => {
+        match $f {
+            DynFunctor::Single($g) => {
+                type $s = Sampler;
+                DynFunctor::Single($body)
+            }
+            DynFunctor::Vec($g) => {
+                type $s = Enumerator;
+                DynFunctor::Vec($body)
+            }
+            DynFunctor::Population($g) => {
+                type $s = DynPopulationSampler;
+                DynFunctor::Population($body)
+            }
+            DynFunctor::Set($g) => {
+                type $s = UniqueEnumerator;
+                DynFunctor::Set($body)
+            }
+            DynFunctor::Map($g) => {
+                type $s = Counter;
+                DynFunctor::Map($body)
+            }
+        }
+    };
+}
+
+impl<I: Inner> Functor<I> for DynFunctor<I> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        DynFunctor::Vec(Functor::pure(i))
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        match self {
+            DynFunctor::Single(_) => 1,
+            DynFunctor::Vec(v) => v.len(),
+            DynFunctor::Population(p) => p.len(),
+            DynFunctor::Set(s) => s.len(),
+            DynFunctor::Map(m) => m.len(),
+        }
+    }
+}
+
+impl<I: Inner> WeightedFunctor<I> for DynFunctor<I> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        let outcomes: Box<dyn Iterator<Item = (&'a I, usize)> + 'a> = match self {
+            DynFunctor::Single(i) => Box::new(core::iter::once((i, 1))),
+            DynFunctor::Vec(v) => Box::new(v.weighted_outcomes()),
+            DynFunctor::Population(p) => Box::new(p.weighted_outcomes()),
+            DynFunctor::Set(s) => Box::new(s.weighted_outcomes()),
+            DynFunctor::Map(m) => Box::new(m.weighted_outcomes()),
+        };
+        outcomes
+    }
+}
+
+impl RandomStrategy for DynStrategy {
+    type Functor<I: Inner> = DynFunctor<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        dispatch!(f, |S, f| S::fmap(f, func))
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        dispatch!(f, |S, f| S::fmap_rand(f, rng, func))
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        dispatch!(f, |S, f| S::fmap_rand_range(f, range, rng, func))
+    }
+}
+
+impl FlattenableRandomStrategy for DynStrategy {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        match f {
+            DynFunctor::Single(a) => {
+                DynFunctor::Single(Sampler::fmap_flat(a, |a| func(a).into_single()))
+            }
+            DynFunctor::Vec(v) => DynFunctor::Vec(Enumerator::fmap_flat(v, |a| func(a).into_vec())),
+            DynFunctor::Population(p) => {
+                let capacity = p.capacity();
+                let outcomes = Enumerator::fmap_flat(p.into_inner(), |a| func(a).into_vec());
+                DynFunctor::Population(DynPopulation::new(outcomes, capacity))
+            }
+            DynFunctor::Set(s) => {
+                DynFunctor::Set(<UniqueEnumerator>::fmap_flat(s, |a| func(a).into_set()))
+            }
+            DynFunctor::Map(m) => DynFunctor::Map(<Counter>::fmap_flat(m, |a| func(a).into_map())),
+        }
+    }
+}
+
+impl WeightedRandomStrategy for DynStrategy {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        dispatch!(f, |S, f| S::fmap_rand_distribution(
+            f,
+            distribution,
+            rng,
+            func
+        ))
+    }
+}
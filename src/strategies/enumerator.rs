@@ -3,9 +3,13 @@ use alloc::vec::Vec;
 use rand::distributions::uniform::SampleUniform;
 use rand::distributions::Standard;
 use rand::prelude::*;
+use rand_core::RngCore;
 
+use crate::patch::apply_patches;
 use crate::{
-    FlattenableRandomStrategy, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    telemetry, EnumerableDistribution, ExactRandomStrategy, FlattenableRandomStrategy, Inner,
+    OrderedRandomStrategy, Patch, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedRandomStrategy,
 };
 
 /// Produces all possible outputs of the random process, with repetition, as a
@@ -28,38 +32,87 @@ impl RandomStrategy for Enumerator {
 
     #[inline]
     fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
-        f.into_iter().map(func).collect()
+        telemetry::collect("Enumerator", f.into_iter().map(func))
     }
 
     #[inline]
-    fn fmap_rand<A: Inner, B: Inner, R: RandomVariable, F: Fn(A, R) -> B>(
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
-        _: &mut impl Rng,
+        _: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>,
     {
-        f.into_iter()
-            .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
-            .map(|(a, r)| func(a, r))
-            .collect()
+        telemetry::collect(
+            "Enumerator",
+            f.into_iter()
+                .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
+                .map(|(a, r)| func(a, r)),
+        )
     }
 
     #[inline]
     fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
         range: impl RandomVariableRange<R>,
-        _: &mut impl Rng,
+        _: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>,
     {
-        f.into_iter()
-            .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
-            .map(|(a, r)| func(a, r))
-            .collect()
+        telemetry::collect(
+            "Enumerator",
+            f.into_iter()
+                .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
+                .map(|(a, r)| func(a, r)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_delta<A: Inner, R: SmallRandomVariable, P: Patch<A>, F: Fn(&A, R) -> P>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        telemetry::collect(
+            "Enumerator",
+            f.into_iter().flat_map(|a| {
+                let patches = R::sample_space().map(|r| func(&a, r)).collect::<Vec<_>>();
+                apply_patches(a, patches)
+            }),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range_delta<
+        A: Inner,
+        R: RandomVariable + SampleUniform,
+        P: Patch<A>,
+        F: Fn(&A, R) -> P,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        telemetry::collect(
+            "Enumerator",
+            f.into_iter().flat_map(|a| {
+                let patches = range
+                    .sample_space()
+                    .map(|r| func(&a, r))
+                    .collect::<Vec<_>>();
+                apply_patches(a, patches)
+            }),
+        )
     }
 }
 
@@ -69,6 +122,37 @@ impl FlattenableRandomStrategy for Enumerator {
         f: Self::Functor<A>,
         func: F,
     ) -> Self::Functor<B> {
-        f.into_iter().flat_map(func).collect()
+        telemetry::collect("Enumerator", f.into_iter().flat_map(func))
     }
 }
+
+impl OrderedRandomStrategy for Enumerator {}
+
+impl WeightedRandomStrategy for Enumerator {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        telemetry::collect(
+            "Enumerator",
+            f.into_iter()
+                .flat_map(|a| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| (a.clone(), r, weight))
+                })
+                .flat_map(|(a, r, weight)| core::iter::repeat(func(a, r)).take(weight as usize)),
+        )
+    }
+}
+
+impl ExactRandomStrategy for Enumerator {}
@@ -0,0 +1,108 @@
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    EnumerableDistribution, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedRandomStrategy,
+};
+
+/// Produces a random subset (technically, submultiset) of possible outputs of
+/// the random process, stored in a fixed-capacity [`heapless::Vec`].
+///
+/// Unlike [`PopulationSampler`], which enumerates every outcome before
+/// discarding the excess, `HeaplessPopulationSampler` selects outcomes as they
+/// are produced using reservoir sampling. It never stores more than `N`
+/// outcomes at once and requires neither `std` nor `alloc`.
+///
+/// [`PopulationSampler`]: crate::PopulationSampler
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct HeaplessPopulationSampler<const N: usize>;
+
+impl<const N: usize> HeaplessPopulationSampler<N> {
+    #[inline(always)]
+    fn select<T>(outcomes: impl Iterator<Item = T>, rng: &mut impl RngCore) -> heapless::Vec<T, N> {
+        let mut reservoir = heapless::Vec::new();
+        for (seen, outcome) in outcomes.enumerate() {
+            if let Err(outcome) = reservoir.push(outcome) {
+                let index = rng.gen_range(0..=seen);
+                if index < N {
+                    reservoir[index] = outcome;
+                }
+            }
+        }
+        reservoir
+    }
+}
+
+impl<const N: usize> RandomStrategy for HeaplessPopulationSampler<N> {
+    type Functor<I: Inner> = heapless::Vec<I, N>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        f.into_iter().map(func).collect()
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::select(
+            f.into_iter()
+                .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
+                .map(|(a, r)| func(a, r)),
+            rng,
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::select(
+            f.into_iter()
+                .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
+                .map(|(a, r)| func(a, r)),
+            rng,
+        )
+    }
+}
+
+impl<const N: usize> WeightedRandomStrategy for HeaplessPopulationSampler<N> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        Self::select(
+            f.into_iter()
+                .flat_map(|a| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| (a.clone(), r, weight))
+                })
+                .flat_map(|(a, r, weight)| core::iter::repeat(func(a, r)).take(weight as usize)),
+            rng,
+        )
+    }
+}
@@ -1,10 +1,16 @@
 use alloc::vec::Vec;
+use core::future::Future;
 
 use rand::distributions::uniform::SampleUniform;
 use rand::distributions::Standard;
 use rand::prelude::*;
+use rand_core::RngCore;
 
-use crate::{Enumerator, Inner, RandomStrategy, RandomVariable, RandomVariableRange};
+use crate::{
+    telemetry, AsyncRandomStrategy, EnumerableDistribution, Enumerator, Inner,
+    OrderedRandomStrategy, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedRandomStrategy,
+};
 
 /// Produces a random subset (technically, submultiset) of possible outputs of
 /// the random process.
@@ -13,15 +19,37 @@ pub struct PopulationSampler<const N: usize>;
 
 impl<const N: usize> PopulationSampler<N> {
     #[inline(always)]
-    fn shrink_to_capacity<T: Inner>(mut f: Vec<T>, rng: &mut impl Rng) -> Vec<T> {
-        while f.len() > N {
-            let index = rng.gen_range(0..f.len());
-            f.swap_remove(index);
-        }
-        f
+    fn shrink_to_capacity<T: Inner>(f: Vec<T>, rng: &mut impl RngCore) -> Vec<T> {
+        shrink_to_capacity("PopulationSampler", f, N, rng)
     }
 }
 
+/// Discards randomly chosen outcomes from `f` until no more than `capacity`
+/// remain, preserving the relative order of those that remain.
+#[inline(always)]
+pub(crate) fn shrink_to_capacity<T: Inner>(
+    strategy: &'static str,
+    mut f: Vec<T>,
+    capacity: usize,
+    rng: &mut impl RngCore,
+) -> Vec<T> {
+    telemetry::record_shrink(strategy, f.len(), f.len().saturating_sub(capacity));
+    if f.len() > capacity {
+        // Selection sampling keeps each outcome with probability equal to the
+        // fraction of the remaining outcomes that are still needed, which
+        // selects a uniformly random subset in a single pass.
+        let mut needed = capacity;
+        let mut remaining = f.len();
+        f.retain(|_| {
+            let keep = rng.gen_range(0..remaining) < needed;
+            remaining -= 1;
+            needed -= keep as usize;
+            keep
+        });
+    }
+    f
+}
+
 impl<const N: usize> RandomStrategy for PopulationSampler<N> {
     type Functor<I: Inner> = Vec<I>;
 
@@ -31,9 +59,9 @@ impl<const N: usize> RandomStrategy for PopulationSampler<N> {
     }
 
     #[inline]
-    fn fmap_rand<A: Inner, B: Inner, R: RandomVariable, F: Fn(A, R) -> B>(
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
-        rng: &mut impl Rng,
+        rng: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
@@ -46,7 +74,7 @@ impl<const N: usize> RandomStrategy for PopulationSampler<N> {
     fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
         range: impl RandomVariableRange<R>,
-        rng: &mut impl Rng,
+        rng: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
@@ -55,3 +83,92 @@ impl<const N: usize> RandomStrategy for PopulationSampler<N> {
         Self::shrink_to_capacity(Enumerator::fmap_rand_range(f, range, rng, func), rng)
     }
 }
+
+impl<const N: usize> AsyncRandomStrategy for PopulationSampler<N> {
+    #[inline]
+    async fn fmap_async<A: Inner, B: Inner, Fut: Future<Output = B>, F: Fn(A) -> Fut>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B> {
+        let mut outcomes = Vec::with_capacity(f.len());
+        for a in f {
+            outcomes.push(func(a).await);
+        }
+        telemetry::record_outcomes("PopulationSampler", outcomes.len(), outcomes.len());
+        outcomes
+    }
+
+    #[inline]
+    async fn fmap_rand_async<
+        A: Inner,
+        B: Inner,
+        R: SmallRandomVariable,
+        Fut: Future<Output = B>,
+        F: Fn(A, R) -> Fut,
+    >(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let mut outcomes = Vec::with_capacity(f.len() * R::sample_space().count());
+        for a in f {
+            for r in R::sample_space() {
+                outcomes.push(func(a.clone(), r).await);
+            }
+        }
+        telemetry::record_outcomes("PopulationSampler", outcomes.len(), outcomes.len());
+        Self::shrink_to_capacity(outcomes, rng)
+    }
+
+    #[inline]
+    async fn fmap_rand_range_async<
+        A: Inner,
+        B: Inner,
+        R: RandomVariable + SampleUniform,
+        Fut: Future<Output = B>,
+        F: Fn(A, R) -> Fut,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let mut outcomes = Vec::with_capacity(f.len() * range.sample_space().count());
+        for a in f {
+            for r in range.sample_space() {
+                outcomes.push(func(a.clone(), r).await);
+            }
+        }
+        telemetry::record_outcomes("PopulationSampler", outcomes.len(), outcomes.len());
+        Self::shrink_to_capacity(outcomes, rng)
+    }
+}
+
+impl<const N: usize> OrderedRandomStrategy for PopulationSampler<N> {}
+
+impl<const N: usize> WeightedRandomStrategy for PopulationSampler<N> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        Self::shrink_to_capacity(
+            Enumerator::fmap_rand_distribution(f, distribution, rng, func),
+            rng,
+        )
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+
+use num_rational::Ratio;
+use num_traits::Zero;
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    telemetry, DefaultState, EnumerableDistribution, ExactRandomStrategy,
+    FlattenableRandomStrategy, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedRandomStrategy,
+};
+
+/// Produces all possible outputs of the random process, without repetition,
+/// stored in a [`HashMap`] alongside their exact probabilities.
+///
+/// `RationalCounter` merges outcomes like [`Counter`], but stores a normalized
+/// [`Ratio`] for each outcome rather than an integer count. Sampling from a
+/// random variable divides probabilities by the size of its sample space
+/// instead of multiplying every other count by it, and
+/// [`FlattenableRandomStrategy::fmap_flat`] normalizes the functor produced
+/// for each outcome before weighting it. Combinators such as
+/// [`FlattenableRandomStrategy::if_then_else`] therefore do not inflate the
+/// result when mixing sub-processes of different sizes, which can quickly
+/// overflow the counts of [`Counter`]. The probabilities stored in a functor
+/// always sum to one.
+///
+/// ```
+/// use num_rational::Ratio;
+/// use rand::prelude::*;
+/// use rand_functors::{FlattenableRandomStrategy, Functor, RandomStrategy, RationalCounter};
+///
+/// let mut rng = thread_rng();
+/// let f = <RationalCounter>::fmap_rand(Functor::pure(()), &mut rng, |_, r: bool| r);
+/// // Roll a d11 for a critical hit, or a d13 otherwise.
+/// let output = <RationalCounter>::if_then_else(
+///     f,
+///     |&critical| critical,
+///     &mut rng,
+///     |_, rng| RationalCounter::fmap_rand_range(Functor::pure(()), 1..=11u8, rng, |_, r| r),
+///     |_, rng| RationalCounter::fmap_rand_range(Functor::pure(()), 1..=13u8, rng, |_, r| r),
+/// );
+/// assert_eq!(output[&1], Ratio::new(1, 22) + Ratio::new(1, 26));
+/// assert_eq!(output[&13], Ratio::new(1, 26));
+/// ```
+///
+/// # Panics
+///
+/// Operations panic if the denominator of a probability overflows a [`u128`].
+///
+/// [`Counter`]: crate::Counter
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RationalCounter<S: BuildHasher + Default = DefaultState> {
+    phantom: PhantomData<S>,
+}
+
+impl<S: BuildHasher + Default> RationalCounter<S> {
+    /// Collects outcomes into a new functor, summing the probabilities of
+    /// outcomes that occur more than once.
+    #[inline(always)]
+    fn merge_probabilities<I: Inner>(
+        capacity: usize,
+        outcomes: impl Iterator<Item = (I, Ratio<u128>)>,
+    ) -> HashMap<I, Ratio<u128>, S> {
+        let mut new_functor = HashMap::with_capacity_and_hasher(capacity, Default::default());
+        let mut produced = 0;
+        outcomes.for_each(|(o, p)| {
+            produced += 1;
+            *new_functor.entry(o).or_insert(Ratio::zero()) += p;
+        });
+        telemetry::record_outcomes("RationalCounter", produced, new_functor.len());
+        new_functor
+    }
+
+    /// Expands the outcomes of `f` not satisfying `absorbing` over the sample
+    /// space produced by `sample_space`, and carries the others forward with
+    /// their probabilities unchanged.
+    #[inline(always)]
+    fn absorb<A: Inner, R, I: Iterator<Item = R>>(
+        f: HashMap<A, Ratio<u128>, S>,
+        absorbing: impl Fn(&A) -> bool,
+        sample_space: impl Fn() -> I,
+        func: impl Fn(A, R) -> A,
+    ) -> HashMap<A, Ratio<u128>, S> {
+        let n = sample_space().count() as u128;
+        let capacity = f.len();
+        let (absorbed, expanded): (Vec<_>, Vec<_>) = f.into_iter().partition(|(a, _)| absorbing(a));
+        Self::merge_probabilities(
+            capacity,
+            absorbed.into_iter().chain(
+                expanded
+                    .into_iter()
+                    .flat_map(|a| sample_space().map(move |r| (a.clone(), r)))
+                    .map(|((a, p), r)| (func(a, r), p / n)),
+            ),
+        )
+    }
+}
+
+impl<S: BuildHasher + Default> RandomStrategy for RationalCounter<S> {
+    type Functor<I: Inner> = HashMap<I, Ratio<u128>, S>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        let capacity = f.len();
+        Self::merge_probabilities(capacity, f.into_iter().map(|(i, p)| (func(i), p)))
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let n = R::sample_space().count() as u128;
+        let capacity = f.len();
+        Self::merge_probabilities(
+            capacity,
+            f.into_iter()
+                .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
+                .map(|((a, p), r)| (func(a, r), p / n)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let n = range.sample_space().count() as u128;
+        let capacity = f.len();
+        Self::merge_probabilities(
+            capacity,
+            f.into_iter()
+                .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
+                .map(|((a, p), r)| (func(a, r), p / n)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_absorbing<A: Inner, R: SmallRandomVariable, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::absorb(f, absorbing, R::sample_space, func)
+    }
+
+    #[inline]
+    fn fmap_rand_range_absorbing<A: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::absorb(f, absorbing, || range.sample_space(), func)
+    }
+}
+
+impl<S: BuildHasher + Default> FlattenableRandomStrategy for RationalCounter<S> {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        let capacity = f.len();
+        let children = f
+            .into_iter()
+            .map(|(i, p)| {
+                let child = func(i);
+                let total = child.values().sum::<Ratio<u128>>();
+                let scale = if total.is_zero() { total } else { p / total };
+                (child, scale)
+            })
+            .collect::<Vec<_>>();
+        Self::merge_probabilities(
+            capacity,
+            children.into_iter().flat_map(|(child, scale)| {
+                child
+                    .into_iter()
+                    .map(move |(output, q)| (output, q * scale))
+            }),
+        )
+    }
+}
+
+impl<S: BuildHasher + Default> WeightedRandomStrategy for RationalCounter<S> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let total = distribution
+            .weighted_sample_space()
+            .map(|(_, weight)| weight as u128)
+            .sum::<u128>();
+        let capacity = f.len();
+        Self::merge_probabilities(
+            capacity,
+            f.into_iter()
+                .flat_map(|a| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| (a.clone(), r, weight))
+                })
+                .map(|((a, p), r, weight)| (func(a, r), p * Ratio::new(weight as u128, total))),
+        )
+    }
+}
+
+impl<S: BuildHasher + Default> ExactRandomStrategy for RationalCounter<S> {}
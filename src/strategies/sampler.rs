@@ -1,9 +1,14 @@
+use core::future::Future;
+
 use rand::distributions::uniform::SampleUniform;
 use rand::distributions::Standard;
 use rand::prelude::*;
+use rand_core::RngCore;
 
 use crate::{
-    FlattenableRandomStrategy, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    AsyncRandomStrategy, EnumerableDistribution, FlattenableRandomStrategy, Inner, RandomStrategy,
+    RandomVariable, RandomVariableRange, SingleShotRandomStrategy, SmallRandomVariable,
+    WeightedRandomStrategy,
 };
 
 /// Samples the desired distributions and produces a single possible output of
@@ -16,32 +21,32 @@ impl RandomStrategy for Sampler {
 
     #[inline]
     fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
-        func(f)
+        Self::fmap_once(f, func)
     }
 
     #[inline]
-    fn fmap_rand<A: Inner, B: Inner, R: RandomVariable, F: FnOnce(A, R) -> B>(
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
-        rng: &mut impl Rng,
+        rng: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>,
     {
-        func(f, rng.gen())
+        Self::fmap_rand_once(f, rng, func)
     }
 
     #[inline]
     fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
         range: impl RandomVariableRange<R>,
-        rng: &mut impl Rng,
+        rng: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>,
     {
-        func(f, rng.gen_range(range))
+        Self::fmap_rand_range_once(f, range, rng, func)
     }
 }
 
@@ -49,8 +54,139 @@ impl FlattenableRandomStrategy for Sampler {
     #[inline]
     fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
         f: Self::Functor<A>,
-        mut func: F,
+        func: F,
+    ) -> Self::Functor<B> {
+        Self::fmap_flat_once(f, func)
+    }
+}
+
+impl WeightedRandomStrategy for Sampler {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        Self::fmap_rand_distribution_once(f, distribution, rng, func)
+    }
+}
+
+impl SingleShotRandomStrategy for Sampler {
+    #[inline]
+    fn fmap_once<A: Inner, B: Inner, F: FnOnce(A) -> B>(
+        f: Self::Functor<A>,
+        func: F,
     ) -> Self::Functor<B> {
         func(f)
     }
+
+    #[inline]
+    fn fmap_rand_once<A: Inner, B: Inner, R: SmallRandomVariable, F: FnOnce(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        func(f, rng.gen())
+    }
+
+    #[inline]
+    fn fmap_rand_range_once<
+        A: Inner,
+        B: Inner,
+        R: RandomVariable + SampleUniform,
+        F: FnOnce(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        assert!(!range.is_empty(), "cannot sample from an empty range");
+        func(f, range.sample_single(rng))
+    }
+
+    #[inline]
+    fn fmap_rand_distribution_once<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: FnOnce(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        func(f, distribution.sample(rng))
+    }
+
+    #[inline]
+    fn fmap_flat_once<A: Inner, B: Inner, F: FnOnce(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B> {
+        func(f)
+    }
+}
+
+impl AsyncRandomStrategy for Sampler {
+    #[inline]
+    async fn fmap_async<A: Inner, B: Inner, Fut: Future<Output = B>, F: Fn(A) -> Fut>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B> {
+        func(f).await
+    }
+
+    #[inline]
+    async fn fmap_rand_async<
+        A: Inner,
+        B: Inner,
+        R: SmallRandomVariable,
+        Fut: Future<Output = B>,
+        F: Fn(A, R) -> Fut,
+    >(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        func(f, rng.gen()).await
+    }
+
+    #[inline]
+    async fn fmap_rand_range_async<
+        A: Inner,
+        B: Inner,
+        R: RandomVariable + SampleUniform,
+        Fut: Future<Output = B>,
+        F: Fn(A, R) -> Fut,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        assert!(!range.is_empty(), "cannot sample from an empty range");
+        func(f, range.sample_single(rng)).await
+    }
 }
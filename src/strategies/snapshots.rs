@@ -0,0 +1,259 @@
+use core::any::type_name;
+use core::marker::PhantomData;
+use std::ops::Deref;
+use std::vec::Vec;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    current_step, EnumerableDistribution, ExactRandomStrategy, FlattenableRandomStrategy, Functor,
+    Inner, OrderedRandomStrategy, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedFunctor, WeightedRandomStrategy,
+};
+
+/// A summary of the functor produced by one operation performed by
+/// [`Snapshots`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Snapshot {
+    /// The name of the operation, such as `fmap_rand`.
+    pub operation: &'static str,
+    /// The label of the [`step`](crate::step) that was running, if there was
+    /// one.
+    pub step: Option<&'static str>,
+    /// The type of the random variable sampled by the operation, if there was
+    /// one.
+    pub random_variable: Option<&'static str>,
+    /// The type of the outcomes produced by the operation.
+    pub outcome_type: &'static str,
+    /// The [`size`](Functor::size) of the functor passed to the operation.
+    pub input_size: usize,
+    /// The [`size`](Functor::size) of the functor produced by the operation.
+    pub output_size: usize,
+}
+
+/// The functor of [`Snapshots`], the functor of the wrapped strategy along
+/// with the snapshots of the operations which produced it.
+///
+/// It dereferences to the wrapped functor, which can be recovered with
+/// [`Snapshotted::into_inner`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshotted<F> {
+    functor: F,
+    snapshots: Vec<Snapshot>,
+}
+
+impl<F> Snapshotted<F> {
+    /// Returns the snapshots of the operations which produced this functor,
+    /// in the order in which they completed.
+    #[inline]
+    pub fn snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
+    }
+
+    /// Returns the wrapped functor.
+    #[inline]
+    pub fn into_inner(self) -> F {
+        self.functor
+    }
+
+    /// Returns the wrapped functor along with the snapshots of the operations
+    /// which produced it.
+    #[inline]
+    pub fn into_parts(self) -> (F, Vec<Snapshot>) {
+        (self.functor, self.snapshots)
+    }
+}
+
+impl<F> Deref for Snapshotted<F> {
+    type Target = F;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.functor
+    }
+}
+
+impl<I: Inner, F: Functor<I>> Functor<I> for Snapshotted<F> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        Snapshotted {
+            functor: F::pure(i),
+            snapshots: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.functor.size()
+    }
+}
+
+impl<I: Inner, F: WeightedFunctor<I>> WeightedFunctor<I> for Snapshotted<F> {
+    type Weight = F::Weight;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, F::Weight)>
+    where
+        I: 'a,
+    {
+        self.functor.weighted_outcomes()
+    }
+}
+
+/// Wraps another [`RandomStrategy`] and records a [`Snapshot`] of the functor
+/// produced by each operation it performs.
+///
+/// The snapshots are stored in its functor, a [`Snapshotted`], in the order in
+/// which the operations completed, so the growth of a distribution can be
+/// inspected step by step without changing the code of the random process.
+/// Operations performed inside the function passed to
+/// [`fmap_flat`](FlattenableRandomStrategy::fmap_flat) are recorded before the
+/// `fmap_flat` itself.
+///
+/// As the intermediate functors of a random process store outcomes of
+/// different types, snapshots summarize them rather than storing them.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{step, Counter, Functor, RandomStrategy, Snapshots};
+///
+/// fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+///     let f = step("roll", || S::fmap_rand_range(Functor::pure(()), 1..=6u8, rng, |_, r| r));
+///     step("halve", || S::fmap(f, |s| s / 2))
+/// }
+///
+/// let output = random_process::<Snapshots<Counter>>(&mut thread_rng());
+/// let sizes: Vec<_> = output.snapshots().iter().map(|s| (s.step, s.output_size)).collect();
+/// assert_eq!(sizes, [(Some("roll"), 6), (Some("halve"), 4)]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Snapshots<S: RandomStrategy> {
+    strategy_phantom: PhantomData<S>,
+}
+
+impl<S: RandomStrategy> Snapshots<S> {
+    /// Wraps `output`, recording a snapshot of it after `snapshots`.
+    #[inline(always)]
+    fn record<B: Inner, G: Functor<B>>(
+        operation: &'static str,
+        random_variable: Option<&'static str>,
+        input_size: usize,
+        mut snapshots: Vec<Snapshot>,
+        output: G,
+    ) -> Snapshotted<G> {
+        snapshots.push(Snapshot {
+            operation,
+            step: current_step(),
+            random_variable,
+            outcome_type: type_name::<B>(),
+            input_size,
+            output_size: output.size(),
+        });
+        Snapshotted {
+            functor: output,
+            snapshots,
+        }
+    }
+}
+
+impl<S: RandomStrategy> RandomStrategy for Snapshots<S> {
+    type Functor<I: Inner> = Snapshotted<S::Functor<I>>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        let input_size = f.size();
+        let output = S::fmap(f.functor, func);
+        Self::record::<B, _>("fmap", None, input_size, f.snapshots, output)
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let input_size = f.size();
+        let output = S::fmap_rand(f.functor, rng, func);
+        Self::record::<B, _>(
+            "fmap_rand",
+            Some(type_name::<R>()),
+            input_size,
+            f.snapshots,
+            output,
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let input_size = f.size();
+        let output = S::fmap_rand_range(f.functor, range, rng, func);
+        Self::record::<B, _>(
+            "fmap_rand_range",
+            Some(type_name::<R>()),
+            input_size,
+            f.snapshots,
+            output,
+        )
+    }
+}
+
+impl<S: FlattenableRandomStrategy> FlattenableRandomStrategy for Snapshots<S> {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        let input_size = f.size();
+        let mut snapshots = f.snapshots;
+        let output = S::fmap_flat(f.functor, |a| {
+            let (child, child_snapshots) = func(a).into_parts();
+            snapshots.extend(child_snapshots);
+            child
+        });
+        Self::record::<B, _>("fmap_flat", None, input_size, snapshots, output)
+    }
+}
+
+impl<S: OrderedRandomStrategy> OrderedRandomStrategy for Snapshots<S> {}
+
+impl<S: WeightedRandomStrategy> WeightedRandomStrategy for Snapshots<S> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let input_size = f.size();
+        let output = S::fmap_rand_distribution(f.functor, distribution, rng, func);
+        Self::record::<B, _>(
+            "fmap_rand_distribution",
+            Some(type_name::<R>()),
+            input_size,
+            f.snapshots,
+            output,
+        )
+    }
+}
+
+impl<S: ExactRandomStrategy> ExactRandomStrategy for Snapshots<S> {}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    telemetry, Counter, DefaultState, EnumerableDistribution, FlattenableRandomStrategy, Inner,
+    RandomStrategy, RandomVariable, RandomVariableRange, SmallRandomVariable,
+    WeightedRandomStrategy,
+};
+
+/// Counts the possible outputs of the random process like [`Counter`], and
+/// can bound them to at most `K` representatives per stratum.
+///
+/// Strategy operations are performed exactly as [`Counter`] performs them.
+/// Between steps of a random process, [`StratifiedCounter::stratify`] groups
+/// the outcomes by a key into strata and keeps at most `K` randomly chosen
+/// representatives from each. Since only the number of representatives per
+/// stratum is bounded, every stratum that was reachable remains represented,
+/// so rare classes of behaviour are not lost the way they can be when
+/// subsampling all outcomes together. The total count of each stratum is
+/// preserved and shared among its representatives, so the probability of
+/// every stratum stays exact.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Functor, RandomStrategy, StratifiedCounter};
+///
+/// let mut rng = thread_rng();
+/// let f = StratifiedCounter::<2>::fmap_rand(Functor::pure(0), &mut rng, |_, r: u8| r);
+/// let f = StratifiedCounter::<2>::stratify(f, |&s| s == 255, &mut rng);
+/// assert_eq!(f.len(), 3);
+/// assert_eq!(f[&255], 1);
+/// assert_eq!(f.values().sum::<usize>(), 256);
+/// ```
+///
+/// Using a `K` of zero causes a compile error.
+///
+/// [`Counter`]: crate::Counter
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct StratifiedCounter<const K: usize>;
+
+impl<const K: usize> StratifiedCounter<K> {
+    const CHECKED_K: usize = {
+        assert!(K != 0, "each stratum must keep at least one representative");
+        K
+    };
+
+    /// Keeps at most `K` distinct outcomes of `f` for each value of `key`.
+    ///
+    /// Representatives are chosen at random without replacement, with
+    /// probability proportional to their counts. The total count of each
+    /// stratum is then divided among its representatives in proportion to
+    /// their counts, so strata are weighted exactly as before.
+    pub fn stratify<I: Inner, Q: Eq + Hash>(
+        f: HashMap<I, usize, DefaultState>,
+        key: impl Fn(&I) -> Q,
+        rng: &mut impl RngCore,
+    ) -> HashMap<I, usize, DefaultState> {
+        let k = Self::CHECKED_K;
+        let size = f.len();
+        let mut strata: HashMap<Q, Vec<(I, usize)>, DefaultState> = HashMap::default();
+        for (outcome, count) in f {
+            strata
+                .entry(key(&outcome))
+                .or_default()
+                .push((outcome, count));
+        }
+
+        let mut stratified = HashMap::with_capacity_and_hasher(
+            size.min(strata.len().saturating_mul(k)),
+            DefaultState::default(),
+        );
+        for (_, mut outcomes) in strata {
+            if outcomes.len() > k {
+                Self::select(&mut outcomes, rng);
+            }
+            stratified.extend(outcomes);
+        }
+        telemetry::record_shrink("StratifiedCounter", size, size - stratified.len());
+        stratified
+    }
+
+    /// Reduces `outcomes` to `K` representatives chosen with probability
+    /// proportional to their counts, sharing the total count among them.
+    fn select<I>(outcomes: &mut Vec<(I, usize)>, rng: &mut impl RngCore) {
+        let total: u128 = outcomes.iter().map(|&(_, count)| count as u128).sum();
+
+        // Efraimidis and Spirakis: keeping the outcomes with the largest
+        // values of u^(1 / count) is a weighted sample without replacement.
+        let mut keyed: Vec<_> = outcomes
+            .drain(..)
+            .map(|(outcome, count)| {
+                let u: f64 = rng.gen();
+                (u.powf(1.0 / count as f64), outcome, count)
+            })
+            .collect();
+        keyed.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+        keyed.truncate(Self::CHECKED_K);
+
+        // Largest remainder apportionment of the total among the survivors.
+        let kept: u128 = keyed.iter().map(|&(_, _, count)| count as u128).sum();
+        let mut shares: Vec<_> = keyed
+            .into_iter()
+            .map(|(_, outcome, count)| {
+                let scaled = count as u128 * total;
+                (outcome, scaled / kept, scaled % kept)
+            })
+            .collect();
+        let assigned: u128 = shares.iter().map(|&(_, share, _)| share).sum();
+        shares.sort_by_key(|&(_, _, remainder)| core::cmp::Reverse(remainder));
+        for (i, (outcome, share, _)) in shares.into_iter().enumerate() {
+            let extra = ((i as u128) < total - assigned) as u128;
+            outcomes.push((outcome, (share + extra) as usize));
+        }
+    }
+}
+
+impl<const K: usize> RandomStrategy for StratifiedCounter<K> {
+    type Functor<I: Inner> = HashMap<I, usize, DefaultState>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        <Counter>::fmap(f, func)
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        <Counter>::fmap_rand(f, rng, func)
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        <Counter>::fmap_rand_range(f, range, rng, func)
+    }
+
+    #[inline]
+    fn fmap_rand_absorbing<A: Inner, R: SmallRandomVariable, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        <Counter>::fmap_rand_absorbing(f, absorbing, rng, func)
+    }
+
+    #[inline]
+    fn fmap_rand_range_absorbing<A: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        <Counter>::fmap_rand_range_absorbing(f, absorbing, range, rng, func)
+    }
+}
+
+impl<const K: usize> FlattenableRandomStrategy for StratifiedCounter<K> {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B> {
+        <Counter>::fmap_flat(f, func)
+    }
+}
+
+impl<const K: usize> WeightedRandomStrategy for StratifiedCounter<K> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        <Counter>::fmap_rand_distribution(f, distribution, rng, func)
+    }
+}
@@ -0,0 +1,252 @@
+use alloc::vec::{IntoIter, Vec};
+use core::ops::Deref;
+
+use num_rational::Ratio;
+use num_traits::{One, Zero};
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    telemetry, EnumerableDistribution, ExactRandomStrategy, FlattenableRandomStrategy,
+    FromWeightedIterator, Functor, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedFunctor, WeightedRandomStrategy,
+};
+
+/// The functor of [`TaggedEnumerator`], a [`Vec`] of outcomes tagged with
+/// their probabilities.
+///
+/// This is a newtype, rather than a bare `Vec<(I, Ratio<u128>)>`, so that it
+/// does not make the [`Functor`] implementation for [`Vec`] ambiguous. It
+/// dereferences to a slice of its elements, and the [`Vec`] can be recovered
+/// with [`Tagged::into_inner`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Tagged<I>(pub Vec<(I, Ratio<u128>)>);
+
+impl<I> Default for Tagged<I> {
+    #[inline]
+    fn default() -> Self {
+        Tagged(Vec::new())
+    }
+}
+
+impl<I> Tagged<I> {
+    /// Returns the tagged outcomes.
+    #[inline]
+    pub fn into_inner(self) -> Vec<(I, Ratio<u128>)> {
+        self.0
+    }
+}
+
+impl<I> Deref for Tagged<I> {
+    type Target = [(I, Ratio<u128>)];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<I> IntoIterator for Tagged<I> {
+    type Item = (I, Ratio<u128>);
+    type IntoIter = IntoIter<(I, Ratio<u128>)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<I> From<Tagged<I>> for Vec<(I, Ratio<u128>)> {
+    #[inline]
+    fn from(tagged: Tagged<I>) -> Self {
+        tagged.0
+    }
+}
+
+impl<I: Inner> Functor<I> for Tagged<I> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        Tagged(alloc::vec![(i, Ratio::one())])
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<I: Inner> WeightedFunctor<I> for Tagged<I> {
+    type Weight = Ratio<u128>;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, Ratio<u128>)>
+    where
+        I: 'a,
+    {
+        self.0.iter().map(|(i, p)| (i, *p))
+    }
+}
+
+impl<I> FromIterator<(I, Ratio<u128>)> for Tagged<I> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (I, Ratio<u128>)>>(iter: T) -> Self {
+        Tagged(iter.into_iter().collect())
+    }
+}
+
+impl<I> Extend<(I, Ratio<u128>)> for Tagged<I> {
+    #[inline]
+    fn extend<T: IntoIterator<Item = (I, Ratio<u128>)>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+impl<I: Inner> FromWeightedIterator<I> for Tagged<I> {
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, Ratio<u128>)>>(&mut self, outcomes: T) {
+        self.0
+            .extend(outcomes.into_iter().filter(|(_, p)| !p.is_zero()));
+    }
+}
+
+/// Produces all possible outputs of the random process, with repetition, as a
+/// [`Tagged`] [`Vec`] in which each outcome is tagged with its exact
+/// probability.
+///
+/// Unlike [`Enumerator`], which relies on the number of times an outcome is
+/// repeated to represent its probability, `TaggedEnumerator` propagates a
+/// [`Ratio`] alongside each outcome. Sub-processes passed to
+/// [`FlattenableRandomStrategy::fmap_flat`] may therefore produce different
+/// numbers of outcomes without skewing the result, and outcomes of an
+/// [`EnumerableDistribution`] are stored once rather than once per unit of
+/// weight. The probabilities stored in a functor always sum to one.
+///
+/// ```
+/// use num_rational::Ratio;
+/// use rand::prelude::*;
+/// use rand_functors::{FlattenableRandomStrategy, Functor, RandomStrategy, TaggedEnumerator};
+///
+/// let f = TaggedEnumerator::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, b: bool| b);
+/// let f = TaggedEnumerator::fmap_flat(f, |b| {
+///     if b {
+///         TaggedEnumerator::fmap_rand_range(Functor::pure(()), 0..3u8, &mut thread_rng(), |_, r| r)
+///     } else {
+///         Functor::pure(3)
+///     }
+/// });
+/// assert_eq!(
+///     f.into_inner(),
+///     [
+///         (3, Ratio::new(1, 2)),
+///         (0, Ratio::new(1, 6)),
+///         (1, Ratio::new(1, 6)),
+///         (2, Ratio::new(1, 6)),
+///     ]
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Operations panic if the denominator of a probability overflows a [`u128`].
+///
+/// [`Enumerator`]: crate::Enumerator
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TaggedEnumerator;
+
+impl RandomStrategy for TaggedEnumerator {
+    type Functor<I: Inner> = Tagged<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        Tagged(telemetry::collect(
+            "TaggedEnumerator",
+            f.into_iter().map(|(a, p)| (func(a), p)),
+        ))
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let n = R::sample_space().count() as u128;
+        Tagged(telemetry::collect(
+            "TaggedEnumerator",
+            f.into_iter()
+                .flat_map(|(a, p)| R::sample_space().map(move |r| (a.clone(), r, p)))
+                .map(|(a, r, p)| (func(a, r), p / n)),
+        ))
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let n = range.sample_space().count() as u128;
+        Tagged(telemetry::collect(
+            "TaggedEnumerator",
+            f.into_iter()
+                .flat_map(|(a, p)| range.sample_space().map(move |r| (a.clone(), r, p)))
+                .map(|(a, r, p)| (func(a, r), p / n)),
+        ))
+    }
+}
+
+impl FlattenableRandomStrategy for TaggedEnumerator {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        Tagged(telemetry::collect(
+            "TaggedEnumerator",
+            f.into_iter()
+                .flat_map(|(a, p)| func(a).into_iter().map(move |(b, q)| (b, p * q))),
+        ))
+    }
+}
+
+impl WeightedRandomStrategy for TaggedEnumerator {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let total = distribution
+            .weighted_sample_space()
+            .map(|(_, weight)| weight as u128)
+            .sum::<u128>();
+        Tagged(telemetry::collect(
+            "TaggedEnumerator",
+            f.into_iter()
+                .flat_map(|(a, p)| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| (a.clone(), r, p, weight))
+                })
+                .map(|(a, r, p, weight)| (func(a, r), p * Ratio::new(weight as u128, total))),
+        ))
+    }
+}
+
+impl ExactRandomStrategy for TaggedEnumerator {}
@@ -0,0 +1,266 @@
+use alloc::vec::{IntoIter, Vec};
+use core::ops::Deref;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    telemetry, EnumerableDistribution, ExactRandomStrategy, FlattenableRandomStrategy,
+    FromWeightedIterator, Functor, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedFunctor, WeightedRandomStrategy,
+};
+
+/// The functor of [`TraceEnumerator`], a [`Vec`] of outcomes, each paired with
+/// the trace of random values that produced it.
+///
+/// A trace records the index of each random value within the sample space it
+/// was drawn from, in the order the values were drawn. This is an index into
+/// [`RandomVariable::sample_space`] for [`RandomStrategy::fmap_rand`], into
+/// [`RandomVariableRange::sample_space`] for
+/// [`RandomStrategy::fmap_rand_range`], and into
+/// [`EnumerableDistribution::weighted_sample_space`] for
+/// [`WeightedRandomStrategy::fmap_rand_distribution`].
+///
+/// Like [`Tagged`](crate::Tagged), this is a newtype so that it does not make
+/// the [`Functor`] implementation for [`Vec`] ambiguous.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Witnessed<I>(pub Vec<(I, Vec<usize>)>);
+
+impl<I> Default for Witnessed<I> {
+    #[inline]
+    fn default() -> Self {
+        Witnessed(Vec::new())
+    }
+}
+
+impl<I> Witnessed<I> {
+    /// Returns the trace of the first outcome satisfying `pred`, if there is
+    /// one.
+    #[inline]
+    pub fn witness(&self, mut pred: impl FnMut(&I) -> bool) -> Option<&[usize]> {
+        self.0
+            .iter()
+            .find(|(outcome, _)| pred(outcome))
+            .map(|(_, trace)| trace.as_slice())
+    }
+
+    /// Returns the outcomes and their traces.
+    #[inline]
+    pub fn into_inner(self) -> Vec<(I, Vec<usize>)> {
+        self.0
+    }
+}
+
+impl<I> Deref for Witnessed<I> {
+    type Target = [(I, Vec<usize>)];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<I> IntoIterator for Witnessed<I> {
+    type Item = (I, Vec<usize>);
+    type IntoIter = IntoIter<(I, Vec<usize>)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<I> From<Witnessed<I>> for Vec<(I, Vec<usize>)> {
+    #[inline]
+    fn from(witnessed: Witnessed<I>) -> Self {
+        witnessed.0
+    }
+}
+
+impl<I: Inner> Functor<I> for Witnessed<I> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        Witnessed(alloc::vec![(i, Vec::new())])
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<I: Inner> WeightedFunctor<I> for Witnessed<I> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        self.0.iter().map(|(i, _)| (i, 1))
+    }
+}
+
+impl<I> FromIterator<(I, Vec<usize>)> for Witnessed<I> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (I, Vec<usize>)>>(iter: T) -> Self {
+        Witnessed(iter.into_iter().collect())
+    }
+}
+
+impl<I> Extend<(I, Vec<usize>)> for Witnessed<I> {
+    #[inline]
+    fn extend<T: IntoIterator<Item = (I, Vec<usize>)>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+impl<I: Inner> FromWeightedIterator<I> for Witnessed<I> {
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, usize)>>(&mut self, outcomes: T) {
+        for (i, weight) in outcomes {
+            self.0
+                .extend(core::iter::repeat((i, Vec::new())).take(weight));
+        }
+    }
+}
+
+/// Appends `index` to a copy of `trace`.
+#[inline(always)]
+pub(crate) fn extend(trace: &[usize], index: usize) -> Vec<usize> {
+    let mut extended = Vec::with_capacity(trace.len() + 1);
+    extended.extend_from_slice(trace);
+    extended.push(index);
+    extended
+}
+
+/// Produces all possible outputs of the random process, with repetition, each
+/// paired with the trace of random values that produced it.
+///
+/// `TraceEnumerator` stores the same outcomes, in the same order, as
+/// [`Enumerator`]. Alongside each outcome, it records the index of every
+/// random value drawn to produce it, as described for [`Witnessed`]. When an
+/// unexpected outcome is found, its trace identifies a concrete sequence of
+/// random values that reproduces it.
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Functor, RandomStrategy, TraceEnumerator};
+///
+/// let f = TraceEnumerator::fmap_rand_range(Functor::pure(0u8), 1..=6u8, &mut thread_rng(), |s, r| s + r);
+/// let f = TraceEnumerator::fmap_rand_range(f, 1..=6u8, &mut thread_rng(), |s, r| s + r);
+/// // Rolling a 6 and then a 6, which are at index 5 of the range.
+/// assert_eq!(f.witness(|&s| s == 12), Some(&[5, 5][..]));
+/// ```
+///
+/// [`Enumerator`]: crate::Enumerator
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TraceEnumerator;
+
+impl RandomStrategy for TraceEnumerator {
+    type Functor<I: Inner> = Witnessed<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        Witnessed(telemetry::collect(
+            "TraceEnumerator",
+            f.into_iter().map(|(a, trace)| (func(a), trace)),
+        ))
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Witnessed(telemetry::collect(
+            "TraceEnumerator",
+            f.into_iter()
+                .flat_map(|(a, trace)| {
+                    R::sample_space()
+                        .enumerate()
+                        .map(move |(index, r)| (a.clone(), r, extend(&trace, index)))
+                })
+                .map(|(a, r, trace)| (func(a, r), trace)),
+        ))
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        Witnessed(telemetry::collect(
+            "TraceEnumerator",
+            f.into_iter()
+                .flat_map(|(a, trace)| {
+                    range
+                        .sample_space()
+                        .enumerate()
+                        .map(move |(index, r)| (a.clone(), r, extend(&trace, index)))
+                })
+                .map(|(a, r, trace)| (func(a, r), trace)),
+        ))
+    }
+}
+
+impl FlattenableRandomStrategy for TraceEnumerator {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        Witnessed(telemetry::collect(
+            "TraceEnumerator",
+            f.into_iter().flat_map(|(a, trace)| {
+                func(a).into_iter().map(move |(b, child_trace)| {
+                    let mut trace = trace.clone();
+                    trace.extend(child_trace);
+                    (b, trace)
+                })
+            }),
+        ))
+    }
+}
+
+impl WeightedRandomStrategy for TraceEnumerator {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        Witnessed(telemetry::collect(
+            "TraceEnumerator",
+            f.into_iter()
+                .flat_map(|(a, trace)| {
+                    distribution.weighted_sample_space().enumerate().map(
+                        move |(index, (r, weight))| (a.clone(), r, weight, extend(&trace, index)),
+                    )
+                })
+                .flat_map(|(a, r, weight, trace)| {
+                    core::iter::repeat((func(a, r), trace)).take(weight as usize)
+                }),
+        ))
+    }
+}
+
+impl ExactRandomStrategy for TraceEnumerator {}
@@ -0,0 +1,144 @@
+use core::any::type_name;
+use core::marker::PhantomData;
+use std::time::Instant;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    current_step, EnumerableDistribution, ExactRandomStrategy, FlattenableRandomStrategy, Functor,
+    Inner, OrderedRandomStrategy, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedRandomStrategy,
+};
+
+/// Wraps another [`RandomStrategy`] and emits [`tracing`] diagnostics for each
+/// operation it performs.
+///
+/// Every call is made inside a `DEBUG`-level span named after the operation
+/// (`fmap`, `fmap_rand`, `fmap_rand_range`, `fmap_flat` or
+/// `fmap_rand_distribution`). The span records the wrapped strategy, the
+/// random variable's type (where there is one), the label of the enclosing
+/// [`step`](crate::step) (if there is one) and the size of the input functor.
+/// Once the wrapped strategy returns, an event is emitted within the span
+/// carrying the size of the output functor and the elapsed time in
+/// microseconds.
+///
+/// `Traced<S>` uses the same functor as `S`, so it can be swapped in for `S`
+/// without changing any other code.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Traced<S: RandomStrategy> {
+    strategy_phantom: PhantomData<S>,
+}
+
+#[inline]
+fn instrument<I: Inner, G: Functor<I>>(
+    span: tracing::Span,
+    input_size: usize,
+    op: impl FnOnce() -> G,
+) -> G {
+    let _entered = span.enter();
+    let start = Instant::now();
+    let output = op();
+    let elapsed = start.elapsed();
+    tracing::debug!(
+        input_size,
+        output_size = output.size(),
+        elapsed_us = elapsed.as_micros() as u64,
+        "strategy operation completed"
+    );
+    output
+}
+
+impl<S: RandomStrategy> RandomStrategy for Traced<S> {
+    type Functor<I: Inner> = S::Functor<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        let span = tracing::debug_span!("fmap", strategy = type_name::<S>(), step = current_step());
+        instrument::<B, _>(span, f.size(), || S::fmap(f, func))
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let span = tracing::debug_span!(
+            "fmap_rand",
+            strategy = type_name::<S>(),
+            step = current_step(),
+            random_variable = type_name::<R>()
+        );
+        instrument::<B, _>(span, f.size(), || S::fmap_rand(f, rng, func))
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let span = tracing::debug_span!(
+            "fmap_rand_range",
+            strategy = type_name::<S>(),
+            step = current_step(),
+            random_variable = type_name::<R>()
+        );
+        instrument::<B, _>(span, f.size(), || S::fmap_rand_range(f, range, rng, func))
+    }
+}
+
+impl<S: FlattenableRandomStrategy> FlattenableRandomStrategy for Traced<S> {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        func: F,
+    ) -> Self::Functor<B> {
+        let span = tracing::debug_span!(
+            "fmap_flat",
+            strategy = type_name::<S>(),
+            step = current_step()
+        );
+        instrument::<B, _>(span, f.size(), || S::fmap_flat(f, func))
+    }
+}
+
+impl<S: OrderedRandomStrategy> OrderedRandomStrategy for Traced<S> {}
+
+impl<S: WeightedRandomStrategy> WeightedRandomStrategy for Traced<S> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let span = tracing::debug_span!(
+            "fmap_rand_distribution",
+            strategy = type_name::<S>(),
+            step = current_step(),
+            random_variable = type_name::<R>()
+        );
+        instrument::<B, _>(span, f.size(), || {
+            S::fmap_rand_distribution(f, distribution, rng, func)
+        })
+    }
+}
+
+impl<S: ExactRandomStrategy> ExactRandomStrategy for Traced<S> {}
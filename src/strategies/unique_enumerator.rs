@@ -1,14 +1,18 @@
-use std::collections::hash_map::RandomState;
 use std::collections::HashSet;
 use std::hash::BuildHasher;
 use std::marker::PhantomData;
+use std::vec::Vec;
 
 use rand::distributions::uniform::SampleUniform;
 use rand::distributions::Standard;
 use rand::prelude::*;
+use rand_core::RngCore;
 
+use crate::patch::apply_patches;
 use crate::{
-    FlattenableRandomStrategy, Inner, RandomStrategy, RandomVariable, RandomVariableRange,
+    telemetry, DefaultState, EnumerableDistribution, ExactRandomStrategy,
+    FlattenableRandomStrategy, Inner, Patch, RandomStrategy, RandomVariable, RandomVariableRange,
+    SmallRandomVariable, WeightedRandomStrategy,
 };
 
 /// Produces all possible outputs of the random process, without repetition,
@@ -18,7 +22,7 @@ use crate::{
 /// many inputs to the same output and the user does not care about the relative
 /// frequencies of possible outputs.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
-pub struct UniqueEnumerator<S: BuildHasher + Default = RandomState> {
+pub struct UniqueEnumerator<S: BuildHasher + Default = DefaultState> {
     phantom: PhantomData<S>,
 }
 
@@ -27,38 +31,137 @@ impl<S: BuildHasher + Default> RandomStrategy for UniqueEnumerator<S> {
 
     #[inline]
     fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
-        f.into_iter().map(func).collect()
+        telemetry::collect("UniqueEnumerator", f.into_iter().map(func))
     }
 
     #[inline]
-    fn fmap_rand<A: Inner, B: Inner, R: RandomVariable, F: Fn(A, R) -> B>(
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
-        _: &mut impl Rng,
+        _: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>,
     {
-        f.into_iter()
-            .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
-            .map(|(a, r)| func(a, r))
-            .collect()
+        telemetry::collect(
+            "UniqueEnumerator",
+            f.into_iter()
+                .flat_map(|a| R::sample_space().map(move |r| (a.clone(), r)))
+                .map(|(a, r)| func(a, r)),
+        )
     }
 
     #[inline]
     fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
         f: Self::Functor<A>,
         range: impl RandomVariableRange<R>,
-        _: &mut impl Rng,
+        _: &mut impl RngCore,
         func: F,
     ) -> Self::Functor<B>
     where
         Standard: Distribution<R>,
     {
-        f.into_iter()
-            .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
-            .map(|(a, r)| func(a, r))
-            .collect()
+        telemetry::collect(
+            "UniqueEnumerator",
+            f.into_iter()
+                .flat_map(|a| range.sample_space().map(move |r| (a.clone(), r)))
+                .map(|(a, r)| func(a, r)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_absorbing<A: Inner, R: SmallRandomVariable, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::absorb(f, absorbing, R::sample_space, func)
+    }
+
+    #[inline]
+    fn fmap_rand_range_absorbing<A: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> A>(
+        f: Self::Functor<A>,
+        absorbing: impl Fn(&A) -> bool,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        Self::absorb(f, absorbing, || range.sample_space(), func)
+    }
+
+    #[inline]
+    fn fmap_rand_delta<A: Inner, R: SmallRandomVariable, P: Patch<A>, F: Fn(&A, R) -> P>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        telemetry::collect(
+            "UniqueEnumerator",
+            f.into_iter().flat_map(|a| {
+                let patches = R::sample_space().map(|r| func(&a, r)).collect::<Vec<_>>();
+                apply_patches(a, patches)
+            }),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range_delta<
+        A: Inner,
+        R: RandomVariable + SampleUniform,
+        P: Patch<A>,
+        F: Fn(&A, R) -> P,
+    >(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<A>
+    where
+        Standard: Distribution<R>,
+    {
+        telemetry::collect(
+            "UniqueEnumerator",
+            f.into_iter().flat_map(|a| {
+                let patches = range
+                    .sample_space()
+                    .map(|r| func(&a, r))
+                    .collect::<Vec<_>>();
+                apply_patches(a, patches)
+            }),
+        )
+    }
+}
+
+impl<S: BuildHasher + Default> UniqueEnumerator<S> {
+    /// Expands the outcomes of `f` not satisfying `absorbing` over the sample
+    /// space produced by `sample_space`, and keeps the others as they are.
+    #[inline(always)]
+    fn absorb<A: Inner, R, I: Iterator<Item = R>>(
+        f: HashSet<A, S>,
+        absorbing: impl Fn(&A) -> bool,
+        sample_space: impl Fn() -> I,
+        func: impl Fn(A, R) -> A,
+    ) -> HashSet<A, S> {
+        let (absorbed, expanded): (Vec<_>, Vec<_>) = f.into_iter().partition(|a| absorbing(a));
+        telemetry::collect(
+            "UniqueEnumerator",
+            absorbed.into_iter().chain(
+                expanded
+                    .into_iter()
+                    .flat_map(|a| sample_space().map(move |r| (a.clone(), r)))
+                    .map(|(a, r)| func(a, r)),
+            ),
+        )
     }
 }
 
@@ -68,6 +171,35 @@ impl<S: BuildHasher + Default> FlattenableRandomStrategy for UniqueEnumerator<S>
         f: Self::Functor<A>,
         func: F,
     ) -> Self::Functor<B> {
-        f.into_iter().flat_map(func).collect()
+        telemetry::collect("UniqueEnumerator", f.into_iter().flat_map(func))
     }
 }
+
+impl<S: BuildHasher + Default> WeightedRandomStrategy for UniqueEnumerator<S> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        telemetry::collect(
+            "UniqueEnumerator",
+            f.into_iter()
+                .flat_map(|a| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, _)| (a.clone(), r))
+                })
+                .map(|(a, r)| func(a, r)),
+        )
+    }
+}
+
+impl<S: BuildHasher + Default> ExactRandomStrategy for UniqueEnumerator<S> {}
@@ -0,0 +1,309 @@
+use alloc::vec::{IntoIter, Vec};
+use core::ops::Deref;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{
+    telemetry, EnumerableDistribution, FlattenableRandomStrategy, FromWeightedIterator, Functor,
+    Inner, RandomStrategy, RandomVariable, RandomVariableRange, SmallRandomVariable,
+    WeightedFunctor, WeightedRandomStrategy,
+};
+
+/// The functor of [`WeightedPopulationSampler`], a [`Vec`] of sampled outcomes
+/// and the probability mass each of them represents.
+///
+/// This is a newtype, rather than a bare `Vec<(I, f64)>`, so that it does not
+/// make the [`Functor`] implementation for [`Vec`] ambiguous. It dereferences
+/// to a slice of its elements, and the [`Vec`] can be recovered with
+/// [`Particles::into_inner`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Particles<I>(pub Vec<(I, f64)>);
+
+impl<I> Default for Particles<I> {
+    #[inline]
+    fn default() -> Self {
+        Particles(Vec::new())
+    }
+}
+
+impl<I> Particles<I> {
+    /// Returns the sampled outcomes and their weights.
+    #[inline]
+    pub fn into_inner(self) -> Vec<(I, f64)> {
+        self.0
+    }
+}
+
+impl<I> Deref for Particles<I> {
+    type Target = [(I, f64)];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<I> IntoIterator for Particles<I> {
+    type Item = (I, f64);
+    type IntoIter = IntoIter<(I, f64)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<I> From<Particles<I>> for Vec<(I, f64)> {
+    #[inline]
+    fn from(particles: Particles<I>) -> Self {
+        particles.0
+    }
+}
+
+impl<I: Inner> Functor<I> for Particles<I> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        Particles(alloc::vec![(i, 1.0)])
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<I: Inner> WeightedFunctor<I> for Particles<I> {
+    type Weight = f64;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, f64)>
+    where
+        I: 'a,
+    {
+        self.0.iter().map(|(i, w)| (i, *w))
+    }
+}
+
+impl<I> FromIterator<(I, f64)> for Particles<I> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (I, f64)>>(iter: T) -> Self {
+        Particles(iter.into_iter().collect())
+    }
+}
+
+impl<I> Extend<(I, f64)> for Particles<I> {
+    #[inline]
+    fn extend<T: IntoIterator<Item = (I, f64)>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+impl<I: Inner> FromWeightedIterator<I> for Particles<I> {
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, f64)>>(&mut self, outcomes: T) {
+        self.0
+            .extend(outcomes.into_iter().filter(|&(_, weight)| weight != 0.0));
+    }
+}
+
+/// Produces a random sample of possible outputs of the random process, each
+/// weighted by the probability mass it represents.
+///
+/// [`PopulationSampler`] stores its outcomes as an [`Enumerator`] would, so
+/// an outcome's probability is represented only by how often it is repeated.
+/// `WeightedPopulationSampler` instead stores a weight alongside each outcome,
+/// which is divided among the outcomes it expands into. Outcomes of an
+/// [`EnumerableDistribution`] are stored once with their weight, and
+/// sub-processes passed to [`FlattenableRandomStrategy::fmap_flat`] may
+/// produce different numbers of outcomes without skewing the result.
+///
+/// Whenever an operation given a random number generator produces more than
+/// `N` outcomes, `N` of them are resampled in proportion to their weights
+/// using systematic resampling, and each is given an equal share of the total
+/// weight. Outcomes keep their relative order, and the expected weight of
+/// every outcome is unchanged, so statistics computed from the weights remain
+/// unbiased. [`FlattenableRandomStrategy::fmap_flat`] is not given a random
+/// number generator, so it never resamples; any excess outcomes are resampled
+/// by the next operation which is.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{
+///     FlattenableRandomStrategy, Functor, RandomStrategy, WeightedFunctor,
+///     WeightedPopulationSampler,
+/// };
+///
+/// let mut rng = thread_rng();
+/// let f = WeightedPopulationSampler::<64>::fmap_rand(Functor::pure(()), &mut rng, |_, b: bool| b);
+/// let f = WeightedPopulationSampler::<64>::fmap_flat(f, |b| {
+///     if b {
+///         WeightedPopulationSampler::<64>::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: u8| r)
+///     } else {
+///         Functor::pure(0)
+///     }
+/// });
+/// let f = WeightedPopulationSampler::<64>::fmap_rand(f, &mut rng, |s, r: bool| (s, r));
+/// assert_eq!(f.len(), 64);
+/// assert!((f.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-9);
+/// ```
+///
+/// [`Enumerator`]: crate::Enumerator
+/// [`PopulationSampler`]: crate::PopulationSampler
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct WeightedPopulationSampler<const N: usize>;
+
+impl<const N: usize> WeightedPopulationSampler<N> {
+    /// Collects `outcomes` into a functor, resampling it if it contains more
+    /// than `N` outcomes.
+    #[inline(always)]
+    fn collect<T: Inner>(
+        outcomes: impl Iterator<Item = (T, f64)>,
+        rng: &mut impl RngCore,
+    ) -> Particles<T> {
+        let outcomes = outcomes.collect::<Vec<_>>();
+        telemetry::record_outcomes("WeightedPopulationSampler", outcomes.len(), outcomes.len());
+        Particles(Self::resample(outcomes, rng))
+    }
+
+    /// Selects `N` outcomes from `f` in proportion to their weights, if it
+    /// contains more than `N`, giving each an equal share of the total weight.
+    #[inline(always)]
+    fn resample<T: Inner>(f: Vec<(T, f64)>, rng: &mut impl RngCore) -> Vec<(T, f64)> {
+        telemetry::record_shrink(
+            "WeightedPopulationSampler",
+            f.len(),
+            f.len().saturating_sub(N),
+        );
+        if f.len() <= N {
+            return f;
+        }
+        // Systematic resampling places `N` evenly spaced points, starting at a
+        // random offset, along the cumulative weights, and selects the outcome
+        // under each point. Each outcome is selected a number of times within
+        // one of its expected number of selections.
+        let total = f.iter().map(|(_, w)| w).sum::<f64>();
+        let spacing = total / N as f64;
+        let offset = rng.gen::<f64>() * spacing;
+        let mut outcomes = f.into_iter();
+        let mut cumulative = 0.0;
+        let mut current = None;
+        let mut resampled = Vec::with_capacity(N);
+        for k in 0..N {
+            let point = offset + k as f64 * spacing;
+            while cumulative <= point {
+                match outcomes.next() {
+                    Some((outcome, weight)) => {
+                        cumulative += weight;
+                        current = Some(outcome);
+                    }
+                    // Rounding may leave the final point just past the total.
+                    None => break,
+                }
+            }
+            if let Some(outcome) = &current {
+                resampled.push((outcome.clone(), spacing));
+            }
+        }
+        resampled
+    }
+}
+
+impl<const N: usize> RandomStrategy for WeightedPopulationSampler<N> {
+    type Functor<I: Inner> = Particles<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        let outcomes = f.into_iter().map(|(a, w)| (func(a), w)).collect::<Vec<_>>();
+        telemetry::record_outcomes("WeightedPopulationSampler", outcomes.len(), outcomes.len());
+        Particles(outcomes)
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let n = R::sample_space().count() as f64;
+        Self::collect(
+            f.into_iter()
+                .flat_map(|(a, w)| R::sample_space().map(move |r| (a.clone(), r, w)))
+                .map(|(a, r, w)| (func(a, r), w / n)),
+            rng,
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let n = range.sample_space().count() as f64;
+        Self::collect(
+            f.into_iter()
+                .flat_map(|(a, w)| range.sample_space().map(move |r| (a.clone(), r, w)))
+                .map(|(a, r, w)| (func(a, r), w / n)),
+            rng,
+        )
+    }
+}
+
+impl<const N: usize> FlattenableRandomStrategy for WeightedPopulationSampler<N> {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        let outcomes = f
+            .into_iter()
+            .flat_map(|(a, w)| {
+                let child = func(a);
+                let total = child.iter().map(|(_, v)| v).sum::<f64>();
+                child.into_iter().map(move |(b, v)| (b, w * v / total))
+            })
+            .collect::<Vec<_>>();
+        telemetry::record_outcomes("WeightedPopulationSampler", outcomes.len(), outcomes.len());
+        Particles(outcomes)
+    }
+}
+
+impl<const N: usize> WeightedRandomStrategy for WeightedPopulationSampler<N> {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        rng: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let total = distribution
+            .weighted_sample_space()
+            .map(|(_, weight)| weight as f64)
+            .sum::<f64>();
+        Self::collect(
+            f.into_iter()
+                .flat_map(|(a, w)| {
+                    distribution
+                        .weighted_sample_space()
+                        .map(move |(r, weight)| (a.clone(), r, w * weight as f64 / total))
+                })
+                .map(|(a, r, w)| (func(a, r), w)),
+            rng,
+        )
+    }
+}
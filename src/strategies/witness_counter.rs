@@ -0,0 +1,313 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::strategies::trace_enumerator::extend;
+use crate::{
+    telemetry, DefaultState, EnumerableDistribution, ExactRandomStrategy,
+    FlattenableRandomStrategy, FromWeightedIterator, Functor, Inner, RandomStrategy,
+    RandomVariable, RandomVariableRange, SmallRandomVariable, WeightedFunctor,
+    WeightedRandomStrategy,
+};
+
+/// The functor of [`WitnessCounter`], a [`HashMap`] from each outcome to its
+/// count and an example trace of random values that produces it.
+///
+/// Traces are represented as for [`Witnessed`](crate::Witnessed). Of all the
+/// traces producing an outcome, the lexicographically smallest is kept, so the
+/// example does not depend on the iteration order of the [`HashMap`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WitnessedCounts<I: Inner>(pub HashMap<I, (usize, Vec<usize>), DefaultState>);
+
+impl<I: Inner> Default for WitnessedCounts<I> {
+    #[inline]
+    fn default() -> Self {
+        WitnessedCounts(HashMap::default())
+    }
+}
+
+impl<I: Inner> WitnessedCounts<I> {
+    /// Returns the example trace producing `outcome`, if it was produced.
+    #[inline]
+    pub fn witness(&self, outcome: &I) -> Option<&[usize]> {
+        self.0.get(outcome).map(|(_, trace)| trace.as_slice())
+    }
+
+    /// Returns the number of times `outcome` was produced.
+    #[inline]
+    pub fn count(&self, outcome: &I) -> usize {
+        self.0.get(outcome).map_or(0, |(count, _)| *count)
+    }
+
+    /// Returns the outcomes along with their counts and example traces.
+    #[inline]
+    pub fn into_inner(self) -> HashMap<I, (usize, Vec<usize>), DefaultState> {
+        self.0
+    }
+
+    /// Adds `count` to the count of `outcome`, keeping the smaller of `trace`
+    /// and its stored trace without cloning either.
+    #[inline(always)]
+    fn insert(&mut self, outcome: I, count: usize, trace: Vec<usize>) {
+        match self.0.entry(outcome) {
+            Entry::Occupied(mut entry) => {
+                let (c, t) = entry.get_mut();
+                *c += count;
+                if trace < *t {
+                    *t = trace;
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((count, trace));
+            }
+        }
+    }
+}
+
+impl<I: Inner> Deref for WitnessedCounts<I> {
+    type Target = HashMap<I, (usize, Vec<usize>), DefaultState>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<I: Inner> IntoIterator for WitnessedCounts<I> {
+    type Item = (I, (usize, Vec<usize>));
+    type IntoIter = std::collections::hash_map::IntoIter<I, (usize, Vec<usize>)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<I: Inner> Functor<I> for WitnessedCounts<I> {
+    #[inline]
+    fn pure(i: I) -> Self {
+        WitnessedCounts([(i, (1, Vec::new()))].into_iter().collect())
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<I: Inner> WeightedFunctor<I> for WitnessedCounts<I> {
+    type Weight = usize;
+
+    #[inline]
+    fn weighted_outcomes<'a>(&'a self) -> impl Iterator<Item = (&'a I, usize)>
+    where
+        I: 'a,
+    {
+        self.0.iter().map(|(i, (count, _))| (i, *count))
+    }
+}
+
+impl<I: Inner> FromIterator<(I, (usize, Vec<usize>))> for WitnessedCounts<I> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (I, (usize, Vec<usize>))>>(iter: T) -> Self {
+        let mut f = Self::default();
+        f.extend(iter);
+        f
+    }
+}
+
+impl<I: Inner> Extend<(I, (usize, Vec<usize>))> for WitnessedCounts<I> {
+    /// Adds the counts of outcomes which are already stored, keeping the
+    /// smaller of their traces.
+    #[inline]
+    fn extend<T: IntoIterator<Item = (I, (usize, Vec<usize>))>>(&mut self, iter: T) {
+        for (outcome, (count, trace)) in iter {
+            self.insert(outcome, count, trace);
+        }
+    }
+}
+
+impl<I: Inner> FromWeightedIterator<I> for WitnessedCounts<I> {
+    /// Adds every outcome with a nonzero count, with an empty trace.
+    #[inline]
+    fn extend_weighted<T: IntoIterator<Item = (I, usize)>>(&mut self, outcomes: T) {
+        self.extend(
+            outcomes
+                .into_iter()
+                .filter(|&(_, count)| count != 0)
+                .map(|(outcome, count)| (outcome, (count, Vec::new()))),
+        );
+    }
+}
+
+/// Produces all possible outputs of the random process, with repetition,
+/// stored in a [`HashMap`] along with an example trace of random values that
+/// produces each of them.
+///
+/// `WitnessCounter` counts outcomes exactly like [`Counter`], so it has the
+/// same memory profile, apart from one trace per distinct outcome. Each trace
+/// answers how an outcome can be reached without a separate run of
+/// [`TraceEnumerator`].
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Functor, RandomStrategy, WitnessCounter};
+///
+/// let f = WitnessCounter::fmap_rand_range(Functor::pure(0u8), 1..=6u8, &mut thread_rng(), |s, r| s + r);
+/// let f = WitnessCounter::fmap_rand_range(f, 1..=6u8, &mut thread_rng(), |s, r| s + r);
+/// assert_eq!(f.count(&4), 3);
+/// // Rolling a 1 and then a 3.
+/// assert_eq!(f.witness(&4), Some(&[0, 2][..]));
+/// ```
+///
+/// [`Counter`]: crate::Counter
+/// [`TraceEnumerator`]: crate::TraceEnumerator
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct WitnessCounter;
+
+impl WitnessCounter {
+    /// Collects outcomes into a new functor, summing the counts and keeping the
+    /// smallest trace of outcomes that occur more than once.
+    #[inline(always)]
+    fn merge<I: Inner>(
+        capacity: usize,
+        outcomes: impl Iterator<Item = (I, usize, Vec<usize>)>,
+    ) -> WitnessedCounts<I> {
+        let mut new_functor = WitnessedCounts(HashMap::with_capacity_and_hasher(
+            capacity,
+            DefaultState::default(),
+        ));
+        let mut produced = 0;
+        outcomes.for_each(|(o, count, trace)| {
+            produced += 1;
+            new_functor.insert(o, count, trace);
+        });
+        telemetry::record_outcomes("WitnessCounter", produced, new_functor.len());
+        new_functor
+    }
+}
+
+impl RandomStrategy for WitnessCounter {
+    type Functor<I: Inner> = WitnessedCounts<I>;
+
+    #[inline]
+    fn fmap<A: Inner, B: Inner, F: Fn(A) -> B>(f: Self::Functor<A>, func: F) -> Self::Functor<B> {
+        let capacity = f.len();
+        Self::merge(
+            capacity,
+            f.into_iter()
+                .map(|(a, (count, trace))| (func(a), count, trace)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand<A: Inner, B: Inner, R: SmallRandomVariable, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let capacity = f.len();
+        Self::merge(
+            capacity,
+            f.into_iter()
+                .flat_map(|(a, (count, trace))| {
+                    R::sample_space()
+                        .enumerate()
+                        .map(move |(index, r)| (a.clone(), r, count, extend(&trace, index)))
+                })
+                .map(|(a, r, count, trace)| (func(a, r), count, trace)),
+        )
+    }
+
+    #[inline]
+    fn fmap_rand_range<A: Inner, B: Inner, R: RandomVariable + SampleUniform, F: Fn(A, R) -> B>(
+        f: Self::Functor<A>,
+        range: impl RandomVariableRange<R>,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B>
+    where
+        Standard: Distribution<R>,
+    {
+        let capacity = f.len();
+        Self::merge(
+            capacity,
+            f.into_iter()
+                .flat_map(|(a, (count, trace))| {
+                    range
+                        .sample_space()
+                        .enumerate()
+                        .map(move |(index, r)| (a.clone(), r, count, extend(&trace, index)))
+                })
+                .map(|(a, r, count, trace)| (func(a, r), count, trace)),
+        )
+    }
+}
+
+impl FlattenableRandomStrategy for WitnessCounter {
+    #[inline]
+    fn fmap_flat<A: Inner, B: Inner, F: FnMut(A) -> Self::Functor<B>>(
+        f: Self::Functor<A>,
+        mut func: F,
+    ) -> Self::Functor<B> {
+        let capacity = f.len();
+        let children = f
+            .into_iter()
+            .map(|(a, (count, trace))| (func(a), count, trace))
+            .collect::<Vec<_>>();
+        Self::merge(
+            capacity,
+            children
+                .into_iter()
+                .flat_map(|(child, outer_count, outer_trace)| {
+                    child
+                        .into_iter()
+                        .map(move |(b, (inner_count, inner_trace))| {
+                            let mut trace = outer_trace.clone();
+                            trace.extend(inner_trace);
+                            (b, inner_count * outer_count, trace)
+                        })
+                }),
+        )
+    }
+}
+
+impl WeightedRandomStrategy for WitnessCounter {
+    #[inline]
+    fn fmap_rand_distribution<
+        A: Inner,
+        B: Inner,
+        R,
+        D: EnumerableDistribution<R>,
+        F: Fn(A, R) -> B,
+    >(
+        f: Self::Functor<A>,
+        distribution: &D,
+        _: &mut impl RngCore,
+        func: F,
+    ) -> Self::Functor<B> {
+        let capacity = f.len();
+        Self::merge(
+            capacity,
+            f.into_iter()
+                .flat_map(|(a, (count, trace))| {
+                    distribution.weighted_sample_space().enumerate().map(
+                        move |(index, (r, weight))| {
+                            (a.clone(), r, count * weight as usize, extend(&trace, index))
+                        },
+                    )
+                })
+                .map(|(a, r, count, trace)| (func(a, r), count, trace)),
+        )
+    }
+}
+
+impl ExactRandomStrategy for WitnessCounter {}
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use rand::distributions::{Distribution, Standard};
+
+use crate::{DefaultState, Inner, RandomStrategy, SmallRandomVariable};
+
+/// Evaluates `process`, a random process evaluated with `S`, once for every
+/// possible value of its input, and returns the functor produced for each
+/// input.
+///
+/// The inputs are the sample space of `T`, so computing the distribution of
+/// outcomes from every possible starting state of a process takes a single
+/// call. As with [`RandomStrategy::fmap_rand`], `T` must be a
+/// [`SmallRandomVariable`], so that sweeping over an intractably large type is
+/// a compile error. Each evaluated input is reported to the callback installed
+/// by [`with_progress`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{sweep, Counter, Functor, RandomStrategy, WeightedFunctor};
+///
+/// let outcomes = sweep::<Counter, u8, _>(|s| {
+///     Counter::fmap_rand(Functor::pure(s), &mut thread_rng(), |s, r: bool| s.saturating_add(r as u8))
+/// });
+/// assert_eq!(outcomes.len(), 256);
+/// assert_eq!(outcomes[&3].probability_where(|&s| s == 4), 0.5);
+/// assert_eq!(outcomes[&255][&255], 2);
+/// ```
+///
+/// [`with_progress`]: crate::with_progress
+pub fn sweep<S: RandomStrategy, T: SmallRandomVariable + Inner, O: Inner>(
+    mut process: impl FnMut(T) -> S::Functor<O>,
+) -> HashMap<T, S::Functor<O>, DefaultState>
+where
+    Standard: Distribution<T>,
+{
+    let inputs = T::sample_space().collect::<Vec<_>>();
+    let total = inputs.len();
+    let mut results = HashMap::with_capacity_and_hasher(total, DefaultState::default());
+    for (i, input) in inputs.into_iter().enumerate() {
+        let f = process(input.clone());
+        results.insert(input, f);
+        crate::progress::report_iteration("sweep", i + 1, Some(total));
+    }
+    results
+}
@@ -0,0 +1,207 @@
+use std::any::type_name;
+use std::collections::HashMap;
+use std::fmt::{Debug, Write};
+use std::vec::Vec;
+
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+
+use crate::{Inner, SmallRandomVariable};
+
+/// A precomputed table of the outcomes of one step of a random process, for
+/// every value in the sample space of a [`SmallRandomVariable`].
+///
+/// A `LookupTable` is compiled by evaluating the step with [`Counter`] once
+/// for each possible state. Afterwards, the outcomes of a step from any state
+/// can be looked up or sampled without evaluating the step again. The table
+/// can also be emitted as Rust source code or as a binary blob, so that it can
+/// be compiled into a program ahead of time.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, Functor, LookupTable, RandomStrategy};
+///
+/// let table = LookupTable::compile(|s: u8| {
+///     Counter::fmap_rand(Functor::pure(s), &mut thread_rng(), |s, r: bool| {
+///         if r { s.saturating_add(1) } else { s / 2 }
+///     })
+/// });
+/// assert_eq!(table.outcomes(&10), [(5, 1), (11, 1)]);
+/// assert!([5, 11].contains(&table.sample(&10, &mut thread_rng())));
+/// ```
+///
+/// [`Counter`]: crate::Counter
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LookupTable<T: Inner> {
+    indices: HashMap<T, usize>,
+    rows: Vec<Vec<(T, u64)>>,
+    cumulative_weights: Vec<Vec<u64>>,
+}
+
+impl<T: Inner + SmallRandomVariable> LookupTable<T>
+where
+    Standard: Distribution<T>,
+{
+    /// Compiles a table by evaluating `step`, a random process evaluated with
+    /// [`Counter`], once for every value in the sample space of `T`.
+    ///
+    /// The outcomes in each row of the table are stored in the order of the
+    /// sample space of `T`, so the table does not depend on the iteration
+    /// order of the [`HashMap`]s returned by `step`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any count produced by `step` is zero.
+    ///
+    /// [`Counter`]: crate::Counter
+    pub fn compile(mut step: impl FnMut(T) -> HashMap<T, usize>) -> Self {
+        let indices = state_indices::<T>();
+        let rows = T::sample_space()
+            .map(|t| {
+                let mut row = step(t)
+                    .into_iter()
+                    .map(|(o, count)| {
+                        assert_ne!(count, 0, "outcomes must have nonzero counts");
+                        (o, count as u64)
+                    })
+                    .collect::<Vec<_>>();
+                row.sort_by_key(|(o, _)| indices[o]);
+                row
+            })
+            .collect();
+        Self::from_rows(indices, rows)
+    }
+
+    /// Reconstructs a table from the binary representation produced by
+    /// [`LookupTable::to_bytes`].
+    ///
+    /// Returns [`None`] if `bytes` is not a valid table for `T`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let states = T::sample_space().collect::<Vec<_>>();
+        let indices = state_indices::<T>();
+        let mut bytes = bytes;
+        let mut take = |n: usize| -> Option<&[u8]> {
+            let (taken, rest) = (bytes.get(..n)?, bytes.get(n..)?);
+            bytes = rest;
+            Some(taken)
+        };
+        let mut rows = Vec::with_capacity(states.len());
+        for _ in 0..states.len() {
+            let len = u32::from_le_bytes(take(4)?.try_into().ok()?);
+            let row = (0..len)
+                .map(|_| {
+                    let index = u32::from_le_bytes(take(4)?.try_into().ok()?);
+                    let weight = u64::from_le_bytes(take(8)?.try_into().ok()?);
+                    if weight == 0 {
+                        return None;
+                    }
+                    Some((states.get(index as usize)?.clone(), weight))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            rows.push(row);
+        }
+        bytes.is_empty().then(|| Self::from_rows(indices, rows))
+    }
+}
+
+impl<T: Inner> LookupTable<T> {
+    #[inline]
+    fn from_rows(indices: HashMap<T, usize>, rows: Vec<Vec<(T, u64)>>) -> Self {
+        let cumulative_weights = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .scan(0u64, |total, (_, weight)| {
+                        *total += weight;
+                        Some(*total)
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            indices,
+            rows,
+            cumulative_weights,
+        }
+    }
+
+    /// Returns the outcomes of a step from `state` and their counts.
+    #[inline]
+    pub fn outcomes(&self, state: &T) -> &[(T, u64)] {
+        &self.rows[self.indices[state]]
+    }
+
+    /// Samples an outcome of a step from `state`, with probability
+    /// proportional to its count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step from `state` has no outcomes.
+    #[inline]
+    pub fn sample(&self, state: &T, rng: &mut impl RngCore) -> T {
+        let index = self.indices[state];
+        let cumulative = &self.cumulative_weights[index];
+        let total = *cumulative.last().expect("step must have outcomes");
+        let x = rng.gen_range(0..total);
+        self.rows[index][cumulative.partition_point(|&c| c <= x)]
+            .0
+            .clone()
+    }
+
+    /// Encodes this table as a binary blob.
+    ///
+    /// For each value in the sample space of `T`, in order, the blob contains
+    /// the number of outcomes of a step from that value as a little-endian
+    /// [`u32`], followed by each outcome as the little-endian [`u32`] position
+    /// of the outcome in the sample space and its little-endian [`u64`] count.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for row in &self.rows {
+            bytes.extend_from_slice(&(row.len() as u32).to_le_bytes());
+            for (o, weight) in row {
+                bytes.extend_from_slice(&(self.indices[o] as u32).to_le_bytes());
+                bytes.extend_from_slice(&weight.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Emits this table as the Rust source of a `static` item named `name`.
+    ///
+    /// The item is an array with one entry for each value in the sample space
+    /// of `T`, in order. Each entry is a slice of the outcomes of a step from
+    /// that value and their counts. Outcomes are written using their [`Debug`]
+    /// representations, which must therefore be valid Rust expressions, as
+    /// they are for [`bool`] and the built-in integer types.
+    pub fn to_rust_source(&self, name: &str) -> String
+    where
+        T: Debug,
+    {
+        let mut source = format!(
+            "pub static {name}: [&[({}, u64)]; {}] = [\n",
+            type_name::<T>(),
+            self.rows.len()
+        );
+        for row in &self.rows {
+            source.push_str("    &[");
+            for (i, (o, weight)) in row.iter().enumerate() {
+                if i > 0 {
+                    source.push_str(", ");
+                }
+                write!(source, "({o:?}, {weight})").expect("writing to a String cannot fail");
+            }
+            source.push_str("],\n");
+        }
+        source.push_str("];\n");
+        source
+    }
+}
+
+/// Maps each value in the sample space of `T` to its position in the sample
+/// space.
+fn state_indices<T: Inner + SmallRandomVariable>() -> HashMap<T, usize>
+where
+    Standard: Distribution<T>,
+{
+    T::sample_space().enumerate().map(|(i, t)| (t, i)).collect()
+}
@@ -0,0 +1,122 @@
+//! Metrics describing the growth of functors, reported through the [`metrics`]
+//! crate.
+//!
+//! With the `metrics` feature enabled, the built-in enumerating strategies
+//! report the metrics named by the constants in this module to whichever
+//! [`metrics::Recorder`] is installed. Every metric carries a `strategy` label
+//! naming the strategy that reported it. [`PopulationSampler`] and
+//! [`DynPopulationSampler`] enumerate outcomes using [`Enumerator`], so their
+//! expansion is reported under the `Enumerator` label, while their shrink
+//! operations are reported under their own.
+//!
+//! [`Sampler`] never stores more than one outcome and reports nothing.
+//!
+//! [`DynPopulationSampler`]: crate::DynPopulationSampler
+//! [`Enumerator`]: crate::Enumerator
+//! [`PopulationSampler`]: crate::PopulationSampler
+//! [`Sampler`]: crate::Sampler
+
+use crate::{Functor, Inner};
+
+/// Counter of the outcomes produced by strategy operations, before any
+/// repeated outcomes are merged or any outcomes are discarded.
+#[cfg(feature = "metrics")]
+pub const OUTCOMES_EXPANDED: &str = "rand_functors.outcomes_expanded";
+
+/// Counter of the outcomes merged into an outcome already stored in a functor.
+#[cfg(feature = "metrics")]
+pub const COLLISIONS: &str = "rand_functors.collisions";
+
+/// Gauge of the number of outcomes stored in the most recently produced
+/// functor.
+#[cfg(feature = "metrics")]
+pub const FUNCTOR_SIZE: &str = "rand_functors.functor_size";
+
+/// Counter of the operations which discarded outcomes to keep a functor within
+/// its capacity.
+#[cfg(feature = "metrics")]
+pub const SHRINKS: &str = "rand_functors.shrinks";
+
+/// Counter of the outcomes discarded by shrink operations.
+#[cfg(feature = "metrics")]
+pub const OUTCOMES_DISCARDED: &str = "rand_functors.outcomes_discarded";
+
+/// Collects `outcomes` into a functor, reporting how many outcomes were
+/// produced and how many of them were stored.
+///
+/// Outcomes are only counted while a progress callback is installed or the
+/// `metrics` feature is enabled. Otherwise, they are collected directly.
+#[inline(always)]
+pub(crate) fn collect<I: Inner, C: Functor<I> + FromIterator<I>>(
+    strategy: &'static str,
+    outcomes: impl Iterator<Item = I>,
+) -> C {
+    #[cfg(feature = "std")]
+    if crate::progress::is_installed() {
+        let mut produced = 0;
+        let functor: C = outcomes
+            .inspect(|_| {
+                produced += 1;
+                if produced % crate::progress::EXPANSION_INTERVAL == 0 {
+                    crate::progress::report(|step| crate::Progress::Expanding {
+                        strategy,
+                        step,
+                        produced,
+                    });
+                }
+            })
+            .collect();
+        record_outcomes(strategy, produced, functor.size());
+        return functor;
+    }
+    #[cfg(feature = "metrics")]
+    {
+        let mut produced = 0;
+        let functor: C = outcomes.inspect(|_| produced += 1).collect();
+        record_outcomes(strategy, produced, functor.size());
+        functor
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = strategy;
+        outcomes.collect()
+    }
+}
+
+/// Reports that an operation produced `produced` outcomes, of which `stored`
+/// remained distinct after merging, to the metrics recorder and the progress
+/// callback.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+#[inline(always)]
+pub(crate) fn record_outcomes(strategy: &'static str, produced: usize, stored: usize) {
+    #[cfg(feature = "std")]
+    crate::progress::report(|step| crate::Progress::Operation {
+        strategy,
+        step,
+        produced,
+        stored,
+    });
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(OUTCOMES_EXPANDED, "strategy" => strategy).increment(produced as u64);
+        metrics::counter!(COLLISIONS, "strategy" => strategy)
+            .increment(produced.saturating_sub(stored) as u64);
+        metrics::gauge!(FUNCTOR_SIZE, "strategy" => strategy).set(stored as f64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (strategy, produced, stored);
+}
+
+/// Reports that a functor of `size` outcomes was shrunk by discarding
+/// `discarded` of them.
+#[inline(always)]
+pub(crate) fn record_shrink(strategy: &'static str, size: usize, discarded: usize) {
+    #[cfg(feature = "metrics")]
+    if discarded > 0 {
+        metrics::counter!(SHRINKS, "strategy" => strategy).increment(1);
+        metrics::counter!(OUTCOMES_DISCARDED, "strategy" => strategy).increment(discarded as u64);
+        metrics::gauge!(FUNCTOR_SIZE, "strategy" => strategy).set((size - discarded) as f64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (strategy, size, discarded);
+}
@@ -0,0 +1,362 @@
+//! Utilities for testing code built on `rand-functors`.
+//!
+//! These allow property tests to share a single definition of a type's domain
+//! with the random processes that use it, check that implementations of
+//! [`RandomVariable`] are consistent, and compare the outputs of random
+//! processes against recorded snapshots of their distributions.
+
+#[cfg(feature = "quickcheck")]
+use std::boxed::Box;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::format;
+use std::string::String;
+#[cfg(feature = "proptest")]
+use std::sync::Arc;
+use std::vec::Vec;
+
+#[cfg(feature = "proptest")]
+use proptest::strategy::Strategy;
+#[cfg(feature = "quickcheck")]
+use quickcheck::{Arbitrary, Gen};
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_core::RngCore;
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Cardinality, Inner, RandomVariable, WeightedFunctor};
+
+/// Checks that the sample space of a [`RandomVariable`] is consistent with its
+/// [`Cardinality`].
+///
+/// This panics if the sample space of `T` yields more values than
+/// [`Cardinality::CARDINALITY`] (which includes sample spaces that never end),
+/// yields fewer values, or yields any value more than once. Each of these
+/// would silently corrupt the results of enumerating strategies like
+/// [`Counter`], so this is intended to be called from the tests of custom
+/// implementations. It takes time linear in the cardinality of `T`.
+/// ```
+/// use rand_functors::testing::check_random_variable;
+///
+/// check_random_variable::<bool>();
+/// check_random_variable::<i16>();
+/// ```
+///
+/// [`Counter`]: crate::Counter
+pub fn check_random_variable<T: Cardinality + Inner + Debug>()
+where
+    Standard: Distribution<T>,
+{
+    let mut seen = HashSet::new();
+    for t in T::sample_space() {
+        assert!(
+            (seen.len() as u128) < T::CARDINALITY,
+            "sample space yields more than CARDINALITY ({}) values",
+            T::CARDINALITY
+        );
+        if let Some(t) = seen.replace(t) {
+            panic!("sample space yields {t:?} more than once");
+        }
+    }
+    assert_eq!(
+        seen.len() as u128,
+        T::CARDINALITY,
+        "sample space yields fewer than CARDINALITY values"
+    );
+}
+
+/// Checks that sampling a [`RandomVariable`] from [`Standard`] is consistent
+/// with a uniform distribution over its sample space.
+///
+/// `samples` values are drawn from [`Standard`] using `rng`, and a chi-square
+/// test is performed on how often each value of the sample space was drawn.
+/// This panics if any value drawn is not in the sample space of `T`, or if the
+/// frequencies are inconsistent with uniformity at a significance level of
+/// 0.001. The critical value of the test is estimated using the
+/// Wilson-Hilferty approximation.
+///
+/// The test requires an expected frequency of at least five for each value,
+/// so this also panics if `samples` is less than five times the number of
+/// values in the sample space. As with any statistical test, a correct
+/// implementation will occasionally fail, so a fixed seed should be used.
+/// ```
+/// use rand::prelude::*;
+/// use rand_chacha::ChaCha8Rng;
+/// use rand_functors::testing::audit_uniformity;
+///
+/// audit_uniformity::<u8>(&mut ChaCha8Rng::seed_from_u64(0), 100_000);
+/// ```
+pub fn audit_uniformity<T: RandomVariable + Inner + Debug>(rng: &mut impl RngCore, samples: usize)
+where
+    Standard: Distribution<T>,
+{
+    let mut frequencies = T::sample_space()
+        .map(|t| (t, 0usize))
+        .collect::<HashMap<_, _>>();
+    let values = frequencies.len();
+    assert!(
+        samples >= 5 * values,
+        "at least {} samples are required for a sample space of {values} values",
+        5 * values
+    );
+    for _ in 0..samples {
+        let t = Standard.sample(rng);
+        match frequencies.get_mut(&t) {
+            Some(frequency) => *frequency += 1,
+            None => panic!("sampled {t:?}, which is not in the sample space"),
+        }
+    }
+
+    let expected = samples as f64 / values as f64;
+    let statistic = frequencies
+        .values()
+        .map(|&observed| (observed as f64 - expected).powi(2) / expected)
+        .sum::<f64>();
+    let degrees_of_freedom = (values - 1).max(1) as f64;
+    // The standard normal quantile for a significance level of 0.001.
+    let z = 3.090232;
+    let h = 2.0 / (9.0 * degrees_of_freedom);
+    let critical_value = degrees_of_freedom * (1.0 - h + z * h.sqrt()).powi(3);
+    assert!(
+        statistic <= critical_value,
+        "sampled frequencies are not uniform over the sample space \
+         (chi-square statistic {statistic:.2} exceeds critical value {critical_value:.2})"
+    );
+}
+
+/// Produces a proptest [`Strategy`] that generates values from the sample
+/// space of a [`RandomVariable`].
+///
+/// Values are generated uniformly from the sample space, which is collected
+/// when this function is called. Failing cases shrink towards the start of the
+/// sample space.
+/// ```
+/// use proptest::prelude::*;
+/// use rand_functors::testing::arb_random_variable;
+///
+/// proptest!(|(x in arb_random_variable::<u8>())| {
+///     prop_assert_eq!(x.wrapping_mul(2) % 2, 0);
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+pub fn arb_random_variable<T: RandomVariable + Clone + Debug>() -> impl Strategy<Value = T>
+where
+    Standard: Distribution<T>,
+{
+    let sample_space = T::sample_space().collect::<Arc<[T]>>();
+    assert!(
+        !sample_space.is_empty(),
+        "sample space of a RandomVariable must not be empty"
+    );
+    (0..sample_space.len()).prop_map(move |i| sample_space[i].clone())
+}
+
+/// A wrapper around a [`RandomVariable`] which implements quickcheck's
+/// [`Arbitrary`].
+///
+/// Values are generated uniformly from the sample space of `T`. Shrinking
+/// produces the values preceding the wrapped value in the sample space,
+/// starting from the beginning of the sample space.
+/// ```
+/// use quickcheck::quickcheck;
+/// use rand_functors::testing::ArbitraryRandomVariable;
+///
+/// fn doubling_is_even(x: ArbitraryRandomVariable<u8>) -> bool {
+///     x.0.wrapping_mul(2) % 2 == 0
+/// }
+///
+/// quickcheck(doubling_is_even as fn(ArbitraryRandomVariable<u8>) -> bool);
+/// ```
+#[cfg(feature = "quickcheck")]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ArbitraryRandomVariable<T>(pub T);
+
+#[cfg(feature = "quickcheck")]
+impl<T: RandomVariable + Clone + PartialEq + 'static> Arbitrary for ArbitraryRandomVariable<T>
+where
+    Standard: Distribution<T>,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        let sample_space = T::sample_space().collect::<Vec<_>>();
+        let value = g
+            .choose(&sample_space)
+            .expect("sample space of a RandomVariable must not be empty");
+        Self(value.clone())
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let value = self.0.clone();
+        Box::new(
+            T::sample_space()
+                .take_while(move |t| *t != value)
+                .map(ArbitraryRandomVariable),
+        )
+    }
+}
+
+/// The default tolerance used to compare the probabilities of two
+/// [`DistributionSnapshot`]s.
+pub const DEFAULT_SNAPSHOT_TOLERANCE: f64 = 1e-9;
+
+/// A recorded distribution of the outcomes of a random process, for comparing
+/// against its output in later test runs.
+///
+/// A snapshot stores the probability of each distinct outcome, keyed by its
+/// [`Debug`] representation, so it does not depend on the weights used by a
+/// particular strategy or on the order in which outcomes were stored.
+/// Outcomes are kept sorted by their representation, so that snapshots are
+/// displayed and serialized in a stable order. With the `json` feature
+/// enabled, snapshots can be saved with [`DistributionSnapshot::to_json`] and
+/// loaded again with [`DistributionSnapshot::from_json`].
+///
+/// Probabilities are compared with a tolerance, as refactoring a process can
+/// change the rounding of its floating-point weights. The
+/// [`assert_distribution_eq!`] macro compares the output of a random process
+/// against a snapshot and lists every difference if they do not match.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::testing::DistributionSnapshot;
+/// use rand_functors::{Counter, Enumerator, Functor, RandomStrategy};
+///
+/// let counter = <Counter>::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: u8| r % 3);
+/// let enumerator = Enumerator::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: u8| r % 3);
+/// assert_eq!(DistributionSnapshot::new(&counter), DistributionSnapshot::new(&enumerator));
+/// assert_eq!(
+///     DistributionSnapshot::new(&counter).to_string(),
+///     "0.3359375000  0\n0.3320312500  1\n0.3320312500  2"
+/// );
+/// ```
+///
+/// [`assert_distribution_eq!`]: crate::assert_distribution_eq
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize), serde(transparent))]
+pub struct DistributionSnapshot {
+    outcomes: BTreeMap<String, f64>,
+}
+
+/// An outcome whose probability differs between two [`DistributionSnapshot`]s,
+/// as found by [`DistributionSnapshot::differences`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnapshotDifference {
+    /// The [`Debug`] representation of the outcome.
+    pub outcome: String,
+    /// The probability of the outcome in the snapshot being checked, or zero
+    /// if it does not occur.
+    pub actual: f64,
+    /// The probability of the outcome in the expected snapshot, or zero if it
+    /// does not occur.
+    pub expected: f64,
+}
+
+impl Display for SnapshotDifference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, found {}",
+            self.outcome, self.expected, self.actual
+        )
+    }
+}
+
+impl DistributionSnapshot {
+    /// Records the distribution of the outcomes stored in `functor`.
+    ///
+    /// Outcomes with the same [`Debug`] representation are merged.
+    pub fn new<I: Inner + Debug>(functor: &impl WeightedFunctor<I>) -> Self {
+        functor
+            .normalize()
+            .into_iter()
+            .map(|(outcome, probability)| (format!("{outcome:?}"), probability))
+            .collect()
+    }
+
+    /// Returns the recorded outcomes and their probabilities, in the order of
+    /// their representations.
+    #[inline]
+    pub fn outcomes(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.outcomes.iter().map(|(o, &p)| (o.as_str(), p))
+    }
+
+    /// Returns the probability recorded for the outcome represented by
+    /// `outcome`, or zero if it was not recorded.
+    #[inline]
+    pub fn probability(&self, outcome: &str) -> f64 {
+        self.outcomes.get(outcome).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the number of distinct outcomes recorded.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    /// Returns `true` if no outcomes were recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+
+    /// Returns every outcome whose probability differs between this snapshot
+    /// and `expected` by more than `tolerance`, in the order of their
+    /// representations.
+    pub fn differences(&self, expected: &Self, tolerance: f64) -> Vec<SnapshotDifference> {
+        let outcomes = self
+            .outcomes
+            .keys()
+            .chain(expected.outcomes.keys())
+            .collect::<BTreeSet<_>>();
+        outcomes
+            .into_iter()
+            .map(|outcome| SnapshotDifference {
+                outcome: outcome.clone(),
+                actual: self.probability(outcome),
+                expected: expected.probability(outcome),
+            })
+            .filter(|d| (d.actual - d.expected).abs() > tolerance)
+            .collect()
+    }
+
+    /// Returns `true` if the probability of every outcome differs between this
+    /// snapshot and `expected` by at most `tolerance`.
+    #[inline]
+    pub fn approx_eq(&self, expected: &Self, tolerance: f64) -> bool {
+        self.differences(expected, tolerance).is_empty()
+    }
+
+    /// Serializes this snapshot as pretty-printed JSON, with an object mapping
+    /// the representation of each outcome to its probability.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("snapshots must be serializable as JSON")
+    }
+
+    /// Deserializes a snapshot from the JSON produced by
+    /// [`DistributionSnapshot::to_json`].
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl FromIterator<(String, f64)> for DistributionSnapshot {
+    fn from_iter<T: IntoIterator<Item = (String, f64)>>(iter: T) -> Self {
+        let mut outcomes = BTreeMap::new();
+        for (outcome, probability) in iter {
+            *outcomes.entry(outcome).or_insert(0.0) += probability;
+        }
+        Self { outcomes }
+    }
+}
+
+impl Display for DistributionSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, (outcome, probability)) in self.outcomes.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{probability:.10}  {outcome}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use rand::distributions::Standard;
+use rand::prelude::*;
+
+use crate::{Cardinality, EnumerableDistribution, RandomVariable, SmallRandomVariable};
+
+/// A [`RandomVariable`] whose enumerated sample space contains only every
+/// `K`th value of the sample space of `R`.
+///
+/// Sampling a `Thinned<R, K>` samples from the full sample space of `R`, while
+/// enumerating strategies only enumerate the values at positions `0`, `K`,
+/// `2 * K`, and so on. Each enumerated value stands in for the `K` values that
+/// follow it, so the outputs of enumerating strategies approximate the
+/// distribution of a random process over `R` with `K` times fewer outcomes.
+/// This makes it possible to coarsely enumerate random processes over types
+/// like [`u32`], which are too large to enumerate exactly.
+///
+/// As values can be sampled that are not in its sample space, `Thinned`
+/// deliberately breaks the usual contract of [`RandomVariable`]. The
+/// approximation is only meaningful when the outcome of a random process
+/// varies little between neighbouring values of `R`.
+///
+/// `Thinned<R, K>` implements [`SmallRandomVariable`], so it can be used with
+/// [`RandomStrategy::fmap_rand`], which gives every enumerated value the same
+/// weight. Enumerating the sample space of a `Thinned` with a step of zero,
+/// with more values than [`u16`], or with a step that does not evenly divide
+/// the number of values of `R` causes a compile error.
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, Functor, RandomStrategy, Thinned};
+///
+/// let f = <Counter>::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, x: Thinned<u32, 65536>| {
+///     x.0 < u32::MAX / 4
+/// });
+/// assert_eq!(f[&true], 16384);
+/// assert_eq!(f[&false], 49152);
+/// ```
+///
+/// When the step does not evenly divide the number of values of `R`, the last
+/// value stands in for fewer than `K` values. [`Standard`] implements
+/// [`EnumerableDistribution`] for `Thinned<R, K>`, weighting each enumerated
+/// value by the number of values it stands in for, so such a `Thinned` can be
+/// enumerated with [`WeightedRandomStrategy::fmap_rand_distribution`]:
+/// ```
+/// use rand::distributions::Standard;
+/// use rand::prelude::*;
+/// use rand_functors::{Counter, Functor, Thinned, WeightedRandomStrategy};
+///
+/// let f = <Counter>::fmap_rand_distribution(
+///     Functor::pure(()),
+///     &Standard,
+///     &mut thread_rng(),
+///     |_, x: Thinned<u8, 100>| x.0,
+/// );
+/// assert_eq!(f[&0], 100);
+/// assert_eq!(f[&100], 100);
+/// assert_eq!(f[&200], 56);
+/// ```
+///
+/// [`RandomStrategy::fmap_rand`]: crate::RandomStrategy::fmap_rand
+/// [`WeightedRandomStrategy::fmap_rand_distribution`]: crate::WeightedRandomStrategy::fmap_rand_distribution
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Thinned<R, const K: usize>(pub R);
+
+impl<R: Cardinality, const K: usize> Thinned<R, K>
+where
+    Standard: Distribution<R>,
+{
+    const CHECKED_STEP: usize = {
+        assert!(K != 0, "a thinned sample space must have a nonzero step");
+        assert!(
+            <Self as Cardinality>::CARDINALITY <= u16::CARDINALITY,
+            "thinned sample space is too large to enumerate"
+        );
+        K
+    };
+
+    const EVEN_STEP: usize = {
+        assert!(
+            R::CARDINALITY % Self::CHECKED_STEP as u128 == 0,
+            "the step of a thinned sample space must evenly divide its cardinality"
+        );
+        K
+    };
+}
+
+impl<R, const K: usize> Distribution<Thinned<R, K>> for Standard
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> Thinned<R, K> {
+        Thinned(self.sample(rng))
+    }
+}
+
+impl<R: Cardinality, const K: usize> RandomVariable for Thinned<R, K>
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn sample_space() -> impl Iterator<Item = Self> {
+        R::sample_space().step_by(Self::EVEN_STEP).map(Thinned)
+    }
+}
+
+impl<R: Cardinality, const K: usize> EnumerableDistribution<Thinned<R, K>> for Standard
+where
+    Standard: Distribution<R>,
+{
+    #[inline]
+    fn weighted_sample_space(&self) -> impl Iterator<Item = (Thinned<R, K>, u64)> {
+        let step = Thinned::<R, K>::CHECKED_STEP;
+        R::sample_space()
+            .step_by(step)
+            .zip((0..).step_by(step))
+            .map(move |(r, start): (R, u128)| {
+                let size = (R::CARDINALITY - start).min(step as u128);
+                (Thinned(r), size as u64)
+            })
+    }
+}
+
+impl<R: Cardinality, const K: usize> SmallRandomVariable for Thinned<R, K> where
+    Standard: Distribution<R>
+{
+}
+
+impl<R: Cardinality, const K: usize> Cardinality for Thinned<R, K>
+where
+    Standard: Distribution<R>,
+{
+    const CARDINALITY: u128 = if K == 0 {
+        R::CARDINALITY
+    } else {
+        R::CARDINALITY.div_ceil(K as u128)
+    };
+}
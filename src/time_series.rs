@@ -0,0 +1,165 @@
+use std::vec::Vec;
+
+use rand_core::RngCore;
+
+/// A numeric projection of the states of a single sampled trajectory, as
+/// recorded by [`sample_series`].
+///
+/// `TimeSeries` provides the statistics used to judge how long a simulated
+/// process takes to forget its initial state, such as lagged
+/// autocorrelations and running averages, and [`TimeSeries::skip`] to discard
+/// the burn-in period once it has been chosen.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TimeSeries {
+    values: Vec<f64>,
+}
+
+impl TimeSeries {
+    /// Returns the recorded values, starting with the initial state.
+    #[inline]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the number of recorded values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no values were recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the mean of the values, or [`None`] if there are none.
+    pub fn mean(&self) -> Option<f64> {
+        if self.values.is_empty() {
+            return None;
+        }
+        Some(self.values.iter().sum::<f64>() / self.values.len() as f64)
+    }
+
+    /// Returns the sample variance of the values, or [`None`] if there are
+    /// fewer than two.
+    pub fn variance(&self) -> Option<f64> {
+        if self.values.len() < 2 {
+            return None;
+        }
+        let mean = self.mean()?;
+        let sum = self.values.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+        Some(sum / (self.values.len() - 1) as f64)
+    }
+
+    /// Returns the autocorrelation of the values at `lag` steps, between `-1`
+    /// and `1`.
+    ///
+    /// Returns [`None`] if there are no pairs of values `lag` steps apart, or
+    /// if every value is the same.
+    pub fn autocorrelation(&self, lag: usize) -> Option<f64> {
+        if lag >= self.values.len() {
+            return None;
+        }
+        let mean = self.mean()?;
+        let deviations = self.values.iter().map(|x| x - mean).collect::<Vec<_>>();
+        let variance = deviations.iter().map(|d| d * d).sum::<f64>();
+        if variance == 0.0 {
+            return None;
+        }
+        let covariance = deviations
+            .iter()
+            .zip(&deviations[lag..])
+            .map(|(a, b)| a * b)
+            .sum::<f64>();
+        Some(covariance / variance)
+    }
+
+    /// Returns the autocorrelations of the values at each lag from zero up to
+    /// and including `max_lag`, stopping early at the first lag for which
+    /// [`TimeSeries::autocorrelation`] returns [`None`].
+    pub fn autocorrelations(&self, max_lag: usize) -> Vec<f64> {
+        (0..=max_lag)
+            .map_while(|lag| self.autocorrelation(lag))
+            .collect()
+    }
+
+    /// Returns the mean of the first `n` values, for each `n` from one up to
+    /// the number of values.
+    pub fn running_mean(&self) -> Vec<f64> {
+        let mut sum = 0.0;
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                sum += x;
+                sum / (i + 1) as f64
+            })
+            .collect()
+    }
+
+    /// Returns the values after the first `burn_in`.
+    #[inline]
+    pub fn skip(&self, burn_in: usize) -> Self {
+        Self {
+            values: self.values.get(burn_in..).unwrap_or_default().to_vec(),
+        }
+    }
+}
+
+impl From<Vec<f64>> for TimeSeries {
+    #[inline]
+    fn from(values: Vec<f64>) -> Self {
+        Self { values }
+    }
+}
+
+impl FromIterator<f64> for TimeSeries {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Runs `step`, a random process evaluated with [`Sampler`], for `steps`
+/// steps starting from `initial`, and records `project` applied to every
+/// state of the trajectory as a [`TimeSeries`].
+///
+/// The series contains `steps + 1` values, the first of which is the
+/// projection of `initial`. Each completed step is reported to the callback
+/// installed by [`with_progress`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_chacha::ChaCha8Rng;
+/// use rand_functors::{sample_series, RandomStrategy, Sampler};
+///
+/// let mut rng = ChaCha8Rng::seed_from_u64(0);
+/// let series = sample_series(0u8, 10_000, &mut rng, |s, rng| {
+///     Sampler::fmap_rand(s, rng, |s, r: bool| if r { s.saturating_add(1).min(9) } else { s.saturating_sub(1) })
+/// }, |&s| s as f64);
+/// assert_eq!(series.len(), 10_001);
+/// assert!(series.autocorrelation(1).unwrap() > 0.5);
+/// assert!((series.skip(100).mean().unwrap() - 4.5).abs() < 0.5);
+/// ```
+///
+/// [`Sampler`]: crate::Sampler
+/// [`with_progress`]: crate::with_progress
+pub fn sample_series<T, R: RngCore>(
+    initial: T,
+    steps: usize,
+    rng: &mut R,
+    mut step: impl FnMut(T, &mut R) -> T,
+    project: impl Fn(&T) -> f64,
+) -> TimeSeries {
+    let mut values = Vec::with_capacity(steps.saturating_add(1));
+    values.push(project(&initial));
+    let mut state = initial;
+    for i in 0..steps {
+        state = step(state, rng);
+        values.push(project(&state));
+        crate::progress::report_iteration("sample_series", i + 1, Some(steps));
+    }
+    TimeSeries { values }
+}
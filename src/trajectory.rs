@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use rand::SeedableRng;
+use rand_core::RngCore;
+
+use crate::{DefaultState, FlattenableRandomStrategy, Functor, Inner, Sampler};
+
+/// Runs `step`, a random process evaluated with `S`, for `steps` steps
+/// starting from `initial`, and returns the full trajectory of states leading
+/// to each outcome rather than only the final state.
+///
+/// Every trajectory contains `steps + 1` states, the first of which is
+/// `initial`. With [`Sampler`], the result is the single sampled trajectory.
+/// Strategies which keep several outcomes keep a trajectory for each of them,
+/// so [`Enumerator`] records every possible trajectory, and [`Counter`]
+/// counts how many ways each trajectory can occur. Use [`run_ensemble`] to
+/// record the trajectories of an ensemble of sampled runs. This
+/// allows statistics which depend on the path taken by the process, such as
+/// the first time it reaches a state, to be computed without restructuring
+/// the process itself. Each completed step is reported to the callback
+/// installed by [`with_progress`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{run_trajectory, Enumerator, Functor, RandomStrategy, Sampler};
+///
+/// let mut rng = thread_rng();
+/// let path = run_trajectory::<Sampler, _>(0u8, 10, |s| {
+///     Sampler::fmap_rand(s, &mut rng, |s, r: bool| s + r as u8)
+/// });
+/// assert_eq!(path.len(), 11);
+/// assert!(path.windows(2).all(|w| w[1] - w[0] <= 1));
+///
+/// let paths = run_trajectory::<Enumerator, _>(0u8, 10, |s| {
+///     Enumerator::fmap_rand(Functor::pure(s), &mut rng, |s, r: bool| s + r as u8)
+/// });
+/// assert_eq!(paths.len(), 1 << 10);
+/// ```
+///
+/// [`Counter`]: crate::Counter
+/// [`Enumerator`]: crate::Enumerator
+/// [`Sampler`]: crate::Sampler
+/// [`with_progress`]: crate::with_progress
+pub fn run_trajectory<S: FlattenableRandomStrategy, T: Inner>(
+    initial: T,
+    steps: usize,
+    mut step: impl FnMut(T) -> S::Functor<T>,
+) -> S::Functor<Vec<T>> {
+    let mut path = Vec::with_capacity(steps.saturating_add(1));
+    path.push(initial);
+    let mut f = Functor::pure(path);
+    for i in 0..steps {
+        f = S::fmap_flat(f, |path: Vec<T>| {
+            let last = path.last().cloned().expect("trajectories are never empty");
+            S::fmap(step(last), move |t| {
+                let mut path = path.clone();
+                path.push(t);
+                path
+            })
+        });
+        crate::progress::report_iteration("run_trajectory", i + 1, Some(steps));
+    }
+    f
+}
+
+/// Runs `step`, a random process evaluated with `S`, for `steps` steps
+/// starting from `initial`, and returns the distribution of states after
+/// every step rather than only the final one.
+///
+/// The result contains `steps + 1` functors, the `k`th of which is the
+/// distribution after `k` steps, starting with [`Functor::pure`] of
+/// `initial`. Each distribution is computed from the one before it, so
+/// evolving for `n` steps costs the same as running the process for `n` steps
+/// once, apart from cloning each intermediate functor. This makes it cheap to
+/// plot transient behaviour, such as how quickly a process approaches its
+/// stationary distribution. Each completed step is reported to the callback
+/// installed by [`with_progress`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{evolve, Counter, Functor, RandomStrategy, WeightedFunctor};
+///
+/// let distributions = evolve::<Counter, _>(0u8, 8, |s| {
+///     Counter::fmap_rand(Functor::pure(s), &mut thread_rng(), |s, r: bool| if r { s.saturating_add(1).min(3) } else { 0 })
+/// });
+/// assert_eq!(distributions.len(), 9);
+/// assert_eq!(distributions[0][&0], 1);
+/// assert_eq!(distributions[1].probability_where(|&s| s == 1), 0.5);
+/// assert!(distributions[8].probability_where(|&s| s == 3) > 0.12);
+/// ```
+///
+/// [`with_progress`]: crate::with_progress
+pub fn evolve<S: FlattenableRandomStrategy, T: Inner>(
+    initial: T,
+    steps: usize,
+    mut step: impl FnMut(T) -> S::Functor<T>,
+) -> Vec<S::Functor<T>>
+where
+    S::Functor<T>: Clone,
+{
+    let mut distributions = Vec::with_capacity(steps.saturating_add(1));
+    let mut f: S::Functor<T> = Functor::pure(initial);
+    for i in 0..steps {
+        let next = S::fmap_flat(f.clone(), &mut step);
+        distributions.push(f);
+        f = next;
+        crate::progress::report_iteration("evolve", i + 1, Some(steps));
+    }
+    distributions.push(f);
+    distributions
+}
+
+/// Runs `step`, a random process evaluated with [`Sampler`], for `steps`
+/// steps starting from each of `initial_states`, and returns the sampled
+/// trajectory of each member of the ensemble in order.
+///
+/// Members are run one after another, each for every step, so a process
+/// which draws from a single random number generator gives each member its
+/// own independent run. Every trajectory contains `steps + 1` states, the
+/// first of which is the member's initial state. Each completed member is
+/// reported to the callback installed by [`with_progress`].
+/// ```
+/// use rand::prelude::*;
+/// use rand_functors::{run_ensemble, RandomStrategy, Sampler};
+///
+/// let mut rng = thread_rng();
+/// let ensemble = run_ensemble([0u8; 16], 10, |s| Sampler::fmap_rand(s, &mut rng, |s, r: bool| s + r as u8));
+/// assert_eq!(ensemble.len(), 16);
+/// assert!(ensemble.iter().all(|path| path.len() == 11 && path[0] == 0));
+/// ```
+///
+/// [`Sampler`]: crate::Sampler
+/// [`with_progress`]: crate::with_progress
+pub fn run_ensemble<T: Inner>(
+    initial_states: impl IntoIterator<Item = T>,
+    steps: usize,
+    mut step: impl FnMut(T) -> T,
+) -> Vec<Vec<T>> {
+    let initial_states = initial_states.into_iter().collect::<Vec<_>>();
+    let members = initial_states.len();
+    initial_states
+        .into_iter()
+        .enumerate()
+        .map(|(i, initial)| {
+            let path = run_trajectory::<Sampler, T>(initial, steps, &mut step);
+            crate::progress::report_iteration("run_ensemble", i + 1, Some(members));
+            path
+        })
+        .collect()
+}
+
+/// The trajectories of an ensemble of runs evolved under common random
+/// numbers by [`ensemble_divergence`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Divergence<T: Inner> {
+    trajectories: Vec<Vec<T>>,
+}
+
+impl<T: Inner> Divergence<T> {
+    /// Returns the trajectory of each member of the ensemble, each starting
+    /// with its initial state.
+    #[inline]
+    pub fn trajectories(&self) -> &[Vec<T>] {
+        &self.trajectories
+    }
+
+    /// Returns the states of every member of the ensemble after `step` steps,
+    /// or [`None`] if fewer steps were run.
+    pub fn states(&self, step: usize) -> Option<Vec<&T>> {
+        self.trajectories
+            .iter()
+            .map(|path| path.get(step))
+            .collect()
+    }
+
+    /// Returns the fraction of pairs of members whose states differ after
+    /// each step, starting with their initial states.
+    ///
+    /// Every fraction is zero if the ensemble has fewer than two members.
+    pub fn differing_pairs(&self) -> Vec<f64> {
+        let members = self.trajectories.len();
+        let pairs = members * members.saturating_sub(1) / 2;
+        let steps = self.trajectories.first().map_or(0, Vec::len);
+        (0..steps)
+            .map(|step| {
+                if pairs == 0 {
+                    return 0.0;
+                }
+                let mut groups = HashMap::<&T, usize, DefaultState>::default();
+                for path in &self.trajectories {
+                    *groups.entry(&path[step]).or_insert(0) += 1;
+                }
+                let equal = groups.values().map(|&n| n * (n - 1) / 2).sum::<usize>();
+                (pairs - equal) as f64 / pairs as f64
+            })
+            .collect()
+    }
+
+    /// Returns the first step after which every member is in the same state,
+    /// or [`None`] if that never happens.
+    pub fn first_coalescence(&self) -> Option<usize> {
+        self.differing_pairs().iter().position(|&d| d == 0.0)
+    }
+
+    /// Returns the mean of `distance` over every pair of members after each
+    /// step, starting with their initial states.
+    ///
+    /// Every mean is zero if the ensemble has fewer than two members.
+    pub fn mean_distance(&self, distance: impl Fn(&T, &T) -> f64) -> Vec<f64> {
+        let members = self.trajectories.len();
+        let pairs = members * members.saturating_sub(1) / 2;
+        let steps = self.trajectories.first().map_or(0, Vec::len);
+        (0..steps)
+            .map(|step| {
+                if pairs == 0 {
+                    return 0.0;
+                }
+                let mut total = 0.0;
+                for (i, a) in self.trajectories.iter().enumerate() {
+                    for b in &self.trajectories[i + 1..] {
+                        total += distance(&a[step], &b[step]);
+                    }
+                }
+                total / pairs as f64
+            })
+            .collect()
+    }
+
+    /// Returns the first step after which the mean of `distance` over every
+    /// pair of members exceeds `threshold`, or [`None`] if that never
+    /// happens.
+    pub fn first_divergence(
+        &self,
+        distance: impl Fn(&T, &T) -> f64,
+        threshold: f64,
+    ) -> Option<usize> {
+        self.mean_distance(distance)
+            .iter()
+            .position(|&d| d > threshold)
+    }
+}
+
+/// Runs `step`, a random process evaluated with [`Sampler`], for `steps`
+/// steps starting from each of `initial_states`, using common random numbers
+/// for every member of the ensemble, and records how their trajectories
+/// diverge.
+///
+/// At each step, every member is given its own copy of the same random
+/// number generator, seeded from `seed` and the number of the step with
+/// [`SeedableRng::seed_from_u64`]. Members in the same state therefore stay
+/// in the same state, and any difference between their trajectories is due
+/// only to the differences between their initial states. This makes it
+/// possible to measure how sensitive a randomized process is to its initial
+/// conditions, by starting the ensemble from perturbations of a single state.
+/// Each completed step is reported to the callback installed by
+/// [`with_progress`].
+/// ```
+/// use rand_chacha::ChaCha8Rng;
+/// use rand_functors::{ensemble_divergence, RandomStrategy, Sampler};
+///
+/// let divergence = ensemble_divergence([0u8, 1, 2, 3], 20, 0, |s, rng: &mut ChaCha8Rng| {
+///     Sampler::fmap_rand(s, rng, |s, r: bool| if r { s / 2 } else { s })
+/// });
+/// assert_eq!(divergence.differing_pairs()[0], 1.0);
+/// assert_eq!(divergence.first_divergence(|a, b| a.abs_diff(*b) as f64, 2.0), None);
+/// assert!(divergence.first_coalescence().is_some());
+/// ```
+///
+/// [`Sampler`]: crate::Sampler
+/// [`with_progress`]: crate::with_progress
+pub fn ensemble_divergence<R: SeedableRng + RngCore, T: Inner>(
+    initial_states: impl IntoIterator<Item = T>,
+    steps: usize,
+    seed: u64,
+    mut step: impl FnMut(T, &mut R) -> T,
+) -> Divergence<T> {
+    let mut trajectories = initial_states
+        .into_iter()
+        .map(|initial| {
+            let mut path = Vec::with_capacity(steps.saturating_add(1));
+            path.push(initial);
+            path
+        })
+        .collect::<Vec<_>>();
+    for i in 0..steps {
+        let step_seed = seed.wrapping_add(i as u64);
+        for path in &mut trajectories {
+            let state = path.last().cloned().expect("trajectories are never empty");
+            path.push(step(state, &mut R::seed_from_u64(step_seed)));
+        }
+        crate::progress::report_iteration("ensemble_divergence", i + 1, Some(steps));
+    }
+    Divergence { trajectories }
+}
@@ -0,0 +1,73 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Binomial;
+use rand_functors::{
+    step, AdaptiveCounter, Counter, FlattenableRandomStrategy, Functor, RandomStrategy,
+    SwitchPoint, WeightedRandomStrategy,
+};
+
+fn random_process<S: WeightedRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u16> {
+    let f = step("roll", || {
+        S::fmap_rand(Functor::pure(0u16), rng, |_, r: u8| r as u16 % 8)
+    });
+    let f = S::fmap(f, |s| s * 2);
+    let distribution = Binomial::new(4, 1, 2).unwrap();
+    let f = step("binomial", || {
+        S::fmap_rand_distribution(f, &distribution, rng, |s, k| s + k as u16)
+    });
+    step("spread", || {
+        S::fmap_rand_range(f, 0..100u16, rng, |s, r| s + 100 * r)
+    })
+}
+
+#[test]
+fn test_adaptive_counter_exact() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<AdaptiveCounter<10000>>(&mut rng);
+    assert_eq!(output.switch(), None);
+    assert_eq!(output.into_inner(), random_process::<Counter>(&mut rng));
+}
+
+#[test]
+fn test_adaptive_counter_switch() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<AdaptiveCounter<1000>>(&mut rng);
+    assert_eq!(
+        output.switch(),
+        Some(SwitchPoint {
+            operation: 3,
+            step: Some("spread")
+        })
+    );
+    assert!(output.len() <= 1000);
+    assert_eq!(output.values().sum::<usize>(), 1000);
+    let exact = random_process::<Counter>(&mut rng);
+    assert!(output.keys().all(|k| exact.contains_key(k)));
+
+    let output = random_process::<AdaptiveCounter<10>>(&mut rng);
+    assert_eq!(
+        output.switch(),
+        Some(SwitchPoint {
+            operation: 2,
+            step: Some("binomial")
+        })
+    );
+}
+
+#[test]
+fn test_adaptive_counter_switch_inside_fmap_flat() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = AdaptiveCounter::<4>::fmap_rand(Functor::pure(()), &mut rng, |_, r: bool| r);
+    let output = AdaptiveCounter::<4>::fmap_flat(f, |_| {
+        step("inner", || {
+            AdaptiveCounter::<4>::fmap_rand(Functor::pure(()), &mut rng, |_, r: u8| r)
+        })
+    });
+    assert_eq!(
+        output.switch(),
+        Some(SwitchPoint {
+            operation: 1,
+            step: Some("inner")
+        })
+    );
+}
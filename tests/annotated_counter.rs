@@ -0,0 +1,64 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::monoid::{Max, Min, Monoid, Sum};
+use rand_functors::{
+    Annotated, AnnotatedCounter, Counter, FlattenableRandomStrategy, FromWeightedIterator, Functor,
+    RandomStrategy,
+};
+
+fn random_process<S: FlattenableRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand_range(Functor::pure(0), 1..=6u8, rng, |s, r| s + r);
+    let f = S::fmap_rand(f, rng, |s, r: bool| if r { s * 2 } else { s });
+    S::fmap_flat(f, |s| {
+        S::fmap_rand_range(Functor::pure(s), 0..2u8, &mut thread_rng(), |s, r| {
+            s / 3 + r
+        })
+    })
+}
+
+#[test]
+fn test_annotated_counter_matches_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let counted = random_process::<Counter>(&mut rng);
+    let annotated = random_process::<AnnotatedCounter<()>>(&mut rng);
+    assert_eq!(annotated.len(), counted.len());
+    assert!(counted.iter().all(|(s, &c)| annotated.count(s) == c));
+}
+
+#[test]
+fn test_annotated_counter_merges_metadata() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    // Tag each roll of a die with its value, then keep only its parity.
+    let f: Annotated<u8, (Min<u8>, Max<u8>)> = Functor::pure(0);
+    let f = AnnotatedCounter::fmap_rand_range(f, 1..=6u8, &mut rng, |_, r| r);
+    let f = f.annotate(|&r, _| (Min(r), Max(r)));
+    let f = AnnotatedCounter::fmap(f, |r| r % 2);
+    assert_eq!(f.count(&0), 3);
+    assert_eq!(f.metadata(&0), Some(&(Min(2), Max(6))));
+    assert_eq!(f.metadata(&1), Some(&(Min(1), Max(5))));
+    assert_eq!(f.metadata(&2), None);
+}
+
+#[test]
+fn test_annotated_counter_fmap_flat() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = Annotated::new(0u8, Sum(1u32));
+    let f = AnnotatedCounter::fmap_rand(f, &mut rng, |_, r: bool| r as u8);
+    let f = AnnotatedCounter::fmap_flat(f, |s| {
+        let child =
+            AnnotatedCounter::fmap_rand(Functor::pure(s), &mut thread_rng(), |s, r: bool| {
+                s & r as u8
+            });
+        child.annotate(|_, m: Sum<u32>| m.combine(Sum(10)))
+    });
+    // Each child combines the metadata of its parent with its own, and 0 is
+    // reached from both parents.
+    assert_eq!(f.count(&0), 3);
+    assert_eq!(f.metadata(&0), Some(&Sum(22)));
+    assert_eq!(f.metadata(&1), Some(&Sum(11)));
+
+    let f = Annotated::<u8, Sum<u32>>::from_weighted_iter([(1, 2), (1, 3), (2, 0)]);
+    assert_eq!(f.count(&1), 5);
+    assert_eq!(f.metadata(&1), Some(&Sum(0)));
+    assert_eq!(f.metadata(&2), None);
+}
@@ -0,0 +1,67 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Approximate, Counter, Functor, ProbabilityBounds, RandomStrategy};
+
+#[test]
+fn test_approximate_top_k() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Counter>::fmap_rand_range(Functor::pure(0), 1..=6u8, &mut rng, |s, r| s + r);
+    let f = <Counter>::fmap_rand_range(f, 1..=6u8, &mut rng, |s, r| s + r);
+    let exact = Approximate::exact(f.clone());
+    assert!(exact.is_exact());
+    assert_eq!(exact.probability(&7), ProbabilityBounds::exact(1.0 / 6.0));
+
+    // The five most likely sums are 5 through 9, with 24 of the 36 rolls.
+    let f = Approximate::exact(f).top_k(5);
+    assert_eq!(f.outcomes().len(), 5);
+    assert!([5, 6, 7, 8, 9].iter().all(|s| f.outcomes().contains_key(s)));
+    assert!((f.discarded() - 12.0 / 36.0).abs() < 1e-12);
+    let seven = f.probability(&7);
+    assert!((seven.lower - 1.0 / 6.0).abs() < 1e-12);
+    assert!((seven.width() - 12.0 / 36.0).abs() < 1e-12);
+    assert_eq!(f.probability(&2).lower, 0.0);
+    assert!(f.probability(&2).contains(1.0 / 36.0));
+
+    assert_eq!(Approximate::exact(f.into_inner()).top_k(0).discarded(), 1.0);
+}
+
+#[test]
+fn test_approximate_repeated_pruning() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut exact = <Counter>::fmap_rand(Functor::pure(0u8), &mut rng, |_, r: u8| r % 16);
+    let mut approximate = Approximate::exact(exact.clone());
+    for _ in 0..4 {
+        exact = <Counter>::fmap_rand(exact, &mut rng, |s, r: bool| s.wrapping_add(r as u8));
+        approximate = approximate
+            .map(|f| <Counter>::fmap_rand(f, &mut rng, |s, r: bool| s.wrapping_add(r as u8)))
+            .prune_below(0.01);
+    }
+    assert!(!approximate.is_exact());
+    assert!(approximate.discarded() < 0.1);
+
+    let total = exact.values().sum::<usize>() as f64;
+    for s in 0..=20u8 {
+        let p = exact.get(&s).map_or(0.0, |&c| c as f64 / total);
+        assert!(approximate.probability(&s).contains(p));
+        let p = exact
+            .iter()
+            .filter(|(&t, _)| t < s)
+            .map(|(_, &c)| c as f64)
+            .sum::<f64>()
+            / total;
+        let bounds = approximate.probability_where(|&t| t < s);
+        assert!(bounds.lower <= p + 1e-12 && p - 1e-12 <= bounds.upper);
+        assert!(bounds.width() <= approximate.discarded() + 1e-12);
+    }
+}
+
+#[test]
+fn test_probability_bounds_display() {
+    let bounds = ProbabilityBounds {
+        lower: 0.25,
+        upper: 0.5,
+    };
+    assert_eq!(bounds.to_string(), "[0.25, 0.5]");
+    assert_eq!(bounds.width(), 0.25);
+    assert!(!bounds.contains(0.75));
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "arrow")]
+
+use std::sync::Arc;
+
+use arrow_array::{Array, ArrayRef, UInt64Array, UInt8Array};
+use arrow_schema::{DataType, Field};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{ArrowExt, ArrowRecord, Counter, Enumerator, Functor, RandomStrategy};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct State {
+    x: u8,
+    y: u8,
+}
+
+impl ArrowRecord for State {
+    fn fields() -> Vec<Field> {
+        vec![
+            Field::new("x", DataType::UInt8, false),
+            Field::new("y", DataType::UInt8, false),
+        ]
+    }
+
+    fn columns(records: &[&Self]) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(UInt8Array::from_iter_values(records.iter().map(|s| s.x))),
+            Arc::new(UInt8Array::from_iter_values(records.iter().map(|s| s.y))),
+        ]
+    }
+}
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<State> {
+    let functor = Functor::pure(State { x: 0, y: 0 });
+    let functor = S::fmap_rand_range(functor, 0..3, rng, |s, x| State { x, ..s });
+    S::fmap_rand(functor, rng, |s, r: bool| State { y: r as u8, ..s })
+}
+
+#[test]
+fn test_enumerator_to_record_batch() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    let batch = output.to_record_batch().unwrap();
+
+    assert_eq!(batch.num_rows(), 6);
+    assert_eq!(batch.num_columns(), 3);
+    assert_eq!(batch.schema().field(2).name(), "weight");
+
+    let x = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<UInt8Array>()
+        .unwrap();
+    assert_eq!(x.values(), &[0, 0, 1, 1, 2, 2]);
+    let weight = batch
+        .column(2)
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap();
+    assert!(weight.values().iter().all(|w| *w == 1));
+}
+
+#[test]
+fn test_counter_to_record_batch() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let functor = <Counter>::fmap(random_process::<Counter>(&mut rng), |s| s.y);
+    let batch = functor.to_record_batch().unwrap();
+
+    assert_eq!(batch.num_rows(), 2);
+    let weight = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap();
+    assert_eq!(weight.values().iter().sum::<u64>(), 6);
+    assert_eq!(weight.null_count(), 0);
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_enumerator_write_parquet() {
+    use std::fs::File;
+
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use rand_functors::ParquetExt;
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    let path = std::env::temp_dir().join("rand_functors_test_enumerator_write_parquet.parquet");
+    output.write_parquet(File::create(&path).unwrap()).unwrap();
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 6);
+    assert_eq!(batches[0], output.to_record_batch().unwrap());
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "alloc")]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    AsyncRandomStrategy, Enumerator, Functor, PopulationSampler, RandomStrategy, Sampler,
+};
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Yields to the executor once before producing `value`, like a remote call.
+async fn remote<T>(value: T) -> T {
+    let mut yielded = false;
+    std::future::poll_fn(|context| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            context.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await;
+    value
+}
+
+async fn random_process<S: AsyncRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand_async(Functor::pure(1), rng, |s, r: bool| remote(s + r as u8)).await;
+    let f = S::fmap_rand_range_async(f, 1..=3u8, rng, |s, r| remote(s * r)).await;
+    S::fmap_async(f, |s| remote(s + 1)).await
+}
+
+fn sync_random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand(Functor::pure(1), rng, |s, r: bool| s + r as u8);
+    let f = S::fmap_rand_range(f, 1..=3u8, rng, |s, r| s * r);
+    S::fmap(f, |s| s + 1)
+}
+
+#[test]
+fn test_async_sampler_matches_sync() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut sync_rng = rng.clone();
+    for _ in 0..100 {
+        assert_eq!(
+            block_on(random_process::<Sampler>(&mut rng)),
+            sync_random_process::<Sampler>(&mut sync_rng)
+        );
+    }
+}
+
+#[test]
+fn test_async_population_sampler_enumerates_within_capacity() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = block_on(random_process::<PopulationSampler<64>>(&mut rng));
+    assert_eq!(output, sync_random_process::<Enumerator>(&mut rng));
+}
+
+#[test]
+fn test_async_population_sampler_shrinks() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let all = sync_random_process::<Enumerator>(&mut rng);
+    let output = block_on(random_process::<PopulationSampler<4>>(&mut rng));
+    assert_eq!(output.len(), 4);
+    assert!(output.iter().all(|s| all.contains(s)));
+}
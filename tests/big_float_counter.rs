@@ -0,0 +1,79 @@
+#![cfg(feature = "dashu-float")]
+
+use dashu_float::FBig;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Binomial;
+use rand_functors::{
+    BigFloatCounter, Counter, DefaultState, FlattenableRandomStrategy, Functor, RandomStrategy,
+    WeightedRandomStrategy,
+};
+
+fn die<S: RandomStrategy>(sides: u8, rng: &mut impl Rng) -> S::Functor<u8> {
+    S::fmap_rand_range(Functor::pure(()), 1..=sides, rng, |_, r| r)
+}
+
+fn close(p: &FBig, expected: f64) -> bool {
+    (p.to_f64().value() - expected).abs() < 1e-15
+}
+
+#[test]
+fn test_big_float_counter_matches_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <BigFloatCounter>::fmap_rand(Functor::pure(()), &mut rng, |_, r: bool| r);
+    let f = <BigFloatCounter>::fmap_flat(f, |critical| {
+        die::<BigFloatCounter>(if critical { 11 } else { 13 }, &mut thread_rng())
+    });
+    assert!(close(&f[&1], 1.0 / 22.0 + 1.0 / 26.0));
+    assert!(close(&f[&13], 1.0 / 26.0));
+    assert!(close(&f.values().cloned().sum(), 1.0));
+
+    let counted = <Counter<DefaultState, u128>>::fmap_rand_range(
+        die::<Counter<DefaultState, u128>>(6, &mut rng),
+        1..=6u8,
+        &mut rng,
+        |s, r| s + r,
+    );
+    let f = <BigFloatCounter>::fmap_rand_range(
+        die::<BigFloatCounter>(6, &mut rng),
+        1..=6u8,
+        &mut rng,
+        |s, r| s + r,
+    );
+    assert_eq!(f.len(), counted.len());
+    assert!(f.iter().all(|(s, p)| close(p, counted[s] as f64 / 36.0)));
+}
+
+#[test]
+fn test_big_float_counter_absorbing_and_distribution() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut f = <BigFloatCounter>::fmap_rand(Functor::pure(0u8), &mut rng, |_, r: bool| r as u8);
+    for _ in 0..3 {
+        f = <BigFloatCounter>::fmap_rand_absorbing(f, |&s| s == 1, &mut rng, |_, r: bool| r as u8);
+    }
+    let sixteenth: FBig = FBig::ONE >> 4;
+    assert_eq!(f[&0], sixteenth);
+    assert_eq!(f[&1], FBig::ONE - sixteenth);
+
+    let f = <BigFloatCounter>::fmap_rand_distribution(
+        Functor::pure(()),
+        &Binomial::new(3, 1, 2).unwrap(),
+        &mut rng,
+        |_, k| k as u8,
+    );
+    assert!(close(&f[&0], 0.125));
+    assert!(close(&f[&1], 0.375));
+    assert!(close(&f[&3], 0.125));
+}
+
+#[test]
+fn test_big_float_counter_precision() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <BigFloatCounter<16>>::fmap_rand_range(Functor::pure(()), 0..3u8, &mut rng, |_, r| r);
+    assert_eq!(f[&0].precision(), 16);
+    let f = <BigFloatCounter<256>>::fmap_rand_range(Functor::pure(()), 0..3u8, &mut rng, |_, r| r);
+    assert_eq!(f[&0].precision(), 256);
+    // Rounding towards zero, the more precise third is the larger.
+    let three: FBig = FBig::from(3u8).with_precision(16).value();
+    assert!(f[&0] > FBig::ONE / three);
+}
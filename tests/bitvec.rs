@@ -0,0 +1,39 @@
+#![cfg(feature = "bitvec")]
+
+use bitvec::vec::BitVec;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Enumerator, Functor, RandomStrategy, ReportExt, WeightedFunctor};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<bool> {
+    let f = S::fmap_rand(Functor::pure(false), rng, |a, b: bool| a || b);
+    S::fmap_rand(f, rng, |a, b: bool| a && b)
+}
+
+#[test]
+fn test_pack_enumerator_outcomes() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let outcomes = random_process::<Enumerator>(&mut rng);
+    let packed: BitVec = outcomes.iter().copied().collect();
+
+    assert_eq!(
+        Functor::<bool>::size(&packed),
+        Functor::<bool>::size(&outcomes)
+    );
+    assert!(packed
+        .weighted_outcomes()
+        .map(|(b, w)| (*b, w))
+        .eq(outcomes.weighted_outcomes().map(|(b, w)| (*b, w))));
+
+    let report = packed.report(None);
+    assert_eq!(report.distinct_outcomes, 2);
+    assert!(!*report.outcomes[0].outcome);
+    assert_eq!(report.outcomes[0].probability, 0.75);
+}
+
+#[test]
+fn test_pure_bitvec() {
+    let packed: BitVec = Functor::pure(true);
+    assert_eq!(Functor::<bool>::size(&packed), 1);
+    assert_eq!(packed.weighted_outcomes().collect::<Vec<_>>(), [(&true, 1)]);
+}
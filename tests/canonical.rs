@@ -0,0 +1,52 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Canonical, Canonicalization, Counter, Functor, RandomStrategy, UniqueEnumerator, D6,
+};
+
+struct Unordered;
+
+impl Canonicalization<[u8; 3]> for Unordered {
+    fn canonicalize(mut value: [u8; 3]) -> [u8; 3] {
+        value.sort();
+        value
+    }
+}
+
+type Players = Canonical<[u8; 3], Unordered>;
+
+// Players who have not rolled yet have a score of zero, so the first score of
+// a canonical state always belongs to one of them.
+fn roll<S: RandomStrategy>(f: S::Functor<Players>, rng: &mut impl Rng) -> S::Functor<Players> {
+    S::fmap_rand(f, rng, |s: Players, r: D6| {
+        s.map(|[a, b, c]| [a + r.value(), b, c])
+    })
+}
+
+#[test]
+fn test_canonical_construction() {
+    let s = Players::new([3, 1, 2]);
+    assert_eq!(s.get(), [1, 2, 3]);
+    assert_eq!(s, Players::from([2, 3, 1]));
+    assert_eq!(*s.map(|[a, b, c]| [c, b, a + 9]), [2, 3, 10]);
+}
+
+#[test]
+fn test_canonical_merges_symmetric_states() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut f = Functor::pure(Players::new([0; 3]));
+    for _ in 0..3 {
+        f = roll::<UniqueEnumerator>(f, &mut rng);
+    }
+    // There are 56 multisets of three rolls, rather than 216 sequences.
+    assert_eq!(f.len(), 56);
+
+    let mut f = Functor::pure(Players::new([0; 3]));
+    for _ in 0..3 {
+        f = roll::<Counter>(f, &mut rng);
+    }
+    assert_eq!(f.len(), 56);
+    assert_eq!(f.values().sum::<usize>(), 216);
+    assert_eq!(f[&Players::new([1, 1, 1])], 1);
+    assert_eq!(f[&Players::new([3, 1, 2])], 6);
+}
@@ -0,0 +1,160 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Counter, Enumerator, FlattenableRandomStrategy, Functor, RandomStrategy, Sampler,
+    UniqueEnumerator, WeightedFunctor,
+};
+
+fn roll<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    S::fmap_rand_range(Functor::pure(()), 1..=4, rng, |_, r| r)
+}
+
+fn reroll_ones<S: FlattenableRandomStrategy>(
+    rng: &mut impl Rng,
+    max_iters: usize,
+) -> S::Functor<u8> {
+    let f = roll::<S>(rng);
+    S::loop_until(f, |&r| r != 1, max_iters, |_| roll::<S>(rng))
+}
+
+#[test]
+fn test_loop_until_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = reroll_ones::<Counter>(&mut rng, 3);
+    assert_eq!(output.to_sorted_vec(), [(1, 1), (2, 85), (3, 85), (4, 85)]);
+}
+
+#[test]
+fn test_loop_until_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = reroll_ones::<Enumerator>(&mut rng, 3);
+    assert_eq!(
+        output.to_sorted_vec(),
+        reroll_ones::<Counter>(&mut rng, 3).to_sorted_vec()
+    );
+    assert_eq!(reroll_ones::<Enumerator>(&mut rng, 0).len(), 4);
+}
+
+#[test]
+fn test_loop_until_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let ones = (0..1000)
+        .filter(|_| reroll_ones::<Sampler>(&mut rng, 3) == 1)
+        .count();
+    assert!(ones < 16);
+}
+
+fn damage<S: FlattenableRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand(Functor::pure(()), rng, |_, critical: bool| critical);
+    S::if_then_else(
+        f,
+        |&critical| critical,
+        rng,
+        |_, rng| S::fmap_rand_range(Functor::pure(()), 1..=6, rng, |_, r: u8| r * 2),
+        |_, rng| S::fmap_rand_range(Functor::pure(()), 1..=3, rng, |_, r: u8| r),
+    )
+}
+
+#[test]
+fn test_if_then_else_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = damage::<Counter>(&mut rng);
+    assert_eq!(
+        output.to_sorted_vec(),
+        [
+            (1, 6),
+            (2, 9),
+            (3, 6),
+            (4, 3),
+            (6, 3),
+            (8, 3),
+            (10, 3),
+            (12, 3)
+        ]
+    );
+}
+
+#[test]
+fn test_if_then_else_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = damage::<Enumerator>(&mut rng);
+    assert_eq!(output.len(), 36);
+    assert_eq!(
+        output.to_sorted_vec(),
+        damage::<Counter>(&mut rng).to_sorted_vec()
+    );
+}
+
+#[test]
+fn test_if_then_else_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for _ in 0..100 {
+        assert!([1, 2, 3, 4, 6, 8, 10, 12].contains(&damage::<Sampler>(&mut rng)));
+    }
+}
+
+fn absorbing_walk<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<i8> {
+    let mut f = Functor::pure(0i8);
+    for _ in 0..3 {
+        f = S::fmap_rand_absorbing(
+            f,
+            |s| s.abs() >= 2,
+            rng,
+            |s, r: bool| {
+                if r {
+                    s + 1
+                } else {
+                    s - 1
+                }
+            },
+        );
+    }
+    S::fmap_rand_range_absorbing(f, |s| s.abs() >= 2, -1..=1, rng, |s, r: i8| s + r)
+}
+
+#[test]
+fn test_absorbing_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = absorbing_walk::<Counter>(&mut rng);
+    assert_eq!(
+        output.to_sorted_vec(),
+        [(-2, 8), (-1, 2), (0, 4), (1, 2), (2, 8)]
+    );
+}
+
+#[test]
+fn test_absorbing_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = absorbing_walk::<Enumerator>(&mut rng);
+    assert_eq!(
+        output.to_sorted_vec(),
+        absorbing_walk::<Counter>(&mut rng).to_sorted_vec()
+    );
+}
+
+#[test]
+fn test_absorbing_unique_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = absorbing_walk::<UniqueEnumerator>(&mut rng);
+    let mut output: Vec<_> = output.into_iter().collect();
+    output.sort();
+    assert_eq!(output, [-2, -1, 0, 1, 2]);
+}
+
+#[test]
+fn test_absorbing_skips_func() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let calls = core::cell::Cell::new(0);
+    let f = <Counter>::fmap_rand_range(Functor::pure(()), 0..4u8, &mut rng, |_, r| r);
+    let output = <Counter>::fmap_rand_absorbing(
+        f,
+        |&s| s != 0,
+        &mut rng,
+        |s, r: bool| {
+            calls.set(calls.get() + 1);
+            s + r as u8
+        },
+    );
+    assert_eq!(calls.get(), 2);
+    assert_eq!(output.to_sorted_vec(), [(0, 1), (1, 3), (2, 2), (3, 2)]);
+}
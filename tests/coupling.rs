@@ -0,0 +1,72 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{coupling_from_the_past, Functor, RandomStrategy, Sampler};
+
+/// A lazy random walk on `0..=4`, monotone under the usual order.
+fn walk<S: RandomStrategy>(s: u8, rng: &mut impl Rng) -> S::Functor<u8> {
+    S::fmap_rand_range(Functor::pure(s), 0..3u8, rng, |s, r| match r {
+        0 => s.saturating_sub(1),
+        1 => s,
+        _ => (s + 1).min(4),
+    })
+}
+
+#[test]
+fn test_coupling_from_the_past() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut counts = [0usize; 5];
+    for _ in 0..2000 {
+        let sample = coupling_from_the_past(
+            0,
+            4,
+            |a, b| a <= b,
+            1 << 16,
+            &mut rng,
+            |s, rng: &mut ChaCha8Rng| walk::<Sampler>(s, rng),
+        )
+        .unwrap();
+        assert!(sample.steps.is_power_of_two());
+        counts[sample.state as usize] += 1;
+    }
+    for count in counts {
+        assert!((300..500).contains(&count), "{counts:?}");
+    }
+}
+
+#[test]
+fn test_coupling_from_the_past_bound() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let sample = coupling_from_the_past(
+        0,
+        4,
+        |a, b| a <= b,
+        3,
+        &mut rng,
+        |s, rng: &mut ChaCha8Rng| walk::<Sampler>(s, rng),
+    );
+    assert_eq!(sample, None);
+
+    let sample = coupling_from_the_past(
+        2,
+        2,
+        |a, b| a <= b,
+        0,
+        &mut rng,
+        |s, rng: &mut ChaCha8Rng| walk::<Sampler>(s, rng),
+    );
+    assert_eq!(sample.map(|s| (s.state, s.steps)), Some((2, 0)));
+}
+
+#[test]
+#[should_panic(expected = "step must be monotone")]
+fn test_coupling_from_the_past_not_monotone() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    coupling_from_the_past(
+        0u8,
+        4,
+        |a, b| a <= b,
+        64,
+        &mut rng,
+        |s, _: &mut ChaCha8Rng| 4 - s,
+    );
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "std")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Counter, CoverageEnumerator, Covered, FlattenableRandomStrategy, Functor, RandomStrategy,
+    WeightedFunctor,
+};
+
+fn random_process<S: FlattenableRandomStrategy>(
+    f: S::Functor<()>,
+    rng: &mut impl Rng,
+) -> S::Functor<u8> {
+    let f = S::fmap_rand_range(f, 0..4u8, rng, |_, r| r);
+    let f = S::fmap_rand(f, rng, |s, r: bool| if r { s } else { 0 });
+    S::fmap_flat(f, |s| {
+        S::fmap_rand_range(Functor::pure(s), 0..2u8, rng, |s, r| s.saturating_sub(r))
+    })
+}
+
+#[test]
+fn test_full_coverage() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<CoverageEnumerator>(Covered::with_coverage((), 1.0), &mut rng);
+    assert_eq!(output.residual(), 0.0);
+    assert_eq!(output.len(), 4);
+    assert_eq!(
+        output,
+        random_process::<CoverageEnumerator>(Functor::pure(()), &mut rng)
+    );
+
+    let expected = random_process::<Counter>(Functor::pure(()), &mut rng).normalize_map();
+    for (outcome, p) in output.iter() {
+        assert!((p - expected[outcome]).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_partial_coverage() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<CoverageEnumerator>(Covered::with_coverage((), 0.9), &mut rng);
+    // P(0) = 11/16, P(1) = 2/16, P(2) = 2/16, P(3) = 1/16.
+    assert_eq!(output.len(), 3);
+    assert!(!output.contains_key(&3));
+    assert_eq!(output.residual(), 1.0 / 16.0);
+    assert_eq!(output.covered(), 15.0 / 16.0);
+    assert_eq!(output.probability_where(|&s| s == 0), 11.0 / 15.0);
+    let bounds = output.probability_bounds(|&s| s >= 2);
+    assert_eq!(bounds.lower, 2.0 / 16.0);
+    assert_eq!(bounds.upper, 3.0 / 16.0);
+}
+
+#[test]
+fn test_pruning_between_steps() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = Covered::with_coverage((), 0.5);
+    let output = CoverageEnumerator::fmap_rand_range(f, 0..4u8, &mut rng, |_, r| r);
+    assert_eq!(output.len(), 2);
+    assert_eq!(output.residual(), 0.5);
+    assert!(output.values().all(|&p| p == 0.25));
+}
+
+#[test]
+#[should_panic(expected = "coverage must be between zero and one")]
+fn test_invalid_coverage() {
+    Covered::with_coverage((), 1.5);
+}
+
+#[test]
+#[should_panic(expected = "cannot enumerate an empty range")]
+fn test_empty_range() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    CoverageEnumerator::fmap_rand_range(
+        Covered::with_coverage((), 0.9),
+        0..0u8,
+        &mut rng,
+        |_, r| r,
+    );
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "csv")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Counter, CsvExt, Enumerator, Functor, RandomStrategy};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng, base: u8) -> S::Functor<u8> {
+    let functor = Functor::pure(base);
+    S::fmap_rand_range(functor, 0..4, rng, |d, r: u8| d.saturating_sub(r) / 2)
+}
+
+#[test]
+fn test_counter_to_csv() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Counter>(&mut rng, 5);
+    let csv = output
+        .to_csv(&["value", "parity"], |&d| [d, d % 2])
+        .unwrap();
+
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("value,parity,weight"));
+    let mut rows = lines.collect::<Vec<_>>();
+    rows.sort();
+    assert_eq!(rows, ["1,1,2", "2,0,2"]);
+}
+
+#[test]
+fn test_to_csv_unequal_lengths() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Counter>(&mut rng, 5);
+    let error = output.to_csv(&["value", "parity"], |&d| [d]).unwrap_err();
+
+    assert!(matches!(
+        error.kind(),
+        csv::ErrorKind::UnequalLengths {
+            expected_len: 3,
+            len: 2,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_enumerator_write_csv() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng, 5);
+    let mut buffer = Vec::new();
+    output.write_csv(&mut buffer, &["value"], |&d| [d]).unwrap();
+
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "value,weight\n2,1\n2,1\n1,1\n1,1\n"
+    );
+}
@@ -1,7 +1,10 @@
 use rand::distributions::Standard;
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
-use rand_functors::{Enumerator, Functor, RandomStrategy, RandomVariable};
+use rand_functors::{
+    Cardinality, Enumerator, Functor, MaxCardinality, RandomStrategy, RandomVariable,
+    SmallRandomVariable,
+};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Pair<A: Clone + RandomVariable, B: Clone + RandomVariable>
@@ -36,6 +39,30 @@ where
     }
 }
 
+impl<A: Clone + SmallRandomVariable, B: Clone + SmallRandomVariable> SmallRandomVariable
+    for Pair<A, B>
+where
+    Standard: Distribution<A>,
+    Standard: Distribution<B>,
+{
+}
+
+impl<A: Clone + Cardinality, B: Clone + Cardinality> Cardinality for Pair<A, B>
+where
+    Standard: Distribution<A>,
+    Standard: Distribution<B>,
+{
+    const CARDINALITY: u128 = A::CARDINALITY.saturating_mul(B::CARDINALITY);
+}
+
+fn bounded_cardinality<R: MaxCardinality<512>>() -> u128
+where
+    Standard: Distribution<R>,
+{
+    let () = R::WITHIN_MAX;
+    R::CARDINALITY
+}
+
 fn dummy_random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<Pair<bool, u8>> {
     S::fmap_rand(Functor::pure(()), rng, |_, pair| pair)
 }
@@ -52,3 +79,14 @@ fn test_enumerator_on_custom_random_variable() {
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_cardinality() {
+    assert_eq!(bool::CARDINALITY, 2);
+    assert_eq!(i16::CARDINALITY, 65536);
+    assert_eq!(u64::CARDINALITY, 1 << 64);
+    assert_eq!(i128::CARDINALITY, u128::MAX);
+    assert_eq!(<Pair<u32, u32>>::CARDINALITY, 1 << 64);
+    assert_eq!(bounded_cardinality::<u8>(), 256);
+    assert_eq!(bounded_cardinality::<Pair<bool, u8>>(), 512);
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Comparison, Counter, DefaultState, DenseCounts, Functor, RandomStrategy};
+
+fn die(sides: i8, rng: &mut impl Rng) -> HashMap<i8, usize> {
+    <Counter>::fmap_rand_range(Functor::pure(()), 1..=sides, rng, |_, r| r)
+}
+
+#[test]
+fn test_dense_counts_compact() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Counter>::fmap_rand_range(die(6, &mut rng), 1..=6i8, &mut rng, |s, r| s - r);
+    let dense = DenseCounts::compact(f.clone(), 11).unwrap();
+    assert_eq!((dense.min(), dense.max()), (Some(-5), Some(5)));
+    assert_eq!(dense.counts(), [1, 2, 3, 4, 5, 6, 5, 4, 3, 2, 1]);
+    assert_eq!(dense.get(0), 6);
+    assert_eq!(dense.get(100), 0);
+    assert_eq!(dense.iter().count(), 11);
+    assert_eq!(dense.into_counter::<DefaultState>(), f);
+
+    let f = DenseCounts::compact(f, 10).unwrap_err();
+    assert_eq!(f.len(), 11);
+
+    let sparse = <Counter>::fmap_rand(
+        Functor::pure(()),
+        &mut rng,
+        |_, r: bool| if r { 0 } else { 3i8 },
+    );
+    let dense = DenseCounts::compact(sparse, 4).unwrap();
+    assert_eq!(dense.counts(), [1, 0, 0, 1]);
+    assert_eq!(dense.iter().collect::<Vec<_>>(), [(0, 1), (3, 1)]);
+
+    let empty = HashMap::<i8, usize>::default();
+    assert!(DenseCounts::compact(empty, 0).unwrap().is_empty());
+}
+
+#[test]
+fn test_dense_counts_convolve() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let counted = <Counter>::fmap_rand_range(die(4, &mut rng), 1..=8i8, &mut rng, |s, r| s + r);
+    let counted = <Counter>::fmap_rand_range(counted, 1..=8i8, &mut rng, |s, r| s - r);
+
+    let d4 = DenseCounts::compact(die(4, &mut rng), 8).unwrap();
+    let d8 = DenseCounts::compact(die(8, &mut rng), 8).unwrap();
+    let negative_d8 = DenseCounts::new(-8, d8.counts().iter().rev().copied().collect());
+    let dense = d4.convolve(&d8).convolve(&negative_d8);
+    assert_eq!(HashMap::from(dense), counted);
+}
+
+#[test]
+fn test_dense_counts_compare() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let counted =
+        <Counter>::fmap_rand_range(die(6, &mut rng), 1..=10i8, &mut rng, |s, r| s.cmp(&r));
+
+    let d6 = DenseCounts::compact(die(6, &mut rng), 10).unwrap();
+    let d10 = DenseCounts::compact(die(10, &mut rng), 10).unwrap();
+    assert_eq!(
+        d6.compare(&d10),
+        Comparison {
+            less: counted[&std::cmp::Ordering::Less],
+            equal: counted[&std::cmp::Ordering::Equal],
+            greater: counted[&std::cmp::Ordering::Greater],
+        }
+    );
+    assert_eq!(d10.compare(&d6).greater, d6.compare(&d10).less);
+}
@@ -0,0 +1,42 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::testing::{audit_uniformity, check_random_variable};
+use rand_functors::{Counter, Die, Enumerator, Functor, RandomStrategy, RandomVariable, D20, D4};
+
+fn attack<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand(Functor::pure(()), rng, |_, r: D20| r.value() >= 15);
+    S::fmap_rand(f, rng, |hit, r: D4| if hit { r.value() } else { 0 })
+}
+
+#[test]
+fn test_die_sample_space() {
+    assert!(D4::sample_space().map(u8::from).eq(1..=4));
+    assert_eq!(Die::<1>::sample_space().count(), 1);
+    assert_eq!(D20::new(20).map(Die::value), Some(20));
+    assert_eq!(D20::new(0), None);
+    assert_eq!(D20::new(21), None);
+    assert_eq!(D4::new(3).unwrap().to_string(), "3");
+}
+
+#[test]
+fn test_die_consistency() {
+    check_random_variable::<D20>();
+    check_random_variable::<Die<255>>();
+    audit_uniformity::<D20>(&mut ChaCha8Rng::seed_from_u64(0), 10000);
+}
+
+#[test]
+fn test_dice_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = attack::<Counter>(&mut rng);
+    assert_eq!(output[&0], 14 * 4);
+    for damage in 1..=4 {
+        assert_eq!(output[&damage], 6);
+    }
+}
+
+#[test]
+fn test_dice_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert_eq!(attack::<Enumerator>(&mut rng).len(), 80);
+}
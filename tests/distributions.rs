@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::{
+    Bernoulli, Binomial, Categorical, ConstBernoulli, DiscreteUniform, DistributionError,
+    Hypergeometric, Probability, TruncatedPoisson,
+};
+use rand_functors::{
+    Counter, DefaultState, EnumerableDistribution, Enumerator, Functor, RandomStrategy, Sampler,
+    UniqueEnumerator, WeightedRandomStrategy,
+};
+
+fn random_process<S: WeightedRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u32> {
+    let functor = Functor::pure(0);
+    let distribution = Binomial::new(3, 1, 3).unwrap();
+    let functor = S::fmap_rand_distribution(functor, &distribution, rng, |s, k| s + k);
+    let distribution = Bernoulli::new(1, 4).unwrap();
+    S::fmap_rand_distribution(functor, &distribution, rng, |s, b| if b { 0 } else { s })
+}
+
+#[test]
+fn test_bernoulli_weighted_sample_space() {
+    let distribution = Bernoulli::new(2, 6).unwrap();
+    assert_eq!(
+        distribution.weighted_sample_space().collect::<Vec<_>>(),
+        [(false, 2), (true, 1)]
+    );
+    let certain = Bernoulli::new(5, 5).unwrap();
+    assert_eq!(
+        certain.weighted_sample_space().collect::<Vec<_>>(),
+        [(true, 1)]
+    );
+    assert_eq!(
+        Bernoulli::new(3, 2),
+        Err(DistributionError::InvalidProbability)
+    );
+    assert_eq!(
+        Bernoulli::new(0, 0),
+        Err(DistributionError::InvalidProbability)
+    );
+}
+
+#[test]
+fn test_const_bernoulli() {
+    assert_eq!(
+        ConstBernoulli::<2, 6>
+            .weighted_sample_space()
+            .collect::<Vec<_>>(),
+        [(false, 2), (true, 1)]
+    );
+    assert_eq!(
+        ConstBernoulli::<0, 7>
+            .weighted_sample_space()
+            .collect::<Vec<_>>(),
+        [(false, 1)]
+    );
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let successes = (0..30000)
+        .filter(|_| ConstBernoulli::<1, 3>.sample(&mut rng))
+        .count();
+    assert!((9500..10500).contains(&successes));
+
+    let output = <Counter>::fmap_rand_distribution(
+        Functor::pure(0u8),
+        &ConstBernoulli::<3, 4>,
+        &mut rng,
+        |s, b| s + b as u8,
+    );
+    assert_eq!(output, HashMap::from([(0, 1), (1, 3)]));
+}
+
+#[test]
+fn test_binomial_weighted_sample_space() {
+    let distribution = Binomial::new(3, 1, 3).unwrap();
+    assert_eq!(
+        distribution.weighted_sample_space().collect::<Vec<_>>(),
+        [(0, 8), (1, 12), (2, 6), (3, 1)]
+    );
+    let fair = Binomial::new(4, 1, 2).unwrap();
+    assert_eq!(
+        fair.weighted_sample_space().collect::<Vec<_>>(),
+        [(0, 1), (1, 4), (2, 6), (3, 4), (4, 1)]
+    );
+    assert_eq!(
+        Binomial::new(200, 1, 3),
+        Err(DistributionError::WeightOverflow)
+    );
+}
+
+#[test]
+fn test_hypergeometric_weighted_sample_space() {
+    // Drawing 4 from 5 successes and 3 failures yields at least one success.
+    let distribution = Hypergeometric::new(8, 5, 4).unwrap();
+    assert_eq!(
+        distribution.weighted_sample_space().collect::<Vec<_>>(),
+        [(1, 1), (2, 6), (3, 6), (4, 1)]
+    );
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for _ in 0..100 {
+        assert!((1..=4).contains(&distribution.sample(&mut rng)));
+    }
+    assert_eq!(
+        Hypergeometric::new(3, 3, 3)
+            .unwrap()
+            .weighted_sample_space()
+            .collect::<Vec<_>>(),
+        [(3, 1)]
+    );
+    assert_eq!(
+        Hypergeometric::new(3, 4, 1),
+        Err(DistributionError::InvalidPopulation)
+    );
+    assert_eq!(
+        Hypergeometric::new(3, 1, 4),
+        Err(DistributionError::InvalidPopulation)
+    );
+}
+
+#[test]
+fn test_discrete_uniform_weighted_sample_space() {
+    let distribution = DiscreteUniform::new(3u8..=5).unwrap();
+    assert_eq!(
+        distribution.weighted_sample_space().collect::<Vec<_>>(),
+        [(3, 1), (4, 1), (5, 1)]
+    );
+    assert_eq!(
+        DiscreteUniform::new(5u8..5),
+        Err(DistributionError::EmptyRange)
+    );
+}
+
+#[test]
+fn test_truncated_poisson_weighted_sample_space() {
+    let distribution = TruncatedPoisson::new(2, 1, 3).unwrap();
+    // Proportional to 2^k / k! = 1, 2, 2, 4/3.
+    assert_eq!(
+        distribution.weighted_sample_space().collect::<Vec<_>>(),
+        [(0, 3), (1, 6), (2, 6), (3, 4)]
+    );
+}
+
+#[test]
+fn test_categorical_weighted_sample_space() {
+    let distribution = Categorical::new([('a', 4), ('b', 0), ('c', 6)]).unwrap();
+    assert_eq!(
+        distribution.weighted_sample_space().collect::<Vec<_>>(),
+        [('a', 2), ('c', 3)]
+    );
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert!((0..100).all(|_| distribution.sample(&mut rng) != 'b'));
+    assert_eq!(
+        Categorical::new([('a', 0)]),
+        Err(DistributionError::InvalidWeights)
+    );
+    assert_eq!(
+        Categorical::new([('a', u64::MAX), ('b', 1)]),
+        Err(DistributionError::WeightOverflow)
+    );
+}
+
+#[test]
+fn test_sampling_matches_weights() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let distribution = Binomial::new(2, 1, 2).unwrap();
+    let mut counts = [0usize; 3];
+    for _ in 0..40000 {
+        counts[distribution.sample(&mut rng) as usize] += 1;
+    }
+    assert!((9000..11000).contains(&counts[0]));
+    assert!((19000..21000).contains(&counts[1]));
+    assert!((9000..11000).contains(&counts[2]));
+}
+
+#[test]
+fn test_weighted_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Sampler>(&mut rng);
+    assert!(output <= 3);
+}
+
+#[test]
+fn test_weighted_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+
+    assert_eq!(output.len(), 27 * 4);
+    let counts = output.iter().fold(HashMap::new(), |mut map, s| {
+        *map.entry(*s).or_insert(0usize) += 1;
+        map
+    });
+    assert_eq!(counts, random_process::<Counter>(&mut rng));
+}
+
+#[test]
+fn test_weighted_unique_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<UniqueEnumerator>(&mut rng);
+    assert_eq!(output.len(), 4);
+}
+
+#[test]
+fn test_weighted_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Counter>(&mut rng);
+
+    assert_eq!(output.len(), 4);
+    assert_eq!(output[&0], 8 * 3 + 27);
+    assert_eq!(output[&1], 12 * 3);
+    assert_eq!(output[&2], 6 * 3);
+    assert_eq!(output[&3], 3);
+}
+
+#[test]
+#[should_panic(expected = "weight must fit in the count type")]
+fn test_weighted_counter_weight_overflow() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let distribution = Categorical::new([('a', 300), ('b', 1)]).unwrap();
+    <Counter<DefaultState, u8>>::fmap_rand_distribution(
+        Functor::pure(()),
+        &distribution,
+        &mut rng,
+        |_, c| c,
+    );
+}
+
+#[test]
+#[should_panic(expected = "weighted count must fit in the count type")]
+fn test_weighted_counter_count_overflow() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Counter<DefaultState, u8>>::fmap_rand(Functor::pure(()), &mut rng, |_, _: bool| ());
+    assert_eq!(f[&()], 2);
+    let distribution = Categorical::new([('a', 200), ('b', 1)]).unwrap();
+    <Counter<DefaultState, u8>>::fmap_rand_distribution(f, &distribution, &mut rng, |_, c| c);
+}
+
+#[cfg(feature = "rand_distr")]
+#[test]
+fn test_weighted_alias() {
+    use rand_functors::distributions::WeightedAlias;
+
+    let distribution = WeightedAlias::new((0..1000u16).map(|i| (i, (i % 3) as u64))).unwrap();
+    assert_eq!(distribution.weighted_sample_space().count(), 666);
+    assert!(distribution
+        .weighted_sample_space()
+        .all(|(i, weight)| weight == (i % 3) as u64));
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert!((0..1000).all(|_| distribution.sample(&mut rng) % 3 != 0));
+
+    let output =
+        <Counter>::fmap_rand_distribution(Functor::pure(()), &distribution, &mut rng, |_, i| i % 3);
+    assert_eq!(output, HashMap::from([(1, 333), (2, 666)]));
+
+    assert_eq!(
+        WeightedAlias::new([(0u8, 0)]).unwrap_err(),
+        DistributionError::InvalidWeights
+    );
+    assert_eq!(
+        WeightedAlias::new([(0u8, u64::MAX), (1, 1)]).unwrap_err(),
+        DistributionError::WeightOverflow
+    );
+}
+
+#[test]
+fn test_fmap_rand_with_prob() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let die = DiscreteUniform::new(1..=6u8).unwrap();
+    let likelihood =
+        |(n, d): (u64, u64), _, p: Probability| (n * p.numerator(), d * p.denominator());
+    let f = <Counter>::fmap_rand_distribution_with_prob(
+        Functor::pure((1, 1)),
+        &die,
+        &mut rng,
+        likelihood,
+    );
+    let f = <Counter>::fmap_rand_with_prob(f, &mut rng, |s, r: bool, p| likelihood(s, r as u8, p));
+    assert_eq!(f.len(), 1);
+    assert_eq!(f[&(1, 12)], 12);
+
+    let categorical = Categorical::new([('a', 1), ('b', 2), ('c', 3)]).unwrap();
+    let mut f = Enumerator::fmap_rand_distribution_with_prob(
+        Functor::pure(()),
+        &categorical,
+        &mut rng,
+        |_, c, p| (c, p),
+    );
+    assert_eq!(f.len(), 6);
+    f.dedup();
+    assert_eq!(
+        f,
+        [
+            ('a', Probability::new(1, 6).unwrap()),
+            ('b', Probability::new(1, 3).unwrap()),
+            ('c', Probability::new(1, 2).unwrap()),
+        ]
+    );
+    for _ in 0..100 {
+        let (c, p) =
+            Sampler::fmap_rand_distribution_with_prob((), &categorical, &mut rng, |_, c, p| (c, p));
+        assert!(f.contains(&(c, p)));
+    }
+
+    let p = Probability::new(4, 8).unwrap();
+    assert_eq!((p.numerator(), p.denominator()), (1, 2));
+    assert_eq!(p.to_string(), "1/2");
+    assert_eq!(f64::from(p), 0.5);
+    assert_eq!(Probability::ONE.to_f64(), 1.0);
+    assert_eq!(
+        Probability::new(3, 2),
+        Err(DistributionError::InvalidProbability)
+    );
+}
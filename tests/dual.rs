@@ -0,0 +1,62 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Binomial;
+use rand_functors::{
+    Counter, Dual, Enumerator, FlattenableRandomStrategy, Functor, PopulationSampler,
+    RandomStrategy, Sampler, UniqueEnumerator, WeightedRandomStrategy,
+};
+
+fn random_process<S: FlattenableRandomStrategy + WeightedRandomStrategy>(
+    rng: &mut impl Rng,
+) -> S::Functor<u8> {
+    let f = S::fmap_rand(Functor::pure(0), rng, |_, r: bool| r as u8);
+    let f = S::fmap_rand_range(f, 1..=3u8, rng, |s, r| s + r);
+    let f = S::fmap_rand_distribution(f, &Binomial::new(2, 1, 3).unwrap(), rng, |s, k| s * k as u8);
+    S::fmap_flat(f, |s| {
+        S::fmap_rand(Functor::pure(s), rng, |s, r: bool| s + r as u8)
+    })
+}
+
+#[test]
+fn test_dual_agree() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Dual<Enumerator, Counter>>(&mut rng);
+    output.assert_agree();
+    let (left, right) = output.into_inner();
+    assert_eq!(left, random_process::<Enumerator>(&mut rng));
+    assert_eq!(right, random_process::<Counter>(&mut rng));
+}
+
+#[test]
+fn test_dual_disagree() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Dual<UniqueEnumerator, Counter>>(&mut rng);
+    let (outcome, p, q) = output.disagreement(1e-9).unwrap();
+    assert!(p != q);
+    assert!(output.left().contains(&outcome));
+}
+
+#[test]
+#[should_panic(expected = "strategies disagree")]
+fn test_dual_assert_agree_panics() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    Dual::<PopulationSampler<4>, Counter>::fmap_rand_range(
+        Functor::pure(()),
+        0..100u8,
+        &mut rng,
+        |_, r| r,
+    )
+    .assert_agree();
+}
+
+#[test]
+fn test_dual_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = Dual::<Sampler, Enumerator>::fmap_rand_range(
+        Functor::pure(()),
+        0..100u8,
+        &mut rng,
+        |_, r| r,
+    );
+    assert!(output.right().contains(output.left()));
+}
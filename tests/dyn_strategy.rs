@@ -0,0 +1,92 @@
+#![cfg(feature = "std")]
+
+use std::collections::{HashMap, HashSet};
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Counter, DynFunctor, DynStrategy, Enumerator, FlattenableRandomStrategy, Functor,
+    RandomStrategy, WeightedFunctor,
+};
+
+fn random_process<S: FlattenableRandomStrategy>(
+    f: S::Functor<u8>,
+    rng: &mut impl Rng,
+) -> S::Functor<u8> {
+    let f = S::fmap_rand(f, rng, |_, r: bool| r as u8);
+    let f = S::fmap_flat(f, |s| {
+        S::fmap_rand_range(Functor::pure(s), 0..2u8, rng, |s, r| s + r)
+    });
+    S::fmap(f, |s| s * 2)
+}
+
+#[test]
+fn test_dyn_strategies() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    let output = random_process::<DynStrategy>(DynStrategy::Enumerator.pure(0), &mut rng);
+    assert_eq!(
+        output,
+        DynFunctor::Vec(random_process::<Enumerator>(Functor::pure(0), &mut rng))
+    );
+
+    let output = random_process::<DynStrategy>(DynStrategy::UniqueEnumerator.pure(0), &mut rng);
+    assert_eq!(output, DynFunctor::Set(HashSet::from([0, 2, 4])));
+
+    let output = random_process::<DynStrategy>(DynStrategy::Counter.pure(0), &mut rng);
+    let expected: HashMap<u8, usize> = random_process::<Counter>(Functor::pure(0), &mut rng);
+    assert_eq!(output, DynFunctor::Map(expected));
+
+    let output = DynStrategy::fmap_rand(
+        random_process::<DynStrategy>(DynStrategy::PopulationSampler(3).pure(0), &mut rng),
+        &mut rng,
+        |s, r: u8| s ^ r,
+    );
+    assert_eq!(output.size(), 3);
+    let DynFunctor::Population(population) = output else {
+        panic!("expected a population, got {output:?}");
+    };
+    assert_eq!(population.capacity(), Some(3));
+
+    let output =
+        DynStrategy::fmap_rand(DynStrategy::Sampler.pure(0), &mut rng, |_, r: bool| r as u8);
+    let output = DynStrategy::fmap_flat(output, |s| Functor::pure(s * 2));
+    let DynFunctor::Single(sample) = output else {
+        panic!("expected a single outcome, got {output:?}");
+    };
+    assert!([0, 2].contains(&sample));
+}
+
+#[test]
+fn test_dyn_functor_weighted_outcomes() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<DynStrategy>(DynStrategy::Counter.pure(0), &mut rng);
+    assert_eq!(output.to_sorted_vec(), [(0, 1), (2, 2), (4, 1)]);
+    let output = random_process::<DynStrategy>(DynStrategy::Enumerator.pure(0), &mut rng);
+    assert_eq!(output.to_sorted_vec(), [(0, 1), (2, 2), (4, 1)]);
+}
+
+#[test]
+fn test_dyn_functor_pure() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<DynStrategy>(Functor::pure(0), &mut rng);
+    assert_eq!(
+        output,
+        DynFunctor::Vec(random_process::<Enumerator>(Functor::pure(0), &mut rng))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_dyn_strategy_sampler_flattening_several_outcomes() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    random_process::<DynStrategy>(DynStrategy::Sampler.pure(0), &mut rng);
+}
+
+#[test]
+#[should_panic]
+fn test_dyn_strategy_mismatched_functor() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = random_process::<DynStrategy>(DynStrategy::Enumerator.pure(0), &mut rng);
+    DynStrategy::fmap_flat(f, |s| DynStrategy::Counter.pure(s));
+}
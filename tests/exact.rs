@@ -0,0 +1,43 @@
+#![cfg(feature = "std")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Binomial;
+use rand_functors::{
+    Counter, Dual, Enumerator, ExactRandomStrategy, Functor, TraceEnumerator, UniqueEnumerator,
+    WeightedRandomStrategy,
+};
+
+fn exact_process<S: ExactRandomStrategy + WeightedRandomStrategy>() -> S::Functor<u8> {
+    let f = S::fmap_rand_exact(Functor::pure(0u8), |s, r: bool| s + r as u8);
+    let f = S::fmap_rand_range_exact(f, 1..=3u8, |s, r| s * r);
+    S::fmap_rand_distribution_exact(f, &Binomial::new(2, 1, 2).unwrap(), |s, k| s + k as u8)
+}
+
+fn random_process<S: WeightedRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand(Functor::pure(0u8), rng, |s, r: bool| s + r as u8);
+    let f = S::fmap_rand_range(f, 1..=3u8, rng, |s, r| s * r);
+    S::fmap_rand_distribution(f, &Binomial::new(2, 1, 2).unwrap(), rng, |s, k| s + k as u8)
+}
+
+#[test]
+fn test_exact_strategies() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert_eq!(
+        exact_process::<Enumerator>(),
+        random_process::<Enumerator>(&mut rng)
+    );
+    assert_eq!(
+        exact_process::<Counter>(),
+        random_process::<Counter>(&mut rng)
+    );
+    assert_eq!(
+        exact_process::<UniqueEnumerator>(),
+        random_process::<UniqueEnumerator>(&mut rng)
+    );
+    assert_eq!(
+        exact_process::<TraceEnumerator>(),
+        random_process::<TraceEnumerator>(&mut rng)
+    );
+    exact_process::<Dual<Enumerator, Counter>>().assert_agree();
+}
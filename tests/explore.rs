@@ -0,0 +1,67 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashSet;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    explore, explore_until, DefaultState, Functor, RandomStrategy, UniqueEnumerator,
+};
+
+fn step(rng: &mut impl Rng, s: u8) -> HashSet<u8, DefaultState> {
+    UniqueEnumerator::fmap_rand(Functor::pure(s), rng, |s, r: bool| {
+        if r {
+            s.saturating_add(1).min(10)
+        } else {
+            s
+        }
+    })
+}
+
+#[test]
+fn test_explore_fixpoint() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let reachable = explore([4], None, |s| step(&mut rng, s));
+    assert_eq!(reachable.states(), &(4..=10).collect::<HashSet<_>>());
+    // Six iterations reach 10, and a seventh reaches nothing new.
+    assert_eq!(reachable.iterations(), 7);
+    assert!(reachable.converged());
+}
+
+#[test]
+fn test_explore_bounded() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let reachable = explore([0, 8], Some(2), |s| step(&mut rng, s));
+    assert_eq!(reachable.iterations(), 2);
+    assert!(!reachable.converged());
+    assert_eq!(
+        reachable.into_states(),
+        [0, 1, 2, 8, 9, 10].into_iter().collect::<HashSet<_>>()
+    );
+}
+
+#[test]
+fn test_explore_empty() {
+    let reachable = explore(None::<u8>, None, Functor::pure);
+    assert!(reachable.states().is_empty());
+    assert_eq!(reachable.iterations(), 0);
+    assert!(reachable.converged());
+}
+
+#[test]
+fn test_explore_until() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut checks = 0;
+    let reachable = explore_until(
+        [4],
+        None,
+        || {
+            checks += 1;
+            checks > 3
+        },
+        |s| step(&mut rng, s),
+    );
+    assert_eq!(reachable.iterations(), 3);
+    assert!(!reachable.converged());
+    assert_eq!(reachable.into_states(), (4..=7).collect::<HashSet<_>>());
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::flags::Flags;
+use rand_functors::testing::{audit_uniformity, check_random_variable};
+use rand_functors::{
+    flags, Cardinality, Counter, Enumerator, Functor, RandomStrategy, RandomVariable,
+};
+
+flags! {
+    struct Status: u8 {
+        const POISONED = 0b0000_0010;
+        const STUNNED = 0b0001_0000;
+        const BURNING = 0b1000_0000;
+    }
+}
+
+flags! {
+    struct Permissions: u32 {
+        const READ = 1 << 2;
+        const WRITE = 1 << 17;
+        const EXECUTE = 1 << 30;
+    }
+}
+
+#[test]
+fn test_flags_sample_space() {
+    assert_eq!(
+        Status::sample_space().map(Status::bits).collect::<Vec<_>>(),
+        [0x00, 0x02, 0x10, 0x12, 0x80, 0x82, 0x90, 0x92]
+    );
+    assert_eq!(Status::CARDINALITY, 8);
+    assert_eq!(Permissions::CARDINALITY, 8);
+    check_random_variable::<Status>();
+    check_random_variable::<Permissions>();
+}
+
+#[test]
+fn test_flags_sampling() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    audit_uniformity::<Status>(&mut rng, 10_000);
+    audit_uniformity::<Permissions>(&mut rng, 10_000);
+}
+
+#[test]
+fn test_flags_methods() {
+    let mut status = Status::POISONED | Status::BURNING;
+    assert!(status.contains(Status::POISONED));
+    assert!(!status.contains(Status::STUNNED));
+    status.insert(Status::STUNNED);
+    status.remove(Status::POISONED);
+    assert_eq!(status, Status::STUNNED | Status::BURNING);
+    assert_eq!(status & Status::BURNING, Status::BURNING);
+    assert!(Status::empty().is_empty());
+    assert_eq!(Status::all(), Status::ALL);
+    assert_eq!(
+        Status::from_bits(0x12),
+        Some(Status::POISONED | Status::STUNNED)
+    );
+    assert_eq!(Status::from_bits(0x13), None);
+    assert_eq!(Status::from_bits_truncate(0xff), Status::all());
+}
+
+#[test]
+fn test_flags_strategies() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let enumeration = <Enumerator>::fmap_rand(Functor::pure(()), &mut rng, |_, s: Status| s);
+    assert_eq!(enumeration, Status::sample_space().collect::<Vec<_>>());
+
+    let counts = <Counter>::fmap_rand(Functor::pure(()), &mut rng, |_, s: Status| {
+        s.contains(Status::POISONED | Status::STUNNED)
+    });
+    assert_eq!(counts, HashMap::from([(false, 6), (true, 2)]));
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::DistributionError;
+use rand_functors::{
+    Counter, Enumerator, FromWeightedIterator, Particles, PopulationSampler, RandomStrategy,
+    Sampler, WeightedFunctor, WeightedPopulationSampler, WeightedRandomStrategy, WitnessCounter,
+    Witnessed, WitnessedCounts,
+};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::from_states([1, 2, 4], rng);
+    S::fmap_rand(f, rng, |s, r: bool| if r { s * 2 } else { s })
+}
+
+#[test]
+fn test_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert_eq!(random_process::<Enumerator>(&mut rng), [1, 2, 2, 4, 4, 8]);
+}
+
+#[test]
+fn test_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let expected: HashMap<u8, usize> = [(1, 1), (2, 2), (4, 2), (8, 1)].into_iter().collect();
+    assert_eq!(random_process::<Counter>(&mut rng), expected);
+}
+
+#[test]
+fn test_population_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<PopulationSampler<4>>(&mut rng);
+    assert_eq!(output.len(), 4);
+    assert!(output.iter().all(|o| [1, 2, 4, 8].contains(o)));
+}
+
+#[test]
+fn test_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for _ in 0..16 {
+        assert!([1, 2, 4, 8].contains(&random_process::<Sampler>(&mut rng)));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_empty_states() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    Enumerator::from_states::<u8>([], &mut rng);
+}
+
+#[test]
+fn test_from_weighted() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let prior = [(1u8, 2), (2, 0), (4, 6)];
+
+    let output = <Counter>::from_weighted(prior, &mut rng).unwrap();
+    let output = <Counter>::fmap_rand(output, &mut rng, |s, r: bool| if r { s * 2 } else { s });
+    let expected: HashMap<u8, usize> = [(1, 1), (2, 1), (4, 3), (8, 3)].into_iter().collect();
+    assert_eq!(output, expected);
+
+    let output = Enumerator::from_weighted(prior, &mut rng).unwrap();
+    assert_eq!(output, [1, 4, 4, 4]);
+
+    for _ in 0..16 {
+        assert_ne!(Sampler::from_weighted(prior, &mut rng).unwrap(), 2);
+    }
+
+    assert_eq!(
+        Enumerator::from_weighted([(0u8, 0)], &mut rng),
+        Err(DistributionError::InvalidWeights)
+    );
+}
+
+#[test]
+fn test_from_weighted_iter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let observed = [(1u8, 2), (2, 0), (4, 3), (1, 1)];
+
+    let output = HashMap::<_, usize>::from_weighted_iter(observed);
+    let output = <Counter>::fmap_rand(output, &mut rng, |s, r: bool| if r { s * 2 } else { s });
+    let expected: HashMap<u8, usize> = [(1, 3), (2, 3), (4, 3), (8, 3)].into_iter().collect();
+    assert_eq!(output, expected);
+
+    let mut output = Vec::from_weighted_iter(observed);
+    assert_eq!(output, [1, 1, 4, 4, 4, 1]);
+    output.extend_weighted([(8, 1)]);
+    assert_eq!(output.len(), 7);
+
+    let output = WitnessedCounts::from_weighted_iter(observed);
+    assert_eq!(output.count(&1), 3);
+    assert_eq!(output.witness(&4), Some(&[][..]));
+    assert!(!output.contains_key(&2));
+    let output =
+        WitnessCounter::fmap_rand(output, &mut rng, |s, r: bool| if r { s * 2 } else { s });
+    assert_eq!(output.count(&8), 3);
+    assert_eq!(output.witness(&8), Some(&[1][..]));
+
+    let output = Witnessed::from_weighted_iter([(1u8, 2)]);
+    assert_eq!(output, Witnessed(vec![(1, vec![]), (1, vec![])]));
+}
+
+#[test]
+fn test_from_iter_particles() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut prior: Particles<u8> = [(1, 0.25), (2, 0.75)].into_iter().collect();
+    prior.extend_weighted([(4, 0.0)]);
+    assert_eq!(prior.len(), 2);
+    let output =
+        WeightedPopulationSampler::<4>::fmap_rand(prior, &mut rng, |s, r: bool| s + r as u8);
+    assert_eq!(output.len(), 4);
+    assert!((output.probability_where(|&s| s == 3) - 0.375).abs() < 1e-12);
+}
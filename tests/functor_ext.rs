@@ -0,0 +1,62 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Bernoulli;
+use rand_functors::{
+    Counter, Enumerator, FlattenableRandomStrategy, Functor, FunctorExt, Sampler, UniqueEnumerator,
+    WeightedRandomStrategy,
+};
+
+fn associated_functions<S: FlattenableRandomStrategy + WeightedRandomStrategy>(
+    rng: &mut impl Rng,
+) -> S::Functor<u8> {
+    let f = S::fmap(Functor::pure(3u8), |s| s * 2);
+    let f = S::fmap_rand(f, rng, |s, r: bool| if r { s + 1 } else { s });
+    let f = S::fmap_rand_range(f, 0..3u8, rng, |s, r| s + r);
+    let f = S::fmap_flat(f, |s| S::fmap(Functor::pure(s), |s| s / 2));
+    S::fmap_rand_distribution(f, &Bernoulli::new(1, 3).unwrap(), rng, |s, r| s + r as u8)
+}
+
+fn methods<S: FlattenableRandomStrategy + WeightedRandomStrategy>(
+    rng: &mut impl Rng,
+) -> S::Functor<u8> {
+    let f: S::Functor<u8> = Functor::pure(3);
+    f.fmap::<S, _>(|s| s * 2)
+        .fmap_rand::<S, _, _>(rng, |s, r: bool| if r { s + 1 } else { s })
+        .fmap_rand_range::<S, _, _>(0..3u8, rng, |s, r| s + r)
+        .fmap_flat::<S, u8>(|s| S::fmap(Functor::pure(s), |s| s / 2))
+        .fmap_rand_distribution::<S, _, _>(&Bernoulli::new(1, 3).unwrap(), rng, |s, r| s + r as u8)
+}
+
+#[test]
+fn test_functor_ext_counter() {
+    let expected = associated_functions::<Counter>(&mut ChaCha8Rng::seed_from_u64(0));
+    let actual = methods::<Counter>(&mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_functor_ext_enumerator() {
+    let expected = associated_functions::<Enumerator>(&mut ChaCha8Rng::seed_from_u64(0));
+    let actual = methods::<Enumerator>(&mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_functor_ext_sampler() {
+    let expected = associated_functions::<Sampler>(&mut ChaCha8Rng::seed_from_u64(0));
+    let actual = methods::<Sampler>(&mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_functor_ext_unique_enumerator() {
+    let expected = associated_functions::<UniqueEnumerator>(&mut ChaCha8Rng::seed_from_u64(0));
+    let actual = methods::<UniqueEnumerator>(&mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_functor_ext_concrete_functor() {
+    let output = vec![1u8, 2].fmap::<Enumerator, _>(|s| s * 2);
+    assert_eq!(output, [2, 4]);
+}
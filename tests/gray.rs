@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::testing::check_random_variable;
+use rand_functors::{
+    Cardinality, Counter, Enumerator, Functor, Gray, RandomStrategy, RandomVariable,
+};
+
+#[test]
+fn test_gray_sample_space() {
+    let values = Gray::<u16>::sample_space()
+        .map(Gray::value)
+        .collect::<Vec<_>>();
+    assert_eq!(values.len(), 65536);
+    assert_eq!(values.iter().collect::<HashSet<_>>().len(), 65536);
+    assert!(values.windows(2).all(|w| (w[0] ^ w[1]).count_ones() == 1));
+    assert_eq!(Gray::<u64>::CARDINALITY, 1 << 64);
+    check_random_variable::<Gray<u8>>();
+}
+
+#[test]
+fn test_gray_index() {
+    for (i, gray) in Gray::<u8>::sample_space().enumerate() {
+        assert_eq!(gray.index(), i as u8);
+        assert_eq!(Gray::<u8>::from_index(i as u8), gray);
+    }
+    assert_eq!(Gray::<u64>::from_index(u64::MAX).index(), u64::MAX);
+    assert_eq!(Gray::<u64>::from_index(u64::MAX).value(), 1 << 63);
+    assert_eq!(Gray::new(0b110u8).changed_bit(Gray::new(0b010)), Some(2));
+    assert_eq!(Gray::new(0b110u8).changed_bit(Gray::new(0b011)), None);
+    assert_eq!(Gray::new(7u8).changed_bit(Gray::new(7)), None);
+}
+
+#[test]
+fn test_gray_incremental_update() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let outputs = <Enumerator>::fmap_rand(Functor::pure(()), &mut rng, |_, r: Gray<u8>| r);
+    let mut popcount = 0;
+    for w in outputs.windows(2) {
+        let bit = w[0].changed_bit(w[1]).unwrap();
+        if w[1].value() & (1 << bit) == 0 {
+            popcount -= 1;
+        } else {
+            popcount += 1;
+        }
+        assert_eq!(popcount, w[1].value().count_ones() as i32);
+    }
+
+    let counts = <Counter>::fmap_rand(Functor::pure(()), &mut rng, |_, r: Gray<u8>| r.value() % 4);
+    assert!((0..4).all(|r| counts[&r] == 64));
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "std")]
+
+use std::hash::{BuildHasher, Hasher};
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Counter, DeterministicState, Functor, RandomStrategy, UniqueEnumerator};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u16> {
+    let f = S::fmap_rand(Functor::pure(0), rng, |s, r: u8| s + r as u16);
+    S::fmap_rand_range(f, 0..7u16, rng, |s, r| s * r % 251)
+}
+
+#[test]
+fn test_deterministic_state_is_reproducible() {
+    let first: Vec<_> =
+        random_process::<Counter<DeterministicState>>(&mut ChaCha8Rng::seed_from_u64(0))
+            .into_iter()
+            .collect();
+    for seed in 1..4 {
+        let next: Vec<_> =
+            random_process::<Counter<DeterministicState>>(&mut ChaCha8Rng::seed_from_u64(seed))
+                .into_iter()
+                .collect();
+        assert_eq!(next, first);
+    }
+
+    let first: Vec<_> =
+        random_process::<UniqueEnumerator<DeterministicState>>(&mut ChaCha8Rng::seed_from_u64(0))
+            .into_iter()
+            .collect();
+    let second: Vec<_> =
+        random_process::<UniqueEnumerator<DeterministicState>>(&mut ChaCha8Rng::seed_from_u64(1))
+            .into_iter()
+            .collect();
+    assert_eq!(second, first);
+}
+
+#[test]
+fn test_deterministic_hasher_is_fixed() {
+    let mut hasher = DeterministicState.build_hasher();
+    hasher.write(b"a");
+    assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+
+    let mut narrow = DeterministicState.build_hasher();
+    narrow.write_usize(42);
+    let mut wide = DeterministicState.build_hasher();
+    wide.write_u64(42);
+    assert_eq!(narrow.finish(), wide.finish());
+}
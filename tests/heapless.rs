@@ -0,0 +1,73 @@
+#![cfg(feature = "heapless")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    distributions::Bernoulli, Enumerator, Functor, HeaplessPopulationSampler, RandomStrategy,
+    WeightedRandomStrategy,
+};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u16> {
+    let f = S::fmap_rand(Functor::pure(0u16), rng, |a, b: u8| a + b as u16);
+    let f = S::fmap_rand(f, rng, |a, b: bool| if b { a * 2 } else { a });
+    S::fmap(f, |a| a + 1)
+}
+
+#[test]
+fn test_heapless_population_sampler() {
+    const N: usize = 64;
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<HeaplessPopulationSampler<N>>(&mut rng);
+    assert_eq!(output.len(), N);
+    assert_eq!(output.capacity(), N);
+
+    let all = random_process::<Enumerator>(&mut rng);
+    assert!(output.iter().all(|o| all.contains(o)));
+}
+
+#[test]
+fn test_heapless_population_sampler_below_capacity() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<HeaplessPopulationSampler<1024>>(&mut rng);
+    let mut output = output.into_iter().collect::<Vec<_>>();
+    let mut all = random_process::<Enumerator>(&mut rng);
+    output.sort_unstable();
+    all.sort_unstable();
+    assert_eq!(output, all);
+}
+
+#[test]
+fn test_heapless_population_sampler_selects_uniformly() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut counts = [0usize; 256];
+    for _ in 0..2048 {
+        let output =
+            HeaplessPopulationSampler::<16>::fmap_rand(Functor::pure(()), &mut rng, |_, r: u8| r);
+        for r in output {
+            counts[r as usize] += 1;
+        }
+    }
+    // Each value is expected to be selected 128 times.
+    assert!(counts.iter().all(|&count| (64..192).contains(&count)));
+}
+
+#[test]
+fn test_heapless_population_sampler_distribution() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let distribution = Bernoulli::new(1, 4).unwrap();
+    let output = HeaplessPopulationSampler::<3>::fmap_rand_distribution(
+        Functor::pure(0u8),
+        &distribution,
+        &mut rng,
+        |a, b| a + b as u8,
+    );
+    assert_eq!(output.len(), 3);
+    assert!(output.iter().all(|&o| o <= 1));
+}
+
+#[test]
+fn test_heapless_pure_without_capacity() {
+    let f: heapless::Vec<u8, 0> = Functor::pure(1);
+    assert!(f.is_empty());
+}
@@ -12,7 +12,13 @@ pub struct State {
 
 fn random_process<S: RandomStrategy>(rng: &mut impl Rng, mut s: State) -> S::Functor<State> {
     s.a += 2;
-    let mut sc = Functor::pure(s);
+    random_process_from::<S>(rng, Functor::pure(s))
+}
+
+fn random_process_from<S: RandomStrategy>(
+    rng: &mut impl Rng,
+    mut sc: S::Functor<State>,
+) -> S::Functor<State> {
     sc = S::fmap_rand(sc, rng, |mut s, r| {
         if r {
             s.a -= 1
@@ -65,6 +71,33 @@ fn test_population_sampler() {
     assert!(output.iter().all(|s| s.b[1] == 19));
 }
 
+#[test]
+fn test_dyn_population_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let s = State { a: 16, b: [90, 19] };
+    for capacity in [0, 1, 103] {
+        let output = random_process_from::<DynPopulationSampler>(
+            &mut rng,
+            DynPopulation::with_capacity(s, capacity),
+        );
+        assert_eq!(output.len(), capacity);
+        assert_eq!(output.capacity(), Some(capacity));
+        assert!(output.iter().all(|s| s.b[1] == 19));
+    }
+}
+
+#[test]
+fn test_dyn_population_sampler_without_capacity() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = <DynPopulationSampler as RandomStrategy>::fmap_rand(
+        Functor::pure(()),
+        &mut rng,
+        |_, r: u8| r,
+    );
+    assert_eq!(output.capacity(), None);
+    assert_eq!(output.into_inner(), Vec::from_iter(0..=u8::MAX));
+}
+
 #[test]
 fn test_enumerator() {
     let mut rng = ChaCha8Rng::seed_from_u64(0);
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::testing::check_random_variable;
+use rand_functors::{
+    product_space, Cardinality, Counter, Enumerator, Functor, Joint, RandomStrategy,
+    RandomVariable, D4,
+};
+
+#[test]
+fn test_product_space() {
+    assert_eq!(
+        product_space(|| 0..2u8, || ['a', 'b', 'c']).collect::<Vec<_>>(),
+        [(0, 'a'), (0, 'b'), (0, 'c'), (1, 'a'), (1, 'b'), (1, 'c')]
+    );
+    assert_eq!(product_space(|| 0..0u8, || 0..3u8).count(), 0);
+}
+
+#[test]
+fn test_joint_sample_space() {
+    assert_eq!(
+        <Joint<bool, bool>>::sample_space().collect::<Vec<_>>(),
+        [
+            Joint(false, false),
+            Joint(false, true),
+            Joint(true, false),
+            Joint(true, true)
+        ]
+    );
+    assert_eq!(<Joint<bool, u8>>::CARDINALITY, 512);
+    assert_eq!(<Joint<u64, Joint<u64, bool>>>::CARDINALITY, u128::MAX);
+    check_random_variable::<Joint<bool, u8>>();
+    check_random_variable::<Joint<D4, Joint<bool, D4>>>();
+}
+
+#[test]
+fn test_joint_strategies() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let counts = <Counter>::fmap_rand(
+        Functor::pure(0u8),
+        &mut rng,
+        |s, Joint(a, b): Joint<D4, D4>| s + a.value() + b.value(),
+    );
+    assert_eq!(counts[&5], 4);
+    assert_eq!(counts.values().sum::<usize>(), 16);
+
+    let enumeration =
+        <Enumerator>::fmap_rand(Functor::pure(()), &mut rng, |_, j: Joint<bool, bool>| {
+            <(bool, bool)>::from(j)
+        });
+    assert_eq!(
+        enumeration,
+        [(false, false), (false, true), (true, false), (true, true)]
+    );
+
+    let mut frequencies = HashMap::new();
+    for _ in 0..1000 {
+        let Joint(a, b) = rng.gen::<Joint<bool, bool>>();
+        *frequencies.entry((a, b)).or_insert(0) += 1;
+    }
+    assert_eq!(frequencies.len(), 4);
+}
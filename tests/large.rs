@@ -0,0 +1,56 @@
+#![cfg(feature = "large-sample-spaces")]
+
+use std::collections::HashSet;
+use std::num::Wrapping;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Cardinality, Counter, Enumerator, Functor, LargeSampleSpace, RandomStrategy, RandomVariable,
+    Sampler,
+};
+
+#[test]
+fn test_large_sample_space() {
+    let mut space = LargeSampleSpace::<u32>::sample_space();
+    assert_eq!(space.size_hint(), (1 << 32, Some(1 << 32)));
+    assert_eq!(space.next(), Some(LargeSampleSpace(0)));
+    assert_eq!(
+        space.size_hint(),
+        (u32::MAX as usize, Some(u32::MAX as usize))
+    );
+    assert_eq!(
+        LargeSampleSpace::<i32>::sample_space().next(),
+        Some(LargeSampleSpace(i32::MIN))
+    );
+    assert_eq!(LargeSampleSpace::<Wrapping<i32>>::CARDINALITY, 1 << 32);
+}
+
+#[test]
+fn test_large_sample_space_reservation() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Enumerator>::fmap_rand(
+        Functor::pure(()),
+        &mut rng,
+        |_, r: LargeSampleSpace<u16>| r.0,
+    );
+    assert_eq!(f.len(), 65536);
+    assert_eq!(f.capacity(), 65536);
+    let f = <Enumerator>::fmap_rand(f, &mut rng, |s, r: LargeSampleSpace<bool>| (s, r.0));
+    assert_eq!(f.len(), 131072);
+}
+
+#[test]
+fn test_large_sample_space_strategies() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Counter>::fmap_rand(
+        Functor::pure(()),
+        &mut rng,
+        |_, r: LargeSampleSpace<u16>| r.0.count_ones(),
+    );
+    assert_eq!(f[&8], 12870);
+    let samples = (0..100)
+        .map(|_| Sampler::fmap_rand((), &mut rng, |_, r: LargeSampleSpace<u64>| r.0))
+        .collect::<HashSet<_>>();
+    assert_eq!(samples.len(), 100);
+}
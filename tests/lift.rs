@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{lift, Counter, Enumerator, Functor, RandomStrategy, Sampler};
+
+fn step(state: u16, reset: bool, increment: u8, double: bool) -> u16 {
+    let state = if reset { 0 } else { state + increment as u16 };
+    if double {
+        state * 2
+    } else {
+        state
+    }
+}
+
+fn manual<S: RandomStrategy>(state: u16, rng: &mut impl Rng) -> S::Functor<u16> {
+    let f = S::fmap_rand(Functor::pure(state), rng, |s, reset: bool| (s, reset));
+    let f = S::fmap_rand(f, rng, |(s, reset), increment: u8| (s, reset, increment));
+    let f = S::fmap_rand(f, rng, |(s, reset, increment), double: bool| {
+        (s, reset, increment, double)
+    });
+    S::fmap(f, |(s, reset, increment, double)| {
+        step(s, reset, increment, double)
+    })
+}
+
+fn lifted<S: RandomStrategy>(state: u16, rng: &mut impl Rng) -> S::Functor<u16> {
+    lift!(S, rng, step, Functor::pure(state); bool, u8, bool)
+}
+
+#[test]
+fn test_lift_counter() {
+    let expected = manual::<Counter>(5, &mut ChaCha8Rng::seed_from_u64(0));
+    let actual = lifted::<Counter>(5, &mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_lift_enumerator() {
+    let expected = manual::<Enumerator>(5, &mut ChaCha8Rng::seed_from_u64(0));
+    let actual = lifted::<Enumerator>(5, &mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_lift_sampler() {
+    let expected = manual::<Sampler>(5, &mut ChaCha8Rng::seed_from_u64(0));
+    let actual = lifted::<Sampler>(5, &mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_lift_closure_and_chaining() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = lift!(Counter, &mut rng, |s: u8, r: bool| s + r as u8, Functor::pure(0); bool);
+    let f = lift!(Counter, &mut rng, |s: u8, r: bool| s + r as u8, f; bool);
+    let expected: HashMap<u8, usize> = [(0, 1), (1, 2), (2, 1)].into_iter().collect();
+    assert_eq!(f, expected);
+}
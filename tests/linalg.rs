@@ -0,0 +1,56 @@
+#![cfg(feature = "nalgebra")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    probability_vector, transition_matrix, Counter, Enumerator, Functor, RandomStrategy,
+};
+
+fn step<S: RandomStrategy>(rng: &mut impl Rng, s: u8) -> S::Functor<u8> {
+    S::fmap_rand(Functor::pure(s), rng, |s, r: bool| {
+        if r {
+            s.wrapping_add(1)
+        } else {
+            s.wrapping_sub(1)
+        }
+    })
+}
+
+#[test]
+fn test_transition_matrix() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let matrix = transition_matrix::<Enumerator, u8>(|s| step::<Enumerator>(&mut rng, s));
+
+    assert_eq!(matrix.shape(), (256, 256));
+    for i in 0..256 {
+        assert_eq!(matrix.row(i).sum(), 1.0);
+    }
+    assert_eq!(matrix[(10, 11)], 0.5);
+    assert_eq!(matrix[(10, 9)], 0.5);
+    assert_eq!(matrix[(0, 255)], 0.5);
+    assert_eq!(matrix[(10, 10)], 0.0);
+}
+
+#[test]
+fn test_probability_vector() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = step::<Counter>(&mut rng, 3);
+    let output = <Counter>::fmap_rand(output, &mut rng, |s, r: bool| s.wrapping_add(r as u8));
+    let vector = probability_vector(&output);
+
+    assert_eq!(vector.len(), 256);
+    for i in 2..=5 {
+        assert_eq!(vector[i], 0.25);
+    }
+    assert_eq!(vector.sum(), 1.0);
+}
+
+#[test]
+fn test_probability_vector_evolves_with_transition_matrix() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let matrix = transition_matrix::<Counter, u8>(|s| step::<Counter>(&mut rng, s));
+    let initial = probability_vector(&<Counter>::fmap(Functor::pure(7u8), |s| s));
+    let next = probability_vector(&step::<Counter>(&mut rng, 7));
+
+    assert_eq!(matrix.transpose() * initial, next);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "std")]
+
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Counter, Enumerator, FlattenableRandomStrategy, Functor, Memoized, RandomStrategy,
+};
+
+fn step(rng: &mut impl Rng, s: u8) -> HashMap<u8, usize> {
+    Counter::fmap_rand_range(Functor::pure(s), 1..=3, rng, |s, r: u8| (s + r) % 5)
+}
+
+#[test]
+fn test_memoized_matches_direct() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut direct = Functor::pure(0);
+    for _ in 0..6 {
+        direct = <Counter>::fmap_flat(direct, |s| step(&mut rng, s));
+    }
+
+    let evaluations = Cell::new(0);
+    let mut memoized = Memoized::<Counter, _, _>::new(|s| {
+        evaluations.set(evaluations.get() + 1);
+        step(&mut rng, s)
+    });
+    assert!(memoized.is_empty());
+    assert_eq!(memoized.run(Functor::pure(0), 6), direct);
+    assert_eq!(memoized.len(), 5);
+    assert!(memoized.hits() > 0);
+    drop(memoized);
+    assert_eq!(evaluations.get(), 5);
+}
+
+#[test]
+fn test_memoized_step_and_clear() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut memoized = Memoized::<Enumerator, _, _>::new(|s: bool| {
+        Enumerator::fmap_rand(Functor::pure(s), &mut rng, |s, r: bool| s ^ r)
+    });
+    assert_eq!(memoized.step(true), [true, false]);
+    assert_eq!(memoized.step(true), [true, false]);
+    assert_eq!((memoized.len(), memoized.hits()), (1, 1));
+    memoized.clear();
+    assert_eq!((memoized.len(), memoized.hits()), (0, 0));
+}
+
+#[test]
+fn test_memoized_run_until() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut memoized = Memoized::<Counter, _, _>::new(|s| step(&mut rng, s));
+    let expected = memoized.run(Functor::pure(0), 2);
+    let mut checks = 0;
+    let (partial, completed) = memoized.run_until(Functor::pure(0), 6, || {
+        checks += 1;
+        checks > 2
+    });
+    assert_eq!((partial, completed), (expected, 2));
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "metrics")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, Key, KeyName, Metadata, Recorder, SharedString,
+    Unit,
+};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::telemetry::{
+    COLLISIONS, FUNCTOR_SIZE, OUTCOMES_DISCARDED, OUTCOMES_EXPANDED, SHRINKS,
+};
+use rand_functors::{
+    Counter as CounterStrategy, Enumerator, Functor, PopulationSampler, RandomStrategy,
+};
+
+#[derive(Default)]
+struct Value(Mutex<f64>);
+
+impl CounterFn for Value {
+    fn increment(&self, value: u64) {
+        *self.0.lock().unwrap() += value as f64;
+    }
+
+    fn absolute(&self, value: u64) {
+        *self.0.lock().unwrap() = value as f64;
+    }
+}
+
+impl GaugeFn for Value {
+    fn increment(&self, value: f64) {
+        *self.0.lock().unwrap() += value;
+    }
+
+    fn decrement(&self, value: f64) {
+        *self.0.lock().unwrap() -= value;
+    }
+
+    fn set(&self, value: f64) {
+        *self.0.lock().unwrap() = value;
+    }
+}
+
+#[derive(Default)]
+struct TestRecorder {
+    values: Mutex<HashMap<(String, String), Arc<Value>>>,
+}
+
+impl TestRecorder {
+    fn value(&self, key: &Key) -> Arc<Value> {
+        let strategy = key
+            .labels()
+            .find(|label| label.key() == "strategy")
+            .unwrap()
+            .value()
+            .to_string();
+        self.values
+            .lock()
+            .unwrap()
+            .entry((key.name().to_string(), strategy))
+            .or_default()
+            .clone()
+    }
+
+    fn get(&self, name: &str, strategy: &str) -> f64 {
+        self.values
+            .lock()
+            .unwrap()
+            .get(&(name.to_string(), strategy.to_string()))
+            .map_or(0.0, |value| *value.0.lock().unwrap())
+    }
+}
+
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.value(key))
+    }
+
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::from_arc(self.value(key))
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand(Functor::pure(0u8), rng, |a, b: bool| a + b as u8);
+    S::fmap_rand(f, rng, |a, b: bool| a + b as u8)
+}
+
+#[test]
+fn test_enumerator_metrics() {
+    let recorder = TestRecorder::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    metrics::with_local_recorder(&recorder, || random_process::<Enumerator>(&mut rng));
+
+    assert_eq!(recorder.get(OUTCOMES_EXPANDED, "Enumerator"), 6.0);
+    assert_eq!(recorder.get(COLLISIONS, "Enumerator"), 0.0);
+    assert_eq!(recorder.get(FUNCTOR_SIZE, "Enumerator"), 4.0);
+}
+
+#[test]
+fn test_counter_metrics() {
+    let recorder = TestRecorder::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    metrics::with_local_recorder(&recorder, || random_process::<CounterStrategy>(&mut rng));
+
+    assert_eq!(recorder.get(OUTCOMES_EXPANDED, "Counter"), 6.0);
+    assert_eq!(recorder.get(COLLISIONS, "Counter"), 1.0);
+    assert_eq!(recorder.get(FUNCTOR_SIZE, "Counter"), 3.0);
+}
+
+#[test]
+fn test_population_sampler_metrics() {
+    let recorder = TestRecorder::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    metrics::with_local_recorder(&recorder, || {
+        random_process::<PopulationSampler<3>>(&mut rng)
+    });
+
+    assert_eq!(recorder.get(SHRINKS, "PopulationSampler"), 1.0);
+    assert_eq!(recorder.get(OUTCOMES_DISCARDED, "PopulationSampler"), 1.0);
+    assert_eq!(recorder.get(FUNCTOR_SIZE, "PopulationSampler"), 3.0);
+}
@@ -0,0 +1,51 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Counter, Enumerator, Functor, RandomStrategy, UniqueEnumerator, WeightedFunctor,
+};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<(u8, u8)> {
+    let f = S::fmap_rand_range(Functor::pure(()), 1..=6u8, rng, |_, r| r);
+    S::fmap_rand(f, rng, |health, hit: bool| {
+        if hit {
+            (health.saturating_sub(4), health)
+        } else {
+            (health, 0)
+        }
+    })
+}
+
+#[test]
+fn test_unique_enumerator_bounds() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = random_process::<UniqueEnumerator>(&mut rng);
+    assert_eq!(f.len(), 12);
+    assert_eq!(
+        f.bounds_by_key(|&outcome| outcome),
+        Some((&(0, 1), &(6, 0)))
+    );
+    assert_eq!(
+        f.bounds_by_key(|&(health, damage)| (damage, health)),
+        Some((&(1, 0), &(2, 6)))
+    );
+}
+
+#[test]
+fn test_bounds_by_key_other_strategies() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let key = |&(health, damage): &(u8, u8)| 10 * health as i32 - damage as i32;
+    let expected = Some((&(0, 4), &(6, 0)));
+    assert_eq!(
+        random_process::<Enumerator>(&mut rng).bounds_by_key(key),
+        expected
+    );
+    assert_eq!(
+        random_process::<Counter>(&mut rng).bounds_by_key(key),
+        expected
+    );
+    assert_eq!(
+        random_process::<UniqueEnumerator>(&mut rng).bounds_by_key(key),
+        expected
+    );
+    assert_eq!(Vec::<u8>::new().bounds_by_key(|&o| o), None);
+}
@@ -0,0 +1,43 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::monoid::{Max, Min, Monoid, Or, Sum};
+use rand_functors::{Counter, Enumerator, Functor, RandomStrategy, WeightedFunctor};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let functor = Functor::pure(0);
+    let functor = S::fmap_rand_range(functor, 0..4, rng, |_, r: u8| r.min(2));
+    S::fmap_rand(functor, rng, |s, r: bool| if r { s * 4 } else { s })
+}
+
+#[test]
+fn test_pow() {
+    assert_eq!(Sum(3u32).pow(0), Sum(0));
+    assert_eq!(Sum(3u32).pow(7), Sum(21));
+    assert_eq!(Max(3u8).pow(0), Max(0));
+    assert_eq!(Min(3u8).pow(5), Min(3));
+    assert_eq!(Or(0b01u8).pow(2), Or(0b01));
+    assert_eq!((Sum(2u8), Or(true)).pow(3), (Sum(6), Or(true)));
+}
+
+#[test]
+fn test_aggregate() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let project = |&s: &u8| (Sum(s as u32), (Min(s), (Max(s), Or(1u16 << s))));
+
+    let enumerated = random_process::<Enumerator>(&mut rng).aggregate(project);
+    let counted = random_process::<Counter>(&mut rng).aggregate(project);
+    assert_eq!(enumerated, counted);
+
+    let (Sum(total), (Min(min), (Max(max), Or(reachable)))) = counted;
+    assert_eq!(total, 1 + 2 * 2 + 4 + 8 * 2);
+    assert_eq!(min, 0);
+    assert_eq!(max, 8);
+    assert_eq!(reachable, 0b1_0001_0111);
+}
+
+#[test]
+fn test_aggregate_empty() {
+    let output: Vec<u8> = Vec::new();
+    assert_eq!(output.aggregate(|&s| Max(s)), Max(0));
+    assert_eq!(output.aggregate(|&s| Min(s)), Min(u8::MAX));
+}
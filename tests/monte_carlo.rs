@@ -0,0 +1,56 @@
+#![cfg(feature = "std")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{monte_carlo, monte_carlo_until, RandomStrategy, Sampler};
+
+fn process(rng: &mut ChaCha8Rng) -> u8 {
+    Sampler::fmap_rand_range((), 1..=6, rng, |_, r: u8| r)
+}
+
+#[test]
+fn test_monte_carlo_is_independent_of_threads() {
+    let single = monte_carlo(10..1010, 1, process);
+    let multiple = monte_carlo(10..1010, 7, process);
+    assert_eq!(single, multiple);
+    assert_eq!(single.samples(), 1000);
+    assert_eq!(single.values().sum::<usize>(), 1000);
+    for seed in 10..1010 {
+        assert!(single[&process(&mut ChaCha8Rng::seed_from_u64(seed))] > 0);
+    }
+}
+
+#[test]
+fn test_monte_carlo_statistics() {
+    let results = monte_carlo(0..20_000, 4, process);
+    assert!((results.mean().unwrap() - 3.5).abs() < 0.05);
+    assert!((results.variance().unwrap() - 35.0 / 12.0).abs() < 0.1);
+    let histogram = results.histogram(1.0..6.0, 5);
+    assert_eq!(histogram[0], results[&1]);
+    assert_eq!(histogram[4], results[&5] + results[&6]);
+    assert_eq!(results.histogram(2.5..4.5, 2), [results[&3], results[&4]]);
+}
+
+#[test]
+fn test_monte_carlo_empty() {
+    let results = monte_carlo(5..5, 3, process);
+    assert_eq!(results.samples(), 0);
+    assert!(results.is_empty());
+    assert_eq!(results.mean(), None);
+    assert_eq!(results.variance(), None);
+}
+
+#[test]
+fn test_monte_carlo_until() {
+    let runs = AtomicUsize::new(0);
+    let results = monte_carlo_until(
+        0..1000,
+        3,
+        || runs.fetch_add(1, Ordering::Relaxed) >= 100,
+        process,
+    );
+    assert_eq!(results.samples(), 100);
+    assert_eq!(results.values().sum::<usize>(), 100);
+}
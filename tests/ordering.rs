@@ -0,0 +1,53 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Bernoulli;
+use rand_functors::{
+    Enumerator, FlattenableRandomStrategy, Functor, OrderedRandomStrategy, PopulationSampler,
+    RandomStrategy, WeightedRandomStrategy,
+};
+
+fn random_process<S: OrderedRandomStrategy + FlattenableRandomStrategy + WeightedRandomStrategy>(
+    rng: &mut impl Rng,
+) -> S::Functor<(u8, bool, bool, u8)> {
+    let f = S::fmap_rand_range(Functor::pure(()), 1..=3u8, rng, |_, a| a);
+    let f = S::fmap_rand(f, rng, |a, b: bool| (a, b));
+    let distribution = Bernoulli::new(1, 3).unwrap();
+    let f = S::fmap_rand_distribution(f, &distribution, rng, |(a, b), c| (a, b, c));
+    S::fmap_flat(f, |(a, b, c)| {
+        S::fmap_rand_range(Functor::pure((a, b, c)), 0..a, rng, |(a, b, c), d| {
+            (a, b, c, d)
+        })
+    })
+}
+
+fn expected() -> Vec<(u8, bool, bool, u8)> {
+    let mut expected = Vec::new();
+    for a in 1..=3 {
+        for b in [false, true] {
+            for c in [false, false, true] {
+                for d in 0..a {
+                    expected.push((a, b, c, d));
+                }
+            }
+        }
+    }
+    expected
+}
+
+#[test]
+fn test_enumerator_order() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert_eq!(random_process::<Enumerator>(&mut rng), expected());
+}
+
+#[test]
+fn test_population_sampler_order() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for _ in 0..20 {
+        let output =
+            <PopulationSampler<8>>::fmap_rand_range(Functor::pure(()), 0..64u8, &mut rng, |_, i| i);
+        let output = <PopulationSampler<8>>::fmap_rand(output, &mut rng, |i, b: bool| (i, b));
+        assert_eq!(output.len(), 8);
+        assert!(output.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -0,0 +1,93 @@
+use std::cell::Cell;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Counter, Enumerator, Functor, Patch, PopulationSampler, RandomStrategy, Sampler,
+    UniqueEnumerator,
+};
+
+thread_local! {
+    static CLONES: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, Eq, Hash, PartialEq)]
+struct State([u8; 64]);
+
+impl Clone for State {
+    fn clone(&self) -> Self {
+        CLONES.with(|clones| clones.set(clones.get() + 1));
+        Self(self.0)
+    }
+}
+
+struct Set(usize, u8);
+
+impl Patch<State> for Set {
+    fn apply(self, state: &mut State) {
+        state.0[self.0] = self.1;
+    }
+}
+
+fn clones() -> usize {
+    CLONES.with(|clones| clones.replace(0))
+}
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<State> {
+    let f = S::fmap_rand_delta(Functor::pure(State([0; 64])), rng, |_, r: u8| Set(0, r % 4));
+    S::fmap_rand_range_delta(f, 1..4, rng, |s, r| (s.0[0] != 0).then_some(Set(r, 1)))
+}
+
+#[test]
+fn test_delta_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    clones();
+    let output = random_process::<Enumerator>(&mut rng);
+    assert_eq!(clones(), 255 + 256 * 2);
+    assert_eq!(output.len(), 768);
+    assert_eq!(output.iter().filter(|s| s.0[0] == 0).count(), 192);
+    assert_eq!(output.iter().filter(|s| s.0[2] == 1).count(), 192);
+}
+
+#[test]
+fn test_delta_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    clones();
+    let output = random_process::<Counter>(&mut rng);
+    assert_eq!(clones(), 255 + 4 * 2);
+    assert_eq!(output.len(), 10);
+    let mut unchanged = State([0; 64]);
+    assert_eq!(output[&unchanged], 192);
+    unchanged.0[0] = 3;
+    unchanged.0[3] = 1;
+    assert_eq!(output[&unchanged], 64);
+}
+
+#[test]
+fn test_delta_unique_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<UniqueEnumerator>(&mut rng);
+    assert_eq!(output.len(), 10);
+}
+
+#[test]
+fn test_delta_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    clones();
+    for _ in 0..100 {
+        let output = random_process::<Sampler>(&mut rng);
+        assert!(output.0[0] < 4);
+        assert!(output.0[4..].iter().all(|&b| b == 0));
+    }
+    assert_eq!(clones(), 0);
+    let output = random_process::<PopulationSampler<8>>(&mut rng);
+    assert!(output.len() <= 8);
+}
+
+#[test]
+fn test_delta_none() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output =
+        <Enumerator>::fmap_rand_delta(vec![State([1; 64])], &mut rng, |_, r: bool| r.then_some(()));
+    assert!(output.iter().all(|s| s.0 == [1; 64]));
+}
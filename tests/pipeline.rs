@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Counter, Enumerator, FlattenableRandomStrategy, Functor, Pipeline, PopulationSampler, Sampler,
+    TraceEnumerator, WeightedFunctor,
+};
+
+fn random_process<S: FlattenableRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand(
+        Functor::pure(3u8),
+        rng,
+        |s, r: bool| if r { s * 2 } else { s },
+    );
+    let f = S::fmap_rand_range(f, 1..=3u8, rng, |s, r| s + r);
+    let f = S::fmap(f, |s| s % 5);
+    S::fmap_flat(f, |s| {
+        S::fmap_rand(Functor::pure(s), rng, |s, r: bool| s + r as u8)
+    })
+}
+
+macro_rules! pipeline {
+    () => {
+        Pipeline::start(3u8)
+            .rand(|s, r: bool| if r { s * 2 } else { s })
+            .rand_range(1..=3u8, |s, r| s + r)
+            .map(|s| s % 5)
+            .flat(|s| Pipeline::start(s).rand(|s, r: bool| s + r as u8))
+    };
+}
+
+#[test]
+fn test_pipeline_counter() {
+    let expected = random_process::<Counter>(&mut ChaCha8Rng::seed_from_u64(0));
+    let actual = pipeline!().run::<Counter>(&mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_pipeline_enumerator() {
+    let expected = random_process::<Enumerator>(&mut ChaCha8Rng::seed_from_u64(0));
+    let actual = pipeline!().run::<Enumerator>(&mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_pipeline_sampler() {
+    let expected = random_process::<Sampler>(&mut ChaCha8Rng::seed_from_u64(0));
+    let actual = pipeline!().run::<Sampler>(&mut ChaCha8Rng::seed_from_u64(0));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_pipeline_run_repeatedly() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let pipeline = pipeline!();
+    let outcomes = pipeline.run::<Enumerator>(&mut rng);
+    for _ in 0..16 {
+        assert!(outcomes.contains(&pipeline.run::<Sampler>(&mut rng)));
+    }
+}
+
+#[test]
+fn test_pipeline_without_flat() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let pipeline = Pipeline::start(0u16).rand(|s, r: u8| s + r as u16);
+    let output = pipeline.run::<PopulationSampler<10>>(&mut rng);
+    assert_eq!(output.len(), 10);
+    assert!(output.iter().all(|&s| s < 256));
+}
+
+#[test]
+fn test_pipeline_find_first() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let pipeline = pipeline!();
+    let traced = pipeline.run::<TraceEnumerator>(&mut rng);
+    for target in 0..8 {
+        let expected = traced
+            .iter()
+            .find(|(s, _)| *s == target)
+            .map(|(s, trace)| (*s, trace.clone()));
+        assert_eq!(pipeline.find_first(|&s| s == target), expected);
+    }
+    assert_eq!(pipeline.find_first(|&s| s == 3), Some((3, vec![1, 0, 1])));
+}
+
+#[test]
+fn test_pipeline_find_first_stops_early() {
+    let expanded = core::cell::Cell::new(0);
+    let pipeline = Pipeline::start(0u32)
+        .rand(|s, r: u16| s + r as u32)
+        .rand(|s, r: u16| s + r as u32)
+        .map(|s| {
+            expanded.set(expanded.get() + 1);
+            s
+        });
+    assert_eq!(pipeline.find_first(|&s| s == 2), Some((2, vec![0, 2])));
+    assert_eq!(expanded.get(), 3);
+}
+
+#[test]
+fn test_pipeline_run_until() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let pipeline = pipeline!();
+    let exact = pipeline.run::<Counter>(&mut rng).normalize_map();
+
+    let full = pipeline.run_until(|| false);
+    assert_eq!(full.residual(), 0.0);
+    assert_eq!(full.len(), exact.len());
+    for (outcome, p) in full.iter() {
+        assert!((p - exact[outcome]).abs() < 1e-12);
+    }
+
+    let mut budget = 5;
+    let partial = pipeline.run_until(|| {
+        budget -= 1;
+        budget < 0
+    });
+    assert_eq!(partial.residual(), 7.0 / 12.0);
+    assert!(partial.iter().all(|(outcome, p)| *p <= exact[outcome]));
+    let covered: f64 = partial.values().sum();
+    assert!((covered + partial.residual() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_pipeline_run_pipelined() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let pipeline = pipeline!();
+    let expected = pipeline.run::<Enumerator>(&mut rng);
+    for (chunk_size, capacity) in [(1, 0), (1, 1), (3, 2), (64, 4)] {
+        assert_eq!(pipeline.run_pipelined(chunk_size, capacity), expected);
+    }
+}
+
+#[test]
+fn test_pipeline_run_pipelined_until() {
+    let pipeline = pipeline!();
+    let expected = pipeline.run_pipelined(1, 1);
+    for chunks in 0..expected.len() {
+        let mut received = 0;
+        let (partial, complete) = pipeline.run_pipelined_until(1, 1, || {
+            received += 1;
+            received > chunks
+        });
+        assert!(!complete);
+        assert_eq!(partial, expected[..chunks]);
+    }
+    assert_eq!(
+        pipeline.run_pipelined_until(1, 1, || false),
+        (expected, true)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_pipeline_run_pipelined_propagates_panics() {
+    let pipeline = Pipeline::start(0u16)
+        .rand(|s, r: u8| s + r as u16)
+        .map(|s| {
+            if s == 200 {
+                panic!("stage panicked")
+            } else {
+                s
+            }
+        });
+    pipeline.run_pipelined(16, 1);
+}
+
+#[test]
+fn test_pipeline_run_budgeted() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let pipeline = pipeline!();
+    let expected = pipeline.run::<Enumerator>(&mut rng);
+
+    let exact = pipeline.run_budgeted(&mut rng, Duration::MAX);
+    assert!(exact.is_exact());
+    assert_eq!(exact.exact_stages(), 4);
+    assert_eq!(exact.into_inner(), expected);
+
+    let sampled = pipeline.run_budgeted(&mut rng, Duration::ZERO);
+    assert!(!sampled.is_exact());
+    assert_eq!(sampled.exact_stages(), 0);
+    assert_eq!(sampled.len(), 1);
+    assert!(expected.contains(&sampled[0]));
+}
+
+#[test]
+fn test_pipeline_run_budgeted_degrades() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let pipeline = Pipeline::start(0u16)
+        .rand(|s, r: u8| s + r as u16)
+        .map(|s| {
+            thread::sleep(Duration::from_micros(200));
+            s * 2
+        })
+        .rand(|s, r: bool| s + r as u16);
+    let partial = pipeline.run_budgeted(&mut rng, Duration::from_millis(20));
+    assert!(!partial.is_exact());
+    assert_eq!(partial.exact_stages(), 2);
+    assert_eq!(partial.len(), 256);
+    for (i, &s) in partial.iter().enumerate() {
+        assert_eq!(s / 2, i as u16);
+    }
+}
+
+#[test]
+fn test_pipeline_run_budgeted_flat() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    // The deadline passes while the pipeline continuing `false` is evaluated,
+    // before the one continuing `true`.
+    let pipeline = Pipeline::start(())
+        .rand(|_, r: bool| r)
+        .flat(|r| {
+            Pipeline::start(r).rand(|r, s: u8| (r, s)).map(move |s| {
+                if !r {
+                    thread::sleep(Duration::from_micros(200));
+                }
+                s
+            })
+        })
+        .rand(|(r, s), t: bool| (r, s, t));
+    let partial = pipeline.run_budgeted(&mut rng, Duration::from_millis(20));
+    assert!(!partial.is_exact());
+    assert_eq!(partial.exact_stages(), 2);
+    assert_eq!(partial.len(), 512);
+    assert_eq!(partial.iter().filter(|&&(r, _, _)| r).count(), 256);
+}
+
+#[test]
+fn test_pipeline_run_counted() {
+    use rand_functors::pipeline::{CountOverflowError, Counts};
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let pipeline = Pipeline::start(3u8)
+        .rand(|s, r: bool| if r { s * 2 } else { s })
+        .rand_range(1..=3u8, |s, r| s + r)
+        .map(|s| s % 5);
+    assert_eq!(pipeline.max_total_count(), Some(6));
+    let counts = pipeline.run_counted(&mut rng).unwrap();
+    assert!(matches!(counts, Counts::Usize(_)));
+    assert_eq!(
+        counts.into_u128(),
+        pipeline
+            .run::<Counter>(&mut rng)
+            .into_iter()
+            .map(|(s, c)| (s, c as u128))
+            .collect::<HashMap<_, _>>()
+    );
+
+    let residue = |s: u8, r: u16| (s + (r % 3) as u8) % 3;
+    let wide = Pipeline::start(0u8)
+        .rand(residue)
+        .rand(residue)
+        .rand(residue)
+        .rand(residue);
+    assert_eq!(wide.max_total_count(), Some(1 << 64));
+    let counts = wide.run_counted(&mut rng).unwrap();
+    assert!(matches!(counts, Counts::U128(_)));
+    assert_eq!((0..3).map(|s| counts.count(&s)).sum::<u128>(), 1 << 64);
+
+    let unbounded = Pipeline::start(0u8).rand_range(0..=u128::MAX, |_, r| (r % 2) as u8);
+    assert_eq!(unbounded.max_total_count(), None);
+    assert_eq!(unbounded.run_counted(&mut rng), Err(CountOverflowError));
+    assert_eq!(pipeline!().run_counted(&mut rng), Err(CountOverflowError));
+}
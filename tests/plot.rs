@@ -0,0 +1,48 @@
+#![cfg(feature = "plotters")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    render_histogram, Counter, Enumerator, Functor, HistogramKind, HistogramOptions, RandomStrategy,
+};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let functor = Functor::pure(0);
+    let functor = S::fmap_rand_range(functor, 1..=6, rng, |s, r: u8| s + r);
+    S::fmap_rand_range(functor, 1..=6, rng, |s, r: u8| s + r)
+}
+
+#[test]
+fn test_render_bar_histogram_svg() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Counter>(&mut rng);
+    let path = std::env::temp_dir().join("rand_functors_test_render_bar_histogram.svg");
+    let options = HistogramOptions {
+        title: Some("2d6".to_string()),
+        ..Default::default()
+    };
+    render_histogram(&output, &path, &options).unwrap();
+
+    let svg = std::fs::read_to_string(&path).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("2d6"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_render_cdf_png() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    let path = std::env::temp_dir().join("rand_functors_test_render_cdf.png");
+    let options = HistogramOptions {
+        kind: HistogramKind::Cdf,
+        width: 320,
+        height: 240,
+        title: None,
+    };
+    render_histogram(&output, &path, &options).unwrap();
+
+    let png = std::fs::read(&path).unwrap();
+    assert_eq!(&png[1..4], b"PNG");
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "std")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    explore, step, with_progress, Counter, Enumerator, Functor, Memoized, Progress, RandomStrategy,
+    Sampler, UniqueEnumerator, EXPANSION_INTERVAL,
+};
+
+fn record<T>(op: impl FnOnce() -> T) -> (T, Vec<Progress>) {
+    let updates = Rc::new(RefCell::new(Vec::new()));
+    let sink = updates.clone();
+    let output = with_progress(move |progress| sink.borrow_mut().push(progress), op);
+    let updates = updates.borrow().clone();
+    (output, updates)
+}
+
+#[test]
+fn test_progress_operations() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let (_, updates) = record(|| {
+        step("roll", || {
+            let f = <Counter>::fmap_rand(Functor::pure(0u8), &mut rng, |_, r: bool| r as u8);
+            Counter::fmap_rand(f, &mut rng, |s, r: bool| s + r as u8)
+        })
+    });
+    assert_eq!(
+        updates,
+        [
+            Progress::Operation {
+                strategy: "Counter",
+                step: Some("roll"),
+                produced: 2,
+                stored: 2,
+            },
+            Progress::Operation {
+                strategy: "Counter",
+                step: Some("roll"),
+                produced: 4,
+                stored: 3,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_progress_expanding() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let (f, updates) = record(|| {
+        let f = Enumerator::fmap_rand(Functor::pure(()), &mut rng, |_, r: u16| r);
+        Enumerator::fmap_rand(f, &mut rng, |s, r: bool| (s, r))
+    });
+    assert_eq!(f.len(), 1 << 17);
+    let expanding = updates
+        .iter()
+        .filter_map(|progress| match progress {
+            Progress::Expanding { produced, .. } => Some(*produced),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        expanding,
+        [
+            EXPANSION_INTERVAL,
+            EXPANSION_INTERVAL,
+            2 * EXPANSION_INTERVAL
+        ]
+    );
+}
+
+#[test]
+fn test_progress_drivers() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let (_, updates) = record(|| {
+        let mut memoized = Memoized::<Counter, _, _>::new(|s: u8| {
+            Counter::fmap_rand(Functor::pure(s), &mut rng, |s, r: bool| s ^ r as u8)
+        });
+        memoized.run(Functor::pure(0), 2)
+    });
+    let iterations = updates
+        .iter()
+        .filter(|progress| matches!(progress, Progress::Iteration { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        iterations,
+        [
+            &Progress::Iteration {
+                driver: "Memoized",
+                step: None,
+                completed: 1,
+                total: Some(2),
+            },
+            &Progress::Iteration {
+                driver: "Memoized",
+                step: None,
+                completed: 2,
+                total: Some(2),
+            },
+        ]
+    );
+
+    let (reachable, updates) = record(|| {
+        explore([0u8], Some(5), |s| {
+            UniqueEnumerator::fmap_rand(Functor::pure(s), &mut rng, |s, r: bool| {
+                s.saturating_add(r as u8).min(2)
+            })
+        })
+    });
+    assert!(updates.contains(&Progress::Iteration {
+        driver: "explore",
+        step: None,
+        completed: reachable.iterations(),
+        total: Some(5),
+    }));
+}
+
+#[test]
+fn test_progress_nesting() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let (inner, outer) = record(|| {
+        let (_, inner) =
+            record(|| <Counter>::fmap_rand(Functor::pure(()), &mut rng, |_, r: bool| r));
+        let _ = Sampler::fmap_rand((), &mut rng, |_, r: bool| r);
+        inner
+    });
+    assert_eq!(inner.len(), 1);
+    assert!(outer.is_empty());
+    let (_, after) = record(|| ());
+    assert!(after.is_empty());
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "pyo3")]
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::python::{evolution_to_python, to_python};
+use rand_functors::{evolve, Counter, Functor, RandomStrategy};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let functor = Functor::pure(0);
+    S::fmap_rand_range(functor, 0..4, rng, |_, r: u8| r.min(2))
+}
+
+#[test]
+fn test_counter_to_python() {
+    Python::initialize();
+    Python::attach(|py| {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let output = random_process::<Counter>(&mut rng);
+        let distribution = to_python(py, &output).unwrap();
+
+        let module = PyModule::new(py, "rand_functors").unwrap();
+        rand_functors::python::register(&module).unwrap();
+        assert!(module.getattr("Distribution").is_ok());
+        assert!(module.getattr("Evolution").is_ok());
+
+        let locals = PyDict::new(py);
+        locals.set_item("d", distribution).unwrap();
+        let result = py
+            .eval(
+                pyo3::ffi::c_str!("(d.outcomes[0], d.probabilities[0], d.total_weight, len(d))"),
+                None,
+                Some(&locals),
+            )
+            .unwrap()
+            .extract::<(u8, f64, f64, usize)>()
+            .unwrap();
+        assert_eq!(result, (2, 0.5, 4.0, 3));
+    });
+}
+
+#[test]
+fn test_evolution_to_python() {
+    Python::initialize();
+    Python::attach(|py| {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let distributions = evolve::<Counter, _>(0u8, 2, |s| {
+            <Counter as RandomStrategy>::fmap_rand(Functor::pure(s), &mut rng, |s, r: bool| {
+                s + r as u8
+            })
+        });
+        let evolution = evolution_to_python(py, &distributions).unwrap();
+
+        let locals = PyDict::new(py);
+        locals.set_item("e", evolution).unwrap();
+        let result = py
+            .eval(
+                pyo3::ffi::c_str!(
+                    "(e.outcomes, e.probabilities, e.distributions[2].outcomes[0], len(e))"
+                ),
+                None,
+                Some(&locals),
+            )
+            .unwrap()
+            .extract::<(Vec<u8>, Vec<Vec<f64>>, u8, usize)>()
+            .unwrap();
+        assert_eq!(
+            result,
+            (
+                vec![0, 1, 2],
+                vec![
+                    vec![1.0, 0.0, 0.0],
+                    vec![0.5, 0.5, 0.0],
+                    vec![0.25, 0.5, 0.25]
+                ],
+                1,
+                3
+            )
+        );
+    });
+}
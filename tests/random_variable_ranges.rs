@@ -2,8 +2,11 @@ use std::collections::HashMap;
 
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::DistributionError;
 use rand_functors::{
-    Counter, Enumerator, Functor, PopulationSampler, RandomStrategy, Sampler, UniqueEnumerator,
+    AdaptiveCounter, Counter, Enumerator, Functor, PopulationSampler, RandomStrategy,
+    RandomVariableRange, Reversed, Sampler, StratifiedCounter, TraceEnumerator, UniqueEnumerator,
+    WeightedPopulationSampler, WitnessCounter,
 };
 
 fn random_process<S: RandomStrategy>(rng: &mut impl Rng, base: u16) -> S::Functor<u16> {
@@ -74,3 +77,148 @@ fn test_rand_range_counter() {
     assert_eq!(d[&39], 6);
     assert_eq!(d[&40], 1);
 }
+
+#[test]
+fn test_unbounded_ranges() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert_eq!(
+        <Enumerator>::fmap_rand_range(Functor::pure(()), 250u8.., &mut rng, |_, r| r),
+        [250, 251, 252, 253, 254, 255]
+    );
+    assert_eq!(
+        <Enumerator>::fmap_rand_range(Functor::pure(()), ..-125i8, &mut rng, |_, r| r),
+        [-128, -127, -126]
+    );
+    assert_eq!(
+        <Enumerator>::fmap_rand_range(Functor::pure(()), ..=2u8, &mut rng, |_, r| r),
+        [0, 1, 2]
+    );
+    let counts = <Counter>::fmap_rand_range(Functor::pure(()), .., &mut rng, |_, r: i8| r < 0);
+    assert_eq!(counts, HashMap::from([(false, 128), (true, 128)]));
+
+    for _ in 0..100 {
+        assert!(Sampler::fmap_rand_range((), 250u8.., &mut rng, |_, r| r) >= 250);
+        assert!(Sampler::fmap_rand_range((), ..=2u8, &mut rng, |_, r| r) <= 2);
+        assert!(Sampler::fmap_rand_range((), ..-125i8, &mut rng, |_, r| r) < -125);
+    }
+    Sampler::fmap_rand_range((), .., &mut rng, |_, r: u128| r);
+}
+
+#[test]
+fn test_rev_space() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert_eq!(
+        <Enumerator>::fmap_rand_range(Functor::pure(()), (3..7u8).rev_space(), &mut rng, |_, r| r),
+        [6, 5, 4, 3]
+    );
+    assert_eq!(
+        <Enumerator>::fmap_rand_range(
+            Functor::pure(()),
+            (..=-126i8).rev_space(),
+            &mut rng,
+            |_, r| r
+        ),
+        [-126, -127, -128]
+    );
+    assert_eq!(
+        (0..3u8)
+            .rev_space()
+            .rev_space()
+            .sample_space()
+            .collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+    for _ in 0..100 {
+        assert!((3..7u8).contains(&Sampler::fmap_rand_range(
+            (),
+            (3..7u8).rev_space(),
+            &mut rng,
+            |_, r| r
+        )));
+    }
+}
+
+#[test]
+fn test_reversed() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Enumerator>::fmap_rand(Functor::pure(()), &mut rng, |_, r: Reversed<u8>| {
+        r.into_inner()
+    });
+    assert!(f.iter().copied().eq((0..=u8::MAX).rev()));
+    let f = <Counter>::fmap_rand(
+        Functor::pure(()),
+        &mut rng,
+        |_, Reversed(r): Reversed<bool>| r,
+    );
+    assert_eq!(f, HashMap::from([(false, 1), (true, 1)]));
+}
+
+fn empty_range_size<S: RandomStrategy>(rng: &mut impl Rng) -> usize {
+    let f = S::fmap_rand_range(Functor::pure(0u8), 5..5u8, rng, |s, r| s + r);
+    let f = S::fmap_rand_range(f, ..0u8, rng, |s, r| s + r);
+    S::fmap_rand(f, rng, |s, r: bool| s + r as u8).size()
+}
+
+#[test]
+fn test_empty_ranges() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert!((5..5u8).is_empty());
+    assert!(RandomVariableRange::<u8>::is_empty(&..0u8));
+    assert!(!RandomVariableRange::<u8>::is_empty(&(255u8..)));
+
+    assert_eq!(empty_range_size::<Enumerator>(&mut rng), 0);
+    assert_eq!(empty_range_size::<Counter>(&mut rng), 0);
+    assert_eq!(empty_range_size::<UniqueEnumerator>(&mut rng), 0);
+    assert_eq!(empty_range_size::<PopulationSampler<4>>(&mut rng), 0);
+    assert_eq!(
+        empty_range_size::<WeightedPopulationSampler<4>>(&mut rng),
+        0
+    );
+    assert_eq!(empty_range_size::<AdaptiveCounter<4>>(&mut rng), 0);
+    assert_eq!(empty_range_size::<StratifiedCounter<4>>(&mut rng), 0);
+    assert_eq!(empty_range_size::<TraceEnumerator>(&mut rng), 0);
+    assert_eq!(empty_range_size::<WitnessCounter>(&mut rng), 0);
+
+    assert_eq!(
+        Sampler::try_fmap_rand_range(0u8, 5..5u8, &mut rng, |s, r| s + r),
+        Err(DistributionError::EmptyRange)
+    );
+    assert_eq!(
+        <Counter>::try_fmap_rand_range(Functor::pure(0u8), 5..5u8, &mut rng, |s, r| s + r),
+        Err(DistributionError::EmptyRange)
+    );
+    assert_eq!(
+        Sampler::try_fmap_rand_range(0u8, 5..=5u8, &mut rng, |s, r| s + r),
+        Ok(5)
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot sample from an empty range")]
+fn test_empty_range_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    Sampler::fmap_rand_range(0u8, 5..5u8, &mut rng, |s, r| s + r);
+}
+
+/// The even numbers below a bound, which only implements `sample_space`.
+struct Evens(u8);
+
+impl RandomVariableRange<u8> for Evens {
+    fn sample_space(&self) -> impl DoubleEndedIterator<Item = u8> {
+        (0..self.0).step_by(2)
+    }
+}
+
+#[test]
+fn test_default_sample_single() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let samples = (0..100)
+        .map(|_| Sampler::fmap_rand_range(0u8, Evens(7), &mut rng, |s, r| s + r))
+        .collect::<Vec<_>>();
+    assert!(samples.iter().all(|s| [0, 2, 4, 6].contains(s)));
+    assert!([0, 2, 4, 6].iter().all(|s| samples.contains(s)));
+    assert_eq!(
+        <Enumerator>::fmap_rand_range(Functor::pure(0u8), Evens(7), &mut rng, |s, r| s + r),
+        [0, 2, 4, 6]
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "num-rational")]
+
+use num_rational::Ratio;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Binomial;
+use rand_functors::{
+    Counter, DefaultState, FlattenableRandomStrategy, Functor, RandomStrategy, RationalCounter,
+    WeightedFunctor, WeightedRandomStrategy,
+};
+
+fn die<S: RandomStrategy>(sides: u8, rng: &mut impl Rng) -> S::Functor<u8> {
+    S::fmap_rand_range(Functor::pure(()), 1..=sides, rng, |_, r| r)
+}
+
+fn random_process<S: FlattenableRandomStrategy>(rng: &mut ChaCha8Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand_range(Functor::pure(()), 0..3u8, rng, |_, r| r);
+    let f = S::if_then_else(
+        f,
+        |&s| s == 0,
+        rng,
+        |_, rng| die::<S>(11, rng),
+        |s, rng| {
+            S::if_then_else(
+                Functor::pure(s),
+                |&s| s == 1,
+                rng,
+                |_, rng| die::<S>(13, rng),
+                |_, rng| die::<S>(17, rng),
+            )
+        },
+    );
+    S::loop_until(f, |&s| s > 1, 2, |_| die::<S>(11, rng))
+}
+
+#[test]
+fn test_rational_counter_does_not_inflate() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let counted = random_process::<Counter<DefaultState, u128>>(&mut rng);
+    let total = counted.values().sum::<u128>();
+    assert!(total > 11 * 13 * 17);
+
+    let f = random_process::<RationalCounter>(&mut rng);
+    assert_eq!(f.values().sum::<Ratio<u128>>(), Ratio::from_integer(1));
+    assert!(f.iter().all(|(s, p)| Ratio::new(counted[s], total) == *p));
+    assert_eq!(
+        f.iter()
+            .filter(|(&s, _)| s > 13)
+            .map(|(_, p)| p)
+            .sum::<Ratio<u128>>(),
+        Ratio::new(4, 51)
+    );
+}
+
+#[test]
+fn test_rational_counter_absorbing_and_distribution() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut f = <RationalCounter>::fmap_rand(Functor::pure(0u8), &mut rng, |_, r: bool| r as u8);
+    for _ in 0..2 {
+        f = <RationalCounter>::fmap_rand_absorbing(f, |&s| s == 1, &mut rng, |_, r: bool| r as u8);
+    }
+    assert_eq!(f[&0], Ratio::new(1, 8));
+    assert_eq!(f[&1], Ratio::new(7, 8));
+
+    let f = <RationalCounter>::fmap_rand_distribution(
+        Functor::pure(()),
+        &Binomial::new(3, 1, 2).unwrap(),
+        &mut rng,
+        |_, k| k as u8,
+    );
+    assert_eq!(
+        f.to_sorted_vec(),
+        [
+            (0, Ratio::new(1, 8)),
+            (1, Ratio::new(3, 8)),
+            (2, Ratio::new(3, 8)),
+            (3, Ratio::new(1, 8))
+        ]
+    );
+}
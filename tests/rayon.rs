@@ -0,0 +1,64 @@
+#![cfg(feature = "rayon")]
+
+use rand_functors::{
+    Counter, Dual, Enumerator, ExactRandomStrategy, FlattenableRandomStrategy, Functor,
+    UniqueEnumerator,
+};
+
+fn child<S: ExactRandomStrategy>(s: u8) -> S::Functor<u8> {
+    S::fmap_rand_range_exact(Functor::pure(s), 0..=s % 4, |s, r| s.wrapping_mul(3) ^ r)
+}
+
+fn parent<S: ExactRandomStrategy + FlattenableRandomStrategy>() -> S::Functor<u8> {
+    S::fmap_rand_exact(Functor::pure(()), |_, r: u8| r)
+}
+
+fn nested<S: ExactRandomStrategy + FlattenableRandomStrategy>(depth: u8, s: u8) -> S::Functor<u8>
+where
+    S::Functor<u8>: Send,
+{
+    let f = child::<S>(s);
+    if depth == 0 {
+        f
+    } else {
+        S::fmap_flat_par(f, |s| nested::<S>(depth - 1, s))
+    }
+}
+
+fn nested_sequential<S: ExactRandomStrategy + FlattenableRandomStrategy>(
+    depth: u8,
+    s: u8,
+) -> S::Functor<u8> {
+    let f = child::<S>(s);
+    if depth == 0 {
+        f
+    } else {
+        S::fmap_flat(f, |s| nested_sequential::<S>(depth - 1, s))
+    }
+}
+
+#[test]
+fn test_fmap_flat_par_matches_fmap_flat() {
+    assert_eq!(
+        <Enumerator>::fmap_flat_par(parent::<Enumerator>(), child::<Enumerator>),
+        <Enumerator>::fmap_flat(parent::<Enumerator>(), child::<Enumerator>),
+    );
+    assert_eq!(
+        <Counter>::fmap_flat_par(parent::<Counter>(), child::<Counter>),
+        <Counter>::fmap_flat(parent::<Counter>(), child::<Counter>),
+    );
+    type D = Dual<Counter, UniqueEnumerator>;
+    assert_eq!(
+        D::fmap_flat_par(parent::<D>(), child::<D>),
+        D::fmap_flat(parent::<D>(), child::<D>),
+    );
+}
+
+#[test]
+fn test_fmap_flat_par_nested() {
+    assert_eq!(
+        nested::<Enumerator>(4, 7),
+        nested_sequential::<Enumerator>(4, 7)
+    );
+    assert_eq!(nested::<Counter>(5, 7), nested_sequential::<Counter>(5, 7));
+}
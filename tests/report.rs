@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Counter, Enumerator, Functor, RandomStrategy, ReportExt, WeightedFunctor};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let functor = Functor::pure(0);
+    S::fmap_rand_range(functor, 0..4, rng, |_, r: u8| r.min(2))
+}
+
+#[test]
+fn test_enumerator_report() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    let report = output.report(None);
+
+    assert_eq!(report.distinct_outcomes, 3);
+    assert_eq!(report.total_weight, 4.0);
+    assert_eq!(report.entropy, 1.5);
+    assert!(!report.truncated);
+    assert_eq!(
+        report
+            .outcomes
+            .iter()
+            .map(|o| (*o.outcome, o.probability))
+            .collect::<Vec<_>>(),
+        [(2, 0.5), (0, 0.25), (1, 0.25)]
+    );
+}
+
+#[test]
+fn test_counter_report_top_k() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Counter>(&mut rng);
+    let report = output.report(Some(1));
+
+    assert_eq!(report.distinct_outcomes, 3);
+    assert!(report.truncated);
+    assert_eq!(report.outcomes.len(), 1);
+    assert_eq!(*report.outcomes[0].outcome, 2);
+    assert_eq!(report.outcomes[0].probability, 0.5);
+}
+
+#[test]
+fn test_display() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    assert_eq!(
+        output.display(None).to_string(),
+        " 50.00%  2\n 25.00%  0\n 25.00%  1"
+    );
+
+    let output = random_process::<Counter>(&mut rng);
+    assert_eq!(
+        format!("{:.0}", output.display(Some(1))),
+        " 50%  2\n ... 2 more outcomes (50%)"
+    );
+    assert_eq!(
+        output.display(Some(0)).to_string(),
+        " ... 3 more outcomes (100.00%)"
+    );
+}
+
+#[test]
+fn test_ascii_histogram() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Counter>(&mut rng);
+    assert_eq!(
+        output.ascii_histogram(4),
+        concat!(
+            "0 | ##    25.00%\n",
+            "1 | ##    25.00%\n",
+            "2 | ####  50.00%\n",
+        )
+    );
+
+    let output = <Counter>::fmap(output, |o| o as i8 - 1);
+    assert_eq!(
+        output.ascii_histogram(2),
+        concat!(
+            "-1 | #   25.00%\n",
+            " 0 | #   25.00%\n",
+            " 1 | ##  50.00%\n",
+        )
+    );
+}
+
+#[test]
+fn test_normalize() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    assert_eq!(output.normalize(), [(2, 0.5), (0, 0.25), (1, 0.25)]);
+
+    let output = random_process::<Counter>(&mut rng);
+    let expected: HashMap<u8, f64> = [(0, 0.25), (1, 0.25), (2, 0.5)].into_iter().collect();
+    assert_eq!(output.normalize_map(), expected);
+}
+
+#[test]
+fn test_probability_where() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    assert_eq!(output.probability_where(|&s| s == 2), 0.5);
+
+    let output = random_process::<Counter>(&mut rng);
+    assert_eq!(output.probability_where(|&s| s < 2), 0.5);
+    assert_eq!(output.probability_where(|&s| s > 2), 0.0);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_report_to_json() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+
+    assert_eq!(
+        output.report(Some(2)).to_json(),
+        concat!(
+            r#"{"distinct_outcomes":3,"total_weight":4.0,"entropy":1.5,"truncated":true,"#,
+            r#""outcomes":[{"outcome":2,"probability":0.5},{"outcome":0,"probability":0.25}]}"#
+        )
+    );
+}
+
+#[test]
+fn test_mean_variance() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    assert_eq!(output.mean_variance(|&s| s as f64), Some((1.25, 0.6875)));
+
+    let output = random_process::<Counter>(&mut rng);
+    let (mean, variance) = output.mean_variance(|&s| s as f64).unwrap();
+    assert!((mean - 1.25).abs() < 1e-12);
+    assert!((variance - 0.6875).abs() < 1e-12);
+
+    let output: Vec<u8> = Vec::new();
+    assert_eq!(output.mean_variance(|&s| s as f64), None);
+}
+
+#[test]
+fn test_group_by() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let expected = HashMap::from([
+        (true, HashMap::from([(0, 1), (2, 2)])),
+        (false, HashMap::from([(1, 1)])),
+    ]);
+
+    let output = random_process::<Enumerator>(&mut rng);
+    assert_eq!(output.group_by(|&s| s % 2 == 0), expected);
+
+    let output = random_process::<Counter>(&mut rng);
+    assert_eq!(output.group_by(|&s| s % 2 == 0), expected);
+}
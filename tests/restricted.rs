@@ -0,0 +1,60 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Counter, Functor, Joint, RandomStrategy, RandomVariable, Restricted, Restriction,
+};
+
+struct NonZero;
+
+impl Restriction<u8> for NonZero {
+    fn allows(value: &u8) -> bool {
+        *value != 0
+    }
+}
+
+struct InsideArena;
+
+impl Restriction<Joint<u8, u8>> for InsideArena {
+    fn allows(Joint(x, y): &Joint<u8, u8>) -> bool {
+        (*x as u32).pow(2) + (*y as u32).pow(2) <= 100
+    }
+}
+
+#[test]
+fn test_restricted_sample_space() {
+    assert_eq!(<Restricted<u8, NonZero>>::sample_space().count(), 255);
+    assert!(<Restricted<u8, NonZero>>::sample_space().all(|x| x.get() != 0));
+    assert_eq!(
+        <Restricted<Joint<u8, u8>, InsideArena>>::sample_space().count(),
+        90
+    );
+    assert!(<Restricted<u8, NonZero>>::new(0).is_none());
+    assert_eq!(
+        <Restricted<u8, NonZero>>::new(3).map(Restricted::into_inner),
+        Some(3)
+    );
+}
+
+#[test]
+fn test_restricted_sampling() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let Joint(x, y) = rng
+            .gen::<Restricted<Joint<u8, u8>, InsideArena>>()
+            .into_inner();
+        assert!(x <= 10 && y <= 10);
+    }
+    assert!((0..1000).all(|_| rng.gen::<Restricted<u8, NonZero>>().get() != 0));
+}
+
+#[test]
+fn test_restricted_strategies() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Counter>::fmap_rand(
+        Functor::pure(()),
+        &mut rng,
+        |_, x: Restricted<u8, NonZero>| x.get() % 2,
+    );
+    assert_eq!(f[&0], 127);
+    assert_eq!(f[&1], 128);
+}
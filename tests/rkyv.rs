@@ -0,0 +1,59 @@
+#![cfg(feature = "rkyv")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    access_outcomes, access_outcomes_unchecked, archive_outcomes, write_archived_outcomes,
+    Enumerator, Functor, RandomStrategy,
+};
+use rkyv::rancor::Error;
+use rkyv::util::AlignedVec;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, rkyv::Archive, rkyv::Serialize)]
+struct State {
+    x: u8,
+    flag: bool,
+}
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<State> {
+    let functor = Functor::pure(State { x: 0, flag: false });
+    let functor = S::fmap_rand(functor, rng, |s, x| State { x, ..s });
+    S::fmap_rand(functor, rng, |s, flag| State { flag, ..s })
+}
+
+#[test]
+fn test_archive_and_access_outcomes() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    let bytes = archive_outcomes(&output).unwrap();
+    let archived = access_outcomes::<State, Error>(&bytes).unwrap();
+
+    assert_eq!(archived.len(), output.len());
+    for (a, o) in archived.iter().zip(output.iter()) {
+        assert_eq!(a.x, o.x);
+        assert_eq!(a.flag, o.flag);
+    }
+}
+
+#[test]
+fn test_write_archived_outcomes() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    let written = write_archived_outcomes(&output, Vec::new()).unwrap();
+
+    let mut bytes = AlignedVec::<16>::new();
+    bytes.extend_from_slice(&written);
+    assert_eq!(&bytes[..], &archive_outcomes(&output).unwrap()[..]);
+
+    let archived = unsafe { access_outcomes_unchecked::<State>(&bytes) };
+    assert_eq!(archived.len(), 512);
+    assert_eq!(archived[511].x, 255);
+    assert!(archived[511].flag);
+}
+
+#[test]
+fn test_access_outcomes_rejects_invalid_archive() {
+    let mut bytes = AlignedVec::<16>::new();
+    bytes.extend_from_slice(&[0xff; 8]);
+    assert!(access_outcomes::<State, Error>(&bytes).is_err());
+}
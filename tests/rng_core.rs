@@ -0,0 +1,56 @@
+use rand::RngCore;
+use rand_functors::{Enumerator, Functor, PopulationSampler, RandomStrategy, Sampler};
+
+/// A generator implementing only `RngCore`, which counts upwards from zero.
+struct CountingRng(u64);
+
+impl RngCore for CountingRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 += 1;
+        self.0 - 1
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+fn random_process<S: RandomStrategy>(rng: &mut impl RngCore) -> S::Functor<u8> {
+    let f = S::fmap_rand(Functor::pure(0u8), rng, |a, b: u8| a.wrapping_add(b));
+    S::fmap_rand_range(f, 0..4u8, rng, |a, b| a / 4 + b)
+}
+
+#[test]
+fn test_rng_core_sampler() {
+    let output = random_process::<Sampler>(&mut CountingRng(0));
+    assert!(output < 68);
+}
+
+#[test]
+fn test_rng_core_enumerator() {
+    let output = random_process::<Enumerator>(&mut CountingRng(0));
+    assert_eq!(output.len(), 1024);
+}
+
+#[test]
+fn test_rng_core_population_sampler() {
+    let output = random_process::<PopulationSampler<10>>(&mut CountingRng(0));
+    assert_eq!(output.len(), 10);
+}
+
+#[test]
+fn test_rng_core_trait_object() {
+    let mut rng = CountingRng(0);
+    let rng: &mut dyn RngCore = &mut rng;
+    let output = random_process::<PopulationSampler<10>>(&mut &mut *rng);
+    assert_eq!(output.len(), 10);
+}
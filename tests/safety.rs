@@ -0,0 +1,66 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    check_safety, Functor, RandomStrategy, RandomVariable, WitnessCounter, WitnessedCounts,
+};
+
+/// A counter which is reset by a coin flip, and otherwise advances by a roll
+/// of a three-sided die only while it is even.
+fn step<S: RandomStrategy>(s: u8, rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand(
+        Functor::pure(s),
+        rng,
+        |s, reset: bool| if reset { 0 } else { s },
+    );
+    S::fmap_rand_range(f, 1..=3u8, rng, |s, r| if s % 2 == 0 { s + r } else { s })
+}
+
+#[test]
+fn test_check_safety_safe() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let check = check_safety(0, 2, |&s| s > 6, |s| step::<WitnessCounter>(s, &mut rng));
+    assert!(check.is_safe());
+    assert_eq!(check.probability(), 0.0);
+    assert!(check.into_counterexample().is_none());
+}
+
+#[test]
+fn test_check_safety_counterexample() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let check = check_safety(0, 4, |&s| s >= 7, |s| step::<WitnessCounter>(s, &mut rng));
+    assert!(!check.is_safe());
+    assert!(check.probability() > 0.0 && check.probability() < 1.0);
+    let counterexample = check.counterexample().unwrap();
+    assert_eq!(counterexample.steps, 3);
+
+    // Replaying the trace reaches the bad state.
+    let mut state = 0;
+    for choices in counterexample.trace.chunks(2) {
+        let reset = bool::sample_space().nth(choices[0]).unwrap();
+        let r = (1..=3u8).nth(choices[1]).unwrap();
+        state = if reset { 0 } else { state };
+        state = if state % 2 == 0 { state + r } else { state };
+    }
+    assert_eq!(state, counterexample.state);
+    assert!(state >= 7);
+    assert_eq!(counterexample.trace, [0, 1, 0, 1, 0, 2]);
+
+    // Reaching 7 or more within three steps requires rolls of 2, 2, and 3,
+    // the last two without a reset.
+    let exact = check_safety(0, 3, |&s| s >= 7, |s| step::<WitnessCounter>(s, &mut rng));
+    assert!((exact.probability() - 1.0 / 108.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_check_safety_initial_state() {
+    let check = check_safety(9u8, 5, |&s| s > 8, Functor::pure);
+    assert_eq!(check.probability(), 1.0);
+    assert_eq!(check.counterexample().unwrap().steps, 0);
+    assert!(check.counterexample().unwrap().trace.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "step must produce at least one outcome")]
+fn test_check_safety_no_outcomes() {
+    check_safety(0u8, 2, |&s| s > 6, |_| WitnessedCounts::default());
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    share, unshare, Counter, Enumerator, Functor, Patch, RandomStrategy, Shared, SharedState,
+};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct World {
+    cells: Vec<u8>,
+}
+
+struct Toggle(usize);
+
+impl Patch<World> for Toggle {
+    fn apply(self, state: &mut World) {
+        state.cells[self.0] ^= 1;
+    }
+}
+
+fn world() -> World {
+    World {
+        cells: vec![0; 4096],
+    }
+}
+
+#[test]
+fn test_shared_unchanged_outcomes() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = share::<Enumerator, _>(vec![world()]);
+    let f = <Enumerator>::fmap_rand(f, &mut rng, |s, r: u8| {
+        if r % 2 == 0 {
+            s
+        } else {
+            s.update(|s| s.cells[r as usize] = r)
+        }
+    });
+    assert_eq!(f.len(), 256);
+    assert!(f.iter().step_by(2).all(|s| Arc::ptr_eq(s, &f[0])));
+    assert!(f.iter().skip(1).step_by(2).all(|s| !Arc::ptr_eq(s, &f[0])));
+
+    let f = unshare::<Enumerator, _>(f);
+    assert_eq!(f[0], world());
+    assert_eq!(f[3].cells[3], 3);
+}
+
+#[test]
+fn test_shared_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = share::<Counter, _>(Functor::pure(world()));
+    let f = <Counter>::fmap_rand_delta(f, &mut rng, |_, r: u8| {
+        (r < 4).then_some(Shared(Toggle(r as usize)))
+    });
+    let f = <Counter>::fmap_rand_delta(f, &mut rng, |_, r: bool| r.then_some(Shared(Toggle(0))));
+    assert_eq!(f.len(), 8);
+    let f = unshare::<Counter, _>(f);
+    assert_eq!(f[&world()], 252 + 1);
+    assert_eq!(f[&world().apply_toggle(0)], 252 + 1);
+    assert_eq!(f[&world().apply_toggle(1).apply_toggle(0)], 1);
+}
+
+#[test]
+fn test_shared_into_owned() {
+    let a = Arc::new(world());
+    let b = a.clone().apply_patch(Toggle(7));
+    assert_eq!(Arc::strong_count(&a), 1);
+    assert_eq!(b.cells[7], 1);
+    assert_eq!(a.clone().into_owned(), world());
+    assert!(!Arc::ptr_eq(&a.clone().update(|_| ()), &a));
+    assert_eq!(a.into_owned(), world());
+}
+
+trait ApplyToggle {
+    fn apply_toggle(self, i: usize) -> Self;
+}
+
+impl ApplyToggle for World {
+    fn apply_toggle(mut self, i: usize) -> Self {
+        Toggle(i).apply(&mut self);
+        self
+    }
+}
@@ -0,0 +1,34 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Bernoulli;
+use rand_functors::{
+    FlattenableRandomStrategy, Functor, RandomStrategy, Sampler, SingleShotRandomStrategy,
+    WeightedRandomStrategy,
+};
+
+#[derive(Debug, Eq, PartialEq)]
+struct Token(u8);
+
+fn random_process<S: SingleShotRandomStrategy>(token: Token, rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand_once(Functor::pure(()), rng, move |_, r: u8| {
+        r.wrapping_add(token.0)
+    });
+    let f = S::fmap_rand_range_once(f, 1..=6u8, rng, |s, r| s.wrapping_mul(r));
+    let distribution = Bernoulli::new(1, 3).unwrap();
+    let f = S::fmap_rand_distribution_once(f, &distribution, rng, |s, b| s ^ b as u8);
+    let f = S::fmap_flat_once(f, |s| Functor::pure(s / 2));
+    S::fmap_once(f, |s| s + 1)
+}
+
+#[test]
+fn test_single_shot_matches_sampler() {
+    let once = random_process::<Sampler>(Token(7), &mut ChaCha8Rng::seed_from_u64(0));
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = Sampler::fmap_rand((), &mut rng, |_, r: u8| r.wrapping_add(7));
+    let f = Sampler::fmap_rand_range(f, 1..=6u8, &mut rng, |s, r| s.wrapping_mul(r));
+    let distribution = Bernoulli::new(1, 3).unwrap();
+    let f = Sampler::fmap_rand_distribution(f, &distribution, &mut rng, |s, b| s ^ b as u8);
+    let f = Sampler::fmap_flat(f, |s: u8| s / 2);
+    assert_eq!(once, Sampler::fmap(f, |s| s + 1));
+}
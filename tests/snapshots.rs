@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    step, Counter, Enumerator, FlattenableRandomStrategy, Functor, RandomStrategy, Snapshot,
+    Snapshots,
+};
+
+fn random_process<S: FlattenableRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = step("flip", || {
+        S::fmap_rand(Functor::pure(()), rng, |_, r: bool| r as u8)
+    });
+    S::fmap_flat(f, |s| {
+        S::fmap_rand_range(Functor::pure(s), 0..2u8, rng, |s, r| s + r)
+    })
+}
+
+#[test]
+fn test_snapshots() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let (output, snapshots) = random_process::<Snapshots<Enumerator>>(&mut rng).into_parts();
+    assert_eq!(output, random_process::<Enumerator>(&mut rng));
+    assert_eq!(
+        snapshots,
+        [
+            Snapshot {
+                operation: "fmap_rand",
+                step: Some("flip"),
+                random_variable: Some("bool"),
+                outcome_type: "u8",
+                input_size: 1,
+                output_size: 2,
+            },
+            Snapshot {
+                operation: "fmap_rand_range",
+                step: None,
+                random_variable: Some("u8"),
+                outcome_type: "u8",
+                input_size: 1,
+                output_size: 2,
+            },
+            Snapshot {
+                operation: "fmap_rand_range",
+                step: None,
+                random_variable: Some("u8"),
+                outcome_type: "u8",
+                input_size: 1,
+                output_size: 2,
+            },
+            Snapshot {
+                operation: "fmap_flat",
+                step: None,
+                random_variable: None,
+                outcome_type: "u8",
+                input_size: 2,
+                output_size: 4,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_snapshots_accumulate() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Snapshots<Counter>>(&mut rng);
+    let output = <Snapshots<Counter>>::fmap(output, |s| s == 0);
+    assert_eq!(output.len(), 2);
+    let snapshots = output.snapshots();
+    assert_eq!(snapshots.len(), 5);
+    assert_eq!(snapshots[4].operation, "fmap");
+    assert_eq!(snapshots[4].output_size, 2);
+    assert_eq!(snapshots[4].outcome_type, "bool");
+}
@@ -0,0 +1,38 @@
+use std::cmp::Reverse;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Counter, Enumerator, Functor, RandomStrategy, WeightedFunctor};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand_range(Functor::pure(0), 0..4, rng, |_, r: u8| r);
+    S::fmap_rand(f, rng, |s, r: bool| if r { s.min(2) } else { s / 2 })
+}
+
+#[test]
+fn test_to_sorted_vec() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let expected = [(0, 3), (1, 3), (2, 2)];
+    assert_eq!(
+        random_process::<Enumerator>(&mut rng).to_sorted_vec(),
+        expected
+    );
+    assert_eq!(
+        random_process::<Counter>(&mut rng).to_sorted_vec(),
+        expected
+    );
+}
+
+#[test]
+fn test_into_sorted_by_key() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert_eq!(
+        random_process::<Enumerator>(&mut rng).into_sorted_by_key(|&o| Reverse(o)),
+        [(2, 2), (1, 3), (0, 3)]
+    );
+    // Ties are left in the order in which outcomes were first stored.
+    assert_eq!(
+        random_process::<Enumerator>(&mut rng).into_sorted_by_key(|&o| o / 2),
+        [(0, 3), (1, 3), (2, 2)]
+    );
+}
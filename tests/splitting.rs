@@ -0,0 +1,53 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{multilevel_splitting, Functor, RandomStrategy, Sampler};
+
+/// A walk which steps up with probability 1/3 and down otherwise.
+fn walk<S: RandomStrategy>(s: u8, rng: &mut impl Rng) -> S::Functor<u8> {
+    S::fmap_rand_range(Functor::pure(s), 0..3u8, rng, |s, r| {
+        if r == 0 {
+            s + 1
+        } else {
+            s - 1
+        }
+    })
+}
+
+#[test]
+fn test_multilevel_splitting() {
+    // The probability of reaching 30 before 0 from 1 is 1 / (2^30 - 1).
+    let levels = (2..=30).map(f64::from).collect::<Vec<_>>();
+    let splitting = multilevel_splitting(
+        1,
+        &levels,
+        2000,
+        0,
+        |&s| s.into(),
+        |&s| s == 0,
+        |s, rng: &mut ChaCha8Rng| walk::<Sampler>(s, rng),
+    );
+    assert_eq!(splitting.stage_probabilities().len(), 29);
+    assert!((0.3..0.37).contains(&splitting.stage_probabilities()[0]));
+    assert!(splitting.states().iter().all(|&s| s == 30));
+    let exact = 1.0 / (2.0f64.powi(30) - 1.0);
+    let error = splitting.relative_error().unwrap();
+    assert!(error < 0.2);
+    assert!((splitting.probability() / exact - 1.0).abs() < 4.0 * error);
+}
+
+#[test]
+fn test_multilevel_splitting_unreachable() {
+    let splitting = multilevel_splitting(
+        1,
+        &[2.0, 3.0],
+        100,
+        0,
+        |&s: &u8| s.into(),
+        |&s| s == 0,
+        |s, _: &mut ChaCha8Rng| s - 1,
+    );
+    assert_eq!(splitting.stage_probabilities(), [0.0]);
+    assert_eq!(splitting.probability(), 0.0);
+    assert_eq!(splitting.relative_error(), None);
+    assert!(splitting.states().is_empty());
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "std")]
+
+use rand_functors::{current_step, step};
+
+#[test]
+fn test_nested_steps() {
+    assert_eq!(current_step(), None);
+    step("outer", || {
+        assert_eq!(current_step(), Some("outer"));
+        step("inner", || assert_eq!(current_step(), Some("inner")));
+        assert_eq!(current_step(), Some("outer"));
+    });
+    assert_eq!(current_step(), None);
+}
+
+#[test]
+fn test_step_restored_after_panic() {
+    let result = std::panic::catch_unwind(|| step("explodes", || panic!("too many outcomes")));
+    assert!(result.is_err());
+    assert_eq!(current_step(), None);
+}
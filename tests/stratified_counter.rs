@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Counter, Functor, RandomStrategy, StratifiedCounter, WeightedFunctor};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u16> {
+    let functor = Functor::pure(0);
+    let functor = S::fmap_rand(functor, rng, |_, r: u8| r as u16);
+    S::fmap_rand_range(functor, 1..=4, rng, |s, r: u16| s * r)
+}
+
+#[test]
+fn test_stratify() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<StratifiedCounter<3>>(&mut rng);
+    let exact = random_process::<Counter>(&mut rng);
+    assert_eq!(output, exact);
+
+    let stratified = StratifiedCounter::<3>::stratify(output, |&s| s % 5, &mut rng);
+    assert_eq!(stratified.len(), 15);
+    for stratum in 0..5 {
+        assert_eq!(
+            stratified.probability_where(|&s| s % 5 == stratum),
+            exact.probability_where(|&s| s % 5 == stratum)
+        );
+    }
+    assert!(stratified
+        .iter()
+        .all(|(s, &count)| exact.contains_key(s) && count > 0));
+}
+
+#[test]
+fn test_stratify_small_strata() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<StratifiedCounter<3>>(&mut rng);
+    let stratified = StratifiedCounter::<3>::stratify(output.clone(), |&s| s > 1012, &mut rng);
+    assert_eq!(stratified.len(), 5);
+    assert_eq!(stratified[&1020], output[&1020]);
+    assert_eq!(stratified[&1016], output[&1016]);
+    assert_eq!(stratified.values().sum::<usize>(), 1024);
+}
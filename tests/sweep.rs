@@ -0,0 +1,31 @@
+#![cfg(feature = "std")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{sweep, Counter, Enumerator, Functor, RandomStrategy, RandomVariable};
+
+fn step<S: RandomStrategy>(s: u8, rng: &mut impl Rng) -> S::Functor<u8> {
+    S::fmap_rand_range(Functor::pure(s), 1..=3u8, rng, |s, r| s.wrapping_mul(r))
+}
+
+#[test]
+fn test_sweep_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let outcomes = sweep::<Counter, u8, _>(|s| step::<Counter>(s, &mut rng));
+    assert_eq!(outcomes.len(), 256);
+    for s in u8::sample_space() {
+        assert_eq!(outcomes[&s], step::<Counter>(s, &mut rng));
+    }
+    assert_eq!(outcomes[&0][&0], 3);
+}
+
+#[test]
+fn test_sweep_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let outcomes = sweep::<Enumerator, bool, _>(|b| {
+        Enumerator::fmap_rand(Functor::pure(b), &mut rng, |b, r: bool| (b, b && r))
+    });
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!(outcomes[&false], [(false, false), (false, false)]);
+    assert_eq!(outcomes[&true], [(true, false), (true, true)]);
+}
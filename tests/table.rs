@@ -0,0 +1,63 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Counter, Functor, LookupTable, RandomStrategy};
+
+fn step(rng: &mut impl Rng, s: bool) -> HashMap<bool, usize> {
+    let f = Counter::fmap_rand(Functor::pure(s), rng, |s, r: bool| s && r);
+    Counter::fmap_rand(f, rng, |s, r: bool| s || r)
+}
+
+#[test]
+fn test_compile() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let table = LookupTable::compile(|s| step(&mut rng, s));
+    assert_eq!(table.outcomes(&false), [(false, 2), (true, 2)]);
+    assert_eq!(table.outcomes(&true), [(false, 1), (true, 3)]);
+
+    let samples = (0..4000).filter(|_| table.sample(&true, &mut rng)).count();
+    assert!((2800..3200).contains(&samples));
+}
+
+#[test]
+fn test_to_rust_source() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let table = LookupTable::compile(|s| step(&mut rng, s));
+    assert_eq!(
+        table.to_rust_source("STEP"),
+        concat!(
+            "pub static STEP: [&[(bool, u64)]; 2] = [\n",
+            "    &[(false, 2), (true, 2)],\n",
+            "    &[(false, 1), (true, 3)],\n",
+            "];\n",
+        )
+    );
+}
+
+#[test]
+fn test_bytes_round_trip() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let table = LookupTable::compile(|s: u8| {
+        Counter::fmap_rand(Functor::pure(s), &mut rng, |s, r: bool| {
+            if r {
+                s.saturating_add(1)
+            } else {
+                s
+            }
+        })
+    });
+    let bytes = table.to_bytes();
+    assert_eq!(LookupTable::<u8>::from_bytes(&bytes), Some(table));
+
+    assert_eq!(
+        LookupTable::<u8>::from_bytes(&bytes[..bytes.len() - 1]),
+        None
+    );
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert_eq!(LookupTable::<u8>::from_bytes(&extended), None);
+    assert_eq!(LookupTable::<bool>::from_bytes(&bytes), None);
+}
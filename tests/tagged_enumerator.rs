@@ -0,0 +1,87 @@
+#![cfg(feature = "num-rational")]
+
+use std::collections::HashMap;
+
+use num_rational::Ratio;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Binomial;
+use rand_functors::{
+    Counter, FlattenableRandomStrategy, Functor, TaggedEnumerator, WeightedFunctor,
+    WeightedRandomStrategy,
+};
+
+fn random_process<S: FlattenableRandomStrategy + WeightedRandomStrategy>(
+    rng: &mut impl Rng,
+) -> S::Functor<u8> {
+    let f = S::fmap_rand_range(Functor::pure(0u8), 1..=3u8, rng, |_, r| r);
+    let distribution = Binomial::new(2, 1, 3).unwrap();
+    let f = S::fmap_rand_distribution(f, &distribution, rng, |s, k| s + k as u8);
+    S::fmap_flat(f, |s| {
+        if s % 2 == 0 {
+            S::fmap_rand(Functor::pure(s), rng, |s, b: bool| s + b as u8)
+        } else {
+            Functor::pure(s)
+        }
+    })
+}
+
+fn probabilities(
+    outcomes: impl IntoIterator<Item = (u8, Ratio<u128>)>,
+) -> HashMap<u8, Ratio<u128>> {
+    outcomes
+        .into_iter()
+        .fold(HashMap::new(), |mut map, (outcome, p)| {
+            *map.entry(outcome).or_default() += p;
+            map
+        })
+}
+
+#[test]
+fn test_tagged_enumerator_probabilities() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = random_process::<TaggedEnumerator>(&mut rng);
+    assert_eq!(f.len(), 3 * 3 + 4);
+    assert_eq!(
+        f.iter().map(|(_, p)| *p).sum::<Ratio<u128>>(),
+        Ratio::from_integer(1)
+    );
+
+    let probabilities = probabilities(f);
+    assert_eq!(probabilities[&1], Ratio::new(4, 27));
+    assert_eq!(probabilities[&2], Ratio::new(4, 27));
+    assert_eq!(probabilities[&3], Ratio::new(13, 27));
+    assert_eq!(probabilities[&4], Ratio::new(5, 54));
+    assert_eq!(probabilities[&5], Ratio::new(7, 54));
+}
+
+#[test]
+fn test_tagged_enumerator_weighted_outcomes() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <TaggedEnumerator>::fmap_rand_distribution(
+        Functor::pure(()),
+        &Binomial::new(3, 1, 2).unwrap(),
+        &mut rng,
+        |_, k| k as u8,
+    );
+    assert_eq!(
+        f.to_sorted_vec(),
+        [
+            (0, Ratio::new(1, 8)),
+            (1, Ratio::new(3, 8)),
+            (2, Ratio::new(3, 8)),
+            (3, Ratio::new(1, 8))
+        ]
+    );
+
+    let counted = <Counter>::fmap_rand_distribution(
+        Functor::pure(()),
+        &Binomial::new(3, 1, 2).unwrap(),
+        &mut rng,
+        |_, k| k as u8,
+    );
+    let total = counted.values().sum::<usize>() as u128;
+    assert!(f
+        .iter()
+        .all(|(k, p)| Ratio::new(counted[k] as u128, total) == *p));
+}
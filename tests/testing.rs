@@ -0,0 +1,214 @@
+#![cfg(feature = "std")]
+
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::testing::{
+    audit_uniformity, check_random_variable, DistributionSnapshot, SnapshotDifference,
+};
+use rand_functors::{
+    assert_distribution_eq, Cardinality, Counter, Enumerator, Functor, RandomStrategy,
+    RandomVariable, WeightedPopulationSampler,
+};
+
+macro_rules! faulty_random_variable {
+    ($name:ident, $cardinality:expr, $sample_space:expr) => {
+        faulty_random_variable!($name, $cardinality, $sample_space, 0..4);
+    };
+    ($name:ident, $cardinality:expr, $sample_space:expr, $sample_range:expr) => {
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        struct $name(u8);
+
+        impl Distribution<$name> for Standard {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $name {
+                $name(rng.gen_range($sample_range).min(3))
+            }
+        }
+
+        impl RandomVariable for $name {
+            fn sample_space() -> impl Iterator<Item = Self> {
+                $sample_space.map($name)
+            }
+        }
+
+        impl Cardinality for $name {
+            const CARDINALITY: u128 = $cardinality;
+        }
+    };
+}
+
+faulty_random_variable!(Correct, 4, 0..4);
+faulty_random_variable!(Duplicated, 4, [0, 1, 1, 2].into_iter());
+faulty_random_variable!(Unbounded, 4, (0..).map(|i: u32| i as u8));
+faulty_random_variable!(Missing, 4, 0..3);
+faulty_random_variable!(Biased, 4, 0..4, 0..5);
+
+#[test]
+fn test_check_random_variable() {
+    check_random_variable::<bool>();
+    check_random_variable::<u8>();
+    check_random_variable::<i16>();
+    check_random_variable::<Correct>();
+}
+
+#[test]
+#[should_panic(expected = "more than once")]
+fn test_check_random_variable_duplicated() {
+    check_random_variable::<Duplicated>();
+}
+
+#[test]
+#[should_panic(expected = "more than CARDINALITY")]
+fn test_check_random_variable_unbounded() {
+    check_random_variable::<Unbounded>();
+}
+
+#[test]
+#[should_panic(expected = "fewer than CARDINALITY")]
+fn test_check_random_variable_missing() {
+    check_random_variable::<Missing>();
+}
+
+#[test]
+fn test_audit_uniformity() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    audit_uniformity::<bool>(&mut rng, 1000);
+    audit_uniformity::<i8>(&mut rng, 10000);
+    audit_uniformity::<Correct>(&mut rng, 1000);
+}
+
+#[test]
+#[should_panic(expected = "not uniform")]
+fn test_audit_uniformity_biased() {
+    audit_uniformity::<Biased>(&mut ChaCha8Rng::seed_from_u64(0), 1000);
+}
+
+#[test]
+#[should_panic(expected = "not in the sample space")]
+fn test_audit_uniformity_missing() {
+    audit_uniformity::<Missing>(&mut ChaCha8Rng::seed_from_u64(0), 1000);
+}
+
+#[test]
+#[should_panic(expected = "samples are required")]
+fn test_audit_uniformity_too_few_samples() {
+    audit_uniformity::<u8>(&mut ChaCha8Rng::seed_from_u64(0), 1000);
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn test_arb_random_variable_covers_sample_space() {
+    use proptest::strategy::{Strategy, ValueTree};
+    use proptest::test_runner::TestRunner;
+    use rand_functors::testing::arb_random_variable;
+
+    let mut runner = TestRunner::deterministic();
+    let strategy = arb_random_variable::<bool>();
+    let values = (0..64)
+        .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+        .collect::<Vec<_>>();
+    assert!(values.contains(&false));
+    assert!(values.contains(&true));
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn test_arb_random_variable_shrinks_towards_start() {
+    use proptest::strategy::{Strategy, ValueTree};
+    use proptest::test_runner::TestRunner;
+    use rand_functors::testing::arb_random_variable;
+
+    let mut runner = TestRunner::deterministic();
+    let mut tree = arb_random_variable::<i8>().new_tree(&mut runner).unwrap();
+    while tree.simplify() {}
+    assert_eq!(tree.current(), i8::MIN);
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn test_arbitrary_random_variable_covers_sample_space() {
+    use quickcheck::{Arbitrary, Gen};
+    use rand_functors::testing::ArbitraryRandomVariable;
+
+    let mut g = Gen::new(8);
+    let values = (0..64)
+        .map(|_| ArbitraryRandomVariable::<bool>::arbitrary(&mut g).0)
+        .collect::<Vec<_>>();
+    assert!(values.contains(&false));
+    assert!(values.contains(&true));
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn test_arbitrary_random_variable_shrinks_towards_start() {
+    use quickcheck::Arbitrary;
+    use rand_functors::testing::ArbitraryRandomVariable;
+
+    let shrunk = ArbitraryRandomVariable(3u8).shrink().collect::<Vec<_>>();
+    assert_eq!(shrunk, [0, 1, 2].map(ArbitraryRandomVariable));
+    assert_eq!(ArbitraryRandomVariable(false).shrink().count(), 0);
+}
+
+fn two_dice<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap_rand_range(Functor::pure(0), 1..=6u8, rng, |s, r| s + r);
+    S::fmap_rand_range(f, 1..=6u8, rng, |s, r| s + r)
+}
+
+#[test]
+fn test_distribution_snapshot() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let counter = DistributionSnapshot::new(&two_dice::<Counter>(&mut rng));
+    let enumerator = DistributionSnapshot::new(&two_dice::<Enumerator>(&mut rng));
+    assert_eq!(counter, enumerator);
+    assert_eq!(counter.len(), 11);
+    assert_eq!(counter.probability("7"), 1.0 / 6.0);
+    assert_eq!(counter.probability("13"), 0.0);
+    let order = counter.outcomes().map(|(o, _)| o).collect::<Vec<_>>();
+    assert_eq!(order[..4], ["10", "11", "12", "2"]);
+
+    let shifted = [("2".to_string(), 0.5), ("3".to_string(), 0.5)]
+        .into_iter()
+        .collect::<DistributionSnapshot>();
+    assert!(!counter.approx_eq(&shifted, 0.1));
+    assert!(counter.approx_eq(&shifted, 0.5));
+    assert_eq!(
+        counter.differences(&shifted, 0.4)[0],
+        SnapshotDifference {
+            outcome: "2".to_string(),
+            actual: 1.0 / 36.0,
+            expected: 0.5,
+        }
+    );
+}
+
+#[test]
+fn test_assert_distribution_eq() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let snapshot = DistributionSnapshot::new(&two_dice::<Counter>(&mut rng));
+    assert_distribution_eq!(two_dice::<Enumerator>(&mut rng), snapshot);
+    let particles = two_dice::<WeightedPopulationSampler<1000>>(&mut rng);
+    assert_distribution_eq!(particles, snapshot, 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "7: expected 0.16666666666666666, found 0")]
+fn test_assert_distribution_eq_mismatch() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let snapshot = DistributionSnapshot::new(&two_dice::<Counter>(&mut rng));
+    let f = <Counter>::fmap(
+        two_dice::<Counter>(&mut rng),
+        |s| if s == 7 { 8 } else { s },
+    );
+    assert_distribution_eq!(f, snapshot);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_distribution_snapshot_json() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let snapshot = DistributionSnapshot::new(&two_dice::<Counter>(&mut rng));
+    let json = snapshot.to_json();
+    assert!(json.starts_with("{\n  \"10\": 0.083"));
+    assert_eq!(DistributionSnapshot::from_json(&json).unwrap(), snapshot);
+    assert!(DistributionSnapshot::from_json("[]").is_err());
+}
@@ -0,0 +1,66 @@
+use rand::distributions::Standard;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Cardinality, Counter, EnumerableDistribution, Enumerator, Functor, RandomStrategy,
+    RandomVariable, Thinned, WeightedRandomStrategy,
+};
+
+#[test]
+fn test_thinned_sample_space() {
+    assert_eq!(
+        <Thinned<u8, 64>>::sample_space()
+            .map(|x| x.0)
+            .collect::<Vec<_>>(),
+        [0, 64, 128, 192]
+    );
+    assert_eq!(<Thinned<u8, 100>>::CARDINALITY, 3);
+    assert_eq!(<Thinned<i32, 65536>>::CARDINALITY, 65536);
+    assert_eq!(
+        <Thinned<i32, 65536>>::sample_space().count() as u128,
+        <Thinned<i32, 65536>>::CARDINALITY
+    );
+    assert_eq!(
+        <Thinned<u64, { 1 << 48 }>>::sample_space().last(),
+        Some(Thinned(u64::MAX - (1 << 48) + 1))
+    );
+}
+
+#[test]
+fn test_thinned_sampling() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert!((0..100).any(|_| rng.gen::<Thinned<u8, 100>>().0 % 100 != 0));
+}
+
+#[test]
+fn test_thinned_enumeration() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Enumerator>::fmap_rand(Functor::pure(()), &mut rng, |_, x: Thinned<u32, 65536>| {
+        x.0 >> 16
+    });
+    assert_eq!(f, (0..65536).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_thinned_bucket_weights() {
+    assert_eq!(
+        EnumerableDistribution::<Thinned<u8, 100>>::weighted_sample_space(&Standard)
+            .map(|(x, weight)| (x.0, weight))
+            .collect::<Vec<_>>(),
+        [(0, 100), (100, 100), (200, 56)]
+    );
+    assert!(
+        EnumerableDistribution::<Thinned<u16, 256>>::weighted_sample_space(&Standard)
+            .all(|(_, weight)| weight == 256)
+    );
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = <Counter>::fmap_rand_distribution(
+        Functor::pure(()),
+        &Standard,
+        &mut rng,
+        |_, x: Thinned<u8, 100>| x.0 < 150,
+    );
+    assert_eq!(f[&true], 200);
+    assert_eq!(f[&false], 56);
+}
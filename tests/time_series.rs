@@ -0,0 +1,55 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{sample_series, RandomStrategy, Sampler, TimeSeries};
+
+#[test]
+fn test_time_series_statistics() {
+    let series = TimeSeries::from(vec![1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(series.mean(), Some(2.5));
+    assert_eq!(series.variance(), Some(5.0 / 3.0));
+    assert_eq!(series.running_mean(), [1.0, 1.5, 2.0, 2.5]);
+    assert_eq!(series.autocorrelation(0), Some(1.0));
+    assert_eq!(series.autocorrelation(1), Some(0.25));
+    assert_eq!(series.autocorrelation(4), None);
+    assert_eq!(series.autocorrelations(10).len(), 4);
+    assert_eq!(series.skip(2).values(), [3.0, 4.0]);
+    assert!(series.skip(5).is_empty());
+
+    let constant = [1.0; 10].into_iter().collect::<TimeSeries>();
+    assert_eq!(constant.autocorrelation(0), None);
+    assert!(constant.autocorrelations(3).is_empty());
+    assert_eq!(TimeSeries::default().mean(), None);
+}
+
+#[test]
+fn test_sample_series_burn_in() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let series = sample_series(
+        1000u32,
+        5000,
+        &mut rng,
+        |s, rng| Sampler::fmap_rand(s, rng, |s, r: bool| if r { s / 2 } else { s + 1 }),
+        |&s| s as f64,
+    );
+    assert_eq!(series.len(), 5001);
+    assert_eq!(series.values()[0], 1000.0);
+    let running_mean = series.running_mean();
+    assert!(running_mean[99] > 10.0);
+    let stationary = series.skip(100);
+    assert!((stationary.mean().unwrap() - 1.5).abs() < 0.2);
+    let autocorrelations = stationary.autocorrelations(20);
+    assert_eq!(autocorrelations[0], 1.0);
+    assert!(autocorrelations[20].abs() < 0.1);
+}
+
+#[test]
+fn test_sample_series_reproducible() {
+    let step = |s: u8, rng: &mut ChaCha8Rng| Sampler::fmap_rand(s, rng, |s, r: u8| s ^ r);
+    let a = sample_series(0, 100, &mut ChaCha8Rng::seed_from_u64(1), step, |&s| {
+        s as f64
+    });
+    let b = sample_series(0, 100, &mut ChaCha8Rng::seed_from_u64(1), step, |&s| {
+        s as f64
+    });
+    assert_eq!(a, b);
+}
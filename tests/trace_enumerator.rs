@@ -0,0 +1,61 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Bernoulli;
+use rand_functors::{
+    EnumerableDistribution, Enumerator, FlattenableRandomStrategy, Functor, RandomVariable,
+    TraceEnumerator, WeightedRandomStrategy,
+};
+
+fn random_process<S: FlattenableRandomStrategy + WeightedRandomStrategy>(
+    rng: &mut impl Rng,
+) -> S::Functor<u8> {
+    let f = S::fmap_rand(Functor::pure(0u8), rng, |_, b: bool| b as u8);
+    let distribution = Bernoulli::new(1, 3).unwrap();
+    let f = S::fmap_rand_distribution(f, &distribution, rng, |s, b| s + 2 * b as u8);
+    S::fmap_flat(f, |s| {
+        if s > 0 {
+            S::fmap_rand_range(Functor::pure(s), 0..3u8, rng, |s, r| s * 10 + r)
+        } else {
+            Functor::pure(s)
+        }
+    })
+}
+
+/// Recomputes the outcome of random_process from a trace.
+fn replay(trace: &[usize]) -> u8 {
+    let b = bool::sample_space().nth(trace[0]).unwrap() as u8;
+    let (c, _) = Bernoulli::new(1, 3)
+        .unwrap()
+        .weighted_sample_space()
+        .nth(trace[1])
+        .unwrap();
+    let s = b + 2 * c as u8;
+    if s > 0 {
+        s * 10 + (0..3u8).nth(trace[2]).unwrap()
+    } else {
+        assert_eq!(trace.len(), 2);
+        s
+    }
+}
+
+#[test]
+fn test_trace_enumerator_matches_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let witnessed = random_process::<TraceEnumerator>(&mut rng);
+    assert_eq!(
+        witnessed.iter().map(|(o, _)| *o).collect::<Vec<_>>(),
+        random_process::<Enumerator>(&mut rng)
+    );
+}
+
+#[test]
+fn test_trace_enumerator_traces_replay() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let witnessed = random_process::<TraceEnumerator>(&mut rng);
+    for (outcome, trace) in witnessed.iter() {
+        assert_eq!(replay(trace), *outcome);
+    }
+    assert_eq!(witnessed.witness(|&o| o == 32), Some(&[1, 1, 2][..]));
+    assert_eq!(witnessed.witness(|&o| o == 0), Some(&[0, 0][..]));
+    assert_eq!(witnessed.witness(|&o| o == 4), None);
+}
@@ -0,0 +1,165 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    Enumerator, FlattenableRandomStrategy, Functor, RandomStrategy, Sampler, Traced,
+};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Debug, Default)]
+struct Recorded {
+    spans: Vec<(String, Vec<(String, String)>)>,
+    events: Vec<Vec<(String, String)>>,
+}
+
+#[derive(Clone, Default)]
+struct Recorder {
+    next_id: Arc<AtomicU64>,
+    recorded: Arc<Mutex<Recorded>>,
+}
+
+#[derive(Default)]
+struct FieldVisitor(Vec<(String, String)>);
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut visitor = FieldVisitor::default();
+        span.record(&mut visitor);
+        self.recorded
+            .lock()
+            .unwrap()
+            .spans
+            .push((span.metadata().name().to_string(), visitor.0));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        self.recorded.lock().unwrap().events.push(visitor.0);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+fn field<'a>(fields: &'a [(String, String)], name: &str) -> &'a str {
+    &fields.iter().find(|(n, _)| n == name).unwrap().1
+}
+
+fn random_process<S: FlattenableRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    let f = S::fmap(Functor::pure(1u8), |a| a + 1);
+    let f = S::fmap_rand(f, rng, |a, b: bool| if b { a } else { a + 1 });
+    S::fmap_flat(f, |a| {
+        S::fmap_rand_range(Functor::pure(a), 0..3u8, rng, |a, b| a + b)
+    })
+}
+
+#[test]
+fn test_traced_records_operations() {
+    let recorder = Recorder::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = tracing::subscriber::with_default(recorder.clone(), || {
+        random_process::<Traced<Enumerator>>(&mut rng)
+    });
+    assert_eq!(
+        output,
+        random_process::<Enumerator>(&mut ChaCha8Rng::seed_from_u64(0))
+    );
+
+    let recorded = recorder.recorded.lock().unwrap();
+    let names: Vec<_> = recorded.spans.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "fmap",
+            "fmap_rand",
+            "fmap_flat",
+            "fmap_rand_range",
+            "fmap_rand_range"
+        ]
+    );
+    for (_, fields) in &recorded.spans {
+        assert!(field(fields, "strategy").ends_with("::Enumerator"));
+    }
+    assert_eq!(field(&recorded.spans[1].1, "random_variable"), "bool");
+    assert_eq!(field(&recorded.spans[3].1, "random_variable"), "u8");
+
+    let sizes: Vec<_> = recorded
+        .events
+        .iter()
+        .map(|fields| {
+            assert!(field(fields, "elapsed_us").parse::<u64>().is_ok());
+            (
+                field(fields, "input_size").to_string(),
+                field(fields, "output_size").to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        sizes,
+        [("1", "1"), ("1", "2"), ("1", "3"), ("1", "3"), ("2", "6")]
+            .map(|(i, o)| (i.to_string(), o.to_string()))
+    );
+}
+
+#[test]
+fn test_traced_sampler() {
+    let recorder = Recorder::default();
+    let output = tracing::subscriber::with_default(recorder.clone(), || {
+        <Traced<Sampler>>::fmap(4u8, |a| a * 2)
+    });
+    assert_eq!(output, 8);
+
+    let recorded = recorder.recorded.lock().unwrap();
+    assert_eq!(recorded.spans.len(), 1);
+    assert!(field(&recorded.spans[0].1, "strategy").ends_with("::Sampler"));
+    assert_eq!(field(&recorded.events[0], "input_size"), "1");
+    assert_eq!(field(&recorded.events[0], "output_size"), "1");
+}
+
+#[test]
+fn test_traced_step() {
+    let recorder = Recorder::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let f = rand_functors::step("roll", || {
+            <Traced<Enumerator>>::fmap_rand(Functor::pure(0u8), &mut rng, |_, b: bool| b as u8)
+        });
+        <Traced<Enumerator>>::fmap(f, |a| a + 1)
+    });
+
+    let recorded = recorder.recorded.lock().unwrap();
+    let names: Vec<_> = recorded.spans.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(names, ["step", "fmap_rand", "fmap"]);
+    assert_eq!(field(&recorded.spans[0].1, "name"), "roll");
+    assert_eq!(field(&recorded.spans[1].1, "step"), "roll");
+    assert!(recorded.spans[2].1.iter().all(|(n, _)| n != "step"));
+}
@@ -0,0 +1,119 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{
+    ensemble_divergence, evolve, run_ensemble, run_trajectory, Counter, Enumerator, Functor,
+    RandomStrategy, Sampler,
+};
+
+fn walk<S: RandomStrategy>(s: i8, rng: &mut impl Rng) -> S::Functor<i8> {
+    S::fmap_rand(
+        Functor::pure(s),
+        rng,
+        |s, r: bool| if r { s + 1 } else { s - 1 },
+    )
+}
+
+#[test]
+fn test_trajectory_sampler() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let path = run_trajectory::<Sampler, _>(0, 20, |s| walk::<Sampler>(s, &mut rng));
+    assert_eq!(path.len(), 21);
+    assert_eq!(path[0], 0);
+    assert!(path.windows(2).all(|w| (w[1] - w[0]).abs() == 1));
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let endpoint = (0..20).fold(0, |s, _| walk::<Sampler>(s, &mut rng));
+    assert_eq!(path[20], endpoint);
+}
+
+#[test]
+fn test_trajectory_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let paths = run_trajectory::<Enumerator, _>(0, 4, |s| walk::<Enumerator>(s, &mut rng));
+    assert_eq!(paths.len(), 16);
+    let first_passage = paths.iter().filter(|p| p.contains(&2)).count();
+    assert_eq!(first_passage, 6);
+
+    let counts = run_trajectory::<Counter, _>(0, 4, |s| walk::<Counter>(s, &mut rng));
+    assert_eq!(counts.len(), 16);
+    assert!(counts.values().all(|&c| c == 1));
+    assert_eq!(
+        run_trajectory::<Counter, _>(5, 0, |s| walk::<Counter>(s, &mut rng))[&vec![5]],
+        1
+    );
+}
+
+#[test]
+fn test_evolve() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let distributions = evolve::<Counter, _>(0, 6, |s| walk::<Counter>(s, &mut rng));
+    assert_eq!(distributions.len(), 7);
+    for (steps, f) in distributions.iter().enumerate() {
+        let expected = (0..steps).fold(Functor::pure(0), |f, _| {
+            Counter::fmap_rand(f, &mut rng, |s, r: bool| if r { s + 1 } else { s - 1 })
+        });
+        assert_eq!(*f, expected);
+    }
+
+    let distributions = evolve::<Sampler, _>(3, 0, |s| walk::<Sampler>(s, &mut rng));
+    assert_eq!(distributions, [3]);
+}
+
+#[test]
+fn test_ensemble() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let ensemble = run_ensemble(-2..=2, 50, |s| walk::<Sampler>(s, &mut rng));
+    assert_eq!(ensemble.len(), 5);
+    for (path, initial) in ensemble.iter().zip(-2..=2) {
+        assert_eq!(path.len(), 51);
+        assert_eq!(path[0], initial);
+    }
+    let increments = |path: &Vec<i8>| path.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+    assert_ne!(increments(&ensemble[0]), increments(&ensemble[1]));
+}
+
+#[test]
+fn test_ensemble_divergence() {
+    // A chaotic map amplifies small perturbations even under common random
+    // numbers.
+    let divergence =
+        ensemble_divergence([100u32, 100, 100, 101], 30, 7, |s, rng: &mut ChaCha8Rng| {
+            Sampler::fmap_rand(s, rng, |s, r: u8| {
+                s.wrapping_mul(2654435761).rotate_left(s % 7) ^ r as u32
+            })
+        });
+    let differing = divergence.differing_pairs();
+    assert_eq!(differing.len(), 31);
+    assert_eq!(differing[0], 0.5);
+    assert!(differing.iter().all(|&d| d == 0.5 || d == 1.0));
+    assert_eq!(divergence.first_coalescence(), None);
+    let states = divergence.states(30).unwrap();
+    assert_eq!(states[0], states[1]);
+    assert_eq!(states[1], states[2]);
+    assert!(divergence.states(31).is_none());
+
+    let distance = |a: &u32, b: &u32| a.abs_diff(*b) as f64;
+    assert_eq!(divergence.mean_distance(distance)[0], 0.5);
+    let diverged = divergence.first_divergence(distance, 1000.0).unwrap();
+    assert!(diverged > 0);
+    assert!(divergence.mean_distance(distance)[..diverged]
+        .iter()
+        .all(|&d| d <= 1000.0));
+
+    let walk = ensemble_divergence(-3..=3, 200, 0, |s: i8, rng: &mut ChaCha8Rng| {
+        Sampler::fmap_rand(
+            s,
+            rng,
+            |s, r: bool| if r { (s + 1).min(3) } else { (s - 1).max(-3) },
+        )
+    });
+    assert_eq!(
+        walk.first_divergence(|a, b| a.abs_diff(*b) as f64, 3.0),
+        None
+    );
+    let coalescence = walk.first_coalescence().unwrap();
+    assert!(walk.differing_pairs()[coalescence..]
+        .iter()
+        .all(|&d| d == 0.0));
+    assert_eq!(walk.trajectories().len(), 7);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "alloc")]
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Binomial;
+use rand_functors::{
+    FlattenableRandomStrategy, Functor, RandomStrategy, WeightedFunctor, WeightedPopulationSampler,
+    WeightedRandomStrategy,
+};
+
+fn random_process<S: FlattenableRandomStrategy>(rng: &mut impl Rng) -> S::Functor<u8> {
+    // Takes the value 0 with probability 1/2, even though it is represented by
+    // only one of the 257 outcomes expanded by the fmap_flat.
+    let f = S::fmap_rand(Functor::pure(()), rng, |_, b: bool| b);
+    let f = S::fmap_flat(f, |b| {
+        if b {
+            S::fmap_rand(Functor::pure(()), &mut thread_rng(), |_, r: u8| r | 1)
+        } else {
+            Functor::pure(0)
+        }
+    });
+    S::fmap_rand(f, rng, |s, _: bool| s)
+}
+
+#[test]
+fn test_weighted_population_sampler_is_unbiased() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut zero = 0.0;
+    for _ in 0..200 {
+        let f = random_process::<WeightedPopulationSampler<32>>(&mut rng);
+        assert_eq!(f.len(), 32);
+        assert!((f.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-9);
+        zero += f.probability_where(|&s| s == 0);
+    }
+    assert!((zero / 200.0 - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn test_weighted_population_sampler_keeps_small_functors() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let f = WeightedPopulationSampler::<8>::fmap_rand_distribution(
+        Functor::pure(()),
+        &Binomial::new(3, 1, 2).unwrap(),
+        &mut rng,
+        |_, k| k as u8,
+    );
+    assert_eq!(
+        f.into_inner(),
+        [(0, 0.125), (1, 0.375), (2, 0.375), (3, 0.125)]
+    );
+
+    let f = WeightedPopulationSampler::<2>::fmap_rand_range(
+        Functor::pure(0u8),
+        0..4u8,
+        &mut rng,
+        |_, r| r,
+    );
+    assert_eq!(f.len(), 2);
+    assert!(f.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 == 0.5));
+}
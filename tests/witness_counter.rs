@@ -0,0 +1,51 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::distributions::Bernoulli;
+use rand_functors::{
+    Counter, FlattenableRandomStrategy, Functor, TraceEnumerator, WeightedRandomStrategy,
+    WitnessCounter,
+};
+
+fn random_process<S: FlattenableRandomStrategy + WeightedRandomStrategy>(
+    rng: &mut impl Rng,
+) -> S::Functor<u8> {
+    let f = S::fmap_rand_range(Functor::pure(0u8), 0..4u8, rng, |_, r| r);
+    let distribution = Bernoulli::new(1, 3).unwrap();
+    let f = S::fmap_rand_distribution(f, &distribution, rng, |s, b| s.max(2 * b as u8));
+    S::fmap_flat(f, |s| {
+        if s % 2 == 0 {
+            S::fmap_rand(Functor::pure(s), rng, |s, b: bool| s + b as u8)
+        } else {
+            Functor::pure(s)
+        }
+    })
+}
+
+#[test]
+fn test_witness_counter_counts() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let witnessed = random_process::<WitnessCounter>(&mut rng);
+    let counted = random_process::<Counter>(&mut rng);
+    assert_eq!(witnessed.len(), counted.len());
+    assert!(counted
+        .iter()
+        .all(|(outcome, count)| witnessed.count(outcome) == *count));
+}
+
+#[test]
+fn test_witness_counter_witnesses() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let witnessed = random_process::<WitnessCounter>(&mut rng);
+    let traces = random_process::<TraceEnumerator>(&mut rng);
+    for (outcome, (_, trace)) in witnessed.iter() {
+        let smallest = traces
+            .iter()
+            .filter(|(o, _)| o == outcome)
+            .map(|(_, t)| t)
+            .min()
+            .unwrap();
+        assert_eq!(trace, smallest);
+    }
+    assert_eq!(witnessed.witness(&3), Some(&[0, 1, 1][..]));
+    assert_eq!(witnessed.witness(&5), None);
+}
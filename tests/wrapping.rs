@@ -0,0 +1,33 @@
+use std::num::Wrapping;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rand_functors::{Cardinality, Counter, Enumerator, Functor, RandomStrategy, RandomVariable};
+
+fn random_process<S: RandomStrategy>(rng: &mut impl Rng) -> S::Functor<Wrapping<u8>> {
+    let f = Functor::pure(Wrapping(200u8));
+    S::fmap_rand(f, rng, |s, r: Wrapping<u8>| s + r)
+}
+
+#[test]
+fn test_wrapping_sample_space() {
+    assert!(Wrapping::<i8>::sample_space().eq((i8::MIN..=i8::MAX).map(Wrapping)));
+    assert_eq!(Wrapping::<u16>::CARDINALITY, 65536);
+}
+
+#[test]
+fn test_wrapping_enumerator() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Enumerator>(&mut rng);
+    assert_eq!(output.len(), 256);
+    assert_eq!(output[0], Wrapping(200));
+    assert_eq!(output[56], Wrapping(0));
+}
+
+#[test]
+fn test_wrapping_counter() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let output = random_process::<Counter>(&mut rng);
+    assert_eq!(output.len(), 256);
+    assert!(output.values().all(|&count| count == 1));
+}